@@ -0,0 +1,285 @@
+//! CBORモジュール
+//!
+//! CBOR（RFC 8949）のエンコード・デコードを提供:
+//! - encode: Qi値をCBORのバイト列に変換
+//! - decode: CBORのバイト列をQi値に変換
+//!
+//! 文字列キーのマップは `MapKey::String`、整数キーは `MapKey::Integer` になる。
+//! バイト文字列は `Value::Bytes` になる。多倍長整数はbignum（タグ2/3）、10進数は
+//! decimal fraction（タグ4）で読み書きし、有理数は浮動小数点数で書き出す。
+//! その他のタグは無視して中身の値を返す。
+
+use crate::check_args;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Value};
+use ciborium::value::Integer;
+use ciborium::Value as Cbor;
+use num_bigint::{BigInt, Sign};
+
+/// Qi値をCBORのバイト列に変換
+///
+/// # 引数
+/// - args[0]: 変換する値
+///
+/// # 戻り値
+/// - 成功時: バイト列
+/// - 失敗時: {:error エラーメッセージ}
+pub fn native_encode(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "cbor/encode");
+
+    let cbor = match value_to_cbor(&args[0]) {
+        Ok(cbor) => cbor,
+        Err(e) => {
+            return Ok(Value::error(fmt_msg(
+                MsgKey::FormatEncodeError,
+                &["cbor/encode", &e],
+            )))
+        }
+    };
+    let mut buf = Vec::new();
+    ciborium::into_writer(&cbor, &mut buf).map_err(|e| e.to_string())?;
+    Ok(Value::Bytes(buf.into()))
+}
+
+/// CBORのバイト列をQi値に変換
+///
+/// # 引数
+/// - args[0]: バイト列
+///
+/// # 戻り値
+/// - 成功時: デコード結果
+/// - 失敗時: {:error エラーメッセージ}
+pub fn native_decode(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "cbor/decode");
+
+    let bytes = match &args[0] {
+        Value::Bytes(b) => b.clone(),
+        _ => return Err(fmt_msg(MsgKey::ArgMustBeType, &["cbor/decode", "bytes"])),
+    };
+
+    match ciborium::from_reader::<Cbor, _>(bytes.as_ref()) {
+        Ok(cbor) => Ok(cbor_to_value(cbor)),
+        Err(e) => Ok(Value::error(fmt_msg(
+            MsgKey::FormatParseError,
+            &["cbor/decode", &e.to_string()],
+        ))),
+    }
+}
+
+/// ciborium::ValueをQi Valueに変換
+fn cbor_to_value(cbor: Cbor) -> Value {
+    match cbor {
+        Cbor::Null => Value::Nil,
+        Cbor::Bool(b) => Value::Bool(b),
+        Cbor::Integer(i) => match i64::try_from(i) {
+            Ok(n) => Value::Integer(n),
+            // i64に収まらない整数は多倍長整数にする
+            Err(_) => Value::BigInt(std::sync::Arc::new(BigInt::from(i128::from(i)))),
+        },
+        Cbor::Float(f) => Value::Float(f),
+        Cbor::Text(s) => Value::String(s),
+        Cbor::Bytes(b) => Value::Bytes(b.into()),
+        Cbor::Array(arr) => Value::Vector(arr.into_iter().map(cbor_to_value).collect()),
+        Cbor::Map(entries) => {
+            let mut map = crate::new_hashmap();
+            for (k, v) in entries {
+                let key = match k {
+                    Cbor::Text(s) => MapKey::String(s),
+                    Cbor::Integer(i) => match i64::try_from(i) {
+                        Ok(n) => MapKey::Integer(n),
+                        Err(_) => continue,
+                    },
+                    Cbor::Bool(b) => MapKey::String(b.to_string()),
+                    _ => continue, // その他のキーはスキップ
+                };
+                map.insert(key, cbor_to_value(v));
+            }
+            Value::Map(map)
+        }
+        Cbor::Tag(tag, inner) => match (tag, *inner) {
+            (2 | 3, Cbor::Bytes(b)) => {
+                crate::builtins::numeric_helpers::bigint_value(bignum_from_cbor(tag, &b))
+            }
+            (4, Cbor::Array(parts)) => decimal_fraction_to_value(parts),
+            (_, inner) => cbor_to_value(inner),
+        },
+        _ => Value::Nil,
+    }
+}
+
+/// bignum（タグ2: 非負、タグ3: -1-n）のバイト列を多倍長整数に変換
+fn bignum_from_cbor(tag: u64, bytes: &[u8]) -> BigInt {
+    let n = BigInt::from_bytes_be(Sign::Plus, bytes);
+    if tag == 3 {
+        -1 - n
+    } else {
+        n
+    }
+}
+
+/// 多倍長整数をCBORの整数に変換（CBORの整数に収まらなければbignum）
+fn bigint_to_cbor(n: &BigInt) -> Cbor {
+    if let Some(i) = num_traits::ToPrimitive::to_i128(n).and_then(|i| Integer::try_from(i).ok()) {
+        return Cbor::Integer(i);
+    }
+    if n.sign() == Sign::Minus {
+        let m: BigInt = -n - 1;
+        let (_, bytes) = m.to_bytes_be();
+        Cbor::Tag(3, Box::new(Cbor::Bytes(bytes)))
+    } else {
+        let (_, bytes) = n.to_bytes_be();
+        Cbor::Tag(2, Box::new(Cbor::Bytes(bytes)))
+    }
+}
+
+/// decimal fraction（タグ4: [指数 仮数]）をQi値に変換
+///
+/// Decimalで表せない場合は浮動小数点数にする
+fn decimal_fraction_to_value(parts: Vec<Cbor>) -> Value {
+    let mut parts = parts.into_iter();
+    let (Some(Cbor::Integer(exp)), Some(mantissa), None) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Value::Nil;
+    };
+    let mantissa = match mantissa {
+        Cbor::Integer(i) => BigInt::from(i128::from(i)),
+        Cbor::Tag(tag @ (2 | 3), inner) => match *inner {
+            Cbor::Bytes(b) => bignum_from_cbor(tag, &b),
+            _ => return Value::Nil,
+        },
+        _ => return Value::Nil,
+    };
+    let exp = i128::from(exp);
+    let exact = num_traits::ToPrimitive::to_i128(&mantissa).and_then(|m| {
+        let scale = u32::try_from(-exp).ok()?;
+        rust_decimal::Decimal::try_from_i128_with_scale(m, scale).ok()
+    });
+    match exact {
+        Some(d) => Value::Decimal(d),
+        None => {
+            let m = num_traits::ToPrimitive::to_f64(&mantissa).unwrap_or(f64::NAN);
+            Value::Float(m * 10f64.powi(exp.clamp(i32::MIN as i128, i32::MAX as i128) as i32))
+        }
+    }
+}
+
+/// Qi Valueをciborium::Valueに変換
+fn value_to_cbor(value: &Value) -> Result<Cbor, String> {
+    Ok(match value {
+        Value::Nil => Cbor::Null,
+        Value::Bool(b) => Cbor::Bool(*b),
+        Value::Integer(i) => Cbor::Integer((*i).into()),
+        Value::Float(f) => Cbor::Float(*f),
+        Value::BigInt(n) => bigint_to_cbor(n),
+        // 有理数に対応する標準タグはないため浮動小数点数で出力
+        Value::Ratio(_) => {
+            Cbor::Float(crate::builtins::numeric_helpers::number_to_f64(value).unwrap_or(f64::NAN))
+        }
+        // 10進数は decimal fraction [-scale mantissa] で誤差なく出力
+        Value::Decimal(d) => Cbor::Tag(
+            4,
+            Box::new(Cbor::Array(vec![
+                Cbor::Integer((-(d.scale() as i64)).into()),
+                bigint_to_cbor(&BigInt::from(d.mantissa())),
+            ])),
+        ),
+        Value::String(s) => Cbor::Text(s.clone()),
+        Value::Char(c) => Cbor::Text(c.to_string()),
+        Value::Keyword(k) => Cbor::Text(k.to_string()),
+        Value::Bytes(b) => Cbor::Bytes(b.to_vec()),
+        Value::Set(items) => return value_to_cbor(&Value::Vector(Value::sorted_set_items(items))),
+        Value::Vector(items) | Value::List(items) => {
+            Cbor::Array(items.iter().map(value_to_cbor).collect::<Result<_, _>>()?)
+        }
+        Value::Map(m) => {
+            let mut entries = Vec::with_capacity(m.len());
+            for (k, v) in m.iter() {
+                let key = match k {
+                    MapKey::Keyword(kw) => Cbor::Text(kw.to_string()),
+                    MapKey::String(s) => Cbor::Text(s.clone()),
+                    MapKey::Symbol(sym) => Cbor::Text(sym.to_string()),
+                    MapKey::Integer(i) => Cbor::Integer((*i).into()),
+                    MapKey::Char(c) => Cbor::Text(c.to_string()),
+                };
+                entries.push((key, value_to_cbor(v)?));
+            }
+            Cbor::Map(entries)
+        }
+        other => return Err(other.type_name().to_string()),
+    })
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category data/cbor
+/// @qi-doc:functions encode, decode
+pub const FUNCTIONS: super::NativeFunctions = &[
+    ("cbor/encode", native_encode),
+    ("cbor/decode", native_decode),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_rfc_examples() {
+        // RFC 8949 Appendix A: {"a": 1, "b": [2, 3]}
+        let bytes = vec![0xa2, 0x61, 0x61, 0x01, 0x61, 0x62, 0x82, 0x02, 0x03];
+        let decoded = native_decode(&[Value::Bytes(bytes.into())]).unwrap();
+        let mut m = crate::new_hashmap();
+        m.insert(MapKey::String("a".into()), Value::Integer(1));
+        m.insert(
+            MapKey::String("b".into()),
+            Value::Vector(vec![Value::Integer(2), Value::Integer(3)].into()),
+        );
+        assert_eq!(decoded, Value::Map(m));
+
+        let encoded = native_encode(&[decoded.clone()]).unwrap();
+        assert_eq!(native_decode(&[encoded]).unwrap(), decoded);
+    }
+
+    #[test]
+    fn test_exact_numbers() {
+        use std::sync::Arc;
+        let roundtrip = |v: Value| native_decode(&[native_encode(&[v]).unwrap()]).unwrap();
+
+        // bignum（タグ2/3）とCBOR整数の範囲内の多倍長整数
+        for n in [
+            BigInt::from(u64::MAX),
+            BigInt::from(i64::MAX) * 4,
+            BigInt::from(i64::MIN) * 4,
+        ] {
+            let v = Value::BigInt(Arc::new(n));
+            assert_eq!(roundtrip(v.clone()), v);
+        }
+        // RFC 8949: 2^64 は C2 49 01 00 00 00 00 00 00 00 00
+        let two64 = Value::BigInt(Arc::new(BigInt::from(u64::MAX) + 1));
+        assert_eq!(
+            native_encode(&[two64]).unwrap(),
+            Value::Bytes(vec![0xc2, 0x49, 1, 0, 0, 0, 0, 0, 0, 0, 0].into())
+        );
+
+        // decimal fraction（タグ4）
+        for s in ["1.5", "-0.001", "12345678901234567890.12"] {
+            let v = Value::Decimal(s.parse().unwrap());
+            assert_eq!(roundtrip(v.clone()), v);
+        }
+        // RFC 8949: 273.15 は C4 82 21 19 6A B3
+        let bytes = vec![0xc4, 0x82, 0x21, 0x19, 0x6a, 0xb3];
+        assert_eq!(
+            native_decode(&[Value::Bytes(bytes.into())]).unwrap(),
+            Value::Decimal("273.15".parse().unwrap())
+        );
+
+        let third = num_rational::BigRational::new(1.into(), 3.into());
+        assert_eq!(
+            roundtrip(Value::Ratio(Arc::new(third))),
+            Value::Float(1.0 / 3.0)
+        );
+    }
+}
@@ -4,8 +4,7 @@ use super::helpers::*;
 use crate::builtins::util::convert_string_map_to_mapkey;
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
-use crate::value::Value;
-use parking_lot::RwLock;
+use crate::value::{Atom, Value};
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::process::{Command, Stdio};
@@ -80,21 +79,21 @@ pub fn native_interactive(args: &[Value]) -> Result<Value, String> {
     handle.insert("pid".to_string(), Value::Integer(pid as i64));
     handle.insert(
         "stdin".to_string(),
-        Value::Atom(Arc::new(RwLock::new(Value::String(format!(
+        Value::Atom(Arc::new(Atom::new(Value::String(format!(
             "#<stdin:{}>",
             pid
         ))))),
     );
     handle.insert(
         "stdout".to_string(),
-        Value::Atom(Arc::new(RwLock::new(Value::String(format!(
+        Value::Atom(Arc::new(Atom::new(Value::String(format!(
             "#<stdout:{}>",
             pid
         ))))),
     );
     handle.insert(
         "stderr".to_string(),
-        Value::Atom(Arc::new(RwLock::new(Value::String(format!(
+        Value::Atom(Arc::new(Atom::new(Value::String(format!(
             "#<stderr:{}>",
             pid
         ))))),
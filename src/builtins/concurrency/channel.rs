@@ -1,6 +1,7 @@
 //! チャネル基本操作
 
 use crate::check_args;
+use crate::eval::Evaluator;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{Channel, Value};
 use crossbeam_channel::{bounded, unbounded};
@@ -22,13 +23,7 @@ pub fn native_chan(args: &[Value]) -> Result<Value, String> {
     let capacity = if args.is_empty() {
         None
     } else if args.len() == 1 {
-        match &args[0] {
-            Value::Integer(n) if *n >= 0 => Some(*n as usize),
-            Value::Integer(_) => {
-                return Err(fmt_msg(MsgKey::MustBeNonNegative, &["chan", "capacity"]))
-            }
-            _ => return Err(fmt_msg(MsgKey::MustBeInteger, &["chan", "capacity"])),
-        }
+        chan_capacity(&args[0])?
     } else {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["chan", "0 or 1"]));
     };
@@ -45,6 +40,33 @@ pub fn native_chan(args: &[Value]) -> Result<Value, String> {
     })))
 }
 
+/// チャネル容量の引数を解釈（nilは無制限）
+fn chan_capacity(value: &Value) -> Result<Option<usize>, String> {
+    match value {
+        Value::Integer(n) if *n >= 0 => Ok(Some(*n as usize)),
+        Value::Integer(_) => Err(fmt_msg(MsgKey::MustBeNonNegative, &["chan", "capacity"])),
+        Value::Nil => Ok(None),
+        _ => Err(fmt_msg(MsgKey::MustBeInteger, &["chan", "capacity"])),
+    }
+}
+
+/// チャネルを作成（トランスデューサ付き）
+///
+/// 送信された値をトランスデューサで変換してから受信側に渡します。
+/// 引数が2つでない場合は通常の `go/chan` と同じです。
+///
+/// 例:
+/// ```qi
+/// (def ch (go/chan 10 (comp (filter even?) (map inc))))
+/// ```
+pub fn native_chan_with_xform(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    if args.len() != 2 {
+        return native_chan(args);
+    }
+    let capacity = chan_capacity(&args[0])?;
+    crate::builtins::transducer::native_chan_with_transducer(capacity, &args[1], evaluator)
+}
+
 /// send! - チャネルに値を送信
 ///
 /// 引数:
@@ -3,7 +3,7 @@
 use super::promise::spawn_promise;
 use crate::builtins::value_helpers::to_positive_usize;
 use crate::check_args;
-use crate::eval::Evaluator;
+use crate::eval::{dynamic, Evaluator};
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{Channel, Value};
 use crossbeam_channel::{bounded, unbounded};
@@ -196,8 +196,10 @@ pub fn native_pipeline(args: &[Value], evaluator: &Evaluator) -> Result<Value, S
         let out_sender = out_sender.clone();
         let xf = xf.clone();
         let eval = evaluator.clone();
+        let bindings = dynamic::capture();
 
         std::thread::spawn(move || {
+            let _guard = dynamic::install(bindings);
             while let Ok(value) = in_receiver.recv() {
                 // 変換関数を適用
                 match eval.apply_function(&xf, &[value]) {
@@ -280,8 +282,10 @@ pub fn native_pipeline_map(args: &[Value], evaluator: &Evaluator) -> Result<Valu
         let out_sender = out_sender.clone();
         let f = Arc::clone(&f);
         let evaluator = Arc::clone(&evaluator);
+        let bindings = dynamic::capture();
 
         std::thread::spawn(move || {
+            let _guard = dynamic::install(bindings);
             while let Ok(msg) = in_receiver.recv() {
                 // [idx, value] の形式でメッセージを受信
                 if let Value::Vector(vec) = msg {
@@ -394,8 +398,10 @@ pub fn native_pipeline_filter(args: &[Value], evaluator: &Evaluator) -> Result<V
         let out_sender = out_sender.clone();
         let pred = Arc::clone(&pred);
         let evaluator = Arc::clone(&evaluator);
+        let bindings = dynamic::capture();
 
         std::thread::spawn(move || {
+            let _guard = dynamic::install(bindings);
             // フィルター不一致を表す内部マーカー
             let filtered_marker = Value::Keyword(std::sync::Arc::from("__filtered__"));
 
@@ -1,7 +1,7 @@
 //! Promise/非同期操作
 
 use crate::check_args;
-use crate::eval::Evaluator;
+use crate::eval::{dynamic, Evaluator};
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{Channel, Value};
 use crossbeam_channel::bounded;
@@ -47,7 +47,10 @@ where
             .clone()
     };
 
+    // 起動元スレッドの動的束縛（binding）を引き継ぐ
+    let bindings = dynamic::capture();
     std::thread::spawn(move || {
+        let _guard = dynamic::install(bindings);
         f(sender);
     });
 
@@ -1,7 +1,7 @@
 //! スコープ・キャンセル・並列実行
 
 use crate::check_args;
-use crate::eval::Evaluator;
+use crate::eval::{dynamic, Evaluator};
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{Channel, Scope, Value};
 use crossbeam_channel::unbounded;
@@ -72,9 +72,11 @@ pub fn native_scope_go(args: &[Value], evaluator: &Evaluator) -> Result<Value, S
     }));
 
     let evaluator_clone = evaluator.clone();
+    let bindings = dynamic::capture();
 
-    // 新しいスレッドで実行
+    // 新しいスレッドで実行（起動元の動的束縛を引き継ぐ）
     std::thread::spawn(move || {
+        let _guard = dynamic::install(bindings);
         // スコープがキャンセルされている場合は実行をスキップ
         if *scope.cancelled.read() {
             let _ = sender.send(Value::Nil);
@@ -221,6 +223,7 @@ pub fn native_parallel_do(args: &[Value], evaluator: &Evaluator) -> Result<Value
 
     // Arcで共有することでcloneコストを削減
     let evaluator = Arc::new(evaluator.clone());
+    let bindings = dynamic::capture();
 
     // Rayonのスレッドプールを使って並列実行（スレッド枯渇を防ぐ）
     use rayon::prelude::*;
@@ -231,8 +234,8 @@ pub fn native_parallel_do(args: &[Value], evaluator: &Evaluator) -> Result<Value
             let func = Arc::new(func.clone());
             let evaluator = Arc::clone(&evaluator);
 
-            // スレッドプール内で実行
-            let result = evaluator.apply_function(&func, &[]);
+            // スレッドプール内で実行（スレッドは使い回されるためタスクごとに束縛を設定）
+            let result = dynamic::with_bindings(&bindings, || evaluator.apply_function(&func, &[]));
             // エラー情報を保持（Railway Oriented Programming）
             match result {
                 Ok(v) => v,
@@ -1,13 +1,15 @@
 //! Coreコレクション操作関数
 //!
-//! リスト基本（29個）: first, rest, last, nth, take, drop, map, filter, reduce, pmap, cons, conj,
+//! リスト基本（30個）: first, rest, last, nth, take, drop, map, mapcat, filter, reduce, pmap, cons, conj,
 //!                     concat, flatten, range, repeat, reverse, zip, sort, distinct, find, every, some,
 //!                     take-while, drop-while, len, count, split-at, interleave
 //! マップ基本（9個）: get, keys, vals, assoc, dissoc, merge, get-in, update-in, update
+//! セット基本（3個）: set, disj, contains?
 //!
-//! 注: map, filter, reduce, pmap, take-while, drop-while, find, every, some, update-in, update
+//! 注: map, mapcat, filter, reduce, pmap, take-while, drop-while, find, every, some, update-in, update
 //!     は Evaluator が必要なため、mod.rs で別途エクスポートされます
 
+use crate::builtins::numeric_helpers::compare_numbers;
 use crate::builtins::value_helpers::usize_to_int_value;
 use crate::check_args;
 use crate::i18n::{fmt_msg, msg, MsgKey};
@@ -79,6 +81,7 @@ pub fn native_len(args: &[Value]) -> Result<Value, String> {
     match &args[0] {
         Value::List(v) | Value::Vector(v) => usize_to_int_value(v.len(), "len"),
         Value::Map(m) => usize_to_int_value(m.len(), "len"),
+        Value::Set(s) => usize_to_int_value(s.len(), "len"),
         Value::String(s) => usize_to_int_value(s.len(), "len"),
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
@@ -119,9 +122,13 @@ pub fn native_conj(args: &[Value]) -> Result<Value, String> {
     if args.len() < 2 {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["conj", "2"]));
     }
+    if let Value::Set(set) = &args[0] {
+        // Setは要素を追加（重複は無視）
+        return Value::set_from(set.iter().cloned().chain(args[1..].iter().cloned()));
+    }
     let seq = args[0]
         .as_seq()
-        .ok_or_else(|| fmt_msg(MsgKey::TypeOnly, &["conj", "lists or vectors"]))?;
+        .ok_or_else(|| fmt_msg(MsgKey::TypeOnly, &["conj", "lists, vectors or sets"]))?;
 
     let mut new_seq = seq.clone();
     match &args[0] {
@@ -259,7 +266,9 @@ pub fn native_reverse(args: &[Value]) -> Result<Value, String> {
 
 /// take - リストの最初のn要素を取得
 pub fn native_take(args: &[Value]) -> Result<Value, String> {
-    check_args!(args, 2, "take");
+    if args.len() != 1 && args.len() != 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["take"]));
+    }
     let n = match &args[0] {
         Value::Integer(i) if *i >= 0 && (*i as u64) <= usize::MAX as u64 => *i as usize,
         Value::Integer(i) if *i >= 0 => {
@@ -269,9 +278,19 @@ pub fn native_take(args: &[Value]) -> Result<Value, String> {
         _ => return Err(fmt_msg(MsgKey::FirstArgMustBe, &["take", "an integer"])),
     };
 
+    // (take n) はトランスデューサを返す
+    if args.len() == 1 {
+        return Ok(super::transducer::transducer("take", &[args[0].clone()]));
+    }
+
+    // ストリームは遅延ストリームを返す
+    if matches!(args[1], Value::Stream(_)) {
+        return super::stream::native_stream_take(args);
+    }
+
     let seq = args[1]
         .as_seq()
-        .ok_or_else(|| fmt_msg(MsgKey::TypeOnly, &["take", "lists or vectors"]))?;
+        .ok_or_else(|| fmt_msg(MsgKey::TypeOnly, &["take", "lists, vectors or streams"]))?;
 
     let result: im::Vector<Value> = seq.iter().take(n).cloned().collect();
 
@@ -295,9 +314,14 @@ pub fn native_drop(args: &[Value]) -> Result<Value, String> {
         _ => return Err(fmt_msg(MsgKey::FirstArgMustBe, &["drop", "an integer"])),
     };
 
+    // ストリームは遅延ストリームを返す
+    if matches!(args[1], Value::Stream(_)) {
+        return super::stream::native_stream_drop(args);
+    }
+
     let seq = args[1]
         .as_seq()
-        .ok_or_else(|| fmt_msg(MsgKey::TypeOnly, &["drop", "lists or vectors"]))?;
+        .ok_or_else(|| fmt_msg(MsgKey::TypeOnly, &["drop", "lists, vectors or streams"]))?;
 
     let result: im::Vector<Value> = seq.iter().skip(n).cloned().collect();
 
@@ -313,23 +337,30 @@ pub fn native_drop(args: &[Value]) -> Result<Value, String> {
 /// 戻り値は入力コレクションの型を維持します
 pub fn native_sort(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "sort");
-    let seq = args[0]
-        .as_seq()
-        .ok_or_else(|| fmt_msg(MsgKey::TypeOnly, &["sort", "lists or vectors"]))?;
-
-    let mut sorted: Vec<Value> = seq.iter().cloned().collect();
+    let mut sorted: Vec<Value> = match &args[0] {
+        Value::List(v) | Value::Vector(v) => v.iter().cloned().collect(),
+        // セットは順序を持たないため、ソート結果はベクタで返す
+        Value::Set(s) => s.iter().cloned().collect(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::TypeOnly,
+                &["sort", "lists, vectors or sets"],
+            ))
+        }
+    };
     sorted.sort_by(|a, b| match (a, b) {
         (Value::Integer(x), Value::Integer(y)) => x.cmp(y),
         (Value::Float(x), Value::Float(y)) => x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal),
         (Value::String(x), Value::String(y)) => x.cmp(y),
-        _ => std::cmp::Ordering::Equal,
+        (Value::Char(x), Value::Char(y)) => x.cmp(y),
+        // 多倍長整数・有理数・10進数を含む数値同士は数値塔で比較
+        _ => compare_numbers(a, b).unwrap_or(std::cmp::Ordering::Equal),
     });
 
     // 元の型を維持
     Ok(match &args[0] {
         Value::List(_) => Value::List(sorted.into()),
-        Value::Vector(_) => Value::Vector(sorted.into()),
-        _ => unreachable!(),
+        _ => Value::Vector(sorted.into()),
     })
 }
 
@@ -418,6 +449,7 @@ pub fn native_keys(args: &[Value]) -> Result<Value, String> {
                     crate::value::MapKey::Symbol(sym) => Value::Symbol(sym.clone()),
                     crate::value::MapKey::String(s) => Value::String(s.clone()),
                     crate::value::MapKey::Integer(n) => Value::Integer(*n),
+                    crate::value::MapKey::Char(c) => Value::Char(*c),
                 })
                 .collect();
             Ok(Value::List(keys))
@@ -536,13 +568,64 @@ pub fn native_get_in(args: &[Value]) -> Result<Value, String> {
     Ok(current)
 }
 
+// ========================================
+// セット操作（Evaluator不要）
+// ========================================
+
+/// set - コレクションからセットを作成（重複は除去）
+pub fn native_set(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "set");
+    match &args[0] {
+        Value::Nil => Ok(Value::Set(crate::new_hashset())),
+        Value::List(v) | Value::Vector(v) => Value::set_from(v.iter().cloned()),
+        Value::Set(_) => Ok(args[0].clone()),
+        _ => Err(fmt_msg(
+            MsgKey::TypeOnly,
+            &["set", "lists, vectors or sets"],
+        )),
+    }
+}
+
+/// disj - セットから要素を取り除く
+pub fn native_disj(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["disj", "1"]));
+    }
+    match &args[0] {
+        Value::Set(set) => {
+            let mut new_set = set.clone();
+            for item in &args[1..] {
+                new_set.remove(item);
+            }
+            Ok(Value::Set(new_set))
+        }
+        Value::Nil => Ok(Value::Nil),
+        _ => Err(fmt_msg(MsgKey::TypeOnly, &["disj", "sets"])),
+    }
+}
+
+/// contains? - セットに要素が含まれるか、マップにキーが存在するか
+pub fn native_contains(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 2, "contains?");
+    match &args[0] {
+        // ハッシュ化できない値はセットに入り得ないので、ハッシュ計算せずにfalse
+        Value::Set(set) => Ok(Value::Bool(args[1].is_hashable() && set.contains(&args[1]))),
+        Value::Map(m) => match args[1].to_map_key() {
+            Ok(key) => Ok(Value::Bool(m.contains_key(&key))),
+            Err(_) => Ok(Value::Bool(false)),
+        },
+        Value::Nil => Ok(Value::Bool(false)),
+        _ => Err(fmt_msg(MsgKey::TypeOnly, &["contains?", "sets or maps"])),
+    }
+}
+
 // ========================================
 // 関数登録テーブル
 // ========================================
 
 /// 登録すべき関数のリスト
 /// @qi-doc:category core/collections
-/// @qi-doc:functions first, rest, last, nth, len, count, cons, conj, concat, reverse, sort, distinct, range, repeat, take, drop, zip, etc.
+/// @qi-doc:functions first, rest, last, nth, len, count, cons, conj, concat, reverse, sort, distinct, range, repeat, take, drop, zip, set, disj, contains?, etc.
 pub const FUNCTIONS: super::NativeFunctions = &[
     // リスト操作
     ("first", native_first),
@@ -571,4 +654,8 @@ pub const FUNCTIONS: super::NativeFunctions = &[
     ("dissoc", native_dissoc),
     ("merge", native_merge),
     ("get-in", native_get_in),
+    // セット操作
+    ("set", native_set),
+    ("disj", native_disj),
+    ("contains?", native_contains),
 ];
@@ -1,6 +1,6 @@
 //! Core高階関数
 //!
-//! 関数基礎（5個）: identity, constantly, partial, comp, apply
+//! 関数基礎（6個）: identity, constantly, partial, comp, apply, trampoline
 
 use crate::check_args;
 use crate::eval::Evaluator;
@@ -77,6 +77,11 @@ pub fn native_comp(args: &[Value], _evaluator: &Evaluator) -> Result<Value, Stri
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["comp", "1"]));
     }
 
+    // トランスデューサ同士は左から順に適用される1つのトランスデューサに合成
+    if let Some(xform) = super::transducer::compose(args) {
+        return Ok(xform);
+    }
+
     // 1つの関数の場合はそのまま返す
     if args.len() == 1 {
         return Ok(args[0].clone());
@@ -127,15 +132,32 @@ pub fn native_apply(args: &[Value], evaluator: &Evaluator) -> Result<Value, Stri
     }
 }
 
+/// trampoline - 結果が関数である間、引数なしで呼び出しを繰り返す
+///
+/// 相互再帰を「次に呼ぶ関数（サンク）を返す」形で書くことで、スタックを消費せずに実行できる
+///
+/// 注意: この関数はEvaluatorを必要とするため、mod.rsでの登録時に特別な処理が必要です
+pub fn native_trampoline(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["trampoline", "1"]));
+    }
+
+    let mut result = evaluator.apply_function(&args[0], &args[1..])?;
+    while matches!(result, Value::Function(_) | Value::NativeFunc(_)) {
+        result = evaluator.apply_function(&result, &[])?;
+    }
+    Ok(result)
+}
+
 // ========================================
 // 関数登録テーブル
 // ========================================
 
 /// 登録すべき関数のリスト（Evaluator不要な関数のみ）
 /// @qi-doc:category core/functions
-/// @qi-doc:functions identity, constantly, partial, comp, apply
+/// @qi-doc:functions identity, constantly, partial, comp, apply, trampoline
 ///
-/// 注意: comp, applyはEvaluatorが必要なため、mod.rsで別途登録されます
+/// 注意: comp, apply, trampolineはEvaluatorが必要なため、mod.rsで別途登録されます
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("identity", native_identity),
     ("constantly", native_constantly),
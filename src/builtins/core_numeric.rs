@@ -1,57 +1,32 @@
 //! Core数値・比較演算関数
 //!
-//! 算術演算（11個）: +, -, *, /, %, inc, dec, abs, min, max, sum
+//! 算術演算（12個）: +, -, *, /, quot, %, inc, dec, abs, min, max, sum
 //! 比較演算（6個）: =, !=, <, >, <=, >=
-//! 合計17個のCore関数
+//! 合計18個のCore関数
+//!
+//! 数値はInteger → BigInt → Ratio → Decimal → Floatの順に昇格する（numeric_helpers参照）
 
 use crate::builtins::numeric_helpers::{
-    checked_abs_value, checked_add_value, checked_int_add, checked_int_mul, checked_int_sub,
-    checked_neg_value, checked_sub_value,
+    absolute, arith, compare_numbers, is_number, negate, number_to_f64, truncate_to_integer,
+    ArithOp,
 };
 use crate::check_args;
-use crate::i18n::{fmt_msg, msg, MsgKey};
+use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::Value;
 
 // ========================================
-// 算術演算（11個）
+// 算術演算（12個）
 // ========================================
 
 /// + - 加算
+///
+/// i64をはみ出すとBigIntに昇格する。Decimal・Floatが混ざるとそれぞれの型で計算する
 pub fn native_add(args: &[Value]) -> Result<Value, String> {
-    let mut int_sum = 0i64;
-    let mut float_sum = 0.0f64;
-    let mut has_float = false;
-
+    let mut sum = Value::Integer(0);
     for arg in args {
-        match arg {
-            Value::Integer(n) => {
-                if has_float {
-                    float_sum += *n as f64;
-                } else {
-                    int_sum = checked_int_add(int_sum, *n, "+")?;
-                }
-            }
-            Value::Float(f) => {
-                if !has_float {
-                    float_sum = int_sum as f64;
-                    has_float = true;
-                }
-                float_sum += f;
-            }
-            _ => {
-                return Err(fmt_msg(
-                    MsgKey::TypeOnlyWithDebug,
-                    &["+", "numbers", &format!("{:?}", arg)],
-                ))
-            }
-        }
-    }
-
-    if has_float {
-        Ok(Value::Float(float_sum))
-    } else {
-        Ok(Value::Integer(int_sum))
+        sum = arith(ArithOp::Add, &sum, arg, "+")?;
     }
+    Ok(sum)
 }
 
 /// - - 減算（または符号反転）
@@ -60,323 +35,126 @@ pub fn native_sub(args: &[Value]) -> Result<Value, String> {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["-", "1"]));
     }
 
-    // 符号反転（単項マイナス）
+    // 符号反転（単項マイナス）: i64::MIN の符号反転はBigIntになる
     if args.len() == 1 {
-        return match &args[0] {
-            Value::Integer(n) => {
-                // ⚠️ SAFETY: i64::MIN の符号反転はオーバーフローするため checked_neg_value() を使用
-                checked_neg_value(*n, "unary negation")
-            }
-            Value::Float(f) => Ok(Value::Float(-f)),
-            _ => Err(fmt_msg(
-                MsgKey::TypeOnlyWithDebug,
-                &["-", "numbers", &format!("{:?}", args[0])],
-            )),
-        };
+        return negate(&args[0], "-");
     }
 
-    // 減算
-    let mut has_float = false;
-    let mut result_int = 0i64;
-    let mut result_float = 0.0f64;
-
-    // 最初の値を設定
-    match &args[0] {
-        Value::Integer(n) => result_int = *n,
-        Value::Float(f) => {
-            has_float = true;
-            result_float = *f;
-        }
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::TypeOnlyWithDebug,
-                &["-", "numbers", &format!("{:?}", args[0])],
-            ))
-        }
+    if !is_number(&args[0]) {
+        return Err(fmt_msg(
+            MsgKey::TypeOnlyWithDebug,
+            &["-", "numbers", &format!("{:?}", args[0])],
+        ));
     }
-
-    // 残りの値を減算
+    let mut result = args[0].clone();
     for arg in &args[1..] {
-        match arg {
-            Value::Integer(n) => {
-                if has_float {
-                    result_float -= *n as f64;
-                } else {
-                    result_int = checked_int_sub(result_int, *n, "-")?;
-                }
-            }
-            Value::Float(f) => {
-                if !has_float {
-                    result_float = result_int as f64;
-                    has_float = true;
-                }
-                result_float -= f;
-            }
-            _ => {
-                return Err(fmt_msg(
-                    MsgKey::TypeOnlyWithDebug,
-                    &["-", "numbers", &format!("{:?}", arg)],
-                ))
-            }
-        }
-    }
-
-    if has_float {
-        Ok(Value::Float(result_float))
-    } else {
-        Ok(Value::Integer(result_int))
+        result = arith(ArithOp::Sub, &result, arg, "-")?;
     }
+    Ok(result)
 }
 
 /// * - 乗算
 pub fn native_mul(args: &[Value]) -> Result<Value, String> {
-    let mut int_product = 1i64;
-    let mut float_product = 1.0f64;
-    let mut has_float = false;
-
+    let mut product = Value::Integer(1);
     for arg in args {
-        match arg {
-            Value::Integer(n) => {
-                if has_float {
-                    float_product *= *n as f64;
-                } else {
-                    int_product = checked_int_mul(int_product, *n, "*")?;
-                }
-            }
-            Value::Float(f) => {
-                if !has_float {
-                    float_product = int_product as f64;
-                    has_float = true;
-                }
-                float_product *= f;
-            }
-            _ => {
-                return Err(fmt_msg(
-                    MsgKey::TypeOnlyWithDebug,
-                    &["*", "numbers", &format!("{:?}", arg)],
-                ))
-            }
-        }
-    }
-
-    if has_float {
-        Ok(Value::Float(float_product))
-    } else {
-        Ok(Value::Integer(int_product))
+        product = arith(ArithOp::Mul, &product, arg, "*")?;
     }
+    Ok(product)
 }
 
 /// / - 除算
+///
+/// 整数同士で割り切れない場合は有理数（1/3）を返す。整数の商が欲しい場合は quot を使う
 pub fn native_div(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "/");
-    match (&args[0], &args[1]) {
-        (Value::Integer(a), Value::Integer(b)) => {
-            if *b == 0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Integer(a / b))
-        }
-        (Value::Float(a), Value::Float(b)) => {
-            if *b == 0.0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Float(a / b))
-        }
-        (Value::Integer(a), Value::Float(b)) => {
-            if *b == 0.0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Float(*a as f64 / b))
-        }
-        (Value::Float(a), Value::Integer(b)) => {
-            if *b == 0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Float(a / *b as f64))
-        }
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["/", "numbers"])),
+    arith(ArithOp::Div, &args[0], &args[1], "/")
+}
+
+/// quot - 整数除算（0方向への切り捨て）
+pub fn native_quot(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 2, "quot");
+    let quotient = arith(ArithOp::Div, &args[0], &args[1], "quot")?;
+    match quotient {
+        // Decimal・Floatの商も小数部を切り捨てて同じ型で返す
+        Value::Decimal(d) => Ok(Value::Decimal(d.trunc())),
+        Value::Float(f) => Ok(Value::Float(f.trunc())),
+        other => truncate_to_integer(&other, "quot"),
     }
 }
 
 /// % - 剰余
+///
 /// 整数・浮動小数の両方に対応。浮動小数が1つでも含まれる場合は浮動小数で返す
 pub fn native_mod(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "%");
-
-    match (&args[0], &args[1]) {
-        (Value::Integer(a), Value::Integer(b)) => {
-            if *b == 0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Integer(a % b))
-        }
-        (Value::Integer(a), Value::Float(b)) => {
-            if *b == 0.0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Float((*a as f64).rem_euclid(*b)))
-        }
-        (Value::Float(a), Value::Integer(b)) => {
-            if *b == 0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Float(a.rem_euclid(*b as f64)))
-        }
-        (Value::Float(a), Value::Float(b)) => {
-            if *b == 0.0 {
-                return Err(msg(MsgKey::DivisionByZero).to_string());
-            }
-            Ok(Value::Float(a.rem_euclid(*b)))
-        }
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["%", "numbers"])),
-    }
+    arith(ArithOp::Rem, &args[0], &args[1], "%")
 }
 
 /// abs - 絶対値
 pub fn native_abs(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "abs");
-    match &args[0] {
-        Value::Integer(n) => {
-            // ⚠️ SAFETY: i64::MIN の絶対値は i64 の範囲外のため checked_abs_value() を使用
-            checked_abs_value(*n, "abs")
-        }
-        Value::Float(f) => Ok(Value::Float(f.abs())),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["abs", "numbers"])),
+    if !is_number(&args[0]) {
+        return Err(fmt_msg(MsgKey::TypeOnly, &["abs", "numbers"]));
     }
+    absolute(&args[0], "abs")
 }
 
-/// min - 最小値
-/// 整数・浮動小数の両方に対応。浮動小数が1つでも含まれる場合は浮動小数で返す
-pub fn native_min(args: &[Value]) -> Result<Value, String> {
+/// min/maxの共通処理
+/// 数値として比較して元の値を返す。浮動小数が1つでも含まれる場合は浮動小数で返す
+fn select_extreme(args: &[Value], name: &str, wanted: std::cmp::Ordering) -> Result<Value, String> {
     if args.is_empty() {
-        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["min", "1"]));
+        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &[name, "1"]));
     }
 
+    let mut best = &args[0];
     let mut has_float = false;
-    let mut min_int = i64::MAX;
-    let mut min_float = f64::INFINITY;
-
-    // 最初の値を設定
-    match &args[0] {
-        Value::Integer(n) => min_int = *n,
-        Value::Float(f) => {
-            has_float = true;
-            min_float = *f;
+    for arg in args {
+        if !is_number(arg) {
+            return Err(fmt_msg(MsgKey::TypeOnly, &[name, "numbers"]));
         }
-        _ => return Err(fmt_msg(MsgKey::TypeOnly, &["min", "numbers"])),
-    }
-
-    // 残りの値と比較
-    for arg in &args[1..] {
-        match arg {
-            Value::Integer(n) => {
-                if has_float {
-                    if (*n as f64) < min_float {
-                        min_float = *n as f64;
-                    }
-                } else if *n < min_int {
-                    min_int = *n;
-                }
-            }
-            Value::Float(f) => {
-                if !has_float {
-                    min_float = min_int as f64;
-                    has_float = true;
-                }
-                if *f < min_float {
-                    min_float = *f;
-                }
-            }
-            _ => return Err(fmt_msg(MsgKey::TypeOnly, &["min", "numbers"])),
+        has_float |= matches!(arg, Value::Float(_));
+        if compare_numbers(arg, best) == Some(wanted) {
+            best = arg;
         }
     }
 
     if has_float {
-        Ok(Value::Float(min_float))
+        Ok(Value::Float(number_to_f64(best).unwrap_or(f64::NAN)))
     } else {
-        Ok(Value::Integer(min_int))
+        Ok(best.clone())
     }
 }
 
+/// min - 最小値
+/// 整数・浮動小数の両方に対応。浮動小数が1つでも含まれる場合は浮動小数で返す
+pub fn native_min(args: &[Value]) -> Result<Value, String> {
+    select_extreme(args, "min", std::cmp::Ordering::Less)
+}
+
 /// max - 最大値
 /// 整数・浮動小数の両方に対応。浮動小数が1つでも含まれる場合は浮動小数で返す
 pub fn native_max(args: &[Value]) -> Result<Value, String> {
-    if args.is_empty() {
-        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["max", "1"]));
-    }
-
-    let mut has_float = false;
-    let mut max_int = i64::MIN;
-    let mut max_float = f64::NEG_INFINITY;
-
-    // 最初の値を設定
-    match &args[0] {
-        Value::Integer(n) => max_int = *n,
-        Value::Float(f) => {
-            has_float = true;
-            max_float = *f;
-        }
-        _ => return Err(fmt_msg(MsgKey::TypeOnly, &["max", "numbers"])),
-    }
-
-    // 残りの値と比較
-    for arg in &args[1..] {
-        match arg {
-            Value::Integer(n) => {
-                if has_float {
-                    if (*n as f64) > max_float {
-                        max_float = *n as f64;
-                    }
-                } else if *n > max_int {
-                    max_int = *n;
-                }
-            }
-            Value::Float(f) => {
-                if !has_float {
-                    max_float = max_int as f64;
-                    has_float = true;
-                }
-                if *f > max_float {
-                    max_float = *f;
-                }
-            }
-            _ => return Err(fmt_msg(MsgKey::TypeOnly, &["max", "numbers"])),
-        }
-    }
-
-    if has_float {
-        Ok(Value::Float(max_float))
-    } else {
-        Ok(Value::Integer(max_int))
-    }
+    select_extreme(args, "max", std::cmp::Ordering::Greater)
 }
 
 /// inc - インクリメント
-/// 整数・浮動小数の両方に対応
+/// i64::MAX + 1 はBigIntになる
 pub fn native_inc(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "inc");
-    match &args[0] {
-        Value::Integer(n) => {
-            // ⚠️ SAFETY: i64::MAX + 1 はオーバーフローするため checked_add_value() を使用
-            checked_add_value(*n, 1, "inc")
-        }
-        Value::Float(f) => Ok(Value::Float(f + 1.0)),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["inc", "numbers"])),
+    if !is_number(&args[0]) {
+        return Err(fmt_msg(MsgKey::TypeOnly, &["inc", "numbers"]));
     }
+    arith(ArithOp::Add, &args[0], &Value::Integer(1), "inc")
 }
 
 /// dec - デクリメント
-/// 整数・浮動小数の両方に対応
+/// i64::MIN - 1 はBigIntになる
 pub fn native_dec(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "dec");
-    match &args[0] {
-        Value::Integer(n) => {
-            // ⚠️ SAFETY: i64::MIN - 1 はアンダーフローするため checked_sub_value() を使用
-            checked_sub_value(*n, 1, "dec")
-        }
-        Value::Float(f) => Ok(Value::Float(f - 1.0)),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["dec", "numbers"])),
+    if !is_number(&args[0]) {
+        return Err(fmt_msg(MsgKey::TypeOnly, &["dec", "numbers"]));
     }
+    arith(ArithOp::Sub, &args[0], &Value::Integer(1), "dec")
 }
 
 /// sum - 合計
@@ -385,36 +163,14 @@ pub fn native_sum(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "sum");
     match &args[0] {
         Value::List(items) | Value::Vector(items) => {
-            let mut int_sum = 0i64;
-            let mut float_sum = 0.0f64;
-            let mut has_float = false;
-
+            let mut sum = Value::Integer(0);
             for item in items {
-                match item {
-                    Value::Integer(n) => {
-                        if has_float {
-                            float_sum += *n as f64;
-                        } else {
-                            // ⚠️ SAFETY: 整数の合計でオーバーフローする可能性があるため checked_int_add() を使用
-                            int_sum = checked_int_add(int_sum, *n, "sum")?;
-                        }
-                    }
-                    Value::Float(f) => {
-                        if !has_float {
-                            float_sum = int_sum as f64;
-                            has_float = true;
-                        }
-                        float_sum += f;
-                    }
-                    _ => return Err(fmt_msg(MsgKey::TypeOnly, &["sum (elements)", "numbers"])),
+                if !is_number(item) {
+                    return Err(fmt_msg(MsgKey::TypeOnly, &["sum (elements)", "numbers"]));
                 }
+                sum = arith(ArithOp::Add, &sum, item, "sum")?;
             }
-
-            if has_float {
-                Ok(Value::Float(float_sum))
-            } else {
-                Ok(Value::Integer(int_sum))
-            }
+            Ok(sum)
         }
         _ => Err(fmt_msg(MsgKey::TypeOnly, &["sum", "lists or vectors"])),
     }
@@ -427,9 +183,14 @@ pub fn native_sum(args: &[Value]) -> Result<Value, String> {
 /// 値の等価性を判定するヘルパー関数
 /// ListとVectorは内容が同じなら等しいと見なす
 /// IntegerとFloatは数値的に等価なら等しいと見なす（Lisp系言語の一般的な仕様）
+/// BigInt・Ratio・Decimalも数値として比較する（1.50M と 1.5M、2 と 2M は等しい）
 fn values_equal(a: &Value, b: &Value) -> bool {
     use std::ptr;
     match (a, b) {
+        (Value::BigInt(_) | Value::Ratio(_) | Value::Decimal(_), _)
+        | (_, Value::BigInt(_) | Value::Ratio(_) | Value::Decimal(_)) => {
+            compare_numbers(a, b) == Some(std::cmp::Ordering::Equal)
+        }
         (Value::Nil, Value::Nil) => true,
         (Value::Bool(a), Value::Bool(b)) => a == b,
         (Value::Integer(a), Value::Integer(b)) => a == b,
@@ -454,10 +215,12 @@ fn values_equal(a: &Value, b: &Value) -> bool {
                 && a.iter()
                     .all(|(k, v)| b.get(k).is_some_and(|bv| values_equal(v, bv)))
         }
+        (Value::Set(a), Value::Set(b)) => a == b,
         (Value::Function(a), Value::Function(b)) => ptr::eq(&**a, &**b),
         (Value::NativeFunc(a), Value::NativeFunc(b)) => a.name == b.name,
         (Value::Macro(a), Value::Macro(b)) => ptr::eq(&**a, &**b),
         (Value::Atom(a), Value::Atom(b)) => ptr::eq(&**a, &**b),
+        (Value::Ref(a), Value::Ref(b)) => ptr::eq(&**a, &**b),
         (Value::Channel(a), Value::Channel(b)) => ptr::eq(&**a, &**b),
         (Value::Uvar(a), Value::Uvar(b)) => a == b,
         _ => false,
@@ -476,52 +239,36 @@ pub fn native_ne(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Bool(!values_equal(&args[0], &args[1])))
 }
 
+/// 比較演算の共通処理（数値以外はエラー、NaNを含む比較はNone）
+fn compare(a: &Value, b: &Value, name: &str) -> Result<Option<std::cmp::Ordering>, String> {
+    if !is_number(a) || !is_number(b) {
+        return Err(fmt_msg(MsgKey::TypeOnly, &[name, "numbers"]));
+    }
+    Ok(compare_numbers(a, b))
+}
+
 /// < - 小なり比較
 pub fn native_lt(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "<");
-    match (&args[0], &args[1]) {
-        (Value::Integer(a), Value::Integer(b)) => Ok(Value::Bool(a < b)),
-        (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a < b)),
-        (Value::Integer(a), Value::Float(b)) => Ok(Value::Bool((*a as f64) < *b)),
-        (Value::Float(a), Value::Integer(b)) => Ok(Value::Bool(*a < (*b as f64))),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["<", "numbers"])),
-    }
+    compare(&args[0], &args[1], "<").map(|o| Value::Bool(o.is_some_and(|o| o.is_lt())))
 }
 
 /// > - 大なり比較
 pub fn native_gt(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, ">");
-    match (&args[0], &args[1]) {
-        (Value::Integer(a), Value::Integer(b)) => Ok(Value::Bool(a > b)),
-        (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a > b)),
-        (Value::Integer(a), Value::Float(b)) => Ok(Value::Bool((*a as f64) > *b)),
-        (Value::Float(a), Value::Integer(b)) => Ok(Value::Bool(*a > (*b as f64))),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &[">", "numbers"])),
-    }
+    compare(&args[0], &args[1], ">").map(|o| Value::Bool(o.is_some_and(|o| o.is_gt())))
 }
 
 /// <= - 小なりイコール比較
 pub fn native_le(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "<=");
-    match (&args[0], &args[1]) {
-        (Value::Integer(a), Value::Integer(b)) => Ok(Value::Bool(a <= b)),
-        (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a <= b)),
-        (Value::Integer(a), Value::Float(b)) => Ok(Value::Bool((*a as f64) <= *b)),
-        (Value::Float(a), Value::Integer(b)) => Ok(Value::Bool(*a <= (*b as f64))),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["<=", "numbers"])),
-    }
+    compare(&args[0], &args[1], "<=").map(|o| Value::Bool(o.is_some_and(|o| o.is_le())))
 }
 
 /// >= - 大なりイコール比較
 pub fn native_ge(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, ">=");
-    match (&args[0], &args[1]) {
-        (Value::Integer(a), Value::Integer(b)) => Ok(Value::Bool(a >= b)),
-        (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a >= b)),
-        (Value::Integer(a), Value::Float(b)) => Ok(Value::Bool((*a as f64) >= *b)),
-        (Value::Float(a), Value::Integer(b)) => Ok(Value::Bool(*a >= (*b as f64))),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &[">=", "numbers"])),
-    }
+    compare(&args[0], &args[1], ">=").map(|o| Value::Bool(o.is_some_and(|o| o.is_ge())))
 }
 
 // ========================================
@@ -530,12 +277,13 @@ pub fn native_ge(args: &[Value]) -> Result<Value, String> {
 
 /// 登録すべき関数のリスト
 /// @qi-doc:category core/numeric
-/// @qi-doc:functions +, -, *, /, %, abs, min, max, inc, dec, sum, =, <, >, <=, >=
+/// @qi-doc:functions +, -, *, /, quot, %, abs, min, max, inc, dec, sum, =, <, >, <=, >=
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("+", native_add),
     ("-", native_sub),
     ("*", native_mul),
     ("/", native_div),
+    ("quot", native_quot),
     ("%", native_mod),
     ("abs", native_abs),
     ("min", native_min),
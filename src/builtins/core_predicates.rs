@@ -1,14 +1,16 @@
 //! Core述語・型判定関数
 //!
-//! 型チェック（13個）: nil?, list?, vector?, map?, string?, bytes?, integer?, float?, number?, keyword?, function?, atom?, stream?
+//! 型チェック（17個）: nil?, list?, vector?, map?, set?, string?, char?, bytes?, integer?, float?, decimal?, ratio?, number?, keyword?, function?, atom?, stream?
 //! コレクション（3個）: coll?, sequential?, empty?
 //! 状態（4個）: some?, true?, false?, error?
 //! 数値（5個）: even?, odd?, positive?, negative?, zero?
-//! 合計25個のCore関数
+//! 合計29個のCore関数
 
+use crate::builtins::numeric_helpers::{compare_numbers, is_number};
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::Value;
+use std::cmp::Ordering;
 
 // ========================================
 // 型チェック（11個）
@@ -38,6 +40,12 @@ pub fn native_map_q(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Bool(matches!(args[0], Value::Map(_))))
 }
 
+/// set? - セットかどうか判定
+pub fn native_set_q(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "set?");
+    Ok(Value::Bool(matches!(args[0], Value::Set(_))))
+}
+
 /// string? - 文字列かどうか判定
 pub fn native_string_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "string?");
@@ -50,10 +58,13 @@ pub fn native_bytes_q(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Bool(matches!(args[0], Value::Bytes(_))))
 }
 
-/// integer? - 整数かどうか判定
+/// integer? - 整数かどうか判定（多倍長整数を含む）
 pub fn native_integer_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "integer?");
-    Ok(Value::Bool(matches!(args[0], Value::Integer(_))))
+    Ok(Value::Bool(matches!(
+        args[0],
+        Value::Integer(_) | Value::BigInt(_)
+    )))
 }
 
 /// float? - 浮動小数点数かどうか判定
@@ -62,13 +73,28 @@ pub fn native_float_q(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Bool(matches!(args[0], Value::Float(_))))
 }
 
+/// char? - 文字（\a）かどうか判定
+pub fn native_char_q(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "char?");
+    Ok(Value::Bool(matches!(args[0], Value::Char(_))))
+}
+
+/// decimal? - 10進数（12.34M）かどうか判定
+pub fn native_decimal_q(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "decimal?");
+    Ok(Value::Bool(matches!(args[0], Value::Decimal(_))))
+}
+
+/// ratio? - 有理数（1/3）かどうか判定
+pub fn native_ratio_q(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "ratio?");
+    Ok(Value::Bool(matches!(args[0], Value::Ratio(_))))
+}
+
 /// number? - 数値判定
 pub fn native_number_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "number?");
-    Ok(Value::Bool(matches!(
-        args[0],
-        Value::Integer(_) | Value::Float(_)
-    )))
+    Ok(Value::Bool(is_number(&args[0])))
 }
 
 /// keyword? - キーワードかどうか判定
@@ -107,7 +133,7 @@ pub fn native_coll_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "coll?");
     Ok(Value::Bool(matches!(
         args[0],
-        Value::List(_) | Value::Vector(_) | Value::Map(_)
+        Value::List(_) | Value::Vector(_) | Value::Map(_) | Value::Set(_)
     )))
 }
 
@@ -127,6 +153,7 @@ pub fn native_empty(args: &[Value]) -> Result<Value, String> {
         Value::Nil => Ok(Value::Bool(true)),
         Value::List(v) | Value::Vector(v) => Ok(Value::Bool(v.is_empty())),
         Value::Map(m) => Ok(Value::Bool(m.is_empty())),
+        Value::Set(s) => Ok(Value::Bool(s.is_empty())),
         Value::String(s) => Ok(Value::Bool(s.is_empty())),
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
@@ -172,6 +199,7 @@ pub fn native_even_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "even?");
     match &args[0] {
         Value::Integer(n) => Ok(Value::Bool(n % 2 == 0)),
+        Value::BigInt(n) => Ok(Value::Bool(num_integer::Integer::is_even(&**n))),
         _ => Err(fmt_msg(MsgKey::TypeOnly, &["even?", "integers"])),
     }
 }
@@ -181,38 +209,41 @@ pub fn native_odd_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "odd?");
     match &args[0] {
         Value::Integer(n) => Ok(Value::Bool(n % 2 != 0)),
+        Value::BigInt(n) => Ok(Value::Bool(num_integer::Integer::is_odd(&**n))),
         _ => Err(fmt_msg(MsgKey::TypeOnly, &["odd?", "integers"])),
     }
 }
 
+/// 数値をゼロと比較する（数値以外はエラー）
+fn sign_of(value: &Value, name: &str) -> Result<Option<Ordering>, String> {
+    if !is_number(value) {
+        return Err(fmt_msg(MsgKey::TypeOnly, &[name, "numbers"]));
+    }
+    Ok(compare_numbers(value, &Value::Integer(0)))
+}
+
 /// positive? - 正の数かどうか判定
 pub fn native_positive_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "positive?");
-    match &args[0] {
-        Value::Integer(n) => Ok(Value::Bool(*n > 0)),
-        Value::Float(f) => Ok(Value::Bool(*f > 0.0)),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["positive?", "numbers"])),
-    }
+    Ok(Value::Bool(
+        sign_of(&args[0], "positive?")? == Some(Ordering::Greater),
+    ))
 }
 
 /// negative? - 負の数かどうか判定
 pub fn native_negative_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "negative?");
-    match &args[0] {
-        Value::Integer(n) => Ok(Value::Bool(*n < 0)),
-        Value::Float(f) => Ok(Value::Bool(*f < 0.0)),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["negative?", "numbers"])),
-    }
+    Ok(Value::Bool(
+        sign_of(&args[0], "negative?")? == Some(Ordering::Less),
+    ))
 }
 
 /// zero? - ゼロかどうか判定
 pub fn native_zero_q(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "zero?");
-    match &args[0] {
-        Value::Integer(n) => Ok(Value::Bool(*n == 0)),
-        Value::Float(f) => Ok(Value::Bool(*f == 0.0)),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["zero?", "numbers"])),
-    }
+    Ok(Value::Bool(
+        sign_of(&args[0], "zero?")? == Some(Ordering::Equal),
+    ))
 }
 
 // ========================================
@@ -221,17 +252,21 @@ pub fn native_zero_q(args: &[Value]) -> Result<Value, String> {
 
 /// 登録すべき関数のリスト
 /// @qi-doc:category core/predicates
-/// @qi-doc:functions nil?, list?, vector?, map?, string?, bytes?, integer?, float?, number?, keyword?, function?, atom?, stream?, coll?, sequential?, empty?, some?, true?, false?, error?, even?, odd?, positive?, negative?, zero?
+/// @qi-doc:functions nil?, list?, vector?, map?, set?, string?, char?, bytes?, integer?, float?, decimal?, ratio?, number?, keyword?, function?, atom?, stream?, coll?, sequential?, empty?, some?, true?, false?, error?, even?, odd?, positive?, negative?, zero?
 pub const FUNCTIONS: super::NativeFunctions = &[
     // 型チェック
     ("nil?", native_nil),
     ("list?", native_list_q),
     ("vector?", native_vector_q),
     ("map?", native_map_q),
+    ("set?", native_set_q),
     ("string?", native_string_q),
+    ("char?", native_char_q),
     ("bytes?", native_bytes_q),
     ("integer?", native_integer_q),
     ("float?", native_float_q),
+    ("decimal?", native_decimal_q),
+    ("ratio?", native_ratio_q),
     ("number?", native_number_q),
     ("keyword?", native_keyword_q),
     ("function?", native_function_q),
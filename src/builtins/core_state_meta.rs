@@ -1,57 +1,129 @@
 //! Core状態管理・メタプログラミング関数
 //!
-//! 状態管理（4個）: atom, deref, swap!, reset!
-//! メタ（6個）: eval, uvar, variable, macro?, macroexpand, source
-//! 合計10個のCore関数
+//! 状態管理（7個）: atom, deref, swap!, reset!, add-watch, remove-watch, set-validator!
+//! メタ（8個）: eval, uvar, variable, gensym, macro?, macroexpand, macroexpand-all, source
+//! 合計15個のCore関数
 
+use crate::builtins::stm;
 use crate::check_args;
 use crate::eval::Evaluator;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::parser::Parser;
-use crate::value::Value;
+use crate::value::{Atom, Value};
 use im::Vector;
-use parking_lot::RwLock;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// グローバルなuvarカウンター
 static UVAR_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// gensym・auto-gensym（`foo#`）共通の連番カウンター
+static GENSYM_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 一意なシンボル名を生成（prefix + 連番）
+///
+/// 生成したシンボルは使い捨てのため、インターンテーブルには登録しない
+pub fn gensym_symbol(prefix: &str) -> Arc<str> {
+    let id = GENSYM_COUNTER.fetch_add(1, Ordering::SeqCst);
+    Arc::from(format!("{}{}", prefix, id))
+}
+
+/// quasiquote内の `foo#` 用のシンボル名を生成（foo__12__auto__）
+pub fn auto_gensym_symbol(base: &str) -> Arc<str> {
+    let id = GENSYM_COUNTER.fetch_add(1, Ordering::SeqCst);
+    Arc::from(format!("{}__{}__auto__", base, id))
+}
+
 // ========================================
-// 状態管理関数（4個）
+// 状態管理関数（7個）
 // ========================================
 
 /// atom - アトムを作成
 pub fn native_atom(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "atom");
 
-    Ok(Value::Atom(Arc::new(RwLock::new(args[0].clone()))))
+    Ok(Value::Atom(Arc::new(Atom::new(args[0].clone()))))
 }
 
-/// deref - アトムから値を取得
+/// deref - アトム・refから値を取得
+///
+/// dosync内でのrefの参照はトランザクション内の値を返す
 pub fn native_deref(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "deref");
 
     match &args[0] {
-        Value::Atom(a) => Ok(a.read().clone()),
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["deref", "atoms"])),
+        Value::Atom(a) => Ok(a.value.read().clone()),
+        Value::Ref(r) => Ok(stm::deref_ref(r)),
+        _ => Err(fmt_msg(MsgKey::TypeOnly, &["deref", "atoms or refs"])),
+    }
+}
+
+/// バリデータで検証してアトムを更新し、ウォッチへ通知する
+fn update_atom(atom: &Value, new_value: Value, evaluator: &Evaluator) -> Result<Value, String> {
+    let Value::Atom(a) = atom else {
+        return Err(fmt_msg(MsgKey::FirstArgMustBe, &["swap!", "an atom"]));
+    };
+
+    let validator = a.validator.read().clone();
+    if let Some(validator) = validator {
+        validate(&validator, &new_value, evaluator)?;
+    }
+
+    let old_value = std::mem::replace(&mut *a.value.write(), new_value.clone());
+
+    // ウォッチ中にadd-watch/remove-watchされても良いようにコピーしてから呼ぶ
+    let watches = a.watches.read().clone();
+    for (key, func) in watches {
+        evaluator.apply_function(
+            &func,
+            &[key, atom.clone(), old_value.clone(), new_value.clone()],
+        )?;
+    }
+    Ok(new_value)
+}
+
+/// バリデータを呼び出し、偽を返した場合はエラー
+fn validate(validator: &Value, value: &Value, evaluator: &Evaluator) -> Result<(), String> {
+    if evaluator
+        .apply_function(validator, std::slice::from_ref(value))?
+        .is_truthy()
+    {
+        Ok(())
+    } else {
+        Err(fmt_msg(MsgKey::ValidatorRejected, &[&value.to_string()]))
     }
 }
 
 /// reset! - アトムの値を直接セット
+///
+/// 関数値として渡された場合の実装（Evaluatorがないためウォッチ・バリデータ付きのアトムは不可）
+/// 直接の呼び出し `(reset! a v)` は native_reset_with_evaluator で処理される
 pub fn native_reset(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "reset!");
 
     match &args[0] {
         Value::Atom(a) => {
+            if a.validator.read().is_some() || !a.watches.read().is_empty() {
+                return Err(fmt_msg(MsgKey::AtomHooksNeedDirectCall, &["reset!"]));
+            }
             let new_value = args[1].clone();
-            *a.write() = new_value.clone();
+            *a.value.write() = new_value.clone();
             Ok(new_value)
         }
         _ => Err(fmt_msg(MsgKey::FirstArgMustBe, &["reset!", "an atom"])),
     }
 }
 
+/// reset! - アトムの値を直接セット（バリデータ・ウォッチ対応）
+pub fn native_reset_with_evaluator(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    check_args!(args, 2, "reset!");
+
+    match &args[0] {
+        Value::Atom(_) => update_atom(&args[0], args[1].clone(), evaluator),
+        _ => Err(fmt_msg(MsgKey::FirstArgMustBe, &["reset!", "an atom"])),
+    }
+}
+
 /// swap! - 関数を適用してアトムをアトミックに更新
 pub fn native_swap(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     if args.len() < 2 {
@@ -60,7 +132,7 @@ pub fn native_swap(args: &[Value], evaluator: &Evaluator) -> Result<Value, Strin
 
     match &args[0] {
         Value::Atom(a) => {
-            let current_value = a.read().clone();
+            let current_value = a.value.read().clone();
             let func = &args[1];
 
             // 関数に現在の値と追加の引数を渡す
@@ -68,13 +140,71 @@ pub fn native_swap(args: &[Value], evaluator: &Evaluator) -> Result<Value, Strin
             func_args.extend_from_slice(&args[2..]);
 
             let new_value = evaluator.apply_function(func, &func_args)?;
-            *a.write() = new_value.clone();
-            Ok(new_value)
+            update_atom(&args[0], new_value, evaluator)
         }
         _ => Err(fmt_msg(MsgKey::FirstArgMustBe, &["swap!", "an atom"])),
     }
 }
 
+/// add-watch - アトムの更新時に呼ばれる関数を登録
+///
+/// 関数は (key atom old new) で呼ばれる。同じキーで登録すると置き換える
+pub fn native_add_watch(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 3, "add-watch");
+
+    match &args[0] {
+        Value::Atom(a) => {
+            let mut watches = a.watches.write();
+            watches.retain(|(key, _)| key != &args[1]);
+            watches.push((args[1].clone(), args[2].clone()));
+            Ok(args[0].clone())
+        }
+        _ => Err(fmt_msg(MsgKey::FirstArgMustBe, &["add-watch", "an atom"])),
+    }
+}
+
+/// remove-watch - add-watchで登録した関数を削除
+pub fn native_remove_watch(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 2, "remove-watch");
+
+    match &args[0] {
+        Value::Atom(a) => {
+            a.watches.write().retain(|(key, _)| key != &args[1]);
+            Ok(args[0].clone())
+        }
+        _ => Err(fmt_msg(
+            MsgKey::FirstArgMustBe,
+            &["remove-watch", "an atom"],
+        )),
+    }
+}
+
+/// set-validator! - アトムの更新を検証する関数を設定（nilで解除）
+///
+/// 設定時に現在の値も検証し、偽を返した場合は設定しない
+pub fn native_set_validator(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    check_args!(args, 2, "set-validator!");
+
+    match &args[0] {
+        Value::Atom(a) => {
+            let validator = match &args[1] {
+                Value::Nil => None,
+                f => {
+                    let current = a.value.read().clone();
+                    validate(f, &current, evaluator)?;
+                    Some(f.clone())
+                }
+            };
+            *a.validator.write() = validator;
+            Ok(Value::Nil)
+        }
+        _ => Err(fmt_msg(
+            MsgKey::FirstArgMustBe,
+            &["set-validator!", "an atom"],
+        )),
+    }
+}
+
 // ========================================
 // メタプログラミング関数（4個）
 // ========================================
@@ -87,6 +217,29 @@ pub fn native_uvar(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Uvar(id))
 }
 
+/// gensym - 一意なシンボルを生成
+///
+/// マクロが展開先で使う一時変数が、利用者の変数を捕捉しないようにするために使う。
+///
+/// # 例
+/// ```qi
+/// (gensym)        ;=> G__12
+/// (gensym "tmp")  ;=> tmp13
+/// ```
+pub fn native_gensym(args: &[Value]) -> Result<Value, String> {
+    if args.len() > 1 {
+        return Err(fmt_msg(MsgKey::Need0Or1Args, &["gensym"]));
+    }
+
+    let prefix = match args.first() {
+        None => "G__",
+        Some(Value::String(s)) => s.as_str(),
+        Some(Value::Symbol(s)) => s.as_ref(),
+        Some(_) => return Err(fmt_msg(MsgKey::GensymPrefixMustBeString, &[])),
+    };
+    Ok(Value::Symbol(gensym_symbol(prefix)))
+}
+
 /// variable - 変数かどうかをチェック
 pub fn native_variable(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "variable");
@@ -328,15 +481,19 @@ fn expand_defn(items: &Vector<Value>, _is_private: bool) -> Result<Value, String
 
 /// 登録すべき関数のリスト（Evaluator不要な関数のみ）
 /// @qi-doc:category core/state-meta
-/// @qi-doc:functions atom, deref, swap!, reset!, eval, uvar, variable, macro?, macroexpand
+/// @qi-doc:functions atom, deref, swap!, reset!, add-watch, remove-watch, set-validator!, eval, uvar, variable, gensym, macro?, macroexpand
 ///
-/// 注意: swap!, evalはEvaluatorが必要なため、mod.rsで別途登録されます
+/// 注意: swap!, set-validator!, evalはEvaluatorが必要なため、mod.rsで別途登録されます
+/// reset!は直接の呼び出しをEvaluator付きで処理し、ここでは関数値として渡された場合用に登録します
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("atom", native_atom),
     ("deref", native_deref),
     ("reset!", native_reset),
+    ("add-watch", native_add_watch),
+    ("remove-watch", native_remove_watch),
     ("uvar", native_uvar),
     ("variable", native_variable),
+    ("gensym", native_gensym),
     ("macro?", native_macro_q),
     ("macroexpand", native_macroexpand),
 ];
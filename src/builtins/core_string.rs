@@ -12,6 +12,9 @@ pub fn native_str(args: &[Value]) -> Result<Value, String> {
         .iter()
         .map(|v| match v {
             Value::String(s) => s.clone(),
+            // 10進数はMサフィックスなしで連結する（金額表示用）
+            Value::Decimal(d) => d.to_string(),
+            Value::Char(c) => c.to_string(),
             _ => format!("{}", v),
         })
         .collect::<String>();
@@ -42,6 +45,8 @@ pub fn native_join(args: &[Value]) -> Result<Value, String> {
                 .iter()
                 .map(|v| match v {
                     Value::String(s) => Ok(s.clone()),
+                    Value::Decimal(d) => Ok(d.to_string()),
+                    Value::Char(c) => Ok(c.to_string()),
                     _ => Ok(format!("{}", v)),
                 })
                 .collect();
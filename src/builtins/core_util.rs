@@ -1,9 +1,12 @@
 //! Coreユーティリティ関数
 //!
-//! 型変換（3個）: to-int, to-float, to-string
+//! 型変換（5個）: to-int, to-float, to-decimal, to-string, char
 //! 日時（3個）: now, timestamp, sleep
-//! 合計6個のCore関数
+//! 合計8個のCore関数
 
+use crate::builtins::numeric_helpers::{
+    bigint_value, number_to_f64, to_decimal, truncate_to_integer,
+};
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::Value;
@@ -11,15 +14,19 @@ use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // ========================================
-// 型変換（3個）
+// 型変換（5個）
 // ========================================
 
 /// to-int - 値を整数に変換
+/// BigInt・Ratio・Decimalは0方向に切り捨て、i64に収まらない値は多倍長整数になる
 pub fn native_to_int(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "to-int");
 
     match &args[0] {
         Value::Integer(i) => Ok(Value::Integer(*i)),
+        Value::BigInt(_) | Value::Ratio(_) | Value::Decimal(_) => {
+            truncate_to_integer(&args[0], "to-int")
+        }
         Value::Float(f) => {
             // NaNまたは無限大チェック
             if f.is_nan() || f.is_infinite() {
@@ -35,10 +42,12 @@ pub fn native_to_int(args: &[Value]) -> Result<Value, String> {
             Ok(Value::Integer(*f as i64))
         }
         Value::String(s) => s
-            .parse::<i64>()
-            .map(Value::Integer)
+            .parse::<num_bigint::BigInt>()
+            .map(bigint_value)
             .map_err(|_| fmt_msg(MsgKey::CannotParseAsInt, &["to-int", s])),
         Value::Bool(b) => Ok(Value::Integer(if *b { 1 } else { 0 })),
+        // 文字はコードポイント
+        Value::Char(c) => Ok(Value::Integer(*c as i64)),
         _ => Err(fmt_msg(
             MsgKey::CannotConvertToInt,
             &["to-int", &format!("{:?}", args[0])],
@@ -46,20 +55,72 @@ pub fn native_to_int(args: &[Value]) -> Result<Value, String> {
     }
 }
 
+/// char - コードポイントまたは1文字の文字列を文字に変換
+pub fn native_char(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "char");
+
+    match &args[0] {
+        Value::Char(c) => Ok(Value::Char(*c)),
+        Value::Integer(n) => u32::try_from(*n)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Value::Char)
+            .ok_or_else(|| fmt_msg(MsgKey::InvalidCodePoint, &["char", &n.to_string()])),
+        Value::String(s) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Value::Char(c)),
+                _ => Err(char_type_error()),
+            }
+        }
+        _ => Err(char_type_error()),
+    }
+}
+
+fn char_type_error() -> String {
+    fmt_msg(
+        MsgKey::TypeOnly,
+        &["char", "integer code point or single-character string"],
+    )
+}
+
 /// to-float - 値を浮動小数点数に変換
 pub fn native_to_float(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "to-float");
 
     match &args[0] {
-        Value::Integer(i) => Ok(Value::Float(*i as f64)),
-        Value::Float(f) => Ok(Value::Float(*f)),
         Value::String(s) => s
             .parse::<f64>()
             .map(Value::Float)
             .map_err(|_| fmt_msg(MsgKey::CannotParseAsFloat, &["to-float", s])),
+        other => number_to_f64(other).map(Value::Float).ok_or_else(|| {
+            fmt_msg(
+                MsgKey::CannotConvertToFloat,
+                &["to-float", &format!("{:?}", args[0])],
+            )
+        }),
+    }
+}
+
+/// to-decimal - 値を10進数に変換
+/// 浮動小数点数は最短表現の10進数として扱う（0.1 → 0.1M）
+pub fn native_to_decimal(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "to-decimal");
+
+    match &args[0] {
+        Value::Decimal(d) => Ok(Value::Decimal(*d)),
+        Value::String(s) => s
+            .trim()
+            .parse::<rust_decimal::Decimal>()
+            .or_else(|_| rust_decimal::Decimal::from_scientific(s.trim()))
+            .map(Value::Decimal)
+            .map_err(|_| fmt_msg(MsgKey::CannotParseAsDecimal, &["to-decimal", s])),
+        Value::Integer(_) | Value::BigInt(_) | Value::Ratio(_) | Value::Float(_) => {
+            to_decimal(&args[0], "to-decimal").map(Value::Decimal)
+        }
         _ => Err(fmt_msg(
-            MsgKey::CannotConvertToFloat,
-            &["to-float", &format!("{:?}", args[0])],
+            MsgKey::CannotConvertToDecimal,
+            &["to-decimal", &format!("{:?}", args[0])],
         )),
     }
 }
@@ -72,6 +133,10 @@ pub fn native_to_string(args: &[Value]) -> Result<Value, String> {
         Value::String(s) => s.clone(),
         Value::Integer(i) => i.to_string(),
         Value::Float(f) => f.to_string(),
+        Value::BigInt(n) => n.to_string(),
+        Value::Ratio(r) => r.to_string(),
+        Value::Decimal(d) => d.to_string(),
+        Value::Char(c) => c.to_string(),
         Value::Bool(b) => b.to_string(),
         Value::Keyword(k) => k.to_string(),
         Value::Nil => "nil".to_string(),
@@ -136,11 +201,13 @@ pub fn native_sleep(args: &[Value]) -> Result<Value, String> {
 
 /// 登録すべき関数のリスト
 /// @qi-doc:category core/util
-/// @qi-doc:functions to-int, to-float, to-string, now, timestamp, sleep
+/// @qi-doc:functions to-int, to-float, to-decimal, to-string, char, now, timestamp, sleep
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("to-int", native_to_int),
     ("to-float", native_to_float),
+    ("to-decimal", native_to_decimal),
     ("to-string", native_to_string),
+    ("char", native_char),
     ("now", native_now),
     ("timestamp", native_timestamp),
     ("sleep", native_sleep),
@@ -0,0 +1,725 @@
+//! 暗号機能
+//!
+//! - ハッシュ: md5 / sha1 / sha256 / sha384 / sha512 / blake3
+//! - HMAC・定数時間比較（Webhook署名の検証等）
+//! - AEAD暗号: AES-256-GCM / ChaCha20-Poly1305
+//! - 鍵導出: HKDF / PBKDF2
+//! - 安全な乱数バイト列、バイト列の hex / base64 変換
+//!
+//! 入力は文字列（UTF-8バイト列として扱う）と `Value::Bytes` の両方を受け付ける。
+//!
+//! このモジュールは `auth-crypto` feature でコンパイルされます。
+
+use crate::builtins::util::kw;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Value};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::ChaCha20Poly1305;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+/// AEAD暗号の鍵長（バイト）
+const KEY_LEN: usize = 32;
+
+/// AEAD暗号のノンス長（バイト、両アルゴリズム共通）
+const NONCE_LEN: usize = 12;
+
+/// PBKDF2のデフォルト反復回数（OWASP推奨値、HMAC-SHA256）
+const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// crypto/random-bytesで一度に生成できる最大バイト数
+const MAX_RANDOM_BYTES: usize = 1024 * 1024;
+
+/// crypto/pbkdf2の最大出力バイト数（出力ブロックごとに全反復を繰り返すため小さく抑える）
+const MAX_PBKDF2_LENGTH: usize = 1024;
+
+/// 出力長が範囲外のときのエラー
+fn length_out_of_range(fn_name: &str, length: usize, max: usize) -> String {
+    fmt_msg(
+        MsgKey::IntegerOutOfRange,
+        &[
+            fn_name,
+            "length",
+            &length.to_string(),
+            "1",
+            &max.to_string(),
+        ],
+    )
+}
+
+/// 文字列またはバイト列の引数をバイトスライスとして取り出す
+fn bytes_arg<'a>(value: &'a Value, fn_name: &str) -> Result<&'a [u8], String> {
+    match value {
+        Value::String(s) => Ok(s.as_bytes()),
+        Value::Bytes(b) => Ok(b.as_ref()),
+        _ => Err(fmt_msg(MsgKey::TypeOnly, &[fn_name, "strings or bytes"])),
+    }
+}
+
+/// 末尾の引数からアルゴリズム名（キーワードまたは文字列）と:bytesフラグを取り出す
+///
+/// 暗号APIで別のアルゴリズムが黙って使われないよう、アルゴリズム指定以外の引数や
+/// 2つ目のアルゴリズム指定はエラーにする。`allow_bytes` が偽なら:bytesもアルゴリズム名扱い
+fn algorithm_args<'a>(
+    args: &'a [Value],
+    default: &'a str,
+    allow_bytes: bool,
+    fn_name: &str,
+) -> Result<(&'a str, bool), String> {
+    let expected = if allow_bytes {
+        "an algorithm keyword or string, optionally followed by :bytes"
+    } else {
+        "a format keyword or string"
+    };
+    let mut algo = None;
+    let mut as_bytes = false;
+    for arg in args {
+        let name = match arg {
+            Value::Keyword(k) if allow_bytes && &**k == "bytes" && !as_bytes => {
+                as_bytes = true;
+                continue;
+            }
+            Value::Keyword(k) => &**k,
+            Value::String(s) => s.as_str(),
+            _ => return Err(fmt_msg(MsgKey::ArgMustBeType, &[fn_name, expected])),
+        };
+        if algo.replace(name).is_some() {
+            return Err(fmt_msg(MsgKey::ArgMustBeType, &[fn_name, expected]));
+        }
+    }
+    Ok((algo.unwrap_or(default), as_bytes))
+}
+
+/// オプションマップを取り出す
+fn opts_arg<'a>(
+    value: Option<&'a Value>,
+    fn_name: &str,
+) -> Result<Option<&'a crate::HashMap<MapKey, Value>>, String> {
+    match value {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::Map(m)) => Ok(Some(m)),
+        Some(_) => Err(fmt_msg(MsgKey::MustBeMap, &[fn_name, "options"])),
+    }
+}
+
+/// オプションマップからキーワード値を取り出す
+fn opt_keyword<'a>(
+    opts: Option<&'a crate::HashMap<MapKey, Value>>,
+    key: &str,
+    default: &'a str,
+) -> &'a str {
+    match opts.and_then(|m| m.get(&kw(key))) {
+        Some(Value::Keyword(k)) => k,
+        Some(Value::String(s)) => s,
+        _ => default,
+    }
+}
+
+/// オプションマップから正の整数を取り出す
+fn opt_positive(
+    opts: Option<&crate::HashMap<MapKey, Value>>,
+    key: &str,
+    default: usize,
+    fn_name: &str,
+) -> Result<usize, String> {
+    match opts.and_then(|m| m.get(&kw(key))) {
+        None | Some(Value::Nil) => Ok(default),
+        Some(Value::Integer(n)) if *n > 0 => Ok(*n as usize),
+        Some(_) => Err(fmt_msg(MsgKey::MustBePositive, &[fn_name, key])),
+    }
+}
+
+/// ダイジェスト結果を16進文字列またはバイト列で返す
+fn digest_value(digest: Vec<u8>, as_bytes: bool) -> Value {
+    if as_bytes {
+        Value::Bytes(Arc::from(digest))
+    } else {
+        Value::String(hex_encode(&digest))
+    }
+}
+
+fn hex_encode(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_decode(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| s.get(i..i + 2).and_then(|h| u8::from_str_radix(h, 16).ok()))
+        .collect()
+}
+
+fn unsupported(fn_name: &str, algo: &str, supported: &str) -> String {
+    fmt_msg(
+        MsgKey::CryptoUnsupportedAlgorithm,
+        &[fn_name, algo, supported],
+    )
+}
+
+/// crypto/hash - ハッシュ値を計算
+///
+/// 引数:
+/// - data: 文字列またはバイト列
+/// - algorithm: :sha256（デフォルト）, :sha384, :sha512, :sha1, :md5, :blake3
+/// - :bytes: 指定時はバイト列を返す（省略時は16進文字列）
+///
+/// 戻り値: ハッシュ値
+pub fn native_hash(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::NeedNArgs, &["crypto/hash", "1-3"]));
+    }
+    let data = bytes_arg(&args[0], "crypto/hash")?;
+    let (algo, as_bytes) = algorithm_args(&args[1..], "sha256", true, "crypto/hash")?;
+
+    let digest = match algo {
+        "sha256" => Sha256::digest(data).to_vec(),
+        "sha384" => Sha384::digest(data).to_vec(),
+        "sha512" => Sha512::digest(data).to_vec(),
+        "sha1" => Sha1::digest(data).to_vec(),
+        "md5" => md5::Md5::digest(data).to_vec(),
+        "blake3" => blake3::hash(data).as_bytes().to_vec(),
+        _ => {
+            return Err(unsupported(
+                "crypto/hash",
+                algo,
+                "sha256, sha384, sha512, sha1, md5, blake3",
+            ))
+        }
+    };
+
+    Ok(digest_value(digest, as_bytes))
+}
+
+/// HMACを計算（HMACは任意長の鍵を受け付けるため、実際には失敗しない）
+fn hmac_digest<M: Mac + KeyInit>(key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).map_err(|e| e.to_string())?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// crypto/hmac - HMACを計算
+///
+/// 引数:
+/// - key: 秘密鍵（文字列またはバイト列）
+/// - data: 署名対象（文字列またはバイト列）
+/// - algorithm: :sha256（デフォルト）, :sha384, :sha512, :sha1, :md5
+/// - :bytes: 指定時はバイト列を返す（省略時は16進文字列）
+///
+/// 戻り値: HMAC値
+pub fn native_hmac(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 4 {
+        return Err(fmt_msg(MsgKey::NeedNArgs, &["crypto/hmac", "2-4"]));
+    }
+    let key = bytes_arg(&args[0], "crypto/hmac")?;
+    let data = bytes_arg(&args[1], "crypto/hmac")?;
+    let (algo, as_bytes) = algorithm_args(&args[2..], "sha256", true, "crypto/hmac")?;
+
+    let digest = match algo {
+        "sha256" => hmac_digest::<Hmac<Sha256>>(key, data)?,
+        "sha384" => hmac_digest::<Hmac<Sha384>>(key, data)?,
+        "sha512" => hmac_digest::<Hmac<Sha512>>(key, data)?,
+        "sha1" => hmac_digest::<Hmac<Sha1>>(key, data)?,
+        "md5" => hmac_digest::<Hmac<md5::Md5>>(key, data)?,
+        _ => {
+            return Err(unsupported(
+                "crypto/hmac",
+                algo,
+                "sha256, sha384, sha512, sha1, md5",
+            ))
+        }
+    };
+
+    Ok(digest_value(digest, as_bytes))
+}
+
+/// crypto/equal? - 定数時間で比較（タイミング攻撃を防ぐ）
+///
+/// 引数:
+/// - a, b: 文字列またはバイト列
+///
+/// 戻り値: 一致すればtrue
+pub fn native_equal(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(fmt_msg(MsgKey::Need2Args, &["crypto/equal?"]));
+    }
+    let a = bytes_arg(&args[0], "crypto/equal?")?;
+    let b = bytes_arg(&args[1], "crypto/equal?")?;
+    // 長さの違いは秘密ではないため、長さが異なる場合はそのままfalse
+    Ok(Value::Bool(a.len() == b.len() && bool::from(a.ct_eq(b))))
+}
+
+/// crypto/random-bytes - 暗号学的に安全な乱数バイト列を生成
+///
+/// 引数:
+/// - n: バイト数
+///
+/// 戻り値: バイト列
+pub fn native_random_bytes(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(fmt_msg(MsgKey::Need1Arg, &["crypto/random-bytes"]));
+    }
+    let n = match &args[0] {
+        Value::Integer(n) if *n >= 0 && (*n as usize) <= MAX_RANDOM_BYTES => *n as usize,
+        Value::Integer(n) => {
+            return Err(fmt_msg(
+                MsgKey::IntegerOutOfRange,
+                &[
+                    "crypto/random-bytes",
+                    "n",
+                    &n.to_string(),
+                    "0",
+                    &MAX_RANDOM_BYTES.to_string(),
+                ],
+            ))
+        }
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::MustBeInteger,
+                &["crypto/random-bytes", "n"],
+            ))
+        }
+    };
+
+    let mut buf = vec![0u8; n];
+    OsRng.fill_bytes(&mut buf);
+    Ok(Value::Bytes(Arc::from(buf)))
+}
+
+/// AEADアルゴリズム
+enum AeadAlgorithm {
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+fn aead_algorithm(name: &str, fn_name: &str) -> Result<AeadAlgorithm, String> {
+    match name {
+        "aes-256-gcm" => Ok(AeadAlgorithm::Aes256Gcm),
+        "chacha20-poly1305" => Ok(AeadAlgorithm::ChaCha20Poly1305),
+        _ => Err(unsupported(fn_name, name, "aes-256-gcm, chacha20-poly1305")),
+    }
+}
+
+fn aead_key<'a>(value: &'a Value, fn_name: &str) -> Result<&'a [u8], String> {
+    let key = bytes_arg(value, fn_name)?;
+    if key.len() != KEY_LEN {
+        return Err(fmt_msg(
+            MsgKey::CryptoInvalidKeyLength,
+            &[fn_name, &KEY_LEN.to_string(), &key.len().to_string()],
+        ));
+    }
+    Ok(key)
+}
+
+/// crypto/encrypt - 認証付き暗号で暗号化
+///
+/// 引数:
+/// - key: 32バイトの鍵（crypto/random-bytes等で生成）
+/// - plaintext: 平文（文字列またはバイト列）
+/// - opts: {:algorithm :aes-256-gcm | :chacha20-poly1305, :aad 追加認証データ}（省略可）
+///
+/// 戻り値: ノンス（12バイト）+ 暗号文 + 認証タグ を連結したバイト列
+pub fn native_encrypt(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["crypto/encrypt"]));
+    }
+    let key = aead_key(&args[0], "crypto/encrypt")?;
+    let plaintext = bytes_arg(&args[1], "crypto/encrypt")?;
+    let opts = opts_arg(args.get(2), "crypto/encrypt")?;
+    let algorithm = aead_algorithm(
+        opt_keyword(opts, "algorithm", "aes-256-gcm"),
+        "crypto/encrypt",
+    )?;
+    let aad = match opts.and_then(|m| m.get(&kw("aad"))) {
+        Some(v) => bytes_arg(v, "crypto/encrypt")?,
+        None => &[],
+    };
+    let payload = Payload {
+        msg: plaintext,
+        aad,
+    };
+
+    let (nonce, ciphertext) = match algorithm {
+        AeadAlgorithm::Aes256Gcm => {
+            let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| e.to_string())?;
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            (nonce.to_vec(), cipher.encrypt(&nonce, payload))
+        }
+        AeadAlgorithm::ChaCha20Poly1305 => {
+            let cipher = ChaCha20Poly1305::new_from_slice(key).map_err(|e| e.to_string())?;
+            let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+            (nonce.to_vec(), cipher.encrypt(&nonce, payload))
+        }
+    };
+    let ciphertext =
+        ciphertext.map_err(|_| fmt_msg(MsgKey::CryptoOperationFailed, &["crypto/encrypt"]))?;
+
+    let mut out = nonce;
+    out.extend_from_slice(&ciphertext);
+    Ok(Value::Bytes(Arc::from(out)))
+}
+
+/// crypto/decrypt - crypto/encryptで暗号化したデータを復号
+///
+/// 引数:
+/// - key: 暗号化に使った32バイトの鍵
+/// - data: crypto/encryptの戻り値
+/// - opts: {:algorithm ..., :aad ..., :as :string}（省略可、:as :stringでUTF-8文字列として返す）
+///
+/// 戻り値: 平文（バイト列）。鍵の誤りや改ざんがあればエラー
+pub fn native_decrypt(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["crypto/decrypt"]));
+    }
+    let key = aead_key(&args[0], "crypto/decrypt")?;
+    let data = bytes_arg(&args[1], "crypto/decrypt")?;
+    let opts = opts_arg(args.get(2), "crypto/decrypt")?;
+    let algorithm = aead_algorithm(
+        opt_keyword(opts, "algorithm", "aes-256-gcm"),
+        "crypto/decrypt",
+    )?;
+    let aad = match opts.and_then(|m| m.get(&kw("aad"))) {
+        Some(v) => bytes_arg(v, "crypto/decrypt")?,
+        None => &[],
+    };
+
+    let failed = || fmt_msg(MsgKey::CryptoOperationFailed, &["crypto/decrypt"]);
+    if data.len() < NONCE_LEN {
+        return Err(failed());
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let payload = Payload {
+        msg: ciphertext,
+        aad,
+    };
+
+    let plaintext = match algorithm {
+        AeadAlgorithm::Aes256Gcm => Aes256Gcm::new_from_slice(key)
+            .map_err(|e| e.to_string())?
+            .decrypt(aes_gcm::Nonce::from_slice(nonce), payload),
+        AeadAlgorithm::ChaCha20Poly1305 => ChaCha20Poly1305::new_from_slice(key)
+            .map_err(|e| e.to_string())?
+            .decrypt(chacha20poly1305::Nonce::from_slice(nonce), payload),
+    }
+    .map_err(|_| failed())?;
+
+    match opt_keyword(opts, "as", "bytes") {
+        "string" => String::from_utf8(plaintext)
+            .map(Value::String)
+            .map_err(|_| fmt_msg(MsgKey::TypeOnly, &["crypto/decrypt", "valid UTF-8 bytes"])),
+        _ => Ok(Value::Bytes(Arc::from(plaintext))),
+    }
+}
+
+/// crypto/hkdf - HKDFで鍵を導出
+///
+/// 引数:
+/// - ikm: 入力鍵素材（文字列またはバイト列）
+/// - length: 出力バイト数
+/// - opts: {:salt ..., :info ..., :hash :sha256 | :sha384 | :sha512}（省略可）
+///
+/// 戻り値: 導出した鍵（バイト列）
+pub fn native_hkdf(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["crypto/hkdf"]));
+    }
+    let ikm = bytes_arg(&args[0], "crypto/hkdf")?;
+    let length = match &args[1] {
+        Value::Integer(n) if *n > 0 => *n as usize,
+        _ => return Err(fmt_msg(MsgKey::MustBePositive, &["crypto/hkdf", "length"])),
+    };
+    let opts = opts_arg(args.get(2), "crypto/hkdf")?;
+    let salt = match opts.and_then(|m| m.get(&kw("salt"))) {
+        Some(v) => Some(bytes_arg(v, "crypto/hkdf")?),
+        None => None,
+    };
+    let info = match opts.and_then(|m| m.get(&kw("info"))) {
+        Some(v) => bytes_arg(v, "crypto/hkdf")?,
+        None => &[],
+    };
+
+    let hash = opt_keyword(opts, "hash", "sha256");
+    let hash_len = match hash {
+        "sha256" => 32,
+        "sha384" => 48,
+        "sha512" => 64,
+        other => return Err(unsupported("crypto/hkdf", other, "sha256, sha384, sha512")),
+    };
+    // HKDFの出力はハッシュ長の255倍まで（確保する前に検査する）
+    if length > 255 * hash_len {
+        return Err(length_out_of_range("crypto/hkdf", length, 255 * hash_len));
+    }
+
+    let mut okm = vec![0u8; length];
+    let expanded = match hash {
+        "sha256" => hkdf::Hkdf::<Sha256>::new(salt, ikm).expand(info, &mut okm),
+        "sha384" => hkdf::Hkdf::<Sha384>::new(salt, ikm).expand(info, &mut okm),
+        _ => hkdf::Hkdf::<Sha512>::new(salt, ikm).expand(info, &mut okm),
+    };
+    expanded.map_err(|e| {
+        fmt_msg(
+            MsgKey::CryptoInvalidParameter,
+            &["crypto/hkdf", "length", &e.to_string()],
+        )
+    })?;
+
+    Ok(Value::Bytes(Arc::from(okm)))
+}
+
+/// crypto/pbkdf2 - PBKDF2（HMAC）でパスワードから鍵を導出
+///
+/// 引数:
+/// - password: パスワード（文字列またはバイト列）
+/// - salt: ソルト（文字列またはバイト列、crypto/random-bytes 16 等）
+/// - opts: {:iterations 600000, :length 32, :hash :sha256 | :sha512 | :sha1}（省略可）
+///
+/// 戻り値: 導出した鍵（バイト列）
+pub fn native_pbkdf2(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["crypto/pbkdf2"]));
+    }
+    let password = bytes_arg(&args[0], "crypto/pbkdf2")?;
+    let salt = bytes_arg(&args[1], "crypto/pbkdf2")?;
+    let opts = opts_arg(args.get(2), "crypto/pbkdf2")?;
+    let iterations = opt_positive(
+        opts,
+        "iterations",
+        DEFAULT_PBKDF2_ITERATIONS as usize,
+        "crypto/pbkdf2",
+    )?;
+    let iterations = u32::try_from(iterations).map_err(|_| {
+        fmt_msg(
+            MsgKey::CryptoInvalidParameter,
+            &["crypto/pbkdf2", "iterations", "too large"],
+        )
+    })?;
+    let length = opt_positive(opts, "length", KEY_LEN, "crypto/pbkdf2")?;
+    if length > MAX_PBKDF2_LENGTH {
+        return Err(length_out_of_range(
+            "crypto/pbkdf2",
+            length,
+            MAX_PBKDF2_LENGTH,
+        ));
+    }
+
+    let mut out = vec![0u8; length];
+    match opt_keyword(opts, "hash", "sha256") {
+        "sha256" => pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, iterations, &mut out),
+        "sha512" => pbkdf2::pbkdf2_hmac::<Sha512>(password, salt, iterations, &mut out),
+        "sha1" => pbkdf2::pbkdf2_hmac::<Sha1>(password, salt, iterations, &mut out),
+        other => return Err(unsupported("crypto/pbkdf2", other, "sha256, sha512, sha1")),
+    }
+
+    Ok(Value::Bytes(Arc::from(out)))
+}
+
+/// crypto/encode - バイト列を文字列にエンコード
+///
+/// 引数:
+/// - data: バイト列（または文字列）
+/// - format: :hex（デフォルト）, :base64, :base64url
+///
+/// 戻り値: エンコード済み文字列
+pub fn native_encode(args: &[Value]) -> Result<Value, String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["crypto/encode"]));
+    }
+    let data = bytes_arg(&args[0], "crypto/encode")?;
+    let (format, _) = algorithm_args(&args[1..], "hex", false, "crypto/encode")?;
+    let encoded = match format {
+        "hex" => hex_encode(data),
+        "base64" => general_purpose::STANDARD.encode(data),
+        "base64url" => general_purpose::URL_SAFE_NO_PAD.encode(data),
+        other => {
+            return Err(unsupported(
+                "crypto/encode",
+                other,
+                "hex, base64, base64url",
+            ))
+        }
+    };
+    Ok(Value::String(encoded))
+}
+
+/// crypto/decode - 文字列をバイト列にデコード
+///
+/// 引数:
+/// - s: エンコード済み文字列
+/// - format: :hex（デフォルト）, :base64, :base64url
+///
+/// 戻り値: バイト列
+pub fn native_decode(args: &[Value]) -> Result<Value, String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["crypto/decode"]));
+    }
+    let s = match &args[0] {
+        Value::String(s) => s.trim(),
+        _ => return Err(fmt_msg(MsgKey::MustBeString, &["crypto/decode", "input"])),
+    };
+    let (format, _) = algorithm_args(&args[1..], "hex", false, "crypto/decode")?;
+    let decoded = match format {
+        "hex" => hex_decode(s),
+        "base64" => general_purpose::STANDARD.decode(s).ok(),
+        "base64url" => general_purpose::URL_SAFE_NO_PAD
+            .decode(s.trim_end_matches('='))
+            .ok(),
+        other => {
+            return Err(unsupported(
+                "crypto/decode",
+                other,
+                "hex, base64, base64url",
+            ))
+        }
+    }
+    .ok_or_else(|| {
+        fmt_msg(
+            MsgKey::CryptoInvalidParameter,
+            &["crypto/decode", "input", &format!("not valid {}", format)],
+        )
+    })?;
+    Ok(Value::Bytes(Arc::from(decoded)))
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト
+/// @qi-doc:category auth/crypto
+/// @qi-doc:functions crypto/hash, crypto/hmac, crypto/equal?, crypto/random-bytes, crypto/encrypt, crypto/decrypt, crypto/hkdf, crypto/pbkdf2, crypto/encode, crypto/decode
+pub const FUNCTIONS: super::NativeFunctions = &[
+    ("crypto/hash", native_hash),
+    ("crypto/hmac", native_hmac),
+    ("crypto/equal?", native_equal),
+    ("crypto/random-bytes", native_random_bytes),
+    ("crypto/encrypt", native_encrypt),
+    ("crypto/decrypt", native_decrypt),
+    ("crypto/hkdf", native_hkdf),
+    ("crypto/pbkdf2", native_pbkdf2),
+    ("crypto/encode", native_encode),
+    ("crypto/decode", native_decode),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(v: &str) -> Value {
+        Value::String(v.to_string())
+    }
+
+    fn kwd(v: &str) -> Value {
+        Value::Keyword(crate::intern::intern_keyword(v))
+    }
+
+    #[test]
+    fn test_hash_known_vectors() {
+        assert_eq!(
+            native_hash(&[s("abc")]).unwrap(),
+            s("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            native_hash(&[s("abc"), kwd("md5")]).unwrap(),
+            s("900150983cd24fb0d6963f7d28e17f72")
+        );
+        assert_eq!(
+            native_hash(&[s("abc"), kwd("sha1")]).unwrap(),
+            s("a9993e364706816aba3e25717850c26c9cd0d89d")
+        );
+    }
+
+    #[test]
+    fn test_hmac_rfc4231() {
+        // RFC 4231 テストケース2
+        assert_eq!(
+            native_hmac(&[s("Jefe"), s("what do ya want for nothing?")]).unwrap(),
+            s("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn test_algorithm_args_are_strict() {
+        // 文字列のアルゴリズム名はキーワードと同じ扱い
+        assert_eq!(
+            native_hash(&[s("abc"), s("sha512")]).unwrap(),
+            native_hash(&[s("abc"), kwd("sha512")]).unwrap()
+        );
+        assert_eq!(
+            native_hmac(&[s("k"), s("abc"), s("sha512"), kwd("bytes")]).unwrap(),
+            native_hmac(&[s("k"), s("abc"), kwd("sha512"), kwd("bytes")]).unwrap()
+        );
+        // 解釈できない引数は既定のSHA-256に黙って戻さずエラー
+        assert!(native_hash(&[s("abc"), Value::Integer(42), kwd("md5")]).is_err());
+        assert!(native_hash(&[s("abc"), kwd("md5"), kwd("sha1")]).is_err());
+        assert!(native_hmac(&[s("k"), s("abc"), Value::Nil]).is_err());
+        assert!(native_encode(&[s("abc"), kwd("bytes")]).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let key = native_random_bytes(&[Value::Integer(32)]).unwrap();
+        for algo in ["aes-256-gcm", "chacha20-poly1305"] {
+            let mut opts = crate::new_hashmap();
+            opts.insert(kw("algorithm"), kwd(algo));
+            opts.insert(kw("as"), kwd("string"));
+            let opts = Value::Map(opts);
+
+            let sealed = native_encrypt(&[key.clone(), s("secret"), opts.clone()]).unwrap();
+            assert_eq!(
+                native_decrypt(&[key.clone(), sealed.clone(), opts.clone()]).unwrap(),
+                s("secret")
+            );
+
+            // 改ざんされたデータは復号できない
+            let Value::Bytes(b) = sealed else {
+                panic!("expected bytes")
+            };
+            let mut tampered = b.to_vec();
+            let last = tampered.len() - 1;
+            tampered[last] ^= 1;
+            assert!(
+                native_decrypt(&[key.clone(), Value::Bytes(Arc::from(tampered)), opts]).is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_pbkdf2_rfc6070() {
+        // RFC 6070 テストケース2（HMAC-SHA1、2回、20バイト）
+        let mut opts = crate::new_hashmap();
+        opts.insert(kw("iterations"), Value::Integer(2));
+        opts.insert(kw("length"), Value::Integer(20));
+        opts.insert(kw("hash"), kwd("sha1"));
+        let key = native_pbkdf2(&[s("password"), s("salt"), Value::Map(opts)]).unwrap();
+        assert_eq!(
+            native_encode(&[key]).unwrap(),
+            s("ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957")
+        );
+    }
+
+    #[test]
+    fn test_derived_length_limits() {
+        // HKDF-SHA256は255*32バイトまで、確保前にエラーにする
+        assert!(native_hkdf(&[s("ikm"), Value::Integer(255 * 32)]).is_ok());
+        assert!(native_hkdf(&[s("ikm"), Value::Integer(255 * 32 + 1)]).is_err());
+        assert!(native_hkdf(&[s("ikm"), Value::Integer(i64::MAX)]).is_err());
+
+        let mut opts = crate::new_hashmap();
+        opts.insert(kw("iterations"), Value::Integer(1));
+        opts.insert(kw("length"), Value::Integer(MAX_PBKDF2_LENGTH as i64 + 1));
+        assert!(native_pbkdf2(&[s("password"), s("salt"), Value::Map(opts)]).is_err());
+
+        assert!(native_hmac(&[s("k"), s("d"), kwd("sha256"), kwd("bytes"), kwd("x")]).is_err());
+    }
+}
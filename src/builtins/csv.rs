@@ -1,18 +1,29 @@
 //! CSV処理関数
 //! RFC 4180 準拠の CSV パーサー・シリアライザー
+//!
+//! 全ての関数は最後の引数に省略可能なオプションマップを受け取る:
+//! - 方言: :delimiter（","）、:quote（"\""、nilでクォートなし）、:escape（nilなら""でエスケープ）、:comment（nil）
+//! - 読み込み: :headers（trueで先頭行を見出しにしてマップで返す、ベクタで列名を指定）、
+//!   :keywordize（マップのキーをキーワードにする、デフォルトtrue）、:types（{:age :int}）、
+//!   :infer（数値・真偽値を自動変換）、:strict（不正な行をエラーにする）、:encoding
+//! - 書き込み: :headers（マップの列順、falseで見出し行を出さない）、:bom、:encoding、:append
+//!
+//! 先頭のUTF-8 BOMは常に読み飛ばす。
 
-use crate::check_args;
+use crate::builtins::util::kw;
 use crate::i18n::{fmt_msg, MsgKey};
-use crate::value::{Stream, Value};
-use parking_lot::RwLock;
+use crate::value::{MapKey, Stream, Value};
+use parking_lot::{Mutex, RwLock};
+use std::io::{BufRead, Write};
 use std::sync::Arc;
 
-/// csv/parse - CSV文字列をパースして Vec<Vec<String>> に変換
-/// 引数: (text [:delimiter delim])
+/// csv/parse - CSV文字列をパースしてリストのリストに変換
+/// 引数: (text [opts]) または (text :delimiter delim)
 /// 例: (csv/parse text :delimiter "\t")  ;; TSV
+///     (csv/parse text {:headers true :infer true})  ;; 見出し付き、マップのリスト
 pub fn native_csv_parse(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() || args.len() > 3 {
-        return Err(fmt_msg(MsgKey::CsvParseNeed1Or3Args, &[]));
+        return Err(fmt_msg(MsgKey::CsvParseNeed1To3Args, &[]));
     }
 
     let csv_str = match &args[0] {
@@ -20,40 +31,31 @@ pub fn native_csv_parse(args: &[Value]) -> Result<Value, String> {
         _ => return Err(fmt_msg(MsgKey::TypeOnly, &["csv/parse", "string"])),
     };
 
-    // オプショナル delimiter引数
-    let delimiter = if args.len() == 3 {
+    let opts = match &args[1..] {
+        [] => CsvOptions::default(),
+        [opts] => CsvOptions::parse("csv/parse", Some(opts))?,
         // :delimiter "\t" 形式
-        match (&args[1], &args[2]) {
-            (Value::Keyword(k), Value::String(d)) if &**k == "delimiter" => {
-                if d.chars().count() != 1 {
-                    return Err(fmt_msg(MsgKey::CsvDelimiterMustBeSingleChar, &[]));
-                }
-                // count() == 1 をチェック済みのため安全
-                #[allow(clippy::expect_used)]
-                d.chars()
-                    .next()
-                    .expect("delimiter must have exactly one character")
+        [Value::Keyword(k), Value::String(d)] if &**k == "delimiter" => {
+            let delimiter =
+                single_char(d).ok_or_else(|| fmt_msg(MsgKey::CsvDelimiterMustBeSingleChar, &[]))?;
+            CsvOptions {
+                delimiter,
+                ..CsvOptions::default()
             }
-            _ => return Err(fmt_msg(MsgKey::CsvInvalidDelimiterArg, &[])),
         }
-    } else {
-        ',' // デフォルト
+        _ => return Err(fmt_msg(MsgKey::CsvInvalidDelimiterArg, &[])),
     };
 
-    let records = parse_csv_with_delimiter(csv_str, delimiter)?;
-
-    // Vec<Vec<String>> を Value::List(Vec<Value::List(Vec<Value::String>)>) に変換
-    let result = records
-        .into_iter()
-        .map(|record| Value::List(record.into_iter().map(Value::String).collect()))
-        .collect();
-
-    Ok(Value::List(result))
+    read_all(csv_str.as_bytes(), opts, "csv/parse")
 }
 
-/// csv/stringify - Vec<Vec<Value>> を CSV文字列に変換
+/// csv/stringify - リストのリスト（またはマップのリスト）をCSV文字列に変換
+/// 引数: (data [opts])
+/// マップの場合は先頭行に見出しを出力する（列順は :headers、省略時はキーの名前順）
 pub fn native_csv_stringify(args: &[Value]) -> Result<Value, String> {
-    check_args!(args, 1, "csv/stringify");
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["csv/stringify"]));
+    }
 
     let records = match &args[0] {
         Value::List(records) | Value::Vector(records) => records,
@@ -64,103 +66,73 @@ pub fn native_csv_stringify(args: &[Value]) -> Result<Value, String> {
             ))
         }
     };
+    let opts = CsvOptions::parse("csv/stringify", args.get(1))?;
 
-    // Value::List を Vec<Vec<String>> に変換
-    let mut string_records = Vec::with_capacity(records.len());
-    for record in records {
-        match record {
-            Value::List(fields) | Value::Vector(fields) => {
-                let mut string_fields = Vec::with_capacity(fields.len());
-                for field in fields {
-                    let s = match field {
-                        Value::String(s) => s.clone(),
-                        Value::Integer(n) => n.to_string(),
-                        Value::Float(f) => f.to_string(),
-                        Value::Bool(b) => b.to_string(),
-                        Value::Nil => String::new(),
-                        _ => {
-                            return Err(fmt_msg(
-                                MsgKey::CsvCannotSerialize,
-                                &[&format!("{:?}", field)],
-                            ))
-                        }
-                    };
-                    string_fields.push(s);
-                }
-                string_records.push(string_fields);
-            }
-            _ => return Err(fmt_msg(MsgKey::CsvRecordMustBeList, &[])),
-        }
+    let mut csv_str = String::new();
+    if opts.bom {
+        csv_str.push('\u{feff}');
     }
-
-    let csv_str = stringify_csv(&string_records);
+    csv_str.push_str(&stringify_records(records, &opts)?);
     Ok(Value::String(csv_str))
 }
 
-/// csv/read-file - CSV ファイルを読み込んで Vec<Vec<String>> に変換
+/// csv/read-file - CSV ファイルを読み込んでリストのリストに変換
+/// 引数: (path [opts])
+/// :encoding は io/read-file と同じ（:auto も使える）
 pub fn native_csv_read_file(args: &[Value]) -> Result<Value, String> {
-    check_args!(args, 1, "csv/read-file");
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["csv/read-file"]));
+    }
 
     let path = match &args[0] {
         Value::String(s) => s,
         _ => return Err(fmt_msg(MsgKey::TypeOnly, &["csv/read-file", "string"])),
     };
+    let opts = CsvOptions::parse("csv/read-file", args.get(1))?;
 
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| fmt_msg(MsgKey::FileReadError, &[path, &e.to_string()]))?;
-
-    // csv/parse と同じ処理
-    let records = parse_csv(&content)?;
-
-    let result = records
-        .into_iter()
-        .map(|record| Value::List(record.into_iter().map(Value::String).collect()))
-        .collect();
+    let content = if opts.encoding == "utf-8" {
+        std::fs::read_to_string(path)
+            .map_err(|e| fmt_msg(MsgKey::FileReadError, &[path, &e.to_string()]))?
+    } else {
+        match super::io::native_read_file(&[
+            Value::String(path.clone()),
+            Value::Keyword("encoding".into()),
+            Value::Keyword(opts.encoding.as_str().into()),
+        ])? {
+            Value::String(s) => s,
+            _ => unreachable!("io/read-file returns a string for text encodings"),
+        }
+    };
 
-    Ok(Value::List(result))
+    read_all(content.as_bytes(), opts, "csv/read-file")
 }
 
 /// csv/read-stream - CSV ファイルをストリームとして読み込み
 ///
-/// **真のストリーミング実装**: 行ごとにメモリに読み込み、
-/// 大きなファイルでもメモリ使用量を抑えます。
+/// **真のストリーミング実装**: レコードごとにメモリに読み込み、
+/// 大きなファイルでもメモリ使用量を抑えます。クォート内の改行にも対応。
+/// :strict true のとき、不正な行は {:error "csv/read-stream: line N: ..." :line N} として返し、
+/// ストリームは止めない。
 pub fn native_csv_read_stream(args: &[Value]) -> Result<Value, String> {
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-
-    check_args!(args, 1, "csv/read-stream");
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["csv/read-stream"]));
+    }
 
     let path = match &args[0] {
         Value::String(s) => s.clone(),
         _ => return Err(fmt_msg(MsgKey::TypeOnly, &["csv/read-stream", "string"])),
     };
+    let opts = CsvOptions::parse("csv/read-stream", args.get(1))?;
 
     // ファイルをバッファリングして開く（ストリーミング）
-    let file =
-        File::open(&path).map_err(|e| fmt_msg(MsgKey::FileReadError, &[&path, &e.to_string()]))?;
-    let reader = BufReader::new(file);
-    let lines = Arc::new(parking_lot::Mutex::new(reader.lines()));
+    let reader = super::io::open_text_reader(&path, &opts.encoding)?;
+    let rows = Mutex::new(RowReader::new(reader, opts));
 
-    // ストリームを作成（行ごとに処理）
+    // ストリームを作成（レコードごとに処理）
     let stream = Stream {
-        next_fn: Box::new(move || {
-            // 次の行を読み取り、CSVとしてパース
-            match lines.lock().next() {
-                Some(Ok(line)) => {
-                    // 1行をCSVとしてパース
-                    match parse_csv_with_delimiter(&line, ',') {
-                        Ok(mut records) if !records.is_empty() => {
-                            // 最初のレコードを取得（1行につき1レコード）
-                            let record = records.remove(0);
-                            Some(Value::List(record.into_iter().map(Value::String).collect()))
-                        }
-                        Ok(_) => None,  // 空行
-                        Err(_) => None, // パースエラーはスキップ（エラーを返すとストリーム全体が停止）
-                    }
-                }
-                Some(Err(_)) => None, // I/Oエラーはスキップ
-                None => None,         // EOF
-            }
+        next_fn: Box::new(move || match rows.lock().next_row()? {
+            Ok(row) => Some(row),
+            Err((line, msg)) => Some(line_error("csv/read-stream", line, &msg)),
         }),
     };
 
@@ -168,159 +140,892 @@ pub fn native_csv_read_stream(args: &[Value]) -> Result<Value, String> {
 }
 
 /// csv/write-file - データをCSV形式でファイルに書き込み
-/// 引数: (data, path) - パイプライン対応
+/// 引数: (data path [opts]) - パイプラインでは (path [opts] data) の順で渡される
 /// 使い方: (data |> (csv/write-file "output.csv"))
 /// csv/stringify + io/write-file の便利関数
 pub fn native_csv_write_file(args: &[Value]) -> Result<Value, String> {
-    check_args!(args, 2, "csv/write-file");
-
-    // データをCSV文字列に変換
-    let csv_str = native_csv_stringify(&[args[0].clone()])?;
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["csv/write-file"]));
+    }
 
-    // ファイルに書き込み
-    let path = match &args[1] {
-        Value::String(s) => s,
+    let (data, path, opts) = split_write_args(args, "csv/write-file")?;
+    let records = match data {
+        Value::List(records) | Value::Vector(records) => records,
         _ => {
             return Err(fmt_msg(
                 MsgKey::TypeOnly,
-                &["csv/write-file (2nd arg)", "string"],
+                &["csv/write-file (data)", "list or vector"],
             ))
         }
     };
+    let opts = CsvOptions::parse("csv/write-file", opts)?;
 
-    std::fs::write(
-        path,
-        match csv_str {
-            Value::String(s) => s,
-            _ => return Err(fmt_msg(MsgKey::CsvWriteFileStringifyFailed, &[])),
-        },
-    )
-    .map_err(|e| fmt_msg(MsgKey::CsvWriteFileFailedToWrite, &[path, &e.to_string()]))?;
+    // データをCSV文字列に変換
+    let csv_str = stringify_records(records, &opts)?;
+
+    // ファイルに書き込み
+    let mut file = FileSink::create(path, &opts, false)
+        .map_err(|e| fmt_msg(MsgKey::CsvWriteFileFailedToWrite, &[path, &e]))?;
+    file.write_str(&csv_str)
+        .and_then(|_| file.flush())
+        .map_err(|e| fmt_msg(MsgKey::CsvWriteFileFailedToWrite, &[path, &e]))?;
 
     Ok(Value::Nil)
 }
 
-/// CSV文字列をパースする（RFC 4180準拠）
-fn parse_csv(input: &str) -> Result<Vec<Vec<String>>, String> {
-    parse_csv_with_delimiter(input, ',')
-}
-
-/// CSV文字列を指定されたdelimiterでパースする
-fn parse_csv_with_delimiter(input: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
-    let mut records = Vec::new();
-    let mut current_record = Vec::new();
-    let mut current_field = String::new();
-    let mut in_quotes = false;
-    let mut chars = input.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        match ch {
-            '"' if in_quotes => {
-                // クォート内でのダブルクォート
-                if chars.peek() == Some(&'"') {
-                    // "" はエスケープされたクォート
-                    current_field.push('"');
-                    chars.next(); // 次のクォートを消費
-                } else {
-                    // クォート終了
-                    in_quotes = false;
+/// csv/write-stream - ストリームやコレクションの各要素を1行ずつCSVとして書き込み
+/// 引数: (data path [opts]) - パイプラインでは (path [opts] data) の順で渡される
+///   data - ストリーム、リストまたはベクタ（要素はリスト・ベクタ・マップ）
+///   opts - CSVオプションに加えて :append（trueなら既存ファイルに追記、見出し行は出さない）
+/// 戻り値: 書き込んだデータ行数（見出し行を除く）
+/// 使い方: (rows |> (csv/write-stream "out.csv" {:headers ["id" "name"]}))
+pub fn native_csv_write_stream(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["csv/write-stream"]));
+    }
+
+    let (data, path, opts) = split_write_args(args, "csv/write-stream")?;
+    let opts = CsvOptions::parse("csv/write-stream", opts)?;
+    // 空でない既存ファイルへの追記のときだけ追記モード（見出し行は既にあるものとして扱う）
+    let append = opts.append
+        && std::fs::metadata(path)
+            .map(|m| m.len() > 0)
+            .unwrap_or(false);
+
+    let mut file = FileSink::create(path, &opts, append)
+        .map_err(|e| fmt_msg(MsgKey::WriteStreamFailedToCreate, &[path, &e]))?;
+    let mut writer = RecordWriter::new(&opts);
+    if append {
+        writer.wrote_header = true;
+    }
+
+    let mut count = 0;
+    let mut write_row = |row: &Value| -> Result<(), String> {
+        writer.write(row, &mut |line| {
+            file.write_str(&line)
+                .and_then(|_| file.write_str("\n"))
+                .map_err(|e| fmt_msg(MsgKey::WriteStreamFailedToWrite, &[path, &e]))
+        })
+    };
+    match data {
+        Value::Stream(stream) => loop {
+            let next = (stream.read().next_fn)();
+            match next {
+                Some(row) => write_row(&row)?,
+                None => break,
+            }
+            count += 1;
+        },
+        Value::Vector(rows) | Value::List(rows) => {
+            for row in rows {
+                write_row(row)?;
+                count += 1;
+            }
+        }
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::ArgMustBeType,
+                &["csv/write-stream (data)", "a stream, vector or list"],
+            ))
+        }
+    }
+
+    file.flush()
+        .map_err(|e| fmt_msg(MsgKey::WriteStreamFailedToWrite, &[path, &e]))?;
+    Ok(Value::Integer(count))
+}
+
+/// 書き込み関数の引数を (data, path, opts) に整理する
+/// 第1引数が文字列ならパイプライン形式 (path [opts] data) として扱う
+fn split_write_args<'a>(
+    args: &'a [Value],
+    fn_name: &str,
+) -> Result<(&'a Value, &'a String, Option<&'a Value>), String> {
+    match args {
+        [Value::String(path), data] => Ok((data, path, None)),
+        [Value::String(path), opts, data] => Ok((data, path, Some(opts))),
+        [data, Value::String(path), rest @ ..] => Ok((data, path, rest.first())),
+        _ => Err(fmt_msg(
+            MsgKey::TypeOnly,
+            &[&format!("{} (path)", fn_name), "string"],
+        )),
+    }
+}
+
+/// 不正な行を表すエラーマップ {:error "fn: line N: msg" :line N}
+fn line_error(fn_name: &str, line: usize, msg: &str) -> Value {
+    let mut m = crate::new_hashmap();
+    m.insert(
+        kw("error"),
+        Value::String(fmt_msg(
+            MsgKey::CsvLineError,
+            &[fn_name, &line.to_string(), msg],
+        )),
+    );
+    m.insert(kw("line"), Value::Integer(line as i64));
+    Value::Map(m)
+}
+
+/// 全てのレコードを読み込む（:strict で不正な行があればエラー）
+fn read_all<R: BufRead>(input: R, opts: CsvOptions, fn_name: &str) -> Result<Value, String> {
+    let mut rows = RowReader::new(input, opts);
+    let mut result = Vec::new();
+    while let Some(row) = rows.next_row() {
+        match row {
+            Ok(row) => result.push(row),
+            Err((line, msg)) => {
+                return Err(fmt_msg(
+                    MsgKey::CsvLineError,
+                    &[fn_name, &line.to_string(), &msg],
+                ))
+            }
+        }
+    }
+    Ok(Value::List(result.into()))
+}
+
+/// レコードをCSV文字列に変換する（末尾の改行なし、BOMは呼び出し側で付ける）
+fn stringify_records(records: &im::Vector<Value>, opts: &CsvOptions) -> Result<String, String> {
+    let mut writer = RecordWriter::new(opts);
+    let mut lines = Vec::with_capacity(records.len());
+    for record in records {
+        writer.write(record, &mut |line| {
+            lines.push(line);
+            Ok(())
+        })?;
+    }
+    Ok(lines.join("\n"))
+}
+
+// ========================================
+// オプション
+// ========================================
+
+/// 見出しの扱い
+#[derive(Clone, PartialEq)]
+enum Headers {
+    /// 指定なし（読み込みはリスト、書き込みはマップのときだけ見出しを出す）
+    Unset,
+    Off,
+    /// 読み込みは先頭行を見出しにする、書き込みは見出しを出す
+    On,
+    /// 列名を指定（読み込みでは先頭行もデータとして扱う）
+    Names(Vec<String>),
+}
+
+#[derive(Clone, Copy)]
+enum ColumnType {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+impl ColumnType {
+    fn name(self) -> &'static str {
+        match self {
+            ColumnType::String => "string",
+            ColumnType::Int => "int",
+            ColumnType::Float => "float",
+            ColumnType::Bool => "bool",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CsvOptions {
+    delimiter: char,
+    quote: Option<char>,
+    escape: Option<char>,
+    comment: Option<char>,
+    headers: Headers,
+    keywordize: bool,
+    /// 列名または列番号（0始まり）ごとの型
+    types: Vec<(MapKey, ColumnType)>,
+    infer: bool,
+    strict: bool,
+    encoding: String,
+    bom: bool,
+    append: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: Some('"'),
+            escape: None,
+            comment: None,
+            headers: Headers::Unset,
+            keywordize: true,
+            types: Vec::new(),
+            infer: false,
+            strict: false,
+            encoding: "utf-8".to_string(),
+            bom: false,
+            append: false,
+        }
+    }
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+impl CsvOptions {
+    fn parse(fn_name: &str, opts: Option<&Value>) -> Result<Self, String> {
+        let mut result = Self::default();
+        let opts = match opts {
+            None | Some(Value::Nil) => return Ok(result),
+            Some(Value::Map(m)) => m,
+            Some(_) => return Err(fmt_msg(MsgKey::MustBeMap, &[fn_name, "options"])),
+        };
+
+        let invalid = |name: &str, value: &Value| {
+            fmt_msg(
+                MsgKey::CsvInvalidOption,
+                &[fn_name, name, &value.to_string()],
+            )
+        };
+        let bool_opt = |name: &str, default: bool| match opts.get(&kw(name)) {
+            None => Ok(default),
+            Some(Value::Bool(b)) => Ok(*b),
+            Some(Value::Nil) => Ok(false),
+            Some(v) => Err(invalid(name, v)),
+        };
+        let char_opt = |name: &str, default: Option<char>| match opts.get(&kw(name)) {
+            None => Ok(default),
+            Some(Value::Nil) => Ok(None),
+            Some(v @ Value::String(s)) => single_char(s).map(Some).ok_or_else(|| invalid(name, v)),
+            Some(v) => Err(invalid(name, v)),
+        };
+
+        if let Some(v) = opts.get(&kw("delimiter")) {
+            result.delimiter = match v {
+                Value::String(s) => single_char(s),
+                _ => None,
+            }
+            .ok_or_else(|| invalid("delimiter", v))?;
+        }
+        result.quote = char_opt("quote", result.quote)?;
+        result.escape = char_opt("escape", None)?;
+        result.comment = char_opt("comment", None)?;
+        result.keywordize = bool_opt("keywordize", true)?;
+        result.infer = bool_opt("infer", false)?;
+        result.strict = bool_opt("strict", false)?;
+        result.bom = bool_opt("bom", false)?;
+        result.append = bool_opt("append", false)?;
+
+        result.headers = match opts.get(&kw("headers")) {
+            None => Headers::Unset,
+            Some(Value::Bool(true)) => Headers::On,
+            Some(Value::Bool(false) | Value::Nil) => Headers::Off,
+            Some(v @ (Value::Vector(names) | Value::List(names))) => Headers::Names(
+                names
+                    .iter()
+                    .map(|n| match n {
+                        Value::String(s) => Ok(s.clone()),
+                        Value::Keyword(k) => Ok(k.to_string()),
+                        _ => Err(invalid("headers", v)),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            Some(v) => return Err(invalid("headers", v)),
+        };
+
+        match opts.get(&kw("types")) {
+            None | Some(Value::Nil) => {}
+            Some(Value::Map(types)) => {
+                for (column, ty) in types.iter() {
+                    let ty = match ty {
+                        Value::Keyword(k) => match &**k {
+                            "string" => ColumnType::String,
+                            "int" | "integer" => ColumnType::Int,
+                            "float" | "number" => ColumnType::Float,
+                            "bool" | "boolean" => ColumnType::Bool,
+                            _ => return Err(invalid("types", ty)),
+                        },
+                        _ => return Err(invalid("types", ty)),
+                    };
+                    result.types.push((column.clone(), ty));
                 }
             }
-            '"' if !in_quotes && current_field.is_empty() => {
-                // フィールドの開始クォート
-                in_quotes = true;
+            Some(v) => return Err(invalid("types", v)),
+        }
+
+        match opts.get(&kw("encoding")) {
+            None | Some(Value::Nil) => {}
+            Some(Value::Keyword(k)) => result.encoding = k.to_string(),
+            Some(v) => return Err(invalid("encoding", v)),
+        }
+
+        Ok(result)
+    }
+
+    /// 列の型（列番号の指定を列名より優先）
+    fn column_type(&self, index: usize, name: Option<&str>) -> Option<ColumnType> {
+        self.types.iter().find_map(|(key, ty)| match key {
+            MapKey::Integer(i) if *i == index as i64 => Some(*ty),
+            MapKey::Keyword(k) if Some(&**k) == name => Some(*ty),
+            MapKey::String(s) if Some(s.as_str()) == name => Some(*ty),
+            _ => None,
+        })
+    }
+}
+
+// ========================================
+// 読み込み
+// ========================================
+
+/// 行番号付きのエラー
+type LineError = (usize, String);
+
+/// パース中のフィールドの状態
+#[derive(PartialEq)]
+enum FieldState {
+    Start,
+    Unquoted,
+    Quoted,
+    AfterQuote,
+}
+
+/// BufReadからレコードを1つずつ読み出すパーサー（クォート内の改行に対応）
+struct RecordReader<R> {
+    reader: R,
+    opts: CsvOptions,
+    /// 読み込んだ物理行数
+    line: usize,
+    /// :strict で期待するフィールド数（見出しまたは最初のレコードで決まる）
+    expected: Option<usize>,
+    done: bool,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    fn new(reader: R, opts: CsvOptions) -> Self {
+        Self {
+            reader,
+            opts,
+            line: 0,
+            expected: None,
+            done: false,
+        }
+    }
+
+    /// 1行読み込む（EOFならNone）
+    fn read_line(&mut self) -> Result<Option<String>, LineError> {
+        let mut buf = String::new();
+        match self.reader.read_line(&mut buf) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.line += 1;
+                if self.line == 1 {
+                    if let Some(rest) = buf.strip_prefix('\u{feff}') {
+                        buf = rest.to_string();
+                    }
+                }
+                Ok(Some(buf))
             }
-            ch if ch == delimiter && !in_quotes => {
-                // フィールド区切り
-                current_record.push(current_field.clone());
-                current_field.clear();
+            Err(e) => {
+                self.done = true;
+                Err((self.line + 1, e.to_string()))
             }
-            '\r' if !in_quotes => {
-                // CRLFの処理
-                if chars.peek() == Some(&'\n') {
-                    chars.next(); // LFを消費
+        }
+    }
+
+    /// 次のレコードを読む（空行とコメント行は読み飛ばす）
+    /// 戻り値: (レコード開始行, フィールド)
+    fn next_record(&mut self) -> Option<Result<(usize, Vec<String>), LineError>> {
+        if self.done {
+            return None;
+        }
+
+        let mut text = loop {
+            match self.read_line() {
+                Ok(Some(line)) => {
+                    let content = line.trim_end_matches(['\r', '\n']);
+                    let is_comment = self.opts.comment.is_some_and(|c| content.starts_with(c));
+                    if !content.is_empty() && !is_comment {
+                        break line;
+                    }
                 }
-                // レコード終了
-                current_record.push(current_field.clone());
-                current_field.clear();
-                if !current_record.is_empty() || !records.is_empty() {
-                    records.push(current_record.clone());
-                    current_record.clear();
+                Ok(None) => {
+                    self.done = true;
+                    return None;
                 }
+                Err(e) => return Some(Err(e)),
             }
-            '\n' if !in_quotes => {
-                // レコード終了
-                current_record.push(current_field.clone());
-                current_field.clear();
-                if !current_record.is_empty() || !records.is_empty() {
-                    records.push(current_record.clone());
-                    current_record.clear();
+        };
+        let start_line = self.line;
+
+        let CsvOptions {
+            delimiter,
+            quote,
+            escape,
+            strict,
+            ..
+        } = self.opts;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut state = FieldState::Start;
+        let mut error = None;
+
+        'record: loop {
+            let mut chars = text.chars().peekable();
+            while let Some(c) = chars.next() {
+                match state {
+                    FieldState::Quoted => {
+                        if Some(c) == escape && escape != quote {
+                            if let Some(next) = chars.next() {
+                                field.push(next);
+                            }
+                        } else if Some(c) == quote {
+                            if chars.peek() == Some(&c) {
+                                // "" はエスケープされたクォート
+                                field.push(c);
+                                chars.next();
+                            } else {
+                                state = FieldState::AfterQuote;
+                            }
+                        } else {
+                            field.push(c);
+                        }
+                    }
+                    _ if c == delimiter => {
+                        fields.push(std::mem::take(&mut field));
+                        state = FieldState::Start;
+                    }
+                    _ if c == '\r' || c == '\n' => break 'record,
+                    FieldState::Start if Some(c) == quote => state = FieldState::Quoted,
+                    _ => {
+                        if (Some(c) == quote || state == FieldState::AfterQuote) && error.is_none()
+                        {
+                            error = Some(fmt_msg(MsgKey::CsvUnexpectedQuote, &[]));
+                        }
+                        field.push(c);
+                        state = FieldState::Unquoted;
+                    }
+                }
+            }
+
+            if state != FieldState::Quoted {
+                break;
+            }
+            // クォート内の改行: 次の行に続く
+            match self.read_line() {
+                Ok(Some(next)) => text = next,
+                Ok(None) => {
+                    self.done = true;
+                    error.get_or_insert_with(|| fmt_msg(MsgKey::CsvUnterminatedQuote, &[]));
+                    break;
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        fields.push(field);
+
+        if strict {
+            let expected = *self.expected.get_or_insert(fields.len());
+            if error.is_none() && fields.len() != expected {
+                error = Some(fmt_msg(
+                    MsgKey::CsvFieldCount,
+                    &[&expected.to_string(), &fields.len().to_string()],
+                ));
+            }
+            if let Some(msg) = error {
+                return Some(Err((start_line, msg)));
+            }
+        }
+        Some(Ok((start_line, fields)))
+    }
+}
+
+/// 見出しより多いフィールドを入れるマップのキー
+const EXTRA_KEY: &str = "_extra";
+
+/// レコードを見出し・型変換を適用した行（リストまたはマップ）に変換する
+struct RowReader<R> {
+    records: RecordReader<R>,
+    /// マップのキーに使う列名（None ならリストで返す）
+    columns: Option<Vec<String>>,
+    header_pending: bool,
+}
+
+impl<R: BufRead> RowReader<R> {
+    fn new(reader: R, opts: CsvOptions) -> Self {
+        let (columns, header_pending) = match &opts.headers {
+            Headers::On => (None, true),
+            Headers::Names(names) => (Some(names.clone()), false),
+            Headers::Unset | Headers::Off => (None, false),
+        };
+        let mut records = RecordReader::new(reader, opts);
+        if let Some(names) = &columns {
+            records.expected = Some(names.len());
+        }
+        Self {
+            records,
+            columns,
+            header_pending,
+        }
+    }
+
+    fn next_row(&mut self) -> Option<Result<Value, LineError>> {
+        if self.header_pending {
+            self.header_pending = false;
+            match self.records.next_record()? {
+                Ok((_, names)) => self.columns = Some(names),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        let (line, fields) = match self.records.next_record()? {
+            Ok(record) => record,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(self.build_row(fields).map_err(|msg| (line, msg)))
+    }
+
+    fn build_row(&self, fields: Vec<String>) -> Result<Value, String> {
+        let opts = &self.records.opts;
+        match &self.columns {
+            None => {
+                let mut values = Vec::with_capacity(fields.len());
+                for (i, field) in fields.into_iter().enumerate() {
+                    values.push(self.convert(field, i, None)?);
                 }
+                Ok(Value::List(values.into()))
             }
-            _ => {
-                // 通常の文字
-                current_field.push(ch);
+            Some(columns) => {
+                let mut map = crate::new_hashmap();
+                let mut fields = fields.into_iter();
+                for (i, name) in columns.iter().enumerate() {
+                    // 足りないフィールドはnil（:strict ではエラー済み）
+                    let value = match fields.next() {
+                        Some(field) => self.convert(field, i, Some(name))?,
+                        None => Value::Nil,
+                    };
+                    let key = if opts.keywordize {
+                        MapKey::Keyword(name.as_str().into())
+                    } else {
+                        MapKey::String(name.clone())
+                    };
+                    map.insert(key, value);
+                }
+                // 見出しより多いフィールドは捨てずに :_extra にベクタで残す
+                let mut extra = Vec::new();
+                for (i, field) in fields.enumerate() {
+                    extra.push(self.convert(field, columns.len() + i, None)?);
+                }
+                if !extra.is_empty() {
+                    let key = if opts.keywordize {
+                        MapKey::Keyword(EXTRA_KEY.into())
+                    } else {
+                        MapKey::String(EXTRA_KEY.to_string())
+                    };
+                    map.insert(key, Value::Vector(extra.into()));
+                }
+                Ok(Value::Map(map))
             }
         }
     }
 
-    // 最後のフィールドとレコードを追加
-    if !current_field.is_empty() || !current_record.is_empty() || in_quotes {
-        current_record.push(current_field);
-        if !current_record.is_empty() {
-            records.push(current_record);
+    /// フィールドを型指定または推論に従って変換する
+    fn convert(&self, field: String, index: usize, name: Option<&str>) -> Result<Value, String> {
+        let opts = &self.records.opts;
+        let ty = opts.column_type(index, name);
+        if ty.is_none() && !opts.infer {
+            return Ok(Value::String(field));
+        }
+        if field.is_empty() {
+            return Ok(Value::Nil);
+        }
+
+        let Some(ty) = ty else {
+            return Ok(infer_value(field));
+        };
+        let trimmed = field.trim();
+        let converted = match ty {
+            ColumnType::String => Some(Value::String(field.clone())),
+            ColumnType::Int => trimmed.parse().ok().map(Value::Integer),
+            ColumnType::Float => trimmed.parse().ok().map(Value::Float),
+            ColumnType::Bool => parse_bool(trimmed).map(Value::Bool),
+        };
+        match converted {
+            Some(v) => Ok(v),
+            // 寛容モードでは変換できない値は文字列のまま
+            None if !opts.strict => Ok(Value::String(field)),
+            None => {
+                let column = name.map_or_else(|| index.to_string(), |n| n.to_string());
+                Err(fmt_msg(
+                    MsgKey::CsvTypeConversion,
+                    &[&column, &field, ty.name()],
+                ))
+            }
         }
     }
+}
 
-    Ok(records)
+fn parse_bool(s: &str) -> Option<bool> {
+    match s {
+        "true" | "TRUE" | "True" => Some(true),
+        "false" | "FALSE" | "False" => Some(false),
+        _ => None,
+    }
 }
 
-/// Vec<Vec<String>> を CSV文字列に変換（RFC 4180準拠）
-fn stringify_csv(records: &[Vec<String>]) -> String {
-    stringify_csv_with_delimiter(records, ',')
+/// 数値・真偽値を推論する（"007" のような先頭0付きの値はIDや郵便番号とみなし文字列のまま）
+fn infer_value(field: String) -> Value {
+    if let Some(b) = parse_bool(&field) {
+        return Value::Bool(b);
+    }
+    let digits = field.strip_prefix('-').unwrap_or(&field);
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    let numeric = digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'));
+    if numeric && !leading_zero {
+        if let Ok(i) = field.parse::<i64>() {
+            return Value::Integer(i);
+        }
+        if let Ok(f) = field.parse::<f64>() {
+            return Value::Float(f);
+        }
+    }
+    Value::String(field)
 }
 
-/// Vec<Vec<String>> を指定されたdelimiterでCSV文字列に変換
-fn stringify_csv_with_delimiter(records: &[Vec<String>], delimiter: char) -> String {
-    let mut result = String::new();
+// ========================================
+// 書き込み
+// ========================================
 
-    for (i, record) in records.iter().enumerate() {
-        for (j, field) in record.iter().enumerate() {
-            // フィールドにdelimiter、ダブルクォート、改行が含まれている場合はクォートで囲む
-            let needs_quoting = field.contains(delimiter)
-                || field.contains('"')
-                || field.contains('\n')
-                || field.contains('\r');
-
-            if needs_quoting {
-                result.push('"');
-                // ダブルクォートをエスケープ
-                for ch in field.chars() {
-                    if ch == '"' {
-                        result.push('"');
-                        result.push('"');
-                    } else {
-                        result.push(ch);
+/// 行をCSVの1行に変換する（見出し行の出力も担当）
+struct RecordWriter<'a> {
+    opts: &'a CsvOptions,
+    /// マップの列順
+    columns: Option<Vec<String>>,
+    wrote_header: bool,
+}
+
+impl<'a> RecordWriter<'a> {
+    fn new(opts: &'a CsvOptions) -> Self {
+        let columns = match &opts.headers {
+            Headers::Names(names) => Some(names.clone()),
+            _ => None,
+        };
+        Self {
+            opts,
+            columns,
+            wrote_header: false,
+        }
+    }
+
+    /// 1行を書き込む（必要なら先に見出し行を書く）
+    fn write(
+        &mut self,
+        row: &Value,
+        emit: &mut dyn FnMut(String) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let fields = match row {
+            Value::Map(m) => {
+                let columns = self.columns.get_or_insert_with(|| {
+                    let mut names: Vec<String> = m.keys().map(key_name).collect();
+                    names.sort();
+                    names
+                });
+                let fields = columns
+                    .iter()
+                    .map(|c| {
+                        let value = m
+                            .get(&kw(c))
+                            .or_else(|| m.get(&MapKey::String(c.clone())))
+                            .unwrap_or(&Value::Nil);
+                        field_to_string(value)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if !self.wrote_header && self.opts.headers != Headers::Off {
+                    emit(format_record(self.opts, columns))?;
+                }
+                fields
+            }
+            Value::List(values) | Value::Vector(values) => {
+                if !self.wrote_header {
+                    if let Headers::Names(names) = &self.opts.headers {
+                        emit(format_record(self.opts, names))?;
                     }
                 }
-                result.push('"');
-            } else {
-                result.push_str(field);
+                values
+                    .iter()
+                    .map(field_to_string)
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            _ => return Err(fmt_msg(MsgKey::CsvRecordMustBeList, &[])),
+        };
+        self.wrote_header = true;
+        emit(format_record(self.opts, &fields))
+    }
+}
+
+/// 1レコードをCSVの1行に変換する（改行なし）
+fn format_record(opts: &CsvOptions, record: &[String]) -> String {
+    let CsvOptions {
+        delimiter,
+        quote,
+        escape,
+        ..
+    } = *opts;
+    let mut result = String::new();
+
+    for (j, field) in record.iter().enumerate() {
+        if j > 0 {
+            result.push(delimiter);
+        }
+        let Some(quote) = quote else {
+            result.push_str(field);
+            continue;
+        };
+
+        // フィールドにdelimiter、クォート、改行が含まれている場合はクォートで囲む
+        let needs_quoting = field
+            .chars()
+            .any(|c| c == delimiter || c == quote || c == '\n' || c == '\r' || Some(c) == escape);
+        if !needs_quoting {
+            result.push_str(field);
+            continue;
+        }
+
+        result.push(quote);
+        for ch in field.chars() {
+            match escape {
+                Some(e) if e != quote && (ch == quote || ch == e) => {
+                    result.push(e);
+                    result.push(ch);
+                }
+                _ if ch == quote => {
+                    // クォートを重ねてエスケープ
+                    result.push(quote);
+                    result.push(quote);
+                }
+                _ => result.push(ch),
             }
+        }
+        result.push(quote);
+    }
+
+    result
+}
+
+fn key_name(key: &MapKey) -> String {
+    match key {
+        MapKey::Keyword(k) => k.to_string(),
+        MapKey::String(s) => s.clone(),
+        MapKey::Symbol(s) => s.to_string(),
+        MapKey::Integer(i) => i.to_string(),
+        MapKey::Char(c) => c.to_string(),
+    }
+}
+
+fn field_to_string(field: &Value) -> Result<String, String> {
+    Ok(match field {
+        Value::String(s) => s.clone(),
+        Value::Keyword(k) => k.to_string(),
+        Value::Integer(n) => n.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::BigInt(n) => n.to_string(),
+        Value::Decimal(d) => d.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Nil => String::new(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::CsvCannotSerialize,
+                &[&format!("{:?}", field)],
+            ))
+        }
+    })
+}
+
+/// エンコーディングを適用して書き込むファイル
+struct FileSink {
+    writer: std::io::BufWriter<std::fs::File>,
+    #[cfg(feature = "encoding-extended")]
+    encoding: Option<&'static encoding_rs::Encoding>,
+}
+
+impl FileSink {
+    /// ファイルを開き、必要ならBOMを書く（追記時は書かない）
+    fn create(path: &str, opts: &CsvOptions, append: bool) -> Result<Self, String> {
+        let encoding_keyword = opts.encoding.as_str();
+        #[cfg(feature = "encoding-extended")]
+        let encoding = match encoding_keyword {
+            "utf-8" => None,
+            "utf-8-bom" => None,
+            other => Some(super::io::resolve_encoding(other)?),
+        };
+        #[cfg(not(feature = "encoding-extended"))]
+        if encoding_keyword != "utf-8" && encoding_keyword != "utf-8-bom" {
+            return Err(fmt_msg(
+                MsgKey::IoEncodingNotSupportedInMinimalBuild,
+                &[encoding_keyword],
+            ));
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)
+            .map_err(|e| e.to_string())?;
+        let mut sink = Self {
+            writer: std::io::BufWriter::new(file),
+            #[cfg(feature = "encoding-extended")]
+            encoding,
+        };
+
+        if !append {
+            let bom = sink.bom(opts);
+            sink.writer.write_all(bom).map_err(|e| e.to_string())?;
+        }
+        Ok(sink)
+    }
 
-            // フィールド区切り
-            if j < record.len() - 1 {
-                result.push(delimiter);
+    /// ファイル先頭に書くBOM
+    fn bom(&self, opts: &CsvOptions) -> &'static [u8] {
+        // UTF-16はio/write-fileと同じく常にBOM付き、その他のエンコーディングはBOMなし
+        #[cfg(feature = "encoding-extended")]
+        if let Some(enc) = self.encoding {
+            if enc == encoding_rs::UTF_16LE {
+                return &[0xFF, 0xFE];
+            } else if enc == encoding_rs::UTF_16BE {
+                return &[0xFE, 0xFF];
+            } else if enc != encoding_rs::UTF_8 {
+                return &[];
             }
         }
+        if opts.bom || opts.encoding == "utf-8-bom" {
+            &[0xEF, 0xBB, 0xBF]
+        } else {
+            &[]
+        }
+    }
 
-        // レコード区切り
-        if i < records.len() - 1 {
-            result.push('\n');
+    fn write_str(&mut self, s: &str) -> Result<(), String> {
+        #[cfg(feature = "encoding-extended")]
+        if let Some(encoding) = self.encoding {
+            let bytes = super::io::encode_chunk(s, encoding);
+            return self.writer.write_all(&bytes).map_err(|e| e.to_string());
         }
+        self.writer
+            .write_all(s.as_bytes())
+            .map_err(|e| e.to_string())
     }
 
-    result
+    fn flush(&mut self) -> Result<(), String> {
+        self.writer.flush().map_err(|e| e.to_string())
+    }
 }
 
 // ========================================
@@ -329,13 +1034,14 @@ fn stringify_csv_with_delimiter(records: &[Vec<String>], delimiter: char) -> Str
 
 /// 登録すべき関数のリスト
 /// @qi-doc:category data/csv
-/// @qi-doc:functions parse, stringify, read-file, write-file, read-stream
+/// @qi-doc:functions parse, stringify, read-file, write-file, read-stream, write-stream
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("csv/parse", native_csv_parse),
     ("csv/stringify", native_csv_stringify),
     ("csv/read-file", native_csv_read_file),
     ("csv/write-file", native_csv_write_file),
     ("csv/read-stream", native_csv_read_stream),
+    ("csv/write-stream", native_csv_write_stream),
 ];
 
 #[cfg(test)]
@@ -440,4 +1146,99 @@ mod tests {
 
         assert_eq!(reparsed, original_parsed);
     }
+
+    fn opts(entries: Vec<(&str, Value)>) -> Value {
+        let mut m = crate::new_hashmap();
+        for (k, v) in entries {
+            m.insert(kw(k), v);
+        }
+        Value::Map(m)
+    }
+
+    #[test]
+    fn test_csv_parse_headers_types_and_strict() {
+        let csv = "\u{feff}id,name,zip\n# comment\n1,\"multi\nline\",007\n\n2,Bob,123";
+        let options = opts(vec![
+            ("headers", Value::Bool(true)),
+            ("infer", Value::Bool(true)),
+            ("comment", Value::String("#".into())),
+        ]);
+        let Value::List(rows) = native_csv_parse(&[Value::String(csv.into()), options]).unwrap()
+        else {
+            panic!("expected list");
+        };
+        assert_eq!(rows.len(), 2);
+        let Value::Map(first) = &rows[0] else {
+            panic!("expected map");
+        };
+        assert_eq!(first.get(&kw("id")), Some(&Value::Integer(1)));
+        assert_eq!(
+            first.get(&kw("name")),
+            Some(&Value::String("multi\nline".into()))
+        );
+        // 先頭0付きの値は推論しない
+        assert_eq!(first.get(&kw("zip")), Some(&Value::String("007".into())));
+
+        let strict = opts(vec![("strict", Value::Bool(true))]);
+        let err = native_csv_parse(&[Value::String("a,b\n1,2,3".into()), strict.clone()]);
+        assert!(err.unwrap_err().contains("2"));
+        assert!(native_csv_parse(&[Value::String("a,\"b".into()), strict]).is_err());
+        // 寛容モードではそのまま返す
+        assert!(native_csv_parse(&[Value::String("a,\"b".into())]).is_ok());
+
+        // 見出しより多いフィールドは :_extra に残る
+        let headers = opts(vec![("headers", Value::Bool(true))]);
+        let Value::List(rows) =
+            native_csv_parse(&[Value::String("a,b\n1,2,3,4".into()), headers]).unwrap()
+        else {
+            panic!("expected list");
+        };
+        let Value::Map(row) = &rows[0] else {
+            panic!("expected map");
+        };
+        assert_eq!(row.get(&kw("b")), Some(&Value::String("2".into())));
+        assert_eq!(
+            row.get(&kw("_extra")),
+            Some(&Value::Vector(
+                vec![Value::String("3".into()), Value::String("4".into())].into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_csv_write_stream_append() {
+        let path = std::env::temp_dir().join(format!("qi-csv-test-{}.csv", std::process::id()));
+        let path_str = Value::String(path.to_str().unwrap().to_string());
+
+        let row = |id: i64, name: &str| {
+            let mut m = crate::new_hashmap();
+            m.insert(kw("id"), Value::Integer(id));
+            m.insert(kw("name"), Value::String(name.into()));
+            Value::Map(m)
+        };
+        let rows = Value::Vector(vec![row(1, "a"), row(2, "b, c")].into());
+        assert_eq!(
+            native_csv_write_stream(&[path_str.clone(), rows]).unwrap(),
+            Value::Integer(2)
+        );
+        let more = Value::Vector(vec![row(3, "d")].into());
+        let append = opts(vec![("append", Value::Bool(true))]);
+        native_csv_write_stream(&[path_str.clone(), append, more]).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "id,name\n1,a\n2,\"b, c\"\n3,d\n");
+
+        let options = opts(vec![("headers", Value::Bool(true))]);
+        let Value::List(read) = native_csv_read_file(&[path_str, options]).unwrap() else {
+            panic!("expected list");
+        };
+        assert_eq!(read[1], {
+            let mut m = crate::new_hashmap();
+            m.insert(kw("id"), Value::String("2".into()));
+            m.insert(kw("name"), Value::String("b, c".into()));
+            Value::Map(m)
+        });
+
+        std::fs::remove_file(&path).ok();
+    }
 }
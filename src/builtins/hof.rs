@@ -1,13 +1,17 @@
 //! 高階関数
 
 use crate::check_args;
-use crate::eval::Evaluator;
+use crate::eval::{dynamic, Evaluator};
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::Value;
 
 /// map - リストの各要素に関数を適用
-/// 戻り値は入力コレクションの型を維持します
+/// 戻り値は入力コレクションの型を維持します（セットはリストを返す）
 pub fn native_map(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    // (map f) はトランスデューサを返す
+    if args.len() == 1 {
+        return Ok(super::transducer::transducer("map", args));
+    }
     check_args!(args, 2, "map");
 
     let func = &args[0];
@@ -30,9 +34,19 @@ pub fn native_map(args: &[Value], evaluator: &Evaluator) -> Result<Value, String
                 .collect::<Result<_, _>>()?;
             Ok(Value::Vector(results))
         }
+        // セットはリストを返す（結果の重複をまとめず、ハッシュ化できない値も返せるように）
+        Value::Set(items) => {
+            let results: im::Vector<_> = items
+                .iter()
+                .map(|item| evaluator.apply_function(func, std::slice::from_ref(item)))
+                .collect::<Result<_, _>>()?;
+            Ok(Value::List(results))
+        }
+        // ストリームは遅延ストリームを返す（realize/reduceまで評価しない）
+        Value::Stream(stream) => Ok(super::stream::lazy_map(func, stream, evaluator)),
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
-            &["map (2nd arg)", "lists or vectors"],
+            &["map (2nd arg)", "lists, vectors, sets or streams"],
         )),
     }
 }
@@ -40,6 +54,10 @@ pub fn native_map(args: &[Value], evaluator: &Evaluator) -> Result<Value, String
 /// filter - リストから条件を満たす要素を抽出
 /// 戻り値は入力コレクションの型を維持します
 pub fn native_filter(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    // (filter pred) はトランスデューサを返す
+    if args.len() == 1 {
+        return Ok(super::transducer::transducer("filter", args));
+    }
     check_args!(args, 2, "filter");
 
     let pred = &args[0];
@@ -66,9 +84,52 @@ pub fn native_filter(args: &[Value], evaluator: &Evaluator) -> Result<Value, Str
             }
             Ok(Value::Vector(results))
         }
+        Value::Set(items) => {
+            let mut results = items.clone();
+            for item in items {
+                let result = evaluator.apply_function(pred, std::slice::from_ref(item))?;
+                if !result.is_truthy() {
+                    results.remove(item);
+                }
+            }
+            Ok(Value::Set(results))
+        }
+        Value::Stream(stream) => Ok(super::stream::lazy_filter(pred, stream, evaluator)),
+        _ => Err(fmt_msg(
+            MsgKey::TypeOnly,
+            &["filter (2nd arg)", "lists, vectors, sets or streams"],
+        )),
+    }
+}
+
+/// mapcat - 各要素に関数を適用し、返されたリスト/ベクタを連結
+/// ストリームを渡した場合は遅延ストリームを返します
+pub fn native_mapcat(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    check_args!(args, 2, "mapcat");
+
+    let func = &args[0];
+
+    match &args[1] {
+        Value::List(items) | Value::Vector(items) => {
+            let mut results = im::Vector::new();
+            for item in items {
+                match evaluator.apply_function(func, std::slice::from_ref(item))? {
+                    Value::List(sub) | Value::Vector(sub) => results.append(sub),
+                    Value::Nil => {}
+                    _ => {
+                        return Err(fmt_msg(
+                            MsgKey::FuncMustReturnType,
+                            &["mapcat", "a list or vector"],
+                        ))
+                    }
+                }
+            }
+            Ok(Value::List(results))
+        }
+        Value::Stream(stream) => Ok(super::stream::lazy_mapcat(func, stream, evaluator)),
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
-            &["filter (2nd arg)", "lists or vectors"],
+            &["mapcat (2nd arg)", "lists, vectors or streams"],
         )),
     }
 }
@@ -104,9 +165,42 @@ pub fn native_reduce(args: &[Value], evaluator: &Evaluator) -> Result<Value, Str
             }
             Ok(acc)
         }
+        Value::Set(items) => {
+            // セットは順序を持たないため、要素の処理順は不定
+            let mut iter = items.iter().cloned();
+            let mut acc = match init {
+                Some(initial) => initial,
+                None => match iter.next() {
+                    Some(first) => first,
+                    None => return Ok(Value::Nil),
+                },
+            };
+            for item in iter {
+                acc = evaluator.apply_function(func, &[acc, item])?;
+            }
+            Ok(acc)
+        }
+        Value::Stream(stream) => {
+            // ストリームを消費して畳み込む（無限ストリームは終了しない）
+            let next = || {
+                let s = stream.read();
+                (s.next_fn)()
+            };
+            let mut acc = match init {
+                Some(initial) => initial,
+                None => match next() {
+                    Some(first) => first,
+                    None => return Ok(Value::Nil),
+                },
+            };
+            while let Some(item) = next() {
+                acc = evaluator.apply_function(func, &[acc, item])?;
+            }
+            Ok(acc)
+        }
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
-            &["reduce (2nd arg)", "lists or vectors"],
+            &["reduce (2nd arg)", "lists, vectors, sets or streams"],
         )),
     }
 }
@@ -138,9 +232,15 @@ pub fn native_pmap(args: &[Value], evaluator: &Evaluator) -> Result<Value, Strin
             }
 
             // すべての関数を並列処理（Evaluatorが&selfなので複数スレッドで共有可能）
+            // 呼び出し元の動的束縛（binding）は各タスクに引き継ぐ
+            let bindings = dynamic::capture();
             let results: Result<Vec<_>, _> = items_vec
                 .par_iter()
-                .map(|item| evaluator.apply_function(func, std::slice::from_ref(item)))
+                .map(|item| {
+                    dynamic::with_bindings(&bindings, || {
+                        evaluator.apply_function(func, std::slice::from_ref(item))
+                    })
+                })
                 .collect();
 
             match collection {
@@ -174,9 +274,24 @@ pub fn native_each(args: &[Value], evaluator: &Evaluator) -> Result<Value, Strin
             }
             Ok(Value::Nil)
         }
+        Value::Stream(stream) => {
+            loop {
+                let next_val = {
+                    let s = stream.read();
+                    (s.next_fn)()
+                };
+                match next_val {
+                    Some(item) => {
+                        evaluator.apply_function(func, &[item])?;
+                    }
+                    None => break,
+                }
+            }
+            Ok(Value::Nil)
+        }
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
-            &["each (2nd arg)", "lists or vectors"],
+            &["each (2nd arg)", "lists, vectors or streams"],
         )),
     }
 }
@@ -205,11 +320,15 @@ pub fn native_pfilter(args: &[Value], evaluator: &Evaluator) -> Result<Value, St
                 items_vec.push(item.clone());
             }
 
-            // 並列でフィルタリング
+            // 並列でフィルタリング（動的束縛は各タスクに引き継ぐ）
+            let bindings = dynamic::capture();
             let results: Result<Vec<_>, _> = items_vec
                 .par_iter()
                 .filter_map(|item| {
-                    match evaluator.apply_function(pred, std::slice::from_ref(item)) {
+                    let result = dynamic::with_bindings(&bindings, || {
+                        evaluator.apply_function(pred, std::slice::from_ref(item))
+                    });
+                    match result {
                         Ok(result) if result.is_truthy() => Some(Ok(item.clone())),
                         Ok(_) => None,
                         Err(e) => Some(Err(e)),
@@ -265,16 +384,25 @@ pub fn native_preduce(args: &[Value], evaluator: &Evaluator) -> Result<Value, St
                 items_vec.push(item.clone());
             }
 
-            // 並列reduce
+            // 並列reduce（動的束縛は各タスクに引き継ぐ）
+            let bindings = dynamic::capture();
             items_vec
                 .par_iter()
                 .try_fold(
                     || init.clone(),
-                    |acc, item| evaluator.apply_function(func, &[acc, item.clone()]),
+                    |acc, item| {
+                        dynamic::with_bindings(&bindings, || {
+                            evaluator.apply_function(func, &[acc, item.clone()])
+                        })
+                    },
                 )
                 .try_reduce(
                     || init.clone(),
-                    |a, b| evaluator.apply_function(func, &[a, b]),
+                    |a, b| {
+                        dynamic::with_bindings(&bindings, || {
+                            evaluator.apply_function(func, &[a, b])
+                        })
+                    },
                 )
         }
         _ => Err(fmt_msg(
@@ -309,9 +437,11 @@ pub fn native_partition(args: &[Value], evaluator: &Evaluator) -> Result<Value,
                 vec![Value::List(truthy.into()), Value::List(falsy.into())].into(),
             ))
         }
+        // ストリームは [真のストリーム 偽のストリーム] を返す
+        Value::Stream(stream) => Ok(super::stream::lazy_partition(pred, stream, evaluator)),
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
-            &["partition (2nd arg)", "lists or vectors"],
+            &["partition (2nd arg)", "lists, vectors or streams"],
         )),
     }
 }
@@ -1,16 +1,15 @@
 use super::*;
 use crate::builtins::util::kw;
 
-/// HTTP GETリクエスト (非同期)
-pub fn native_get_async(args: &[Value]) -> Result<Value, String> {
-    if args.is_empty() {
-        return Err(fmt_msg(MsgKey::Need1Arg, &["http/get-async"]));
-    }
-
-    let url = match &args[0] {
-        Value::String(s) => s.clone(),
-        _ => return Err(fmt_msg(MsgKey::MustBeString, &["http/get-async", "URL"])),
-    };
+/// リクエストを非同期用スレッドプールで実行し、結果が届くチャネルを返す
+///
+/// 結果は成功時 {:ok 値}、失敗時 {:error メッセージ} で送られる。
+/// プールのスレッド数には上限があり、それを超えたリクエストは空きが出るまで待つ
+fn spawn_request<F>(request: F) -> Result<Value, String>
+where
+    F: FnOnce() -> Result<Value, String> + Send + 'static,
+{
+    let pool = crate::builtins::lazy_init::http_client::get_async_pool()?;
 
     let (sender, receiver) = bounded(1);
     let result_channel = Arc::new(crate::value::Channel {
@@ -18,63 +17,54 @@ pub fn native_get_async(args: &[Value]) -> Result<Value, String> {
         receiver,
     });
 
-    rayon::spawn(move || {
-        let result = simple::native_get(&[Value::String(url)]);
-        let response = match result {
-            Ok(value) => {
-                // 成功: {:ok value}
-                let mut map = crate::new_hashmap();
-                map.insert(kw("ok"), value);
-                Value::Map(map)
-            }
-            Err(err_msg) => {
-                // エラー: {:error message}
-                let mut map = crate::new_hashmap();
-                map.insert(kw("error"), Value::String(err_msg));
-                Value::Map(map)
-            }
+    pool.spawn(move || {
+        let mut map = crate::new_hashmap();
+        match request() {
+            Ok(value) => map.insert(kw("ok"), value),
+            Err(err_msg) => map.insert(kw("error"), Value::String(err_msg)),
         };
-        let _ = sender.send(response);
+        let _ = sender.send(Value::Map(map));
     });
 
     Ok(Value::Channel(result_channel))
 }
 
+/// HTTP GETリクエスト (非同期)
+/// 引数: クライアント（省略可）、URL文字列、オプション（省略可、http/getと同じ。:retry等も使用可）
+/// 戻り値: 結果（{:ok body} または {:error message}）が届くチャネル
+pub fn native_get_async(args: &[Value]) -> Result<Value, String> {
+    let (_, rest) = client::split_session(args, "http/get-async")?;
+
+    if rest.is_empty() {
+        return Err(fmt_msg(MsgKey::Need1Arg, &["http/get-async"]));
+    }
+
+    if !matches!(&rest[0], Value::String(_)) {
+        return Err(fmt_msg(MsgKey::MustBeString, &["http/get-async", "URL"]));
+    }
+
+    // クライアントハンドルを含めてそのままhttp/getへ渡す
+    let call_args = args.to_vec();
+
+    spawn_request(move || simple::native_get(&call_args))
+}
+
 /// HTTP POSTリクエスト (非同期)
+/// 引数: クライアント（省略可）、URL文字列、ボディデータ、オプション（省略可、http/postと同じ。:retry等も使用可）
+/// 戻り値: 結果（{:ok body} または {:error message}）が届くチャネル
 pub fn native_post_async(args: &[Value]) -> Result<Value, String> {
-    crate::check_args!(args, 2, "http/post-async");
+    let (_, rest) = client::split_session(args, "http/post-async")?;
 
-    let url = match &args[0] {
-        Value::String(s) => s.clone(),
-        _ => return Err(fmt_msg(MsgKey::MustBeString, &["http/post-async", "URL"])),
-    };
-
-    let body = args[1].clone();
+    if rest.len() < 2 || rest.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["http/post-async"]));
+    }
 
-    let (sender, receiver) = bounded(1);
-    let result_channel = Arc::new(crate::value::Channel {
-        sender: Arc::new(parking_lot::Mutex::new(Some(sender.clone()))),
-        receiver,
-    });
+    if !matches!(&rest[0], Value::String(_)) {
+        return Err(fmt_msg(MsgKey::MustBeString, &["http/post-async", "URL"]));
+    }
 
-    rayon::spawn(move || {
-        let result = simple::native_post(&[Value::String(url), body]);
-        let response = match result {
-            Ok(value) => {
-                // 成功: {:ok value}
-                let mut map = crate::new_hashmap();
-                map.insert(kw("ok"), value);
-                Value::Map(map)
-            }
-            Err(err_msg) => {
-                // エラー: {:error message}
-                let mut map = crate::new_hashmap();
-                map.insert(kw("error"), Value::String(err_msg));
-                Value::Map(map)
-            }
-        };
-        let _ = sender.send(response);
-    });
+    // クライアントハンドルを含めてそのままhttp/postへ渡す
+    let call_args = args.to_vec();
 
-    Ok(Value::Channel(result_channel))
+    spawn_request(move || simple::native_post(&call_args))
 }
@@ -0,0 +1,352 @@
+//! クエリパラメータ・フォーム・マルチパートのエンコード
+//!
+//! - :query - URLにエンコードして付与（既存のクエリ文字列には追記）
+//! - :form - application/x-www-form-urlencoded のボディ
+//! - :multipart - multipart/form-data のボディ（:path / :stream のパートはメモリに載せずに送信）
+//!
+//! ボディ引数にストリーム（`io/file-stream` 等）を渡した場合も逐次読み出して送信する。
+
+use super::*;
+use crate::builtins::util::kw;
+use crate::value::MapKey;
+use reqwest::blocking::{multipart, RequestBuilder};
+use std::io::Read;
+
+/// :query / :form / :multipart の設定
+#[derive(Default)]
+pub(super) struct BodyOptions {
+    /// エラーメッセージ用の関数名
+    fn_name: String,
+    query: Vec<(String, String)>,
+    form: Option<Vec<(String, String)>>,
+    multipart: Option<Vec<PartSpec>>,
+}
+
+/// マルチパートの1パート
+struct PartSpec {
+    name: String,
+    source: PartSource,
+    filename: Option<String>,
+    content_type: Option<String>,
+}
+
+/// パートの中身
+enum PartSource {
+    Text(String),
+    Bytes(Vec<u8>),
+    /// ファイルパス（送信時に開いて逐次読み出す）
+    File(String),
+    /// Qiのストリーム（送信時に要素を逐次読み出す）
+    Stream(Arc<RwLock<Stream>>),
+}
+
+impl BodyOptions {
+    /// :form / :multipart がボディ引数を置き換えるか
+    pub fn replaces_body(&self) -> bool {
+        self.form.is_some() || self.multipart.is_some()
+    }
+}
+
+/// オプションマップから:query / :form / :multipartを読み取る
+pub(super) fn parse_body_options(
+    opts: &crate::HashMap<MapKey, Value>,
+    fn_name: &str,
+) -> Result<BodyOptions, String> {
+    let query = match opts.get(&kw("query")) {
+        None | Some(Value::Nil) => Vec::new(),
+        Some(v) => encode_pairs(v, fn_name, ":query")?,
+    };
+    let form = match opts.get(&kw("form")) {
+        None | Some(Value::Nil) => None,
+        Some(v) => Some(encode_pairs(v, fn_name, ":form")?),
+    };
+    let multipart = match opts.get(&kw("multipart")) {
+        None | Some(Value::Nil) => None,
+        Some(Value::Vector(parts)) | Some(Value::List(parts)) => Some(
+            parts
+                .iter()
+                .map(|p| parse_part(p, fn_name))
+                .collect::<Result<_, _>>()?,
+        ),
+        Some(_) => {
+            return Err(fmt_msg(
+                MsgKey::HttpInvalidBodyOption,
+                &[fn_name, ":multipart", "expected a vector of part maps"],
+            ))
+        }
+    };
+    if form.is_some() && multipart.is_some() {
+        return Err(fmt_msg(
+            MsgKey::HttpInvalidBodyOption,
+            &[fn_name, ":form", "cannot be combined with :multipart"],
+        ));
+    }
+    Ok(BodyOptions {
+        fn_name: fn_name.to_string(),
+        query,
+        form,
+        multipart,
+    })
+}
+
+/// Mapをキーと値の組に変換する（ベクタの値は同じキーで繰り返す）
+fn encode_pairs(
+    value: &Value,
+    fn_name: &str,
+    option: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let invalid = |detail: &str| fmt_msg(MsgKey::HttpInvalidBodyOption, &[fn_name, option, detail]);
+    let Value::Map(m) = value else {
+        return Err(invalid("expected a map"));
+    };
+
+    let mut pairs = Vec::new();
+    for (k, v) in m.iter() {
+        let key = match k {
+            MapKey::String(s) => s.clone(),
+            MapKey::Keyword(s) | MapKey::Symbol(s) => s.to_string(),
+            MapKey::Integer(i) => i.to_string(),
+            MapKey::Char(c) => c.to_string(),
+        };
+        match v {
+            Value::Nil => {}
+            Value::Vector(items) | Value::List(items) => {
+                for item in items {
+                    let s = scalar_to_string(item).ok_or_else(|| invalid(&key))?;
+                    pairs.push((key.clone(), s));
+                }
+            }
+            _ => pairs.push((
+                key.clone(),
+                scalar_to_string(v).ok_or_else(|| invalid(&key))?,
+            )),
+        }
+    }
+    // Mapの反復順は不定なので、URLが安定するようキー順に並べる（同じキー内の順序は保持）
+    pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(pairs)
+}
+
+/// パラメータ値として使えるスカラー値を文字列にする
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Keyword(k) => Some(k.to_string()),
+        Value::Integer(n) => Some(n.to_string()),
+        Value::Float(f) => Some(f.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_part(value: &Value, fn_name: &str) -> Result<PartSpec, String> {
+    let invalid = |detail: &str| {
+        fmt_msg(
+            MsgKey::HttpInvalidBodyOption,
+            &[fn_name, ":multipart", detail],
+        )
+    };
+    let Value::Map(m) = value else {
+        return Err(invalid("each part must be a map"));
+    };
+
+    let name = match m.get(&kw("name")) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Keyword(k)) => k.to_string(),
+        _ => return Err(invalid("each part needs a :name")),
+    };
+    let optional_string = |key: &str| match m.get(&kw(key)) {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(invalid(&format!(":{} must be a string", key))),
+    };
+    let filename = optional_string("filename")?;
+    let content_type = optional_string("content-type")?;
+
+    let source = if let Some(v) = m.get(&kw("path")) {
+        match v {
+            Value::String(path) => PartSource::File(path.clone()),
+            _ => return Err(invalid(":path must be a string")),
+        }
+    } else if let Some(v) = m.get(&kw("stream")) {
+        match v {
+            Value::Stream(s) => PartSource::Stream(s.clone()),
+            _ => return Err(invalid(":stream must be a stream")),
+        }
+    } else {
+        match m.get(&kw("value")) {
+            Some(Value::Bytes(b)) => PartSource::Bytes(b.to_vec()),
+            Some(v) => PartSource::Text(
+                scalar_to_string(v)
+                    .ok_or_else(|| invalid(&format!("invalid :value for {}", name)))?,
+            ),
+            None => return Err(invalid("each part needs :value, :path or :stream")),
+        }
+    };
+
+    Ok(PartSpec {
+        name,
+        source,
+        filename,
+        content_type,
+    })
+}
+
+/// リクエストに:query / :form / :multipartを適用する
+pub(super) fn apply(
+    mut request: RequestBuilder,
+    opts: &BodyOptions,
+) -> Result<RequestBuilder, String> {
+    if !opts.query.is_empty() {
+        request = request.query(&opts.query);
+    }
+    if let Some(form) = &opts.form {
+        request = request.form(form);
+    }
+    if let Some(parts) = &opts.multipart {
+        request = request.multipart(build_multipart(parts, &opts.fn_name)?);
+    }
+    Ok(request)
+}
+
+fn build_multipart(parts: &[PartSpec], fn_name: &str) -> Result<multipart::Form, String> {
+    let invalid = |detail: &str| {
+        fmt_msg(
+            MsgKey::HttpInvalidBodyOption,
+            &[fn_name, ":multipart", detail],
+        )
+    };
+
+    let mut form = multipart::Form::new();
+    for spec in parts {
+        let mut part = match &spec.source {
+            PartSource::Text(s) => multipart::Part::text(s.clone()),
+            PartSource::Bytes(b) => multipart::Part::bytes(b.clone()),
+            // Part::fileはファイル名とContent-Typeを推測し、送信時に逐次読み出す
+            PartSource::File(path) => {
+                multipart::Part::file(path).map_err(|e| invalid(&format!("{}: {}", path, e)))?
+            }
+            PartSource::Stream(s) => multipart::Part::reader(StreamReader::new(s.clone())),
+        };
+        if let Some(filename) = &spec.filename {
+            part = part.file_name(filename.clone());
+        }
+        if let Some(content_type) = &spec.content_type {
+            part = part
+                .mime_str(content_type)
+                .map_err(|e| invalid(&format!("{}: {}", content_type, e)))?;
+        }
+        form = form.part(spec.name.clone(), part);
+    }
+    Ok(form)
+}
+
+/// Qiのストリームをstd::io::Readとして読み出すアダプタ
+///
+/// 要素はバイト列・バイト値のベクタ（`io/file-stream :bytes`）・文字列（行として改行を付加）を受け付ける。
+/// 文字列は行として連結するため、テキストモードのファイルストリームはバイト単位で元のファイルと一致しない。
+pub(super) struct StreamReader {
+    stream: Arc<RwLock<Stream>>,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl StreamReader {
+    pub fn new(stream: Arc<RwLock<Stream>>) -> Self {
+        Self {
+            stream,
+            buffer: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// 次の要素をバッファに読み込む（終端ならfalse）
+    fn fill(&mut self) -> std::io::Result<bool> {
+        let next = (self.stream.write().next_fn)();
+        let Some(item) = next else {
+            return Ok(false);
+        };
+        self.buffer = match item {
+            Value::Bytes(b) => b.to_vec(),
+            Value::String(s) => {
+                let mut line = s.into_bytes();
+                line.push(b'\n');
+                line
+            }
+            Value::Vector(items) | Value::List(items) => items
+                .iter()
+                .map(|v| match v {
+                    Value::Integer(n) if (0..=255).contains(n) => Ok(*n as u8),
+                    _ => Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "stream element must contain byte values",
+                    )),
+                })
+                .collect::<std::io::Result<_>>()?,
+            other => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("unsupported stream element: {}", other),
+                ))
+            }
+        };
+        self.pos = 0;
+        Ok(true)
+    }
+}
+
+impl Read for StreamReader {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos >= self.buffer.len() {
+            if !self.fill()? {
+                return Ok(0);
+            }
+        }
+        let n = out.len().min(self.buffer.len() - self.pos);
+        out[..n].copy_from_slice(&self.buffer[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_pairs_repeats_vector_values() {
+        let mut m = crate::new_hashmap();
+        m.insert(
+            kw("tag"),
+            Value::Vector(vec![Value::String("a b".into()), Value::Integer(2)].into()),
+        );
+        m.insert(kw("page"), Value::Integer(1));
+        let pairs = encode_pairs(&Value::Map(m), "http/get", ":query").unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("page".to_string(), "1".to_string()),
+                ("tag".to_string(), "a b".to_string()),
+                ("tag".to_string(), "2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_reader() {
+        let items = std::sync::Mutex::new(
+            vec![
+                Value::String("line".into()),
+                Value::Vector(vec![Value::Integer(65), Value::Integer(66)].into()),
+            ]
+            .into_iter(),
+        );
+        let stream = Stream {
+            next_fn: Box::new(move || items.lock().ok()?.next()),
+        };
+        let mut reader = StreamReader::new(Arc::new(RwLock::new(stream)));
+        let mut out = String::new();
+        reader.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "line\nAB");
+    }
+}
@@ -1,18 +1,27 @@
+use super::body::{self, BodyOptions};
+use super::client::HttpSession;
+use super::retry::{self, RequestPolicy, RetryError};
 use super::*;
 use crate::map_i18n_err;
 use reqwest::Method;
 
 /// HTTPリクエストの実装（シンプル版：bodyの文字列のみ返す）
 #[allow(clippy::expect_used)] // 静的キーワードの初期化（CONTRIBUTING.md参照）
+#[allow(clippy::too_many_arguments)]
 pub(super) fn http_request(
     method: &str,
     url: &str,
     body: Option<&Value>,
     headers: Option<&crate::HashMap<crate::value::MapKey, Value>>,
     timeout_ms: u64,
+    policy: &RequestPolicy,
+    body_opts: &BodyOptions,
+    session: Option<&HttpSession>,
 ) -> Result<Value, String> {
     // 詳細版を呼び出す
-    let result = http_request_detailed(method, url, body, headers, timeout_ms)?;
+    let result = http_request_detailed(
+        method, url, body, headers, timeout_ms, policy, body_opts, session,
+    )?;
 
     // 詳細版の戻り値を処理
     match result {
@@ -87,17 +96,43 @@ pub(super) fn http_request(
 
 /// HTTPリクエストの実装（詳細版：Map形式で詳細情報を返す）
 #[allow(clippy::expect_used)] // 静的キーワードの初期化（CONTRIBUTING.md参照）
+#[allow(clippy::too_many_arguments)]
 pub(super) fn http_request_detailed(
     method: &str,
     url: &str,
     body: Option<&Value>,
     headers: Option<&crate::HashMap<crate::value::MapKey, Value>>,
     timeout_ms: u64,
+    policy: &RequestPolicy,
+    body_opts: &BodyOptions,
+    session: Option<&HttpSession>,
 ) -> Result<Value, String> {
+    // :form / :multipart はボディ引数を置き換える
+    let body = if body_opts.replaces_body() {
+        None
+    } else {
+        body
+    };
+
+    // クライアントハンドル指定時はベースURLとデフォルトヘッダーを反映
+    let resolved_url;
+    let merged_headers;
+    let (url, headers) = match session {
+        Some(s) => {
+            resolved_url = s.resolve_url(url);
+            merged_headers = s.merge_headers(headers);
+            (resolved_url.as_str(), merged_headers.as_ref())
+        }
+        None => (url, headers),
+    };
+
+    // クライアントハンドル指定時はそのClientを使用（Cookie・接続を共有）
     // デフォルトタイムアウト（30秒）の場合は共有Clientを使用
     // カスタムタイムアウトの場合のみ新しいClientを作成
     let custom_client;
-    let client = if timeout_ms == 30000 {
+    let client = if let Some(s) = session {
+        &s.client
+    } else if timeout_ms == 30000 {
         crate::builtins::lazy_init::http_client::get_client()?
     } else {
         custom_client = map_i18n_err!(
@@ -123,6 +158,11 @@ pub(super) fn http_request_detailed(
         _ => return Err(fmt_msg(MsgKey::HttpUnsupportedMethod, &[method])),
     };
 
+    // クライアントハンドル使用時の個別タイムアウトはリクエスト単位で上書き
+    if session.is_some() && timeout_ms != 30000 {
+        request = request.timeout(Duration::from_millis(timeout_ms));
+    }
+
     // 圧縮が必要かチェック（大文字小文字を区別しない）
     let should_compress = headers
         .and_then(|h| {
@@ -162,6 +202,10 @@ pub(super) fn http_request_detailed(
                         key_owned = i.to_string();
                         &key_owned
                     }
+                    crate::value::MapKey::Char(c) => {
+                        key_owned = c.to_string();
+                        &key_owned
+                    }
                 };
                 let value = val.trim_matches('"');
                 // HTTPヘッダーインジェクション対策: 改行文字チェック
@@ -176,6 +220,12 @@ pub(super) fn http_request_detailed(
     // ボディ追加
     if let Some(b) = body {
         match b {
+            Value::Stream(s) => {
+                // ストリームは逐次読み出して送信（メモリに全体を載せない）
+                request = request.body(reqwest::blocking::Body::new(body::StreamReader::new(
+                    s.clone(),
+                )));
+            }
             Value::Bytes(data) => {
                 // バイナリデータをそのまま送信
                 if should_compress {
@@ -252,7 +302,27 @@ pub(super) fn http_request_detailed(
         }
     }
 
-    // リクエスト送信
+    request = body::apply(request, body_opts)?;
+
+    // リクエスト送信（:retry / :circuit-breakerを適用、試行ごとにリクエストを複製）
+    let (mut attempts, policy) = Attempts::new(request, policy);
+    let result = retry::execute(
+        url,
+        &policy,
+        || send_detailed(attempts.next()?),
+        retry::classify_response,
+    );
+
+    match result {
+        Ok(value) => Ok(value),
+        Err(RetryError::CircuitOpen(message)) => Ok(error_response("circuit-open", message)),
+        Err(RetryError::Failed(e)) => Err(e),
+    }
+}
+
+/// リクエストを1回送信してレスポンスをMapに変換（通信エラーは{:error ...}マップ）
+#[allow(clippy::expect_used)] // 静的キーワードの初期化（CONTRIBUTING.md参照）
+fn send_detailed(request: reqwest::blocking::RequestBuilder) -> Result<Value, String> {
     match request.send() {
         Ok(response) => {
             let status = response.status().as_u16() as i64;
@@ -315,37 +385,89 @@ pub(super) fn http_request_detailed(
                 "timeout"
             } else if e.is_connect() {
                 "network"
+            } else if retry::is_connection_reset(&e) {
+                "connection-reset"
             } else {
                 "unknown"
             };
 
-            // エラーレスポンスもキーワードキーに変更
-            let error_key = Value::Keyword(crate::intern::intern_keyword("error"))
-                .to_map_key()
-                .expect("error keyword should be valid");
-            let type_key = Value::Keyword(crate::intern::intern_keyword("type"))
-                .to_map_key()
-                .expect("type keyword should be valid");
-            let message_key = Value::Keyword(crate::intern::intern_keyword("message"))
-                .to_map_key()
-                .expect("message keyword should be valid");
+            Ok(error_response(error_type, e.to_string()))
+        }
+    }
+}
 
-            Ok(Value::Map(
-                [(
-                    error_key,
-                    Value::Map(
-                        [
-                            (type_key, Value::String(error_type.to_string())),
-                            (message_key, Value::String(e.to_string())),
-                        ]
-                        .into_iter()
-                        .collect(),
-                    ),
-                )]
+/// 通信エラーのマップ {:error {:type ... :message ...}} を生成
+#[allow(clippy::expect_used)] // 静的キーワードの初期化（CONTRIBUTING.md参照）
+fn error_response(error_type: &str, message: String) -> Value {
+    // エラーレスポンスもキーワードキーに変更
+    let error_key = Value::Keyword(crate::intern::intern_keyword("error"))
+        .to_map_key()
+        .expect("error keyword should be valid");
+    let type_key = Value::Keyword(crate::intern::intern_keyword("type"))
+        .to_map_key()
+        .expect("type keyword should be valid");
+    let message_key = Value::Keyword(crate::intern::intern_keyword("message"))
+        .to_map_key()
+        .expect("message keyword should be valid");
+
+    Value::Map(
+        [(
+            error_key,
+            Value::Map(
+                [
+                    (type_key, Value::String(error_type.to_string())),
+                    (message_key, Value::String(message)),
+                ]
                 .into_iter()
                 .collect(),
-            ))
+            ),
+        )]
+        .into_iter()
+        .collect(),
+    )
+}
+
+/// 試行ごとに送信するリクエストを用意する
+///
+/// ストリーミングボディ（ファイル・ストリーム）を含むリクエストは複製できないため、
+/// リトライを無効にして1回だけ送信する（サーキットブレーカーは有効のまま）。
+struct Attempts {
+    request: Option<reqwest::blocking::RequestBuilder>,
+    cloneable: bool,
+}
+
+impl Attempts {
+    /// 戻り値: (試行の供給元, 実際に適用する耐障害性設定)
+    fn new(
+        request: reqwest::blocking::RequestBuilder,
+        policy: &RequestPolicy,
+    ) -> (Self, RequestPolicy) {
+        let cloneable = request.try_clone().is_some();
+        let mut policy = policy.clone();
+        if !cloneable {
+            policy.retry = None;
         }
+        (
+            Self {
+                request: Some(request),
+                cloneable,
+            },
+            policy,
+        )
+    }
+
+    fn next(&mut self) -> Result<reqwest::blocking::RequestBuilder, String> {
+        let request = if self.cloneable {
+            self.request.as_ref().and_then(|r| r.try_clone())
+        } else {
+            self.request.take()
+        };
+        request.ok_or_else(|| {
+            fmt_msg(
+                MsgKey::HttpClientError,
+                &["request cannot be cloned for retry"],
+            )
+        })
     }
 }
 
@@ -360,9 +482,30 @@ pub(super) fn http_stream(
     basic_auth: Option<(String, String)>,
     bearer_token: Option<String>,
     is_bytes: bool,
+    policy: &RequestPolicy,
+    body_opts: &BodyOptions,
+    session: Option<&HttpSession>,
 ) -> Result<Value, String> {
+    let body = if body_opts.replaces_body() {
+        None
+    } else {
+        body
+    };
+    let resolved_url;
+    let merged_headers;
+    let (url, headers) = match session {
+        Some(s) => {
+            resolved_url = s.resolve_url(url);
+            merged_headers = s.merge_headers(headers);
+            (resolved_url.as_str(), merged_headers.as_ref())
+        }
+        None => (url, headers),
+    };
+
     // タイムアウト設定を考慮したClientを取得
-    let client = if let Some(timeout) = timeout_ms {
+    let client = if let Some(s) = session {
+        s.client.clone()
+    } else if let Some(timeout) = timeout_ms {
         let timeout_duration = std::time::Duration::from_millis(timeout);
         map_i18n_err!(
             reqwest::blocking::Client::builder()
@@ -385,6 +528,10 @@ pub(super) fn http_stream(
         _ => return Err(fmt_msg(MsgKey::HttpUnsupportedMethod, &[method])),
     };
 
+    if let (Some(_), Some(timeout)) = (session, timeout_ms) {
+        request = request.timeout(Duration::from_millis(timeout));
+    }
+
     // ヘッダー追加
     if let Some(h) = headers {
         for (k, v) in h.iter() {
@@ -394,6 +541,7 @@ pub(super) fn http_stream(
                     crate::value::MapKey::Symbol(s) => s.as_ref(),
                     crate::value::MapKey::Keyword(s) => s.as_ref(),
                     crate::value::MapKey::Integer(i) => &i.to_string(),
+                    crate::value::MapKey::Char(c) => &c.to_string(),
                 };
                 let value = val.trim_matches('"');
                 request = request.header(key, value);
@@ -414,6 +562,11 @@ pub(super) fn http_stream(
     // ボディ追加
     if let Some(b) = body {
         match b {
+            Value::Stream(s) => {
+                request = request.body(reqwest::blocking::Body::new(body::StreamReader::new(
+                    s.clone(),
+                )));
+            }
             Value::Bytes(data) => {
                 // バイナリデータをそのまま送信
                 request = request.body(data.as_ref().to_vec());
@@ -434,8 +587,30 @@ pub(super) fn http_stream(
         }
     }
 
-    // リクエスト送信
-    let response = map_i18n_err!(request.send(), MsgKey::HttpStreamRequestFailed)?;
+    request = body::apply(request, body_opts)?;
+
+    // リクエスト送信（:retry / :circuit-breakerを適用）
+    let (mut attempts, policy) = Attempts::new(request, policy);
+    let sent = retry::execute(
+        url,
+        &policy,
+        || attempts.next().map(|r| r.send()),
+        |result| match result {
+            Ok(response) => retry::Outcome::Status(
+                response.status().as_u16(),
+                response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(retry::parse_retry_after),
+            ),
+            Err(e) => retry::classify_error(e),
+        },
+    );
+    let response = match sent {
+        Ok(result) => map_i18n_err!(result, MsgKey::HttpStreamRequestFailed)?,
+        Err(RetryError::CircuitOpen(message) | RetryError::Failed(message)) => return Err(message),
+    };
 
     if !response.status().is_success() {
         return Err(fmt_msg(
@@ -1,12 +1,15 @@
 use super::*;
 
 /// HTTP GETリクエスト（詳細版）
-/// 引数: URL文字列、オプション（省略可）
+/// 引数: クライアント（省略可）、URL文字列、オプション（省略可、:query等）
 /// 戻り値: {:status 200 :headers {...} :body "..."}
 /// エラー時: {:error {:type "timeout" :message "..."}}
 /// 例: (http/get! "https://api.example.com")  ;=> {:status 200 :body "..."}
 /// 例: (http/get! "https://api.example.com" {:headers {"Authorization" "Bearer token"}})
+/// 例: (http/get! api "/users")  ; http/clientで作成したクライアントを使用
 pub fn native_get_bang(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/get!")?;
+
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::Need1Arg, &["http/get!"]));
     }
@@ -17,18 +20,30 @@ pub fn native_get_bang(args: &[Value]) -> Result<Value, String> {
     };
 
     let opts = args.get(1);
-    let (headers, timeout) = helpers::parse_http_options(opts)?;
-
-    core::http_request_detailed("GET", url, None, headers.as_ref(), timeout)
+    let (headers, timeout, policy, body_opts) =
+        helpers::parse_http_options(opts, "http/get!", "GET")?;
+
+    core::http_request_detailed(
+        "GET",
+        url,
+        None,
+        headers.as_ref(),
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
 
 /// HTTP POSTリクエスト（詳細版）
-/// 引数: URL文字列、ボディデータ、オプション（省略可）
+/// 引数: クライアント（省略可）、URL文字列、ボディデータ、オプション（省略可）
 /// 戻り値: {:status 200 :headers {...} :body "..."}
 /// エラー時: {:error {:type "timeout" :message "..."}}
 /// 例: (http/post! "https://api.example.com" {:key "value"})
 /// 例: (http/post! "https://api.example.com" {:key "value"} {:headers {"Authorization" "Bearer token"}})
 pub fn native_post_bang(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/post!")?;
+
     if args.len() < 2 {
         return Err(fmt_msg(MsgKey::Need2Args, &["http/post!"]));
     }
@@ -39,18 +54,30 @@ pub fn native_post_bang(args: &[Value]) -> Result<Value, String> {
     };
 
     let opts = args.get(2);
-    let (headers, timeout) = helpers::parse_http_options(opts)?;
-
-    core::http_request_detailed("POST", url, Some(&args[1]), headers.as_ref(), timeout)
+    let (headers, timeout, policy, body_opts) =
+        helpers::parse_http_options(opts, "http/post!", "POST")?;
+
+    core::http_request_detailed(
+        "POST",
+        url,
+        Some(&args[1]),
+        headers.as_ref(),
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
 
 /// HTTP PUTリクエスト（詳細版）
-/// 引数: URL文字列、ボディデータ、オプション（省略可）
+/// 引数: クライアント（省略可）、URL文字列、ボディデータ、オプション（省略可）
 /// 戻り値: {:status 200 :headers {...} :body "..."}
 /// エラー時: {:error {:type "timeout" :message "..."}}
 /// 例: (http/put! "https://api.example.com/1" {:key "value"})
 /// 例: (http/put! "https://api.example.com/1" {:key "value"} {:timeout 10000})
 pub fn native_put_bang(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/put!")?;
+
     if args.len() < 2 {
         return Err(fmt_msg(MsgKey::Need2Args, &["http/put!"]));
     }
@@ -61,18 +88,30 @@ pub fn native_put_bang(args: &[Value]) -> Result<Value, String> {
     };
 
     let opts = args.get(2);
-    let (headers, timeout) = helpers::parse_http_options(opts)?;
-
-    core::http_request_detailed("PUT", url, Some(&args[1]), headers.as_ref(), timeout)
+    let (headers, timeout, policy, body_opts) =
+        helpers::parse_http_options(opts, "http/put!", "PUT")?;
+
+    core::http_request_detailed(
+        "PUT",
+        url,
+        Some(&args[1]),
+        headers.as_ref(),
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
 
 /// HTTP DELETEリクエスト（詳細版）
-/// 引数: URL文字列、オプション（省略可）
+/// 引数: クライアント（省略可）、URL文字列、オプション（省略可、:query等）
 /// 戻り値: {:status 200 :headers {...} :body "..."}
 /// エラー時: {:error {:type "timeout" :message "..."}}
 /// 例: (http/delete! "https://api.example.com/1")
 /// 例: (http/delete! "https://api.example.com/1" {:headers {"Authorization" "Bearer token"}})
 pub fn native_delete_bang(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/delete!")?;
+
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::Need1Arg, &["http/delete!"]));
     }
@@ -83,18 +122,30 @@ pub fn native_delete_bang(args: &[Value]) -> Result<Value, String> {
     };
 
     let opts = args.get(1);
-    let (headers, timeout) = helpers::parse_http_options(opts)?;
-
-    core::http_request_detailed("DELETE", url, None, headers.as_ref(), timeout)
+    let (headers, timeout, policy, body_opts) =
+        helpers::parse_http_options(opts, "http/delete!", "DELETE")?;
+
+    core::http_request_detailed(
+        "DELETE",
+        url,
+        None,
+        headers.as_ref(),
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
 
 /// HTTP PATCHリクエスト（詳細版）
-/// 引数: URL文字列、ボディデータ、オプション（省略可）
+/// 引数: クライアント（省略可）、URL文字列、ボディデータ、オプション（省略可）
 /// 戻り値: {:status 200 :headers {...} :body "..."}
 /// エラー時: {:error {:type "timeout" :message "..."}}
 /// 例: (http/patch! "https://api.example.com/1" {:key "value"})
 /// 例: (http/patch! "https://api.example.com/1" {:key "value"} {:timeout 10000})
 pub fn native_patch_bang(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/patch!")?;
+
     if args.len() < 2 {
         return Err(fmt_msg(MsgKey::Need2Args, &["http/patch!"]));
     }
@@ -105,18 +156,30 @@ pub fn native_patch_bang(args: &[Value]) -> Result<Value, String> {
     };
 
     let opts = args.get(2);
-    let (headers, timeout) = helpers::parse_http_options(opts)?;
-
-    core::http_request_detailed("PATCH", url, Some(&args[1]), headers.as_ref(), timeout)
+    let (headers, timeout, policy, body_opts) =
+        helpers::parse_http_options(opts, "http/patch!", "PATCH")?;
+
+    core::http_request_detailed(
+        "PATCH",
+        url,
+        Some(&args[1]),
+        headers.as_ref(),
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
 
 /// HTTP HEADリクエスト（詳細版）
-/// 引数: URL文字列、オプション（省略可）
+/// 引数: クライアント（省略可）、URL文字列、オプション（省略可、:query等）
 /// 戻り値: {:status 200 :headers {...} :body ""}
 /// エラー時: {:error {:type "timeout" :message "..."}}
 /// 例: (http/head! "https://api.example.com")
 /// 例: (http/head! "https://api.example.com" {:headers {"Authorization" "Bearer token"}})
 pub fn native_head_bang(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/head!")?;
+
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::Need1Arg, &["http/head!"]));
     }
@@ -127,18 +190,30 @@ pub fn native_head_bang(args: &[Value]) -> Result<Value, String> {
     };
 
     let opts = args.get(1);
-    let (headers, timeout) = helpers::parse_http_options(opts)?;
-
-    core::http_request_detailed("HEAD", url, None, headers.as_ref(), timeout)
+    let (headers, timeout, policy, body_opts) =
+        helpers::parse_http_options(opts, "http/head!", "HEAD")?;
+
+    core::http_request_detailed(
+        "HEAD",
+        url,
+        None,
+        headers.as_ref(),
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
 
 /// HTTP OPTIONSリクエスト（詳細版）
-/// 引数: URL文字列、オプション（省略可）
+/// 引数: クライアント（省略可）、URL文字列、オプション（省略可、:query等）
 /// 戻り値: {:status 200 :headers {...} :body "..."}
 /// エラー時: {:error {:type "timeout" :message "..."}}
 /// 例: (http/options! "https://api.example.com")
 /// 例: (http/options! "https://api.example.com" {:headers {"Authorization" "Bearer token"}})
 pub fn native_options_bang(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/options!")?;
+
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::Need1Arg, &["http/options!"]));
     }
@@ -149,7 +224,17 @@ pub fn native_options_bang(args: &[Value]) -> Result<Value, String> {
     };
 
     let opts = args.get(1);
-    let (headers, timeout) = helpers::parse_http_options(opts)?;
-
-    core::http_request_detailed("OPTIONS", url, None, headers.as_ref(), timeout)
+    let (headers, timeout, policy, body_opts) =
+        helpers::parse_http_options(opts, "http/options!", "OPTIONS")?;
+
+    core::http_request_detailed(
+        "OPTIONS",
+        url,
+        None,
+        headers.as_ref(),
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
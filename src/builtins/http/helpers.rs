@@ -1,5 +1,15 @@
+use super::body::{self, BodyOptions};
+use super::retry::{self, RequestPolicy};
 use super::*;
 
+/// parse_http_optionsの戻り値（ヘッダー、タイムアウトms、耐障害性設定、クエリ・フォーム・マルチパート）
+pub(super) type HttpOptions = (
+    Option<crate::HashMap<crate::value::MapKey, Value>>,
+    u64,
+    RequestPolicy,
+    BodyOptions,
+);
+
 /// gzip圧縮ヘルパー関数
 pub(super) fn compress_gzip(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
     let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
@@ -7,15 +17,22 @@ pub(super) fn compress_gzip(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
     encoder.finish()
 }
 
-/// オプションMapからヘッダーとタイムアウトを抽出する
-/// 引数: オプションMap（Option<&Value>）
-/// 戻り値: (ヘッダーMap, タイムアウトms)
+/// オプションMapからヘッダー・タイムアウト・リトライ設定を抽出する
+/// 引数: オプションMap（Option<&Value>）、関数名、HTTPメソッド（リトライのデフォルトに使用）
+/// 戻り値: (ヘッダーMap, タイムアウトms, :retry / :circuit-breaker設定, :query / :form / :multipart設定)
 pub(super) fn parse_http_options(
     opts: Option<&Value>,
-) -> Result<(Option<crate::HashMap<crate::value::MapKey, Value>>, u64), String> {
+    fn_name: &str,
+    method: &str,
+) -> Result<HttpOptions, String> {
     let Some(Value::Map(opts_map)) = opts else {
         // オプションがない場合はデフォルト値
-        return Ok((None, 30000));
+        return Ok((
+            None,
+            30000,
+            RequestPolicy::default(),
+            BodyOptions::default(),
+        ));
     };
 
     // キーを準備
@@ -79,5 +96,8 @@ pub(super) fn parse_http_options(
         Some(headers)
     };
 
-    Ok((headers_opt, timeout))
+    let policy = retry::parse_policy(opts_map, fn_name, method)?;
+    let body_opts = body::parse_body_options(opts_map, fn_name)?;
+
+    Ok((headers_opt, timeout, policy, body_opts))
 }
@@ -3,6 +3,9 @@
 //! HTTP通信関数を提供:
 //! - get/post/put/delete/patch/head/options: 各HTTPメソッド
 //! - request: 詳細なリクエスト設定
+//! - client: 再利用可能なクライアント（Cookie・ベースURL・プロキシ・TLS設定、各関数の第1引数に指定可）
+//! - :query / :form / :multipart: クエリ・フォーム・マルチパートのエンコード（ファイルとストリームは逐次送信）
+//! - :retry / :circuit-breaker: リトライ・バックオフ・ホスト単位のサーキットブレーカー（全バリアント共通）
 //! - get-async/post-async: 非同期版
 //! - get-stream/post-stream/request-stream: ストリーミング版
 //!
@@ -20,14 +23,18 @@ use std::sync::Arc;
 use std::time::Duration;
 
 mod async_ops;
+mod body;
+mod client;
 mod core;
 mod detailed;
 mod helpers;
 mod request;
+mod retry;
 mod simple;
 mod stream;
 
 pub use async_ops::*;
+pub use client::*;
 pub use detailed::*;
 pub use request::*;
 pub use simple::*;
@@ -39,7 +46,7 @@ pub use stream::*;
 
 /// 登録すべき関数のリスト
 /// @qi-doc:category net/http
-/// @qi-doc:functions get, post, put, delete, patch, head, options, request, get!, post!, put!, delete!, patch!, head!, options!, get-stream, post-stream, request-stream
+/// @qi-doc:functions get, post, put, delete, patch, head, options, request, client, client-close, get!, post!, put!, delete!, patch!, head!, options!, get-stream, post-stream, request-stream
 pub const FUNCTIONS: super::NativeFunctions = &[
     // シンプル版（bodyのみ返す）
     ("http/get", native_get),
@@ -59,6 +66,9 @@ pub const FUNCTIONS: super::NativeFunctions = &[
     ("http/options!", native_options_bang),
     // 詳細制御版（元からの仕様）
     ("http/request", native_request),
+    // クライアント（セッション）
+    ("http/client", native_client),
+    ("http/client-close", native_client_close),
     // 非同期版
     ("http/get-async", native_get_async),
     ("http/post-async", native_post_async),
@@ -1,7 +1,12 @@
+use super::body;
+use super::retry;
 use super::*;
 
 /// 詳細なHTTPリクエスト
+/// 引数: クライアント（省略可）、リクエスト設定Map
 pub fn native_request(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/request")?;
+
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::Need1Arg, &["http/request"]));
     }
@@ -98,5 +103,17 @@ pub fn native_request(args: &[Value]) -> Result<Value, String> {
         })
         .unwrap_or(30000);
 
-    core::http_request_detailed(method, url, body, headers_ref, timeout)
+    let policy = retry::parse_policy(opts, "http/request", method)?;
+    let body_opts = body::parse_body_options(opts, "http/request")?;
+
+    core::http_request_detailed(
+        method,
+        url,
+        body,
+        headers_ref,
+        timeout,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
@@ -0,0 +1,574 @@
+//! リトライ・バックオフ・サーキットブレーカー
+//!
+//! - :retry - 一時的な失敗（502/503/504、接続エラー等）を自動で再試行する
+//!   - Retry-Afterヘッダー（秒数またはHTTP-date）があればその時間だけ待つ
+//!   - 非冪等なメソッド（POST/PATCH）は、:onを明示しない限り送信前の接続エラーのみ再試行
+//! - :circuit-breaker - ホストごとに連続失敗を数え、閾値を超えたら一定時間リクエストを止める
+
+use super::*;
+use crate::builtins::util::kw;
+use crate::value::MapKey;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Instant;
+
+/// デフォルトの最大リトライ回数
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// デフォルトのバックオフ基準時間（ミリ秒）
+const DEFAULT_BASE_DELAY_MS: u64 = 200;
+
+/// デフォルトの最大待機時間（ミリ秒）
+const DEFAULT_MAX_DELAY_MS: u64 = 30_000;
+
+/// デフォルトのサーキットブレーカー閾値（連続失敗回数）
+const DEFAULT_CIRCUIT_THRESHOLD: u32 = 5;
+
+/// デフォルトのサーキットブレーカー復帰時間（ミリ秒）
+const DEFAULT_CIRCUIT_RESET_MS: u64 = 30_000;
+
+/// バックオフ方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Backoff {
+    /// 毎回同じ待機時間
+    Constant,
+    /// 試行回数に比例
+    Linear,
+    /// 試行ごとに2倍
+    Exponential,
+}
+
+/// リトライ対象の失敗
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum RetryOn {
+    /// ステータスコード
+    Status(u16),
+    /// 接続エラー（接続失敗・接続リセット）
+    ConnectError,
+    /// タイムアウト
+    Timeout,
+}
+
+/// リトライ設定
+#[derive(Debug, Clone)]
+pub(super) struct RetryPolicy {
+    /// 最大リトライ回数（初回を含まない）
+    pub max: u32,
+    pub backoff: Backoff,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub on: Vec<RetryOn>,
+    /// 送信済みの可能性がある失敗も再試行するか（:onを明示した場合と冪等なメソッド）
+    pub retry_sent: bool,
+}
+
+/// サーキットブレーカー設定
+#[derive(Debug, Clone)]
+pub(super) struct CircuitConfig {
+    pub threshold: u32,
+    pub reset: Duration,
+}
+
+/// リクエストごとの耐障害性設定（:retry / :circuit-breaker）
+#[derive(Debug, Clone, Default)]
+pub(super) struct RequestPolicy {
+    pub retry: Option<RetryPolicy>,
+    pub circuit: Option<CircuitConfig>,
+}
+
+/// 1回の試行結果の分類
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Outcome {
+    /// レスポンスを受信（Retry-Afterがあれば待機時間）
+    Status(u16, Option<Duration>),
+    /// 接続エラー（sent: リクエストが送信された可能性があるか）
+    Connect {
+        sent: bool,
+    },
+    Timeout,
+    Other,
+}
+
+/// リトライ実行時のエラー
+pub(super) enum RetryError {
+    /// サーキットブレーカーが開いている
+    CircuitOpen(String),
+    /// 試行自体の失敗（リトライ対象外）
+    Failed(String),
+}
+
+/// ホストごとのサーキット状態
+#[derive(Default)]
+struct Circuit {
+    failures: u32,
+    open_until: Option<Instant>,
+    /// 半開状態で試行中のリクエストがあるか
+    probing: bool,
+}
+
+static CIRCUITS: LazyLock<parking_lot::Mutex<HashMap<String, Circuit>>> =
+    LazyLock::new(|| parking_lot::Mutex::new(HashMap::new()));
+
+/// メソッドが冪等かどうか
+fn is_idempotent(method: &str) -> bool {
+    matches!(
+        method.to_uppercase().as_str(),
+        "GET" | "HEAD" | "OPTIONS" | "PUT" | "DELETE"
+    )
+}
+
+/// オプションマップから:retry / :circuit-breakerを読み取る
+pub(super) fn parse_policy(
+    opts: &crate::HashMap<MapKey, Value>,
+    fn_name: &str,
+    method: &str,
+) -> Result<RequestPolicy, String> {
+    Ok(RequestPolicy {
+        retry: match opts.get(&kw("retry")) {
+            None | Some(Value::Nil) | Some(Value::Bool(false)) => None,
+            Some(v) => Some(parse_retry(v, fn_name, method)?),
+        },
+        circuit: match opts.get(&kw("circuit-breaker")) {
+            None | Some(Value::Nil) | Some(Value::Bool(false)) => None,
+            Some(v) => Some(parse_circuit(v, fn_name)?),
+        },
+    })
+}
+
+fn parse_retry(value: &Value, fn_name: &str, method: &str) -> Result<RetryPolicy, String> {
+    let invalid = |detail: &str| fmt_msg(MsgKey::HttpInvalidRetry, &[fn_name, detail]);
+    let idempotent = is_idempotent(method);
+
+    let mut policy = RetryPolicy {
+        max: DEFAULT_MAX_RETRIES,
+        backoff: Backoff::Exponential,
+        base_delay: Duration::from_millis(DEFAULT_BASE_DELAY_MS),
+        max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_MS),
+        on: if idempotent {
+            vec![
+                RetryOn::Status(502),
+                RetryOn::Status(503),
+                RetryOn::Status(504),
+                RetryOn::ConnectError,
+                RetryOn::Timeout,
+            ]
+        } else {
+            vec![RetryOn::ConnectError]
+        },
+        retry_sent: idempotent,
+    };
+
+    let m = match value {
+        Value::Bool(true) => return Ok(policy),
+        Value::Integer(n) if *n >= 0 => {
+            policy.max = *n as u32;
+            return Ok(policy);
+        }
+        Value::Map(m) => m,
+        _ => return Err(invalid("expected true, an integer or a map")),
+    };
+
+    if let Some(v) = m.get(&kw("max")) {
+        match v {
+            Value::Integer(n) if *n >= 0 => policy.max = *n as u32,
+            _ => return Err(invalid(":max must be a non-negative integer")),
+        }
+    }
+    if let Some(v) = m.get(&kw("backoff")) {
+        policy.backoff = match v {
+            Value::Keyword(k) if &**k == "exponential" => Backoff::Exponential,
+            Value::Keyword(k) if &**k == "linear" => Backoff::Linear,
+            Value::Keyword(k) if &**k == "constant" => Backoff::Constant,
+            _ => {
+                return Err(invalid(
+                    ":backoff must be :exponential, :linear or :constant",
+                ))
+            }
+        };
+    }
+    if let Some(v) = m.get(&kw("delay")) {
+        match v {
+            Value::Integer(n) if *n >= 0 => policy.base_delay = Duration::from_millis(*n as u64),
+            _ => return Err(invalid(":delay must be a non-negative integer (ms)")),
+        }
+    }
+    if let Some(v) = m.get(&kw("max-delay")) {
+        match v {
+            Value::Integer(n) if *n >= 0 => policy.max_delay = Duration::from_millis(*n as u64),
+            _ => return Err(invalid(":max-delay must be a non-negative integer (ms)")),
+        }
+    }
+    if let Some(v) = m.get(&kw("on")) {
+        let (Value::Vector(items) | Value::List(items)) = v else {
+            return Err(invalid(":on must be a vector"));
+        };
+        policy.on = items
+            .iter()
+            .map(|item| match item {
+                Value::Integer(code) if (100..=599).contains(code) => {
+                    Ok(RetryOn::Status(*code as u16))
+                }
+                Value::Keyword(k) if &**k == "connect-error" => Ok(RetryOn::ConnectError),
+                Value::Keyword(k) if &**k == "timeout" => Ok(RetryOn::Timeout),
+                _ => Err(invalid(&format!(":on entry {}", item))),
+            })
+            .collect::<Result<_, _>>()?;
+        // :onを明示した場合は送信済みの失敗も再試行する（利用者の判断）
+        policy.retry_sent = true;
+    }
+
+    Ok(policy)
+}
+
+fn parse_circuit(value: &Value, fn_name: &str) -> Result<CircuitConfig, String> {
+    let invalid = |detail: &str| fmt_msg(MsgKey::HttpInvalidCircuitBreaker, &[fn_name, detail]);
+
+    let mut config = CircuitConfig {
+        threshold: DEFAULT_CIRCUIT_THRESHOLD,
+        reset: Duration::from_millis(DEFAULT_CIRCUIT_RESET_MS),
+    };
+
+    let m = match value {
+        Value::Bool(true) => return Ok(config),
+        Value::Map(m) => m,
+        _ => return Err(invalid("expected true or a map")),
+    };
+
+    if let Some(v) = m.get(&kw("threshold")) {
+        match v {
+            Value::Integer(n) if *n > 0 => config.threshold = *n as u32,
+            _ => return Err(invalid(":threshold must be a positive integer")),
+        }
+    }
+    if let Some(v) = m.get(&kw("reset")) {
+        match v {
+            Value::Integer(n) if *n >= 0 => config.reset = Duration::from_millis(*n as u64),
+            _ => return Err(invalid(":reset must be a non-negative integer (ms)")),
+        }
+    }
+
+    Ok(config)
+}
+
+/// リトライとサーキットブレーカーを適用してリクエストを実行する
+///
+/// attemptは1回分のリクエストを送信し、classifyはその結果を分類する。
+/// リトライを使い切った場合は最後の結果を返す。
+pub(super) fn execute<T>(
+    url: &str,
+    policy: &RequestPolicy,
+    mut attempt: impl FnMut() -> Result<T, String>,
+    classify: impl Fn(&T) -> Outcome,
+) -> Result<T, RetryError> {
+    let host = circuit_key(url);
+    let mut retries = 0;
+
+    loop {
+        if let Some(circuit) = &policy.circuit {
+            if let Err(wait) = circuit_acquire(&host, circuit) {
+                return Err(RetryError::CircuitOpen(fmt_msg(
+                    MsgKey::HttpCircuitOpen,
+                    &[&host, &wait.as_millis().to_string()],
+                )));
+            }
+        }
+
+        let result = match attempt() {
+            Ok(result) => result,
+            Err(e) => {
+                // 失敗として記録する（半開状態の試行中フラグを必ず解除するため）
+                if let Some(circuit) = &policy.circuit {
+                    circuit_record(&host, circuit, false);
+                }
+                return Err(RetryError::Failed(e));
+            }
+        };
+        let outcome = classify(&result);
+
+        if let Some(circuit) = &policy.circuit {
+            circuit_record(&host, circuit, !is_failure(&outcome));
+        }
+
+        let Some(retry) = &policy.retry else {
+            return Ok(result);
+        };
+        if retries >= retry.max || !should_retry(retry, &outcome) {
+            return Ok(result);
+        }
+
+        retries += 1;
+        std::thread::sleep(retry_delay(retry, retries, &outcome));
+    }
+}
+
+/// サーキットブレーカーで失敗として数えるか（接続エラー・タイムアウト・5xx）
+fn is_failure(outcome: &Outcome) -> bool {
+    match outcome {
+        Outcome::Status(code, _) => *code >= 500,
+        Outcome::Connect { .. } | Outcome::Timeout => true,
+        Outcome::Other => false,
+    }
+}
+
+fn should_retry(policy: &RetryPolicy, outcome: &Outcome) -> bool {
+    match outcome {
+        Outcome::Status(code, _) => {
+            policy.retry_sent && policy.on.contains(&RetryOn::Status(*code))
+        }
+        Outcome::Connect { sent } => {
+            (!sent || policy.retry_sent) && policy.on.contains(&RetryOn::ConnectError)
+        }
+        Outcome::Timeout => policy.retry_sent && policy.on.contains(&RetryOn::Timeout),
+        Outcome::Other => false,
+    }
+}
+
+/// n回目のリトライ前の待機時間（Retry-Afterを優先、:max-delayで上限）
+fn retry_delay(policy: &RetryPolicy, n: u32, outcome: &Outcome) -> Duration {
+    let delay = match outcome {
+        Outcome::Status(_, Some(retry_after)) => *retry_after,
+        _ => match policy.backoff {
+            Backoff::Constant => policy.base_delay,
+            Backoff::Linear => policy.base_delay.saturating_mul(n),
+            Backoff::Exponential => policy
+                .base_delay
+                .saturating_mul(2u32.saturating_pow(n.saturating_sub(1))),
+        },
+    };
+    delay.min(policy.max_delay)
+}
+
+/// Retry-Afterヘッダーを解釈（秒数またはHTTP-date）
+pub(super) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(
+        at.duration_since(std::time::SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// サーキットのキー（スキーム・ホスト・ポート）
+fn circuit_key(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(u) => format!(
+            "{}://{}:{}",
+            u.scheme(),
+            u.host_str().unwrap_or(""),
+            u.port_or_known_default().unwrap_or(0)
+        ),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// リクエストを通してよいか判定（開いていれば残り時間を返す）
+fn circuit_acquire(host: &str, config: &CircuitConfig) -> Result<(), Duration> {
+    let mut circuits = CIRCUITS.lock();
+    let circuit = circuits.entry(host.to_string()).or_default();
+
+    let Some(until) = circuit.open_until else {
+        return Ok(());
+    };
+    let now = Instant::now();
+    if now < until {
+        return Err(until - now);
+    }
+    // 半開状態: 1件だけ試行を通す
+    if circuit.probing {
+        return Err(config.reset.min(Duration::from_secs(1)));
+    }
+    circuit.probing = true;
+    Ok(())
+}
+
+/// 試行結果をサーキットに記録
+fn circuit_record(host: &str, config: &CircuitConfig, success: bool) {
+    let mut circuits = CIRCUITS.lock();
+    let circuit = circuits.entry(host.to_string()).or_default();
+    circuit.probing = false;
+
+    if success {
+        circuit.failures = 0;
+        circuit.open_until = None;
+        return;
+    }
+
+    circuit.failures += 1;
+    // 半開状態での失敗、または閾値到達で開く
+    if circuit.open_until.is_some() || circuit.failures >= config.threshold {
+        circuit.open_until = Some(Instant::now() + config.reset);
+    }
+}
+
+/// 詳細版のレスポンスマップ（{:status ...} または {:error {:type ...}}）を分類
+pub(super) fn classify_response(value: &Value) -> Outcome {
+    let Value::Map(m) = value else {
+        return Outcome::Other;
+    };
+
+    if let Some(Value::Integer(status)) = m.get(&kw("status")) {
+        let retry_after = match m.get(&kw("headers")) {
+            Some(Value::Map(h)) => match h.get(&MapKey::String("retry-after".to_string())) {
+                Some(Value::String(s)) => parse_retry_after(s),
+                _ => None,
+            },
+            _ => None,
+        };
+        return Outcome::Status(*status as u16, retry_after);
+    }
+
+    match m.get(&kw("error")) {
+        Some(Value::Map(err)) => match err.get(&kw("type")) {
+            Some(Value::String(t)) if t == "network" => Outcome::Connect { sent: false },
+            Some(Value::String(t)) if t == "connection-reset" => Outcome::Connect { sent: true },
+            Some(Value::String(t)) if t == "timeout" => Outcome::Timeout,
+            _ => Outcome::Other,
+        },
+        _ => Outcome::Other,
+    }
+}
+
+/// reqwestのエラーを分類
+pub(super) fn classify_error(e: &reqwest::Error) -> Outcome {
+    if e.is_timeout() {
+        Outcome::Timeout
+    } else if e.is_connect() {
+        Outcome::Connect { sent: false }
+    } else if is_connection_reset(e) {
+        Outcome::Connect { sent: true }
+    } else {
+        Outcome::Other
+    }
+}
+
+/// 接続リセット・切断によるエラーか（送信後に接続が切れた場合）
+pub(super) fn is_connection_reset(e: &reqwest::Error) -> bool {
+    use std::error::Error;
+    use std::io::ErrorKind;
+
+    let mut source = e.source();
+    while let Some(err) = source {
+        if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            if matches!(
+                io.kind(),
+                ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::UnexpectedEof
+            ) {
+                return true;
+            }
+        }
+        if err
+            .to_string()
+            .contains("connection closed before message completed")
+        {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(v: Value, method: &str) -> RetryPolicy {
+        parse_retry(&v, "http/get", method).unwrap()
+    }
+
+    #[test]
+    fn test_idempotency_aware_defaults() {
+        let get = policy(Value::Bool(true), "GET");
+        assert!(should_retry(&get, &Outcome::Status(503, None)));
+        assert!(should_retry(&get, &Outcome::Connect { sent: true }));
+
+        let post = policy(Value::Bool(true), "POST");
+        assert!(!should_retry(&post, &Outcome::Status(503, None)));
+        assert!(!should_retry(&post, &Outcome::Connect { sent: true }));
+        assert!(should_retry(&post, &Outcome::Connect { sent: false }));
+
+        let mut on = crate::new_hashmap();
+        on.insert(kw("on"), Value::Vector(vec![Value::Integer(503)].into()));
+        let explicit = policy(Value::Map(on), "POST");
+        assert!(should_retry(&explicit, &Outcome::Status(503, None)));
+        assert!(!should_retry(&explicit, &Outcome::Status(502, None)));
+    }
+
+    #[test]
+    fn test_backoff_and_retry_after() {
+        let p = policy(Value::Integer(5), "GET");
+        assert_eq!(
+            retry_delay(&p, 1, &Outcome::Timeout),
+            Duration::from_millis(200)
+        );
+        assert_eq!(
+            retry_delay(&p, 3, &Outcome::Timeout),
+            Duration::from_millis(800)
+        );
+        assert_eq!(
+            retry_delay(&p, 1, &Outcome::Status(503, Some(Duration::from_secs(2)))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            retry_delay(&p, 1, &Outcome::Status(503, Some(Duration::from_secs(120)))),
+            Duration::from_millis(DEFAULT_MAX_DELAY_MS)
+        );
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_circuit_opens_and_half_opens() {
+        let config = CircuitConfig {
+            threshold: 2,
+            reset: Duration::from_millis(20),
+        };
+        let host = "test://circuit:1";
+        assert!(circuit_acquire(host, &config).is_ok());
+        circuit_record(host, &config, false);
+        assert!(circuit_acquire(host, &config).is_ok());
+        circuit_record(host, &config, false);
+        assert!(circuit_acquire(host, &config).is_err());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(circuit_acquire(host, &config).is_ok());
+        // 半開状態では2件目を通さない
+        assert!(circuit_acquire(host, &config).is_err());
+        circuit_record(host, &config, true);
+        assert!(circuit_acquire(host, &config).is_ok());
+    }
+
+    #[test]
+    fn test_failed_probe_releases_half_open() {
+        let policy = RequestPolicy {
+            retry: None,
+            circuit: Some(CircuitConfig {
+                threshold: 1,
+                reset: Duration::from_millis(20),
+            }),
+        };
+        let url = "http://probe-release.test:1/";
+        let _ = execute(url, &policy, || Ok(()), |_| Outcome::Timeout);
+
+        // 半開状態の試行がレスポンス読み取りエラー等で失敗しても、試行中のままにしない
+        std::thread::sleep(Duration::from_millis(30));
+        let failed = execute(
+            url,
+            &policy,
+            || Err::<(), _>("read error".into()),
+            |_| Outcome::Other,
+        );
+        assert!(matches!(failed, Err(RetryError::Failed(_))));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(execute(url, &policy, || Ok(()), |_| Outcome::Other).is_ok());
+    }
+}
@@ -1,9 +1,34 @@
+use super::body::{self, BodyOptions};
+use super::retry::{self, RequestPolicy};
 use super::*;
 
+/// url以降の追加引数から:bytesフラグとオプションMap（:retry / :circuit-breaker / :query等）を取り出す
+fn parse_stream_args(
+    rest: &[Value],
+    fn_name: &str,
+    method: &str,
+) -> Result<(bool, RequestPolicy, BodyOptions), String> {
+    let is_bytes = rest
+        .iter()
+        .any(|v| matches!(v, Value::Keyword(k) if &**k == "bytes"));
+    let (policy, body_opts) = match rest.iter().find(|v| matches!(v, Value::Map(_))) {
+        Some(Value::Map(opts)) => (
+            retry::parse_policy(opts, fn_name, method)?,
+            body::parse_body_options(opts, fn_name)?,
+        ),
+        _ => (RequestPolicy::default(), BodyOptions::default()),
+    };
+    Ok((is_bytes, policy, body_opts))
+}
+
 /// HTTP GET（ストリーミング版）- レスポンスボディを行ごとに遅延読み込み
 /// 引数: (http/get-stream "url") - テキストモード（行ごと）
 ///      (http/get-stream "url" :bytes) - バイナリモード（バイトチャンクごと）
+///      (http/get-stream "url" {:retry 3}) - オプション（:retry / :circuit-breaker）
+///      (http/get-stream client "/path") - http/clientで作成したクライアントを使用
 pub fn native_get_stream(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/get-stream")?;
+
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::Need1Arg, &["http/get-stream"]));
     }
@@ -13,23 +38,40 @@ pub fn native_get_stream(args: &[Value]) -> Result<Value, String> {
         _ => return Err(fmt_msg(MsgKey::MustBeString, &["http/get-stream", "URL"])),
     };
 
-    let is_bytes = args.len() >= 2 && matches!(&args[1], Value::Keyword(k) if &**k == "bytes");
+    let (is_bytes, policy, body_opts) = parse_stream_args(&args[1..], "http/get-stream", "GET")?;
 
-    core::http_stream("GET", &url, None, None, None, None, None, is_bytes)
+    core::http_stream(
+        "GET",
+        &url,
+        None,
+        None,
+        None,
+        None,
+        None,
+        is_bytes,
+        &policy,
+        &body_opts,
+        session.as_deref(),
+    )
 }
 
 /// HTTP POST（ストリーミング版）- レスポンスボディを行ごとに遅延読み込み
 /// 引数: (http/post-stream "url" body) - テキストモード
 ///      (http/post-stream "url" body :bytes) - バイナリモード
+///      (http/post-stream "url" body {:retry {:on [503]}}) - オプション（:retry / :circuit-breaker）
 pub fn native_post_stream(args: &[Value]) -> Result<Value, String> {
-    crate::check_args!(args, 2, "http/post-stream");
+    let (session, args) = client::split_session(args, "http/post-stream")?;
+
+    if args.len() < 2 {
+        return Err(fmt_msg(MsgKey::Need2Args, &["http/post-stream"]));
+    }
 
     let url = match &args[0] {
         Value::String(s) => s.clone(),
         _ => return Err(fmt_msg(MsgKey::MustBeString, &["http/post-stream", "URL"])),
     };
 
-    let is_bytes = args.len() >= 3 && matches!(&args[2], Value::Keyword(k) if &**k == "bytes");
+    let (is_bytes, policy, body_opts) = parse_stream_args(&args[2..], "http/post-stream", "POST")?;
 
     core::http_stream(
         "POST",
@@ -40,6 +82,9 @@ pub fn native_post_stream(args: &[Value]) -> Result<Value, String> {
         None,
         None,
         is_bytes,
+        &policy,
+        &body_opts,
+        session.as_deref(),
     )
 }
 
@@ -47,6 +92,8 @@ pub fn native_post_stream(args: &[Value]) -> Result<Value, String> {
 /// 引数: (http/request-stream {:method "GET" :url "..."}) - テキストモード
 ///      (http/request-stream {:method "GET" :url "..."} :bytes) - バイナリモード
 pub fn native_request_stream(args: &[Value]) -> Result<Value, String> {
+    let (session, args) = client::split_session(args, "http/request-stream")?;
+
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::Need1Arg, &["http/request-stream"]));
     }
@@ -137,6 +184,8 @@ pub fn native_request_stream(args: &[Value]) -> Result<Value, String> {
     });
 
     let is_bytes = args.len() >= 2 && matches!(&args[1], Value::Keyword(k) if &**k == "bytes");
+    let policy = retry::parse_policy(config, "http/request-stream", &method)?;
+    let body_opts = body::parse_body_options(config, "http/request-stream")?;
 
     core::http_stream(
         &method,
@@ -147,5 +196,8 @@ pub fn native_request_stream(args: &[Value]) -> Result<Value, String> {
         basic_auth,
         bearer_token,
         is_bytes,
+        &policy,
+        &body_opts,
+        session.as_deref(),
     )
 }
@@ -32,7 +32,7 @@ use encoding_rs::{
 
 /// エンコーディングを解決
 #[cfg(feature = "encoding-extended")]
-fn resolve_encoding(keyword: &str) -> Result<&'static Encoding, String> {
+pub(crate) fn resolve_encoding(keyword: &str) -> Result<&'static Encoding, String> {
     match keyword {
         // Unicode
         "utf-8" | "utf8" => Ok(UTF_8),
@@ -144,34 +144,118 @@ fn auto_detect_encoding(bytes: &[u8], path: &str) -> Result<String, String> {
 fn encode_string(content: &str, encoding: &'static Encoding, add_bom: bool) -> Vec<u8> {
     let mut result = Vec::new();
 
-    // UTF-16は手動でエンコード（encoding_rsがエンコーダーをサポートしていないため）
     // UTF-16はデフォルトでBOM付き（Excel互換）
     if encoding == UTF_16LE {
-        // UTF-16LEはデフォルトでBOM付き
         result.extend_from_slice(&[0xFF, 0xFE]); // UTF-16LE BOM
-        for code_unit in content.encode_utf16() {
-            result.extend_from_slice(&code_unit.to_le_bytes());
-        }
-        return result;
+    } else if encoding == UTF_16BE {
+        result.extend_from_slice(&[0xFE, 0xFF]); // UTF-16BE BOM
+    } else if add_bom && encoding == UTF_8 {
+        result.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
     }
 
+    result.extend_from_slice(&encode_chunk(content, encoding));
+    result
+}
+
+/// 文字列をBOMなしでエンコード（ストリーミング書き込みで少しずつ書く場合に使う）
+#[cfg(feature = "encoding-extended")]
+pub(crate) fn encode_chunk(content: &str, encoding: &'static Encoding) -> Vec<u8> {
+    // UTF-16は手動でエンコード（encoding_rsがエンコーダーをサポートしていないため）
+    if encoding == UTF_16LE {
+        return content
+            .encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+    }
     if encoding == UTF_16BE {
-        // UTF-16BEはデフォルトでBOM付き
-        result.extend_from_slice(&[0xFE, 0xFF]); // UTF-16BE BOM
-        for code_unit in content.encode_utf16() {
-            result.extend_from_slice(&code_unit.to_be_bytes());
+        return content
+            .encode_utf16()
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect();
+    }
+
+    let (encoded, _, _) = encoding.encode(content);
+    encoded.into_owned()
+}
+
+/// ファイルを指定エンコーディングのテキストとして逐次読み込むリーダーを開く
+///
+/// 読み込んだ内容はUTF-8に変換される。BOMがあればそちらのエンコーディングを優先して除去する。
+/// ファイル全体を読み込まないため `:auto`（自動検出）は使えない。
+pub(crate) fn open_text_reader(
+    path: &str,
+    encoding_keyword: &str,
+) -> Result<Box<dyn BufRead + Send>, String> {
+    let file =
+        File::open(path).map_err(|e| fmt_msg(MsgKey::FileReadError, &[path, &e.to_string()]))?;
+
+    #[cfg(feature = "encoding-extended")]
+    {
+        if encoding_keyword == "utf-8" || encoding_keyword == "utf-8-bom" {
+            return Ok(Box::new(BufReader::new(file)));
         }
-        return result;
+        let encoding = resolve_encoding(encoding_keyword)?;
+        Ok(Box::new(BufReader::new(DecodeReader {
+            inner: file,
+            decoder: encoding.new_decoder(),
+            out: Vec::new(),
+            pos: 0,
+            eof: false,
+        })))
     }
 
-    // その他のエンコーディング
-    if add_bom && encoding == UTF_8 {
-        result.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+    #[cfg(not(feature = "encoding-extended"))]
+    {
+        if encoding_keyword != "utf-8" && encoding_keyword != "utf-8-bom" {
+            return Err(fmt_msg(
+                MsgKey::IoEncodingNotSupportedInMinimalBuild,
+                &[encoding_keyword],
+            ));
+        }
+        Ok(Box::new(BufReader::new(file)))
     }
+}
 
-    let (encoded, _, _) = encoding.encode(content);
-    result.extend_from_slice(&encoded);
-    result
+/// 読み込みながらUTF-8に変換するリーダー
+#[cfg(feature = "encoding-extended")]
+struct DecodeReader<R> {
+    inner: R,
+    decoder: encoding_rs::Decoder,
+    /// 変換済みでまだ返していないUTF-8バイト列
+    out: Vec<u8>,
+    pos: usize,
+    eof: bool,
+}
+
+#[cfg(feature = "encoding-extended")]
+impl<R: std::io::Read> std::io::Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos >= self.out.len() {
+            if self.eof {
+                return Ok(0);
+            }
+            let mut input = [0u8; 8192];
+            let n = self.inner.read(&mut input)?;
+            self.eof = n == 0;
+            let capacity = self.decoder.max_utf8_buffer_length(n).unwrap_or(n * 4 + 16);
+            let mut decoded = String::with_capacity(capacity);
+            let (_, _, had_errors) =
+                self.decoder
+                    .decode_to_string(&input[..n], &mut decoded, self.eof);
+            if had_errors {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("failed to decode as {}", self.decoder.encoding().name()),
+                ));
+            }
+            self.out = decoded.into_bytes();
+            self.pos = 0;
+        }
+        let n = buf.len().min(self.out.len() - self.pos);
+        buf[..n].copy_from_slice(&self.out[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
 }
 
 /// キーワード引数からオプションを抽出
@@ -4,16 +4,23 @@
 //! - parse: JSON文字列をパース
 //! - stringify: 値をJSON文字列に変換（コンパクト）
 //! - pretty: 値をJSON文字列に変換（整形済み）
+//! - read-stream: トップレベル配列のJSONファイルを要素ごとのストリームとして読み込み
 
+use crate::builtins::util::kw;
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
-use crate::value::Value;
+use crate::value::{MapKey, Stream, Value};
+use parking_lot::{Mutex, RwLock};
 use serde_json;
+use std::io::{BufRead, BufReader};
+use std::sync::Arc;
 
 /// JSON文字列をパースしてQi値に変換
 ///
 /// # 引数
 /// - args[0]: JSON文字列
+/// - args[1]: オプション（省略可）
+///   - :decimal - trueの場合、小数を10進数（Decimal）として読み込む
 ///
 /// # 戻り値
 /// - 成功時: パース結果（値そのまま）
@@ -27,9 +34,10 @@ pub fn native_parse(args: &[Value]) -> Result<Value, String> {
         Value::String(s) => s,
         _ => return Err(fmt_msg(MsgKey::ArgMustBeType, &["json/parse", "a string"])),
     };
+    let decimal = parse_decimal_option(args.get(1), "json/parse")?;
 
     match serde_json::from_str::<serde_json::Value>(json_str) {
-        Ok(json) => Ok(json_to_value(json)),
+        Ok(json) => Ok(json_to_value_with(json, decimal)),
         Err(e) => Ok(Value::error(format!("JSONパースエラー: {}", e))),
     }
 }
@@ -68,31 +76,328 @@ pub fn native_pretty(args: &[Value]) -> Result<Value, String> {
     }
 }
 
+/// json/read-stream - トップレベル配列のJSONファイルを要素ごとのストリームとして読み込み
+///
+/// ファイル全体をメモリに載せず、配列の要素を1つずつパースして返す。
+/// 壊れた要素は `{:error "... line N: ..." :line N}` として返し、次の要素から読み続ける。
+///
+/// # 引数
+/// - args[0]: ファイルパス
+/// - args[1]: オプション（省略可）
+///   - :on-error - :report（デフォルト、エラーマップを返す）または :skip（読み飛ばす）
+pub fn native_read_stream(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["json/read-stream"]));
+    }
+
+    let path = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => return Err(fmt_msg(MsgKey::TypeOnly, &["json/read-stream", "string"])),
+    };
+    let skip_errors = parse_on_error(args.get(1), "json/read-stream")?;
+
+    let file = std::fs::File::open(&path)
+        .map_err(|e| fmt_msg(MsgKey::FileReadError, &[&path, &e.to_string()]))?;
+    let scanner = Mutex::new(ArrayScanner::new(BufReader::new(file)));
+
+    let stream = Stream {
+        next_fn: Box::new(move || {
+            let mut scanner = scanner.lock();
+            loop {
+                let result = match scanner.next_element()? {
+                    Ok((line, bytes)) => serde_json::from_slice::<serde_json::Value>(&bytes)
+                        .map(json_to_value)
+                        .map_err(|e| (line + e.line().saturating_sub(1), error_message(&e))),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(v) => return Some(v),
+                    Err(_) if skip_errors => continue,
+                    Err((line, msg)) => return Some(line_error("json/read-stream", line, &msg)),
+                }
+            }
+        }),
+    };
+
+    Ok(Value::Stream(Arc::new(RwLock::new(stream))))
+}
+
+/// :on-error オプションを読み取る（:skipならtrue）
+pub(super) fn parse_on_error(opts: Option<&Value>, fn_name: &str) -> Result<bool, String> {
+    let Some(Value::Map(opts)) = opts else {
+        return match opts {
+            None | Some(Value::Nil) => Ok(false),
+            Some(_) => Err(fmt_msg(MsgKey::MustBeMap, &[fn_name, "options"])),
+        };
+    };
+    match opts.get(&kw("on-error")) {
+        None | Some(Value::Nil) => Ok(false),
+        Some(Value::Keyword(k)) if &**k == "report" => Ok(false),
+        Some(Value::Keyword(k)) if &**k == "skip" => Ok(true),
+        Some(_) => Err(fmt_msg(
+            MsgKey::JsonInvalidOption,
+            &[fn_name, ":on-error", "expected :report or :skip"],
+        )),
+    }
+}
+
+/// :decimal オプションを読み取る
+pub(super) fn parse_decimal_option(opts: Option<&Value>, fn_name: &str) -> Result<bool, String> {
+    let Some(Value::Map(opts)) = opts else {
+        return match opts {
+            None | Some(Value::Nil) => Ok(false),
+            Some(_) => Err(fmt_msg(MsgKey::MustBeMap, &[fn_name, "options"])),
+        };
+    };
+    match opts.get(&kw("decimal")) {
+        None | Some(Value::Nil) => Ok(false),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(_) => Err(fmt_msg(
+            MsgKey::JsonInvalidOption,
+            &[fn_name, ":decimal", "expected true or false"],
+        )),
+    }
+}
+
+/// serde_jsonのエラーから位置情報（" at line N column M"）を除いた説明を取り出す
+pub(super) fn error_message(e: &serde_json::Error) -> String {
+    let msg = e.to_string();
+    match msg.rsplit_once(" at line ") {
+        Some((head, _)) => head.to_string(),
+        None => msg,
+    }
+}
+
+/// 行番号付きのエラーマップ {:error "fn: line N: msg" :line N} を作る
+pub(super) fn line_error(fn_name: &str, line: usize, msg: &str) -> Value {
+    let mut map = crate::new_hashmap();
+    map.insert(
+        kw("error"),
+        Value::String(fmt_msg(
+            MsgKey::JsonLineError,
+            &[fn_name, &line.to_string(), msg],
+        )),
+    );
+    map.insert(kw("line"), Value::Integer(line as i64));
+    Value::Map(map)
+}
+
+/// トップレベル配列を要素ごとに切り出すスキャナ
+///
+/// 文字列とネストだけを追跡して要素の境界（深さ0の `,` と `]`）を探し、
+/// 要素のバイト列をまとめて返す。要素自体のパースはserde_jsonに任せる。
+/// スキャン結果（エラーは行番号と説明）
+type ScanResult<T> = Result<T, (usize, String)>;
+
+struct ArrayScanner<R: BufRead> {
+    bytes: std::io::Bytes<R>,
+    /// 現在の行番号（1始まり）
+    line: usize,
+    state: ScanState,
+}
+
+#[derive(PartialEq)]
+enum ScanState {
+    Start,
+    InArray,
+    Done,
+}
+
+impl<R: BufRead> ArrayScanner<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            bytes: reader.bytes(),
+            line: 1,
+            state: ScanState::Start,
+        }
+    }
+
+    fn next_byte(&mut self) -> ScanResult<Option<u8>> {
+        match self.bytes.next() {
+            Some(Ok(b)) => {
+                if b == b'\n' {
+                    self.line += 1;
+                }
+                Ok(Some(b))
+            }
+            Some(Err(e)) => Err((self.line, e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> ScanResult<Option<u8>> {
+        loop {
+            match self.next_byte()? {
+                Some(b) if b.is_ascii_whitespace() => {}
+                other => return Ok(other),
+            }
+        }
+    }
+
+    /// 次の要素を（開始行, バイト列）で返す。配列の終端ではNone
+    fn next_element(&mut self) -> Option<ScanResult<(usize, Vec<u8>)>> {
+        let result = self.scan();
+        if result.is_err() {
+            self.state = ScanState::Done;
+        }
+        result.transpose()
+    }
+
+    fn scan(&mut self) -> ScanResult<Option<(usize, Vec<u8>)>> {
+        if self.state == ScanState::Start {
+            match self.skip_whitespace()? {
+                Some(b'[') => self.state = ScanState::InArray,
+                _ => return Err((self.line, "expected a top-level array".to_string())),
+            }
+            // 空配列
+            let first = self.skip_whitespace()?;
+            if first == Some(b']') {
+                self.state = ScanState::Done;
+                return Ok(None);
+            }
+            return self.read_element(first).map(Some);
+        }
+        if self.state == ScanState::Done {
+            return Ok(None);
+        }
+        let first = self.skip_whitespace()?;
+        self.read_element(first).map(Some)
+    }
+
+    /// 先頭バイトから、深さ0の `,` または `]` までを読み取る
+    fn read_element(&mut self, first: Option<u8>) -> ScanResult<(usize, Vec<u8>)> {
+        let unexpected_end = |line| (line, "unexpected end of file".to_string());
+        let first = first.ok_or_else(|| unexpected_end(self.line))?;
+        let start_line = self.line;
+        let mut buf = vec![first];
+        let mut depth = 0usize;
+        let mut in_string = first == b'"';
+        let mut escaped = false;
+        if first == b'{' || first == b'[' {
+            depth = 1;
+        }
+
+        loop {
+            let b = self.next_byte()?.ok_or_else(|| unexpected_end(self.line))?;
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if b == b'\\' {
+                    escaped = true;
+                } else if b == b'"' {
+                    in_string = false;
+                }
+                buf.push(b);
+                continue;
+            }
+            match b {
+                b'"' => in_string = true,
+                b'{' | b'[' => depth += 1,
+                b']' if depth == 0 => {
+                    self.state = ScanState::Done;
+                    return Ok((start_line, buf));
+                }
+                b',' if depth == 0 => return Ok((start_line, buf)),
+                b'}' | b']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            buf.push(b);
+        }
+    }
+}
+
+// ========================================
+// json/query・json/validate 共通ヘルパー
+// ========================================
+
+/// マップキーをJSONのプロパティ名として取り出す
+pub(super) fn key_name(key: &MapKey) -> String {
+    match key {
+        MapKey::Keyword(k) => k.to_string(),
+        MapKey::String(s) => s.clone(),
+        MapKey::Symbol(s) => s.to_string(),
+        MapKey::Integer(i) => i.to_string(),
+        MapKey::Char(c) => c.to_string(),
+    }
+}
+
+/// プロパティ名でマップを引く（キーワード・文字列・シンボルのどのキーでも一致させる）
+pub(super) fn map_get<'a>(m: &'a crate::HashMap<MapKey, Value>, name: &str) -> Option<&'a Value> {
+    m.get(&kw(name))
+        .or_else(|| m.get(&MapKey::String(name.to_string())))
+        .or_else(|| m.get(&MapKey::Symbol(Arc::from(name))))
+}
+
+/// マップの要素をプロパティ名順に並べる（Mapの反復順は不定なので結果を安定させる）
+pub(super) fn sorted_entries(m: &crate::HashMap<MapKey, Value>) -> Vec<(String, &Value)> {
+    let mut entries: Vec<(String, &Value)> = m.iter().map(|(k, v)| (key_name(k), v)).collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// JSONとしての等価判定
+///
+/// 整数と浮動小数点数は数値として比較し、キーワードは文字列、リストはベクタと同じに扱う。
+/// マップはキーの種類（キーワード・文字列）を区別せずプロパティ名で比較する。
+pub(super) fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Integer(x), Value::Float(y)) | (Value::Float(y), Value::Integer(x)) => {
+            (*x as f64) == *y
+        }
+        (Value::Keyword(x), Value::String(y)) | (Value::String(y), Value::Keyword(x)) => {
+            x.as_ref() == y.as_str()
+        }
+        (Value::Vector(x) | Value::List(x), Value::Vector(y) | Value::List(y)) => {
+            x.len() == y.len() && x.iter().zip(y.iter()).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Map(x), Value::Map(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| map_get(y, &key_name(k)).is_some_and(|w| json_eq(v, w)))
+        }
+        _ => a == b,
+    }
+}
+
 /// serde_json::ValueをQi Valueに変換
-fn json_to_value(json: serde_json::Value) -> Value {
+pub(crate) fn json_to_value(json: serde_json::Value) -> Value {
+    json_to_value_with(json, false)
+}
+
+/// serde_json::ValueをQi Valueに変換（decimal: 小数を10進数として読み込む）
+pub(crate) fn json_to_value_with(json: serde_json::Value, decimal: bool) -> Value {
     match json {
         serde_json::Value::Null => Value::Nil,
         serde_json::Value::Bool(b) => Value::Bool(b),
         serde_json::Value::Number(n) => {
             if let Some(i) = n.as_i64() {
                 Value::Integer(i)
+            } else if let Some(u) = n.as_u64() {
+                // 2^63以上の整数は多倍長整数として保持（データロスを防ぐ）
+                Value::BigInt(Arc::new(num_bigint::BigInt::from(u)))
+            } else if let Some(d) = decimal.then(|| n.to_string()).and_then(|s| {
+                s.parse::<rust_decimal::Decimal>()
+                    .or_else(|_| rust_decimal::Decimal::from_scientific(&s))
+                    .ok()
+            }) {
+                Value::Decimal(d)
             } else if let Some(f) = n.as_f64() {
                 Value::Float(f)
             } else {
-                // i64/f64で表現できない大きな数値は文字列として保持（データロスを防ぐ）
-                // 例: 2^63以上の整数、高精度の小数
                 Value::String(n.to_string())
             }
         }
         serde_json::Value::String(s) => Value::String(s),
-        serde_json::Value::Array(arr) => {
-            Value::Vector(arr.into_iter().map(json_to_value).collect())
-        }
+        serde_json::Value::Array(arr) => Value::Vector(
+            arr.into_iter()
+                .map(|v| json_to_value_with(v, decimal))
+                .collect(),
+        ),
         serde_json::Value::Object(obj) => {
             // JSONオブジェクトのキーをKeywordキーに変換（Qi標準のアクセス方法に対応）
             let string_map: std::collections::HashMap<String, Value> = obj
                 .into_iter()
-                .map(|(k, v)| (k, json_to_value(v)))
+                .map(|(k, v)| (k, json_to_value_with(v, decimal)))
                 .collect();
             Value::Map(crate::builtins::util::convert_string_map_to_mapkey(
                 string_map,
@@ -102,7 +407,7 @@ fn json_to_value(json: serde_json::Value) -> Value {
 }
 
 /// Qi Valueをserde_json::Valueに変換
-fn value_to_json(value: &Value) -> serde_json::Value {
+pub(crate) fn value_to_json(value: &Value) -> serde_json::Value {
     match value {
         Value::Nil => serde_json::Value::Null,
         Value::Bool(b) => serde_json::Value::Bool(*b),
@@ -110,7 +415,21 @@ fn value_to_json(value: &Value) -> serde_json::Value {
         Value::Float(f) => serde_json::Number::from_f64(*f)
             .map(serde_json::Value::Number)
             .unwrap_or(serde_json::Value::Null),
+        // u64に収まらない多倍長整数は精度を失わないよう文字列で出力
+        Value::BigInt(n) => num_traits::ToPrimitive::to_u64(n.as_ref())
+            .map(|u| serde_json::Value::Number(u.into()))
+            .unwrap_or_else(|| serde_json::Value::String(n.to_string())),
+        Value::Ratio(_) => crate::builtins::numeric_helpers::number_to_f64(value)
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        // 10進数はf64で誤差なく表せる場合のみ数値、それ以外は文字列で出力
+        Value::Decimal(d) => crate::builtins::numeric_helpers::decimal_to_exact_f64(d)
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| serde_json::Value::String(d.to_string())),
         Value::String(s) => serde_json::Value::String(s.clone()),
+        Value::Char(c) => serde_json::Value::String(c.to_string()),
         Value::Bytes(b) => {
             // バイナリデータはBase64エンコードして文字列として出力
             #[cfg(feature = "string-encoding")]
@@ -145,6 +464,8 @@ fn value_to_json(value: &Value) -> serde_json::Value {
             }
             serde_json::Value::Array(arr)
         }
+        // セットは配列に変換（出力を安定させるため要素は表示順に並べる）
+        Value::Set(items) => value_to_json(&Value::Vector(Value::sorted_set_items(items))),
         Value::Map(m) => {
             // サイズが分かっているので事前確保
             let mut obj = serde_json::Map::with_capacity(m.len());
@@ -171,6 +492,7 @@ fn value_to_json(value: &Value) -> serde_json::Value {
                         // 整数キー → 文字列化
                         i.to_string()
                     }
+                    crate::value::MapKey::Char(c) => c.to_string(),
                 };
                 obj.insert(json_key, value_to_json(v));
             }
@@ -186,9 +508,36 @@ fn value_to_json(value: &Value) -> serde_json::Value {
 
 /// 登録すべき関数のリスト（Evaluator不要な関数のみ）
 /// @qi-doc:category data/json
-/// @qi-doc:functions parse, stringify, pretty
+/// @qi-doc:functions parse, stringify, pretty, read-stream
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("json/parse", native_parse),
     ("json/stringify", native_stringify),
     ("json/pretty", native_pretty),
+    ("json/read-stream", native_read_stream),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_array_scanner_splits_elements() {
+        let src = "[1, \"a,]\\\"\",\n {\"b\": [2, {}]},\n{bad}, []]";
+        let mut scanner = ArrayScanner::new(src.as_bytes());
+        let mut elements = Vec::new();
+        while let Some(item) = scanner.next_element() {
+            let (line, bytes) = item.unwrap();
+            elements.push((line, String::from_utf8(bytes).unwrap()));
+        }
+        assert_eq!(
+            elements,
+            vec![
+                (1, "1".to_string()),
+                (1, "\"a,]\\\"\"".to_string()),
+                (2, "{\"b\": [2, {}]}".to_string()),
+                (3, "{bad}".to_string()),
+                (3, "[]".to_string()),
+            ]
+        );
+    }
+}
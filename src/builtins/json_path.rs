@@ -0,0 +1,784 @@
+//! JSONPathモジュール
+//!
+//! `json/query` でJSONPath（RFC 9535）を使ってマップ・ベクタから値を取り出す。
+//!
+//! 対応する構文:
+//! - `$` ルート、`.name` / `['name']` 子要素、`.*` / `[*]` ワイルドカード
+//! - `[0]` / `[-1]` インデックス、`[0,2]` / `['a','b']` 複数選択、`[1:5:2]` スライス
+//! - `..name` / `..*` / `..[0]` 再帰下降
+//! - `[?@.price < 10 && @.tags]` フィルタ（`==` `!=` `<` `<=` `>` `>=` `&&` `||` `!` `()`、存在チェック）
+//! - フィルタ内の関数 `length()` `count()` `match()` `search()` `value()`
+//!
+//! プロパティ名はキーワード・文字列どちらのキーにも一致する。
+//! マップのワイルドカードはプロパティ名順に返す。
+
+use super::json::{json_eq, map_get, sorted_entries};
+use crate::check_args;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::Value;
+
+/// JSONPathでデータから値を取り出す
+///
+/// # 引数
+/// - args[0]: JSONPath文字列（`$` から始まる）
+/// - args[1]: 対象データ（json/parseの結果など）
+///
+/// # 戻り値
+/// - 一致した値のベクタ（一致しなければ空ベクタ）
+///
+/// # 例
+/// ```qi
+/// (json/query "$.store.book[?@.price < 10].title" data)
+/// (data |> (json/query "$..author"))
+/// ```
+pub fn native_query(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 2, "json/query");
+
+    let path = match &args[0] {
+        Value::String(s) => s,
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::FirstArgMustBe,
+                &["json/query", "a JSONPath string"],
+            ))
+        }
+    };
+
+    let query = Parser::new(path).parse_query()?;
+    let root = &args[1];
+    Ok(Value::Vector(select(&query.segments, root, root).into()))
+}
+
+// ========================================
+// 構文木
+// ========================================
+
+struct Query {
+    /// `$` から始まるか（falseなら `@`）
+    absolute: bool,
+    segments: Vec<Segment>,
+}
+
+enum Segment {
+    /// 直下の子要素に対する選択
+    Child(Vec<Selector>),
+    /// 自身と全ての子孫に対する選択（`..`）
+    Descendant(Vec<Selector>),
+}
+
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    Filter(Expr),
+}
+
+enum Expr {
+    Or(Vec<Expr>),
+    And(Vec<Expr>),
+    Not(Box<Expr>),
+    Compare(Operand, CmpOp, Operand),
+    /// 存在チェック（クエリ）または真偽値を返す関数
+    Test(Operand),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+enum Operand {
+    Literal(Value),
+    Query(Query),
+    Call(String, Vec<Operand>),
+}
+
+// ========================================
+// パーサー
+// ========================================
+
+struct Parser<'a> {
+    src: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            chars: src.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, detail: &str) -> String {
+        fmt_msg(
+            MsgKey::JsonPathInvalid,
+            &["json/query", self.src, &self.pos.to_string(), detail],
+        )
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let n = s.chars().count();
+        if self.chars[self.pos.min(self.chars.len())..]
+            .iter()
+            .take(n)
+            .copied()
+            .eq(s.chars())
+        {
+            self.pos += n;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        self.skip_ws();
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c)))
+        }
+    }
+
+    /// トップレベルのクエリ（`$` から末尾まで）
+    fn parse_query(&mut self) -> Result<Query, String> {
+        self.skip_ws();
+        if !self.eat('$') {
+            return Err(self.error("query must start with '$'"));
+        }
+        let segments = self.parse_segments()?;
+        self.skip_ws();
+        if self.pos < self.chars.len() {
+            return Err(self.error("unexpected character"));
+        }
+        Ok(Query {
+            absolute: true,
+            segments,
+        })
+    }
+
+    fn parse_segments(&mut self) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        loop {
+            // フィルタ内では `@ .a` のような空白を許さない（比較演算子との区別のため）
+            match self.peek() {
+                Some('.') if self.peek_at(1) == Some('.') => {
+                    self.pos += 2;
+                    let selectors = match self.peek() {
+                        Some('[') => self.parse_bracket()?,
+                        Some('*') => {
+                            self.pos += 1;
+                            vec![Selector::Wildcard]
+                        }
+                        _ => vec![Selector::Name(self.parse_name()?)],
+                    };
+                    segments.push(Segment::Descendant(selectors));
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    if self.eat('*') {
+                        segments.push(Segment::Child(vec![Selector::Wildcard]));
+                    } else {
+                        segments.push(Segment::Child(vec![Selector::Name(self.parse_name()?)]));
+                    }
+                }
+                Some('[') => segments.push(Segment::Child(self.parse_bracket()?)),
+                _ => return Ok(segments),
+            }
+        }
+    }
+
+    /// `.` の後のプロパティ名（Qiのキーでよく使う `-` も許す）
+    fn parse_name(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '-' || (c as u32) >= 0x80)
+        {
+            self.pos += 1;
+        }
+        if self.pos == start || self.chars[start].is_ascii_digit() {
+            self.pos = start;
+            return Err(self.error("expected a property name"));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    /// `[...]` の選択子リスト
+    fn parse_bracket(&mut self) -> Result<Vec<Selector>, String> {
+        self.expect('[')?;
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_ws();
+            selectors.push(self.parse_selector()?);
+            self.skip_ws();
+            if self.eat(']') {
+                return Ok(selectors);
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, String> {
+        match self.peek() {
+            Some('\'') | Some('"') => Ok(Selector::Name(self.parse_string()?)),
+            Some('*') => {
+                self.pos += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.pos += 1;
+                self.skip_ws();
+                Ok(Selector::Filter(self.parse_or()?))
+            }
+            _ => {
+                let start = self.parse_opt_int()?;
+                self.skip_ws();
+                if !self.eat(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected a selector"));
+                }
+                self.skip_ws();
+                let end = self.parse_opt_int()?;
+                self.skip_ws();
+                let step = if self.eat(':') {
+                    self.skip_ws();
+                    self.parse_opt_int()?
+                } else {
+                    None
+                };
+                Ok(Selector::Slice(start, end, step))
+            }
+        }
+    }
+
+    fn parse_opt_int(&mut self) -> Result<Option<i64>, String> {
+        let start = self.pos;
+        self.eat('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        text.parse()
+            .map(Some)
+            .map_err(|_| self.error("invalid integer"))
+    }
+
+    /// 'string' または "string"（バックスラッシュエスケープ対応）
+    fn parse_string(&mut self) -> Result<String, String> {
+        let quote = self.peek().ok_or_else(|| self.error("expected a string"))?;
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    let c = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match c {
+                        'n' => out.push('\n'),
+                        't' => out.push('\t'),
+                        'r' => out.push('\r'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'u' => {
+                            let hex: String = self.chars.iter().skip(self.pos).take(4).collect();
+                            let code = u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid \\u escape"))?;
+                            self.pos += 4;
+                            out.push(code);
+                        }
+                        other => out.push(other),
+                    }
+                }
+                Some(c) => {
+                    self.pos += 1;
+                    out.push(c);
+                }
+            }
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut items = vec![self.parse_and()?];
+        loop {
+            self.skip_ws();
+            if !self.eat_str("||") {
+                break;
+            }
+            items.push(self.parse_and()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::Or(items)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut items = vec![self.parse_unary()?];
+        loop {
+            self.skip_ws();
+            if !self.eat_str("&&") {
+                break;
+            }
+            items.push(self.parse_unary()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::And(items)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        self.skip_ws();
+        if self.peek() == Some('!') && self.peek_at(1) != Some('=') {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat('(') {
+            let expr = self.parse_or()?;
+            self.expect(')')?;
+            return Ok(expr);
+        }
+
+        let left = self.parse_operand()?;
+        self.skip_ws();
+        let op = if self.eat_str("==") {
+            CmpOp::Eq
+        } else if self.eat_str("!=") {
+            CmpOp::Ne
+        } else if self.eat_str("<=") {
+            CmpOp::Le
+        } else if self.eat_str(">=") {
+            CmpOp::Ge
+        } else if self.eat('<') {
+            CmpOp::Lt
+        } else if self.eat('>') {
+            CmpOp::Gt
+        } else {
+            return match left {
+                Operand::Literal(_) => Err(self.error("a literal cannot be used as a test")),
+                _ => Ok(Expr::Test(left)),
+            };
+        };
+        self.skip_ws();
+        let right = self.parse_operand()?;
+        Ok(Expr::Compare(left, op, right))
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        self.skip_ws();
+        match self.peek() {
+            Some('@') | Some('$') => {
+                let absolute = self.peek() == Some('$');
+                self.pos += 1;
+                Ok(Operand::Query(Query {
+                    absolute,
+                    segments: self.parse_segments()?,
+                }))
+            }
+            Some('\'') | Some('"') => Ok(Operand::Literal(Value::String(self.parse_string()?))),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    self.pos += 1;
+                }
+                let word: String = self.chars[start..self.pos].iter().collect();
+                match word.as_str() {
+                    "true" => Ok(Operand::Literal(Value::Bool(true))),
+                    "false" => Ok(Operand::Literal(Value::Bool(false))),
+                    "null" => Ok(Operand::Literal(Value::Nil)),
+                    "length" | "count" | "match" | "search" | "value" => {
+                        self.expect('(')?;
+                        let mut args = Vec::new();
+                        self.skip_ws();
+                        if !self.eat(')') {
+                            loop {
+                                args.push(self.parse_operand()?);
+                                self.skip_ws();
+                                if self.eat(')') {
+                                    break;
+                                }
+                                self.expect(',')?;
+                            }
+                        }
+                        let arity = if matches!(word.as_str(), "match" | "search") {
+                            2
+                        } else {
+                            1
+                        };
+                        if args.len() != arity {
+                            return Err(
+                                self.error(&format!("{}() takes {} argument(s)", word, arity))
+                            );
+                        }
+                        Ok(Operand::Call(word, args))
+                    }
+                    _ => {
+                        self.pos = start;
+                        Err(self.error(&format!("unknown function or literal '{}'", word)))
+                    }
+                }
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Operand, String> {
+        let start = self.pos;
+        self.eat('-');
+        let mut is_float = false;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() {
+                self.pos += 1;
+            } else if c == '.' || c == 'e' || c == 'E' {
+                is_float = true;
+                self.pos += 1;
+            } else if (c == '+' || c == '-') && matches!(self.chars[self.pos - 1], 'e' | 'E') {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        let value = if is_float {
+            text.parse().map(Value::Float).ok()
+        } else {
+            text.parse().map(Value::Integer).ok()
+        };
+        value
+            .map(Operand::Literal)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+// ========================================
+// 評価
+// ========================================
+
+/// セグメントを順に適用して一致したノードを集める
+fn select(segments: &[Segment], node: &Value, root: &Value) -> Vec<Value> {
+    let mut nodes = vec![node.clone()];
+    for segment in segments {
+        let mut next = Vec::new();
+        for n in &nodes {
+            match segment {
+                Segment::Child(selectors) => apply_selectors(selectors, n, root, &mut next),
+                Segment::Descendant(selectors) => {
+                    let mut stack = vec![n.clone()];
+                    // 深さ優先・文書順に自身と子孫を訪れる
+                    while let Some(current) = stack.pop() {
+                        apply_selectors(selectors, &current, root, &mut next);
+                        let mut children = children(&current);
+                        children.reverse();
+                        stack.extend(children);
+                    }
+                }
+            }
+        }
+        nodes = next;
+    }
+    nodes
+}
+
+/// 直下の子要素（マップはプロパティ名順）
+fn children(node: &Value) -> Vec<Value> {
+    match node {
+        Value::Vector(items) | Value::List(items) => items.iter().cloned().collect(),
+        Value::Map(m) => sorted_entries(m)
+            .into_iter()
+            .map(|(_, v)| v.clone())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn apply_selectors(selectors: &[Selector], node: &Value, root: &Value, out: &mut Vec<Value>) {
+    for selector in selectors {
+        match selector {
+            Selector::Name(name) => {
+                if let Value::Map(m) = node {
+                    if let Some(v) = map_get(m, name) {
+                        out.push(v.clone());
+                    }
+                }
+            }
+            Selector::Wildcard => out.extend(children(node)),
+            Selector::Index(i) => {
+                if let Value::Vector(items) | Value::List(items) = node {
+                    let len = items.len() as i64;
+                    let idx = if *i < 0 { len + i } else { *i };
+                    if (0..len).contains(&idx) {
+                        out.push(items[idx as usize].clone());
+                    }
+                }
+            }
+            Selector::Slice(start, end, step) => {
+                if let Value::Vector(items) | Value::List(items) = node {
+                    for idx in slice_indices(items.len() as i64, *start, *end, *step) {
+                        out.push(items[idx as usize].clone());
+                    }
+                }
+            }
+            Selector::Filter(expr) => {
+                out.extend(children(node).into_iter().filter(|c| eval(expr, c, root)));
+            }
+        }
+    }
+}
+
+/// RFC 9535 のスライス規則でインデックスを列挙する
+fn slice_indices(len: i64, start: Option<i64>, end: Option<i64>, step: Option<i64>) -> Vec<i64> {
+    let step = step.unwrap_or(1);
+    let normalize = |i: i64| if i >= 0 { i } else { len + i };
+    let mut indices = Vec::new();
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+        let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+        let mut i = lower;
+        while i < upper {
+            indices.push(i);
+            i += step;
+        }
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+        let lower = normalize(end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+        let mut i = upper;
+        while lower < i {
+            indices.push(i);
+            i += step;
+        }
+    }
+    indices
+}
+
+fn eval(expr: &Expr, current: &Value, root: &Value) -> bool {
+    match expr {
+        Expr::Or(items) => items.iter().any(|e| eval(e, current, root)),
+        Expr::And(items) => items.iter().all(|e| eval(e, current, root)),
+        Expr::Not(e) => !eval(e, current, root),
+        Expr::Test(Operand::Query(q)) => !run_query(q, current, root).is_empty(),
+        Expr::Test(operand) => matches!(
+            operand_value(operand, current, root),
+            Some(Value::Bool(true))
+        ),
+        Expr::Compare(left, op, right) => compare(
+            operand_value(left, current, root).as_ref(),
+            *op,
+            operand_value(right, current, root).as_ref(),
+        ),
+    }
+}
+
+fn run_query(query: &Query, current: &Value, root: &Value) -> Vec<Value> {
+    let start = if query.absolute { root } else { current };
+    select(&query.segments, start, root)
+}
+
+/// 比較に使う値（クエリは1件に一致したときだけ値を持つ）
+fn operand_value(operand: &Operand, current: &Value, root: &Value) -> Option<Value> {
+    match operand {
+        Operand::Literal(v) => Some(v.clone()),
+        Operand::Query(q) => {
+            let mut nodes = run_query(q, current, root);
+            if nodes.len() == 1 {
+                nodes.pop()
+            } else {
+                None
+            }
+        }
+        Operand::Call(name, args) => match name.as_str() {
+            "count" => match &args[0] {
+                Operand::Query(q) => Some(Value::Integer(run_query(q, current, root).len() as i64)),
+                _ => None,
+            },
+            "length" => match operand_value(&args[0], current, root)? {
+                Value::String(s) => Some(Value::Integer(s.chars().count() as i64)),
+                Value::Vector(items) | Value::List(items) => {
+                    Some(Value::Integer(items.len() as i64))
+                }
+                Value::Map(m) => Some(Value::Integer(m.len() as i64)),
+                _ => None,
+            },
+            "value" => operand_value(&args[0], current, root),
+            "match" | "search" => {
+                let (Some(Value::String(s)), Some(Value::String(pattern))) = (
+                    operand_value(&args[0], current, root),
+                    operand_value(&args[1], current, root),
+                ) else {
+                    return Some(Value::Bool(false));
+                };
+                // match()は全体一致、search()は部分一致
+                let pattern = if name == "match" {
+                    format!("^(?:{})$", pattern)
+                } else {
+                    pattern
+                };
+                let matched = crate::builtins::string::get_or_compile_regex(&pattern)
+                    .map(|re| re.is_match(&s))
+                    .unwrap_or(false);
+                Some(Value::Bool(matched))
+            }
+            _ => None,
+        },
+    }
+}
+
+/// 比較演算（値がない同士は等しい、順序比較は数値同士・文字列同士のみ）
+fn compare(left: Option<&Value>, op: CmpOp, right: Option<&Value>) -> bool {
+    let eq = match (left, right) {
+        (None, None) => true,
+        (Some(a), Some(b)) => json_eq(a, b),
+        _ => false,
+    };
+    match op {
+        CmpOp::Eq => eq,
+        CmpOp::Ne => !eq,
+        CmpOp::Le | CmpOp::Ge if eq => true,
+        _ => {
+            let ordering = match (left, right) {
+                (Some(a), Some(b)) => match (as_number(a), as_number(b)) {
+                    (Some(x), Some(y)) => x.partial_cmp(&y),
+                    _ => match (a, b) {
+                        (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+                        _ => None,
+                    },
+                },
+                _ => None,
+            };
+            match (ordering, op) {
+                (Some(o), CmpOp::Lt | CmpOp::Le) => o.is_lt(),
+                (Some(o), CmpOp::Gt | CmpOp::Ge) => o.is_gt(),
+                _ => false,
+            }
+        }
+    }
+}
+
+fn as_number(v: &Value) -> Option<f64> {
+    match v {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category data/json
+/// @qi-doc:functions query
+pub const FUNCTIONS: super::NativeFunctions = &[("json/query", native_query)];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(path: &str, data: &Value) -> Value {
+        native_query(&[Value::String(path.into()), data.clone()]).unwrap()
+    }
+
+    #[test]
+    fn test_query() {
+        let json = r#"{"store": {"book": [
+            {"title": "A", "price": 8.95, "tags": ["x"]},
+            {"title": "B", "price": 12, "author": "Tolkien"},
+            {"title": "C", "price": 22.99, "author": "Melville", "tags": []}
+        ], "bicycle": {"price": 19.95}}}"#;
+        let data = super::super::json::json_to_value(serde_json::from_str(json).unwrap());
+        let strings = |items: &[&str]| {
+            Value::Vector(items.iter().map(|s| Value::String(s.to_string())).collect())
+        };
+
+        assert_eq!(
+            query("$.store.book[*].title", &data),
+            strings(&["A", "B", "C"])
+        );
+        assert_eq!(query("$..author", &data), strings(&["Tolkien", "Melville"]));
+        assert_eq!(
+            query("$.store.book[?@.price < 20 && !@.author].title", &data),
+            strings(&["A"])
+        );
+        assert_eq!(
+            query("$.store.book[?(@.tags)]['title']", &data),
+            strings(&["A", "C"])
+        );
+        assert_eq!(
+            query("$.store.book[?match(@.author, 'T.*')].title", &data),
+            strings(&["B"])
+        );
+        assert_eq!(query("$.store.book[-1:].title", &data), strings(&["C"]));
+        assert_eq!(
+            query("$.store.book[::-2].title", &data),
+            strings(&["C", "A"])
+        );
+        assert_eq!(
+            query("$..price", &data),
+            Value::Vector(
+                vec![
+                    Value::Float(19.95),
+                    Value::Float(8.95),
+                    Value::Integer(12),
+                    Value::Float(22.99)
+                ]
+                .into()
+            )
+        );
+        assert!(native_query(&[Value::String("$.store[".into()), data]).is_err());
+    }
+}
@@ -0,0 +1,858 @@
+//! JSON Schemaモジュール
+//!
+//! `json/validate` で標準のJSON Schema（draft 2020-12のコア語彙）による検証を行う。
+//! 最初のエラーで止めずに全てのエラーを集め、それぞれの位置をJSON Pointerで返す。
+//!
+//! 対応キーワード:
+//! - 汎用: type, enum, const
+//! - 数値: multipleOf, maximum, exclusiveMaximum, minimum, exclusiveMinimum
+//! - 文字列: minLength, maxLength, pattern
+//! - 配列: items, prefixItems, contains, minContains, maxContains, minItems, maxItems, uniqueItems
+//! - オブジェクト: properties, patternProperties, additionalProperties, required,
+//!   minProperties, maxProperties, propertyNames, dependentRequired, dependentSchemas
+//! - 合成: allOf, anyOf, oneOf, not, if/then/else
+//! - 参照: $ref（`#` および `#/$defs/...` などルートスキーマ内のJSON Pointer）
+//!
+//! formatは注釈として扱い検証しない。unevaluatedItems/unevaluatedPropertiesと外部参照は未対応。
+//! スキーマのキーはキーワード（`:minLength`）・文字列（`"minLength"`）のどちらでもよい。
+
+use super::json::{json_eq, map_get, sorted_entries, value_to_json};
+use crate::builtins::util::kw;
+use crate::check_args;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::Value;
+
+/// $refの展開を打ち切る深さ（自己参照スキーマの無限再帰対策）
+const MAX_REF_DEPTH: usize = 256;
+
+/// JSON Schemaでデータを検証する
+///
+/// # 引数
+/// - args[0]: スキーマ（マップまたは真偽値）
+/// - args[1]: 検証するデータ
+///
+/// # 戻り値
+/// - 成功時: {:ok data}
+/// - 失敗時: {:error [{:path "/items/0" :keyword "minimum" :message "..." :schema-path "/properties/items/items/minimum"} ...]}
+///
+/// # 例
+/// ```qi
+/// (def schema (json/parse (io/read-file "user.schema.json")))
+/// (match (json/validate schema user)
+///   {:ok u} -> (save u)
+///   {:error errs} -> (each (fn [e] (println f"{(get e :path)}: {(get e :message)}")) errs))
+/// ```
+pub fn native_validate(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 2, "json/validate");
+
+    let schema = &args[0];
+    let data = &args[1];
+    let validator = Validator { root: schema };
+    let errors = validator.validate(schema, data, &Location::root(), 0)?;
+
+    let mut result = crate::new_hashmap();
+    if errors.is_empty() {
+        result.insert(kw("ok"), data.clone());
+    } else {
+        let errors: Vec<Value> = errors.into_iter().map(SchemaError::into_value).collect();
+        result.insert(kw("error"), Value::Vector(errors.into()));
+    }
+    Ok(Value::Map(result))
+}
+
+/// 検証エラー1件
+struct SchemaError {
+    path: String,
+    keyword: &'static str,
+    message: String,
+    schema_path: String,
+}
+
+impl SchemaError {
+    fn into_value(self) -> Value {
+        let mut m = crate::new_hashmap();
+        m.insert(kw("path"), Value::String(self.path));
+        m.insert(kw("keyword"), Value::String(self.keyword.to_string()));
+        m.insert(kw("message"), Value::String(self.message));
+        m.insert(kw("schema-path"), Value::String(self.schema_path));
+        Value::Map(m)
+    }
+}
+
+/// データ側とスキーマ側の現在位置（JSON Pointer）
+#[derive(Clone)]
+struct Location {
+    data: String,
+    schema: String,
+}
+
+impl Location {
+    fn root() -> Self {
+        Self {
+            data: String::new(),
+            schema: String::new(),
+        }
+    }
+
+    fn data(&self, token: &str) -> Self {
+        Self {
+            data: format!("{}/{}", self.data, escape_pointer(token)),
+            schema: self.schema.clone(),
+        }
+    }
+
+    fn schema(&self, token: &str) -> Self {
+        Self {
+            data: self.data.clone(),
+            schema: format!("{}/{}", self.schema, escape_pointer(token)),
+        }
+    }
+}
+
+/// JSON Pointerのトークンをエスケープする（`~` → `~0`、`/` → `~1`）
+fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// エラーメッセージ中に値をJSONとして表示する
+fn show(value: &Value) -> String {
+    value_to_json(value).to_string()
+}
+
+fn as_number(v: &Value) -> Option<f64> {
+    match v {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// JSONの型名（キーワードは文字列、リストは配列として扱う）
+fn json_type(v: &Value) -> &'static str {
+    match v {
+        Value::Nil => "null",
+        Value::Bool(_) => "boolean",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "number",
+        Value::String(_) | Value::Keyword(_) => "string",
+        Value::Vector(_) | Value::List(_) => "array",
+        Value::Map(_) => "object",
+        _ => "unknown",
+    }
+}
+
+fn matches_type(v: &Value, name: &str) -> bool {
+    match name {
+        // 小数部が0の浮動小数点数も整数として扱う
+        "integer" => match v {
+            Value::Integer(_) => true,
+            Value::Float(f) => f.is_finite() && f.fract() == 0.0,
+            _ => false,
+        },
+        "number" => matches!(v, Value::Integer(_) | Value::Float(_)),
+        other => json_type(v) == other,
+    }
+}
+
+/// キーワードの検証エラーを追加する（スキーマ側の位置は現在位置/キーワード）
+fn fail(
+    errors: &mut Vec<SchemaError>,
+    loc: &Location,
+    keyword: &'static str,
+    path: &str,
+    message: String,
+) {
+    errors.push(SchemaError {
+        path: path.to_string(),
+        keyword,
+        message,
+        schema_path: loc.schema(keyword).schema,
+    });
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Validator<'a> {
+    fn invalid(&self, loc: &Location, detail: &str) -> String {
+        let at = if loc.schema.is_empty() {
+            "#"
+        } else {
+            &loc.schema
+        };
+        fmt_msg(MsgKey::JsonSchemaInvalid, &["json/validate", at, detail])
+    }
+
+    fn is_valid(
+        &self,
+        schema: &Value,
+        data: &Value,
+        loc: &Location,
+        depth: usize,
+    ) -> Result<bool, String> {
+        Ok(self.validate(schema, data, loc, depth)?.is_empty())
+    }
+
+    /// スキーマでデータを検証し、全てのエラーを返す（スキーマ自体が不正ならErr）
+    fn validate(
+        &self,
+        schema: &Value,
+        data: &Value,
+        loc: &Location,
+        depth: usize,
+    ) -> Result<Vec<SchemaError>, String> {
+        let s = match schema {
+            Value::Bool(true) => return Ok(Vec::new()),
+            Value::Bool(false) => {
+                return Ok(vec![SchemaError {
+                    path: loc.data.clone(),
+                    keyword: "false",
+                    message: fmt_msg(MsgKey::ValidateFalseSchema, &[]),
+                    schema_path: loc.schema.clone(),
+                }])
+            }
+            Value::Map(m) => m,
+            _ => return Err(self.invalid(loc, "schema must be an object or boolean")),
+        };
+
+        let mut errors = Vec::new();
+
+        // 参照（兄弟キーワードと併用できる）
+        if let Some(reference) = map_get(s, "$ref") {
+            let Value::String(reference) = reference else {
+                return Err(self.invalid(loc, "$ref must be a string"));
+            };
+            if depth >= MAX_REF_DEPTH {
+                return Err(self.invalid(loc, "$ref nesting is too deep"));
+            }
+            let target = self.resolve(reference).ok_or_else(|| {
+                self.invalid(loc, &format!("cannot resolve $ref '{}'", reference))
+            })?;
+            errors.extend(self.validate(target, data, &loc.schema("$ref"), depth + 1)?);
+        }
+
+        // 汎用
+        if let Some(types) = map_get(s, "type") {
+            let names: Vec<&str> = match types {
+                Value::String(t) => vec![t.as_str()],
+                Value::Vector(ts) | Value::List(ts) => ts
+                    .iter()
+                    .map(|t| match t {
+                        Value::String(t) => Ok(t.as_str()),
+                        _ => Err(self.invalid(loc, "type must be a string or array of strings")),
+                    })
+                    .collect::<Result<_, _>>()?,
+                _ => return Err(self.invalid(loc, "type must be a string or array of strings")),
+            };
+            if !names.iter().any(|t| matches_type(data, t)) {
+                fail(
+                    &mut errors,
+                    loc,
+                    "type",
+                    &loc.data,
+                    fmt_msg(MsgKey::ValidateTypeMismatch, &[&names.join(" or ")]),
+                );
+            }
+        }
+        if let Some(allowed) = map_get(s, "enum") {
+            let (Value::Vector(allowed) | Value::List(allowed)) = allowed else {
+                return Err(self.invalid(loc, "enum must be an array"));
+            };
+            if !allowed.iter().any(|v| json_eq(v, data)) {
+                let choices: Vec<String> = allowed.iter().map(show).collect();
+                fail(
+                    &mut errors,
+                    loc,
+                    "enum",
+                    &loc.data,
+                    fmt_msg(MsgKey::ValidateEnum, &[&choices.join(", ")]),
+                );
+            }
+        }
+        if let Some(expected) = map_get(s, "const") {
+            if !json_eq(expected, data) {
+                fail(
+                    &mut errors,
+                    loc,
+                    "const",
+                    &loc.data,
+                    fmt_msg(MsgKey::ValidateConst, &[&show(expected)]),
+                );
+            }
+        }
+
+        // 数値
+        if let Some(n) = as_number(data) {
+            let limit = |name: &str| -> Result<Option<(f64, String)>, String> {
+                match map_get(s, name) {
+                    None => Ok(None),
+                    Some(v) => as_number(v)
+                        .map(|x| Some((x, show(v))))
+                        .ok_or_else(|| self.invalid(loc, &format!("{} must be a number", name))),
+                }
+            };
+            if let Some((m, text)) = limit("multipleOf")? {
+                if m <= 0.0 {
+                    return Err(self.invalid(loc, "multipleOf must be greater than 0"));
+                }
+                let ok = match (data, map_get(s, "multipleOf")) {
+                    (Value::Integer(x), Some(Value::Integer(y))) => x % y == 0,
+                    _ => {
+                        let q = n / m;
+                        q.is_finite() && (q - q.round()).abs() < 1e-9
+                    }
+                };
+                if !ok {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "multipleOf",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMultipleOf, &[&text]),
+                    );
+                }
+            }
+            if let Some((max, text)) = limit("maximum")? {
+                if n > max {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "maximum",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMaxValue, &[&text]),
+                    );
+                }
+            }
+            if let Some((max, text)) = limit("exclusiveMaximum")? {
+                if n >= max {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "exclusiveMaximum",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateExclusiveMax, &[&text]),
+                    );
+                }
+            }
+            if let Some((min, text)) = limit("minimum")? {
+                if n < min {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "minimum",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMinValue, &[&text]),
+                    );
+                }
+            }
+            if let Some((min, text)) = limit("exclusiveMinimum")? {
+                if n <= min {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "exclusiveMinimum",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateExclusiveMin, &[&text]),
+                    );
+                }
+            }
+        }
+
+        // 文字列
+        let text = match data {
+            Value::String(t) => Some(t.to_string()),
+            Value::Keyword(k) => Some(k.to_string()),
+            _ => None,
+        };
+        if let Some(text) = text {
+            let len = text.chars().count();
+            if let Some(min) = self.count(s, "minLength", loc)? {
+                if len < min {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "minLength",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMinLength, &[&min.to_string()]),
+                    );
+                }
+            }
+            if let Some(max) = self.count(s, "maxLength", loc)? {
+                if len > max {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "maxLength",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMaxLength, &[&max.to_string()]),
+                    );
+                }
+            }
+            if let Some(pattern) = map_get(s, "pattern") {
+                let re = self.regex(pattern, loc, "pattern")?;
+                if !re.is_match(&text) {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "pattern",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidatePattern, &[re.as_str()]),
+                    );
+                }
+            }
+        }
+
+        // 配列
+        if let Value::Vector(items) | Value::List(items) = data {
+            let mut evaluated = 0;
+            if let Some(prefix) = map_get(s, "prefixItems") {
+                let (Value::Vector(prefix) | Value::List(prefix)) = prefix else {
+                    return Err(self.invalid(loc, "prefixItems must be an array"));
+                };
+                let base = loc.schema("prefixItems");
+                for (i, (sub, item)) in prefix.iter().zip(items.iter()).enumerate() {
+                    let sub_loc = base.schema(&i.to_string()).data(&i.to_string());
+                    errors.extend(self.validate(sub, item, &sub_loc, depth)?);
+                }
+                evaluated = prefix.len();
+            }
+            if let Some(sub) = map_get(s, "items") {
+                let base = loc.schema("items");
+                for (i, item) in items.iter().enumerate().skip(evaluated) {
+                    errors.extend(self.validate(sub, item, &base.data(&i.to_string()), depth)?);
+                }
+            }
+            if let Some(sub) = map_get(s, "contains") {
+                let base = loc.schema("contains");
+                let mut matched = 0;
+                for (i, item) in items.iter().enumerate() {
+                    if self.is_valid(sub, item, &base.data(&i.to_string()), depth)? {
+                        matched += 1;
+                    }
+                }
+                let min = self.count(s, "minContains", loc)?.unwrap_or(1);
+                if matched < min {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "contains",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMinContains, &[&min.to_string()]),
+                    );
+                }
+                if let Some(max) = self.count(s, "maxContains", loc)? {
+                    if matched > max {
+                        fail(
+                            &mut errors,
+                            loc,
+                            "maxContains",
+                            &loc.data,
+                            fmt_msg(MsgKey::ValidateMaxContains, &[&max.to_string()]),
+                        );
+                    }
+                }
+            }
+            if let Some(min) = self.count(s, "minItems", loc)? {
+                if items.len() < min {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "minItems",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMinItems, &[&min.to_string()]),
+                    );
+                }
+            }
+            if let Some(max) = self.count(s, "maxItems", loc)? {
+                if items.len() > max {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "maxItems",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMaxItems, &[&max.to_string()]),
+                    );
+                }
+            }
+            if matches!(map_get(s, "uniqueItems"), Some(Value::Bool(true))) {
+                let duplicate =
+                    (1..items.len()).find(|&j| (0..j).any(|i| json_eq(&items[i], &items[j])));
+                if let Some(j) = duplicate {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "uniqueItems",
+                        &loc.data(&j.to_string()).data,
+                        fmt_msg(MsgKey::ValidateUniqueItems, &[]),
+                    );
+                }
+            }
+        }
+
+        // オブジェクト
+        if let Value::Map(props) = data {
+            let entries = sorted_entries(props);
+            let declared = match map_get(s, "properties") {
+                None => None,
+                Some(Value::Map(declared)) => Some(declared),
+                Some(_) => return Err(self.invalid(loc, "properties must be an object")),
+            };
+            let patterns = match map_get(s, "patternProperties") {
+                None => Vec::new(),
+                Some(Value::Map(patterns)) => {
+                    let base = loc.schema("patternProperties");
+                    sorted_entries(patterns)
+                        .into_iter()
+                        .map(|(p, sub)| {
+                            let re = self.regex(&Value::String(p.clone()), &base, &p)?;
+                            Ok((p, re, sub))
+                        })
+                        .collect::<Result<Vec<_>, String>>()?
+                }
+                Some(_) => return Err(self.invalid(loc, "patternProperties must be an object")),
+            };
+
+            if let Some(declared) = declared {
+                let base = loc.schema("properties");
+                for (name, sub) in sorted_entries(declared) {
+                    if let Some(value) = map_get(props, &name) {
+                        let sub_loc = base.schema(&name).data(&name);
+                        errors.extend(self.validate(sub, value, &sub_loc, depth)?);
+                    }
+                }
+            }
+            let base = loc.schema("patternProperties");
+            for (name, value) in &entries {
+                for (p, re, sub) in &patterns {
+                    if re.is_match(name) {
+                        let sub_loc = base.schema(p).data(name);
+                        errors.extend(self.validate(sub, value, &sub_loc, depth)?);
+                    }
+                }
+            }
+            if let Some(additional) = map_get(s, "additionalProperties") {
+                let base = loc.schema("additionalProperties");
+                for (name, value) in &entries {
+                    let is_declared = declared.is_some_and(|d| map_get(d, name).is_some());
+                    if is_declared || patterns.iter().any(|(_, re, _)| re.is_match(name)) {
+                        continue;
+                    }
+                    if let Value::Bool(false) = additional {
+                        fail(
+                            &mut errors,
+                            loc,
+                            "additionalProperties",
+                            &loc.data(name).data,
+                            fmt_msg(MsgKey::ValidateAdditionalProperty, &[]),
+                        );
+                    } else {
+                        errors.extend(self.validate(additional, value, &base.data(name), depth)?);
+                    }
+                }
+            }
+            if let Some(required) = map_get(s, "required") {
+                for name in self.strings(required, loc, "required")? {
+                    if map_get(props, &name).is_none() {
+                        fail(
+                            &mut errors,
+                            loc,
+                            "required",
+                            &loc.data(&name).data,
+                            fmt_msg(MsgKey::ValidateRequired, &[]),
+                        );
+                    }
+                }
+            }
+            if let Some(min) = self.count(s, "minProperties", loc)? {
+                if props.len() < min {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "minProperties",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMinProperties, &[&min.to_string()]),
+                    );
+                }
+            }
+            if let Some(max) = self.count(s, "maxProperties", loc)? {
+                if props.len() > max {
+                    fail(
+                        &mut errors,
+                        loc,
+                        "maxProperties",
+                        &loc.data,
+                        fmt_msg(MsgKey::ValidateMaxProperties, &[&max.to_string()]),
+                    );
+                }
+            }
+            if let Some(sub) = map_get(s, "propertyNames") {
+                let base = loc.schema("propertyNames");
+                for (name, _) in &entries {
+                    let key = Value::String(name.clone());
+                    errors.extend(self.validate(sub, &key, &base.data(name), depth)?);
+                }
+            }
+            match map_get(s, "dependentRequired") {
+                None => {}
+                Some(Value::Map(deps)) => {
+                    for (name, needed) in sorted_entries(deps) {
+                        if map_get(props, &name).is_none() {
+                            continue;
+                        }
+                        for other in self.strings(needed, loc, "dependentRequired")? {
+                            if map_get(props, &other).is_none() {
+                                fail(
+                                    &mut errors,
+                                    loc,
+                                    "dependentRequired",
+                                    &loc.data(&other).data,
+                                    fmt_msg(MsgKey::ValidateDependentRequired, &[&name]),
+                                );
+                            }
+                        }
+                    }
+                }
+                Some(_) => return Err(self.invalid(loc, "dependentRequired must be an object")),
+            }
+            match map_get(s, "dependentSchemas") {
+                None => {}
+                Some(Value::Map(deps)) => {
+                    let base = loc.schema("dependentSchemas");
+                    for (name, sub) in sorted_entries(deps) {
+                        if map_get(props, &name).is_some() {
+                            errors.extend(self.validate(sub, data, &base.schema(&name), depth)?);
+                        }
+                    }
+                }
+                Some(_) => return Err(self.invalid(loc, "dependentSchemas must be an object")),
+            }
+        }
+
+        // 合成
+        if let Some(subs) = map_get(s, "allOf") {
+            let base = loc.schema("allOf");
+            for (i, sub) in self.schemas(subs, loc, "allOf")?.iter().enumerate() {
+                errors.extend(self.validate(sub, data, &base.schema(&i.to_string()), depth)?);
+            }
+        }
+        if let Some(subs) = map_get(s, "anyOf") {
+            let base = loc.schema("anyOf");
+            let mut any = false;
+            for (i, sub) in self.schemas(subs, loc, "anyOf")?.iter().enumerate() {
+                if self.is_valid(sub, data, &base.schema(&i.to_string()), depth)? {
+                    any = true;
+                    break;
+                }
+            }
+            if !any {
+                fail(
+                    &mut errors,
+                    loc,
+                    "anyOf",
+                    &loc.data,
+                    fmt_msg(MsgKey::ValidateAnyOf, &[]),
+                );
+            }
+        }
+        if let Some(subs) = map_get(s, "oneOf") {
+            let base = loc.schema("oneOf");
+            let mut matched = 0;
+            for (i, sub) in self.schemas(subs, loc, "oneOf")?.iter().enumerate() {
+                if self.is_valid(sub, data, &base.schema(&i.to_string()), depth)? {
+                    matched += 1;
+                }
+            }
+            if matched != 1 {
+                fail(
+                    &mut errors,
+                    loc,
+                    "oneOf",
+                    &loc.data,
+                    fmt_msg(MsgKey::ValidateOneOf, &[&matched.to_string()]),
+                );
+            }
+        }
+        if let Some(sub) = map_get(s, "not") {
+            if self.is_valid(sub, data, &loc.schema("not"), depth)? {
+                fail(
+                    &mut errors,
+                    loc,
+                    "not",
+                    &loc.data,
+                    fmt_msg(MsgKey::ValidateNot, &[]),
+                );
+            }
+        }
+        if let Some(condition) = map_get(s, "if") {
+            let branch = if self.is_valid(condition, data, &loc.schema("if"), depth)? {
+                "then"
+            } else {
+                "else"
+            };
+            if let Some(sub) = map_get(s, branch) {
+                errors.extend(self.validate(sub, data, &loc.schema(branch), depth)?);
+            }
+        }
+
+        Ok(errors)
+    }
+
+    /// `#` または `#/...` 形式の参照をルートスキーマ内で解決する
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        let mut current = self.root;
+        for token in pointer.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            current = match current {
+                Value::Map(m) => map_get(m, &token)?,
+                Value::Vector(items) | Value::List(items) => {
+                    items.get(token.parse::<usize>().ok()?)?
+                }
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// 非負整数のキーワード（minLengthなど）
+    fn count(
+        &self,
+        s: &crate::HashMap<crate::value::MapKey, Value>,
+        name: &str,
+        loc: &Location,
+    ) -> Result<Option<usize>, String> {
+        match map_get(s, name) {
+            None => Ok(None),
+            Some(Value::Integer(n)) if *n >= 0 => Ok(Some(*n as usize)),
+            Some(Value::Float(f)) if *f >= 0.0 && f.fract() == 0.0 => Ok(Some(*f as usize)),
+            Some(_) => Err(self.invalid(loc, &format!("{} must be a non-negative integer", name))),
+        }
+    }
+
+    fn strings(&self, v: &Value, loc: &Location, name: &str) -> Result<Vec<String>, String> {
+        match v {
+            Value::Vector(items) | Value::List(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::String(s) => Ok(s.clone()),
+                    Value::Keyword(k) => Ok(k.to_string()),
+                    _ => Err(self.invalid(loc, &format!("{} must be an array of strings", name))),
+                })
+                .collect(),
+            _ => Err(self.invalid(loc, &format!("{} must be an array of strings", name))),
+        }
+    }
+
+    fn schemas<'v>(
+        &self,
+        v: &'v Value,
+        loc: &Location,
+        name: &str,
+    ) -> Result<&'v im::Vector<Value>, String> {
+        match v {
+            Value::Vector(items) | Value::List(items) if !items.is_empty() => Ok(items),
+            _ => Err(self.invalid(loc, &format!("{} must be a non-empty array", name))),
+        }
+    }
+
+    fn regex(&self, pattern: &Value, loc: &Location, name: &str) -> Result<regex::Regex, String> {
+        let Value::String(pattern) = pattern else {
+            return Err(self.invalid(loc, &format!("{} must be a string", name)));
+        };
+        crate::builtins::string::get_or_compile_regex(pattern)
+            .map_err(|e| self.invalid(loc, &format!("invalid pattern '{}': {}", pattern, e)))
+    }
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category data/json
+/// @qi-doc:functions validate
+pub const FUNCTIONS: super::NativeFunctions = &[("json/validate", native_validate)];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json(src: &str) -> Value {
+        super::super::json::json_to_value(serde_json::from_str(src).unwrap())
+    }
+
+    fn error_field(err: &Value, field: &str) -> String {
+        let Value::Map(m) = err else {
+            panic!("expected error map");
+        };
+        match m.get(&kw(field)) {
+            Some(Value::String(s)) => s.clone(),
+            other => panic!("missing {}: {:?}", field, other),
+        }
+    }
+
+    #[test]
+    fn test_validate_collects_all_errors() {
+        let schema = json(
+            r##"{
+            "$defs": {"tag": {"type": "string", "minLength": 2}},
+            "type": "object",
+            "required": ["name", "a/b"],
+            "properties": {
+                "name": {"type": "string"},
+                "a/b": true,
+                "age": {"type": "integer", "minimum": 0},
+                "tags": {"type": "array", "items": {"$ref": "#/$defs/tag"}, "uniqueItems": true}
+            },
+            "additionalProperties": false
+        }"##,
+        );
+
+        let valid = json(r#"{"name": "qi", "a/b": 1, "tags": ["ab", "cd"]}"#);
+        assert!(matches!(
+            native_validate(&[schema.clone(), valid]).unwrap(),
+            Value::Map(m) if m.contains_key(&kw("ok"))
+        ));
+
+        let invalid = json(r#"{"age": -1.5, "tags": ["ab", "x", "ab"], "extra": true}"#);
+        let Value::Map(result) = native_validate(&[schema, invalid]).unwrap() else {
+            panic!("expected map");
+        };
+        let Some(Value::Vector(errors)) = result.get(&kw("error")) else {
+            panic!("expected errors");
+        };
+        let mut found: Vec<(String, String)> = errors
+            .iter()
+            .map(|e| (error_field(e, "path"), error_field(e, "keyword")))
+            .collect();
+        found.sort();
+        let expected = [
+            ("/age", "minimum"),
+            ("/age", "type"),
+            ("/a~1b", "required"),
+            ("/extra", "additionalProperties"),
+            ("/name", "required"),
+            ("/tags/1", "minLength"),
+            ("/tags/2", "uniqueItems"),
+        ];
+        assert_eq!(
+            found,
+            expected
+                .iter()
+                .map(|(p, k)| (p.to_string(), k.to_string()))
+                .collect::<Vec<_>>()
+        );
+        let min_length = errors
+            .iter()
+            .find(|e| error_field(e, "keyword") == "minLength")
+            .unwrap();
+        assert_eq!(
+            error_field(min_length, "schema-path"),
+            "/properties/tags/items/$ref/minLength"
+        );
+
+        let bad_schema = json(r##"{"$ref": "#/missing"}"##);
+        assert!(native_validate(&[bad_schema, Value::Nil]).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! JSON Linesモジュール
+//!
+//! 1行に1つのJSON値を置くJSON Lines（NDJSON）を扱う関数を提供:
+//! - read-stream: JSON Linesファイルを値のストリームとして読み込み
+//! - write-stream: ストリームやコレクションの各要素を1行ずつ書き込み
+//!
+//! 読み込みは `io/file-stream` の行ストリームの上に組み立てており、ファイル全体をメモリに載せない。
+//! 壊れた行は `{:error "jsonl/read-stream: line N: ..." :line N}` として返すので、
+//! ストリームを止めずに `error?` で振り分けられる。
+
+use super::json::{error_message, json_to_value, line_error, parse_on_error, value_to_json};
+use crate::builtins::util::kw;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{Stream, Value};
+use parking_lot::{Mutex, RwLock};
+use std::io::Write;
+use std::sync::Arc;
+
+/// jsonl/read-stream - JSON Linesファイルを値のストリームとして読み込み
+///
+/// # 引数
+/// - args[0]: ファイルパス
+/// - args[1]: オプション（省略可）
+///   - :on-error - :report（デフォルト、エラーマップを返す）または :skip（読み飛ばす）
+///
+/// # 戻り値
+/// - 各行をパースした値のストリーム（空行は読み飛ばす）
+pub fn native_read_stream(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["jsonl/read-stream"]));
+    }
+
+    let path = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => return Err(fmt_msg(MsgKey::TypeOnly, &["jsonl/read-stream", "string"])),
+    };
+    let skip_errors = parse_on_error(args.get(1), "jsonl/read-stream")?;
+
+    let lines = match super::io::native_file_stream(&[Value::String(path)])? {
+        Value::Stream(s) => s,
+        _ => unreachable!("io/file-stream always returns a stream"),
+    };
+    let line_no = Mutex::new(0usize);
+
+    let stream = Stream {
+        next_fn: Box::new(move || loop {
+            let line = (lines.read().next_fn)()?;
+            let n = {
+                let mut line_no = line_no.lock();
+                *line_no += 1;
+                *line_no
+            };
+            let Value::String(text) = line else {
+                continue;
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<serde_json::Value>(&text) {
+                Ok(json) => return Some(json_to_value(json)),
+                Err(_) if skip_errors => continue,
+                Err(e) => {
+                    let msg = format!("{} (column {})", error_message(&e), e.column());
+                    return Some(line_error("jsonl/read-stream", n, &msg));
+                }
+            }
+        }),
+    };
+
+    Ok(Value::Stream(Arc::new(RwLock::new(stream))))
+}
+
+/// jsonl/write-stream - 各要素をJSONとして1行ずつ書き込み
+/// 引数: (data, path [opts])
+///   data - ストリーム、ベクタまたはリスト
+///   opts - :append（trueなら既存ファイルに追記）
+/// パイプラインでは (path [opts] data) の順で渡されるため、第1引数が文字列ならこちらとして扱う
+/// 戻り値: 書き込んだ行数
+/// 使い方: (events |> (jsonl/write-stream "events.jsonl" {:append true}))
+pub fn native_write_stream(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["jsonl/write-stream"]));
+    }
+
+    let (data, path, opts) = match args {
+        [Value::String(path), rest @ ..] => {
+            let (opts, data) = match rest {
+                [data] => (None, data),
+                [opts, data] => (Some(opts), data),
+                _ => unreachable!(),
+            };
+            (data, path, opts)
+        }
+        [data, Value::String(path), rest @ ..] => (data, path, rest.first()),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::TypeOnly,
+                &["jsonl/write-stream (path)", "string"],
+            ))
+        }
+    };
+    let append = match opts {
+        None | Some(Value::Nil) => false,
+        Some(Value::Map(opts)) => matches!(opts.get(&kw("append")), Some(Value::Bool(true))),
+        Some(_) => {
+            return Err(fmt_msg(
+                MsgKey::MustBeMap,
+                &["jsonl/write-stream", "options"],
+            ))
+        }
+    };
+
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .map_err(|e| fmt_msg(MsgKey::WriteStreamFailedToCreate, &[path, &e.to_string()]))?;
+    let mut writer = std::io::BufWriter::new(file);
+
+    let mut write_line = |value: &Value| -> Result<(), String> {
+        let line = serde_json::to_string(&value_to_json(value))
+            .map_err(|e| fmt_msg(MsgKey::WriteStreamFailedToWrite, &[path, &e.to_string()]))?;
+        writeln!(writer, "{}", line)
+            .map_err(|e| fmt_msg(MsgKey::WriteStreamFailedToWrite, &[path, &e.to_string()]))
+    };
+
+    let mut count = 0;
+    match data {
+        Value::Stream(stream) => loop {
+            let next = (stream.read().next_fn)();
+            match next {
+                Some(v) => write_line(&v)?,
+                None => break,
+            }
+            count += 1;
+        },
+        Value::Vector(items) | Value::List(items) => {
+            for item in items {
+                write_line(item)?;
+                count += 1;
+            }
+        }
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::ArgMustBeType,
+                &["jsonl/write-stream (data)", "a stream, vector or list"],
+            ))
+        }
+    }
+
+    writer
+        .flush()
+        .map_err(|e| fmt_msg(MsgKey::WriteStreamFailedToWrite, &[path, &e.to_string()]))?;
+    Ok(Value::Integer(count))
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category data/jsonl
+/// @qi-doc:functions read-stream, write-stream
+pub const FUNCTIONS: super::NativeFunctions = &[
+    ("jsonl/read-stream", native_read_stream),
+    ("jsonl/write-stream", native_write_stream),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn realize(stream: Value) -> Vec<Value> {
+        let Value::Stream(s) = stream else {
+            panic!("expected stream");
+        };
+        std::iter::from_fn(|| (s.read().next_fn)()).collect()
+    }
+
+    #[test]
+    fn test_write_and_read_with_line_errors() {
+        let path = std::env::temp_dir().join(format!("qi-jsonl-test-{}.jsonl", std::process::id()));
+        let path_str = path.to_str().unwrap().to_string();
+
+        let mut m = crate::new_hashmap();
+        m.insert(kw("id"), Value::Integer(1));
+        let rows = Value::Vector(vec![Value::Map(m.clone()), Value::Integer(2)].into());
+        let written = native_write_stream(&[rows, Value::String(path_str.clone())]).unwrap();
+        assert_eq!(written, Value::Integer(2));
+
+        // 壊れた行と空行を追記する
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(f, "{{broken\n\n[3]").unwrap();
+
+        let values = realize(native_read_stream(&[Value::String(path_str.clone())]).unwrap());
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[0], Value::Map(m));
+        let Value::Map(err) = &values[2] else {
+            panic!("expected error map");
+        };
+        assert_eq!(err.get(&kw("line")), Some(&Value::Integer(3)));
+
+        let mut opts = crate::new_hashmap();
+        opts.insert(kw("on-error"), Value::Keyword("skip".into()));
+        let values =
+            realize(native_read_stream(&[Value::String(path_str), Value::Map(opts)]).unwrap());
+        assert_eq!(values.len(), 3);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
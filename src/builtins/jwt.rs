@@ -1,21 +1,39 @@
 //! JWT（JSON Web Token）認証機能
 //!
+//! 鍵はHMACシークレット（文字列）・PEM・JWK・JWKS（kidで選択）を受け付ける。
+//! JWKSはファイルまたはURLから `jwt/jwks` で読み込み、一定時間キャッシュする。
+//!
 //! このモジュールは `auth-jwt` feature でコンパイルされます。
 
-use crate::builtins::util::to_map_key;
+use crate::builtins::util::{kw, to_map_key};
 use crate::builtins::value_helpers::{get_int_arg, get_string_ref};
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{MapKey, Value};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::jwk::{AlgorithmParameters, Jwk, PublicKeyUse};
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use parking_lot::Mutex;
 use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// JWKSのデフォルトキャッシュ時間（秒）
+const DEFAULT_JWKS_TTL_SECS: i64 = 3600;
+
+/// `jwt/jwks` で読み込んだJWKSのキャッシュ（キー: ファイルパスまたはURL）
+static JWKS_CACHE: LazyLock<Mutex<HashMap<String, (Instant, Value)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 /// jwt/sign - JWTトークンを生成
 ///
 /// 引数:
 /// - payload: マップ（クレーム）
-/// - secret: シークレットキー（文字列）
-/// - algorithm: アルゴリズム（オプション、デフォルト: "HS256"）
+/// - key: シークレットキー（文字列）またはPEM形式の秘密鍵
+/// - algorithm: アルゴリズム（オプション、デフォルト: "HS256"、PEMは鍵の種類から推定）
+///   またはオプションマップ {:alg "RS256" :exp 3600 :kid "key-1"}
 /// - exp: 有効期限（秒数、オプション）
 ///
 /// 戻り値: トークン文字列 または {:error message}
@@ -30,23 +48,53 @@ pub fn native_jwt_sign(args: &[Value]) -> Result<Value, String> {
         _ => return Err(fmt_msg(MsgKey::TypeOnly, &["jwt/sign (payload)", "maps"])),
     };
 
-    // シークレットキー
+    // シークレットキーまたはPEM
     let secret = get_string_ref(args, 1, "jwt/sign")?;
 
-    // アルゴリズム（オプション、デフォルト: HS256）
-    let algorithm = if args.len() >= 3 {
-        let alg_str = get_string_ref(args, 2, "jwt/sign")?;
-        parse_algorithm(alg_str)?
-    } else {
-        Algorithm::HS256
-    };
-
-    // 有効期限（オプション、秒数）
-    let exp_seconds = if args.len() == 4 {
-        Some(get_int_arg(args, 3, "jwt/sign")?)
-    } else {
-        None
+    // アルゴリズム・有効期限・kid（位置引数またはオプションマップ）
+    let (algorithm, exp_seconds, kid) = match args.get(2) {
+        Some(Value::Map(opts)) => {
+            if args.len() == 4 {
+                return Err(fmt_msg(MsgKey::NeedNArgs, &["jwt/sign", "2-3"]));
+            }
+            let algorithm = match opts.get(&kw("alg")) {
+                None | Some(Value::Nil) => None,
+                Some(v) => Some(parse_algorithm_value(v, "jwt/sign")?),
+            };
+            let exp = match opts.get(&kw("exp")) {
+                None | Some(Value::Nil) => None,
+                Some(Value::Integer(n)) => Some(*n),
+                Some(_) => {
+                    return Err(fmt_msg(
+                        MsgKey::JwtInvalidOption,
+                        &["jwt/sign", ":exp", "must be an integer (seconds)"],
+                    ))
+                }
+            };
+            let kid = match opts.get(&kw("kid")) {
+                None | Some(Value::Nil) => None,
+                Some(Value::String(s)) => Some(s.clone()),
+                Some(_) => {
+                    return Err(fmt_msg(
+                        MsgKey::JwtInvalidOption,
+                        &["jwt/sign", ":kid", "must be a string"],
+                    ))
+                }
+            };
+            (algorithm, exp, kid)
+        }
+        Some(_) => {
+            let alg_str = get_string_ref(args, 2, "jwt/sign")?;
+            let exp = if args.len() == 4 {
+                Some(get_int_arg(args, 3, "jwt/sign")?)
+            } else {
+                None
+            };
+            (Some(parse_algorithm(alg_str)?), exp, None)
+        }
+        None => (None, None, None),
     };
+    let (encoding_key, algorithm) = encoding_key(secret, algorithm)?;
 
     // QiのマップをJSON Valueに変換
     let mut claims = qi_map_to_json(payload)?;
@@ -64,8 +112,8 @@ pub fn native_jwt_sign(args: &[Value]) -> Result<Value, String> {
     }
 
     // JWTトークン生成
-    let header = Header::new(algorithm);
-    let encoding_key = EncodingKey::from_secret(secret.as_bytes());
+    let mut header = Header::new(algorithm);
+    header.kid = kid;
 
     match encode(&header, &claims, &encoding_key) {
         Ok(token) => Ok(Value::String(token)),
@@ -77,8 +125,15 @@ pub fn native_jwt_sign(args: &[Value]) -> Result<Value, String> {
 ///
 /// 引数:
 /// - token: JWTトークン（文字列）
-/// - secret: シークレットキー（文字列）
-/// - algorithm: アルゴリズム（オプション、デフォルト: "HS256"）
+/// - key: シークレットキー（文字列）、PEM形式の公開鍵、JWK（マップ）、JWKS（{:keys [...]}）
+/// - algorithm: アルゴリズム（オプション、デフォルト: "HS256"、PEM/JWKは鍵の種類から決定）
+///   またはオプションマップ
+///   :alg - 許可するアルゴリズム（文字列またはベクタ）
+///   :aud - 期待するaudience（文字列またはベクタ、トークンにaudがある場合は必須）
+///   :iss - 期待するissuer（文字列またはベクタ）
+///   :sub - 期待するsubject
+///   :leeway - exp/nbfの許容誤差（秒、デフォルト: 60）
+///   :required - 必須クレーム名のベクタ（例: [:exp :sub]）
 ///
 /// 戻り値: ペイロードマップ または {:error message}
 pub fn native_jwt_verify(args: &[Value]) -> Result<Value, String> {
@@ -89,26 +144,62 @@ pub fn native_jwt_verify(args: &[Value]) -> Result<Value, String> {
     // トークン
     let token = get_string_ref(args, 0, "jwt/verify")?;
 
-    // シークレットキー
-    let secret = get_string_ref(args, 1, "jwt/verify")?;
+    // 鍵（JWKSの場合は複数）
+    let keys = parse_verify_keys(&args[1])?;
+
+    // 検証オプション（位置引数のアルゴリズム名またはオプションマップ）
+    let opts = match args.get(2) {
+        None => VerifyOptions::default(),
+        Some(Value::Map(m)) => parse_verify_options(m)?,
+        Some(_) => {
+            let alg_str = get_string_ref(args, 2, "jwt/verify")?;
+            VerifyOptions {
+                algorithms: Some(vec![parse_algorithm(alg_str)?]),
+                ..VerifyOptions::default()
+            }
+        }
+    };
 
-    // アルゴリズム（オプション、デフォルト: HS256）
-    let algorithm = if args.len() == 3 {
-        let alg_str = get_string_ref(args, 2, "jwt/verify")?;
-        parse_algorithm(alg_str)?
-    } else {
-        Algorithm::HS256
+    // ヘッダーのkidで鍵を選択
+    let header = match decode_header(token) {
+        Ok(h) => h,
+        Err(e) => return Ok(Value::error(e.to_string())),
+    };
+    let Some(key) = select_key(&keys, header.kid.as_deref()) else {
+        let kid = header.kid.as_deref().unwrap_or("(none)");
+        return Ok(Value::error(fmt_msg(MsgKey::JwtKeyNotFound, &[kid])));
+    };
+
+    // 許可するアルゴリズム: 明示指定 > JWKのalg > 鍵の種類（HMACは従来どおりHS256）
+    let algorithms = match (&opts.algorithms, key.alg) {
+        (Some(algs), _) => algs.clone(),
+        (None, Some(alg)) => vec![alg],
+        (None, None) => key.family.default_algorithms(),
     };
 
-    // JWTトークン検証
-    let decoding_key = DecodingKey::from_secret(secret.as_bytes());
-    let mut validation = Validation::new(algorithm);
-    // expクレームは必須ではないが、存在する場合は検証する
-    validation.required_spec_claims.clear(); // 必須クレームをクリア（expは必須ではない）
-                                             // validate_exp = true のままにして、expが存在する場合は期限を検証する
+    let mut validation = Validation::new(algorithms[0]);
+    validation.algorithms = algorithms;
+    // expクレームは必須ではないが、存在する場合は検証する（必須にするのは:required）
+    validation.required_spec_claims.clear();
+    if let Some(aud) = &opts.aud {
+        validation.set_audience(aud);
+    }
+    if let Some(iss) = &opts.iss {
+        validation.set_issuer(iss);
+    }
+    validation.sub = opts.sub.clone();
+    if let Some(leeway) = opts.leeway {
+        validation.leeway = leeway;
+    }
 
-    match decode::<JsonValue>(token, &decoding_key, &validation) {
+    match decode::<JsonValue>(token, &key.key, &validation) {
         Ok(token_data) => {
+            // 必須クレームの存在確認（exp等の値の検証はdecodeで済んでいる）
+            for claim in &opts.required {
+                if token_data.claims.get(claim).is_none_or(JsonValue::is_null) {
+                    return Ok(Value::error(fmt_msg(MsgKey::JwtMissingClaim, &[claim])));
+                }
+            }
             let payload = json_to_qi_value(&token_data.claims)?;
             Ok(payload)
         }
@@ -132,6 +223,7 @@ pub fn native_jwt_decode(args: &[Value]) -> Result<Value, String> {
     let mut validation = Validation::default();
     validation.insecure_disable_signature_validation();
     validation.validate_exp = false;
+    validation.validate_aud = false;
     validation.required_spec_claims.clear(); // 必須クレームをクリア
 
     match decode::<JsonValue>(token, &DecodingKey::from_secret(&[]), &validation) {
@@ -158,10 +250,340 @@ pub fn native_jwt_decode(args: &[Value]) -> Result<Value, String> {
     }
 }
 
+/// jwt/jwks - JWKS（JSON Web Key Set）をファイルまたはURLから読み込む
+///
+/// 引数:
+/// - source: ファイルパスまたはURL（例: "https://issuer/.well-known/jwks.json"）
+/// - opts: オプション（省略可）
+///   :ttl - キャッシュする秒数（デフォルト: 3600、0でキャッシュしない）
+///   :refresh - trueならキャッシュを無視して再読み込み（鍵のローテーション時）
+///
+/// 戻り値: JWKSマップ（{:keys [...]}、jwt/verifyの鍵として渡す）
+pub fn native_jwt_jwks(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::NeedNArgs, &["jwt/jwks", "1-2"]));
+    }
+
+    let source = get_string_ref(args, 0, "jwt/jwks")?;
+    let empty = crate::new_hashmap();
+    let opts = match args.get(1) {
+        None | Some(Value::Nil) => &empty,
+        Some(Value::Map(m)) => m,
+        Some(_) => return Err(fmt_msg(MsgKey::MustBeMap, &["jwt/jwks", "options"])),
+    };
+    let ttl = match opts.get(&kw("ttl")) {
+        None | Some(Value::Nil) => DEFAULT_JWKS_TTL_SECS,
+        Some(Value::Integer(n)) if *n >= 0 => *n,
+        Some(_) => {
+            return Err(fmt_msg(
+                MsgKey::JwtInvalidOption,
+                &[
+                    "jwt/jwks",
+                    ":ttl",
+                    "must be a non-negative integer (seconds)",
+                ],
+            ))
+        }
+    };
+    let refresh = matches!(opts.get(&kw("refresh")), Some(Value::Bool(true)));
+    let ttl = Duration::from_secs(ttl as u64);
+
+    if !refresh {
+        if let Some((loaded_at, jwks)) = JWKS_CACHE.lock().get(source) {
+            if loaded_at.elapsed() < ttl {
+                return Ok(jwks.clone());
+            }
+        }
+    }
+
+    let load_failed =
+        |detail: &str| fmt_msg(MsgKey::JwtJwksLoadFailed, &["jwt/jwks", source, detail]);
+    let text = if source.starts_with("http://") || source.starts_with("https://") {
+        fetch_jwks(source)?
+    } else {
+        std::fs::read_to_string(source).map_err(|e| load_failed(&e.to_string()))?
+    };
+    let json: JsonValue = serde_json::from_str(&text).map_err(|e| load_failed(&e.to_string()))?;
+    if !json.get("keys").is_some_and(JsonValue::is_array) {
+        return Err(load_failed("missing \"keys\" array"));
+    }
+    let jwks = json_to_qi_value(&json)?;
+
+    if !ttl.is_zero() {
+        JWKS_CACHE
+            .lock()
+            .insert(source.to_string(), (Instant::now(), jwks.clone()));
+    }
+    Ok(jwks)
+}
+
+/// JWKSをHTTPで取得する（http/getと同じクライアントを使う）
+#[cfg(feature = "http-client")]
+fn fetch_jwks(url: &str) -> Result<String, String> {
+    match crate::builtins::http::native_get(&[Value::String(url.to_string())]) {
+        Ok(Value::String(body)) => Ok(body),
+        Ok(other) => Ok(other.to_string()),
+        Err(e) => Err(fmt_msg(MsgKey::JwtJwksLoadFailed, &["jwt/jwks", url, &e])),
+    }
+}
+
+#[cfg(not(feature = "http-client"))]
+fn fetch_jwks(_url: &str) -> Result<String, String> {
+    Err(fmt_msg(MsgKey::HttpClientNotEnabled, &[]))
+}
+
 // ========================================
 // ヘルパー関数
 // ========================================
 
+/// 鍵の種類（アルゴリズムファミリー）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyFamily {
+    Hmac,
+    Rsa,
+    Ec,
+    Ed,
+}
+
+impl KeyFamily {
+    /// アルゴリズムが明示されていないときに許可するアルゴリズム
+    fn default_algorithms(self) -> Vec<Algorithm> {
+        match self {
+            // 従来の挙動（文字列シークレットはHS256）を維持する
+            KeyFamily::Hmac => vec![Algorithm::HS256],
+            KeyFamily::Rsa => vec![
+                Algorithm::RS256,
+                Algorithm::RS384,
+                Algorithm::RS512,
+                Algorithm::PS256,
+                Algorithm::PS384,
+                Algorithm::PS512,
+            ],
+            KeyFamily::Ec => vec![Algorithm::ES256, Algorithm::ES384],
+            KeyFamily::Ed => vec![Algorithm::EdDSA],
+        }
+    }
+
+    fn of(alg: Algorithm) -> KeyFamily {
+        match alg {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => KeyFamily::Hmac,
+            Algorithm::ES256 | Algorithm::ES384 => KeyFamily::Ec,
+            Algorithm::EdDSA => KeyFamily::Ed,
+            _ => KeyFamily::Rsa,
+        }
+    }
+}
+
+/// 検証用の鍵
+struct VerifyKey {
+    key: DecodingKey,
+    family: KeyFamily,
+    /// JWKの"alg"（指定されていればこのアルゴリズムのみ許可）
+    alg: Option<Algorithm>,
+    /// JWKの"kid"
+    kid: Option<String>,
+}
+
+/// jwt/verifyの検証オプション
+#[derive(Default)]
+struct VerifyOptions {
+    algorithms: Option<Vec<Algorithm>>,
+    aud: Option<Vec<String>>,
+    iss: Option<Vec<String>>,
+    sub: Option<String>,
+    leeway: Option<u64>,
+    required: Vec<String>,
+}
+
+/// PEM形式の文字列か
+fn is_pem(key: &str) -> bool {
+    key.trim_start().starts_with("-----BEGIN")
+}
+
+/// 署名用の鍵を作成（PEMでアルゴリズム未指定なら鍵の種類から推定）
+fn encoding_key(
+    key: &str,
+    algorithm: Option<Algorithm>,
+) -> Result<(EncodingKey, Algorithm), String> {
+    let invalid = |e: jsonwebtoken::errors::Error| {
+        fmt_msg(MsgKey::JwtInvalidKey, &["jwt/sign", &e.to_string()])
+    };
+    let pem = key.as_bytes();
+    match algorithm {
+        Some(alg) if !is_pem(key) || KeyFamily::of(alg) == KeyFamily::Hmac => {
+            Ok((EncodingKey::from_secret(pem), alg))
+        }
+        Some(alg) => {
+            let key = match KeyFamily::of(alg) {
+                KeyFamily::Rsa => EncodingKey::from_rsa_pem(pem),
+                KeyFamily::Ec => EncodingKey::from_ec_pem(pem),
+                _ => EncodingKey::from_ed_pem(pem),
+            };
+            Ok((key.map_err(invalid)?, alg))
+        }
+        None if is_pem(key) => EncodingKey::from_rsa_pem(pem)
+            .map(|k| (k, Algorithm::RS256))
+            .or_else(|_| EncodingKey::from_ec_pem(pem).map(|k| (k, Algorithm::ES256)))
+            .or_else(|_| EncodingKey::from_ed_pem(pem).map(|k| (k, Algorithm::EdDSA)))
+            .map_err(invalid),
+        None => Ok((EncodingKey::from_secret(pem), Algorithm::HS256)),
+    }
+}
+
+/// jwt/verifyの鍵引数を解釈する（JWKSなら含まれる全ての署名用の鍵）
+fn parse_verify_keys(value: &Value) -> Result<Vec<VerifyKey>, String> {
+    let invalid = |detail: &str| fmt_msg(MsgKey::JwtInvalidKey, &["jwt/verify", detail]);
+    match value {
+        Value::String(s) if is_pem(s) => {
+            let pem = s.as_bytes();
+            let (key, family) = DecodingKey::from_rsa_pem(pem)
+                .map(|k| (k, KeyFamily::Rsa))
+                .or_else(|_| DecodingKey::from_ec_pem(pem).map(|k| (k, KeyFamily::Ec)))
+                .or_else(|_| DecodingKey::from_ed_pem(pem).map(|k| (k, KeyFamily::Ed)))
+                .map_err(|e| invalid(&e.to_string()))?;
+            Ok(vec![VerifyKey {
+                key,
+                family,
+                alg: None,
+                kid: None,
+            }])
+        }
+        Value::String(s) => Ok(vec![VerifyKey {
+            key: DecodingKey::from_secret(s.as_bytes()),
+            family: KeyFamily::Hmac,
+            alg: None,
+            kid: None,
+        }]),
+        Value::Map(m) => match qi_map_to_json(m)? {
+            // JWKS: 解釈できない鍵や暗号化用の鍵は読み飛ばす
+            JsonValue::Object(obj) if obj.contains_key("keys") => {
+                let Some(JsonValue::Array(items)) = obj.get("keys") else {
+                    return Err(invalid("\"keys\" must be an array"));
+                };
+                Ok(items
+                    .iter()
+                    .filter_map(|item| serde_json::from_value::<Jwk>(item.clone()).ok())
+                    .filter(|jwk| jwk.common.public_key_use != Some(PublicKeyUse::Encryption))
+                    .filter_map(|jwk| jwk_to_key(&jwk).ok())
+                    .collect())
+            }
+            json => {
+                let jwk: Jwk = serde_json::from_value(json).map_err(|e| invalid(&e.to_string()))?;
+                Ok(vec![jwk_to_key(&jwk)?])
+            }
+        },
+        _ => Err(invalid("expected a secret, PEM string, JWK or JWKS map")),
+    }
+}
+
+/// JWKから検証用の鍵を作成する
+fn jwk_to_key(jwk: &Jwk) -> Result<VerifyKey, String> {
+    let key = DecodingKey::from_jwk(jwk)
+        .map_err(|e| fmt_msg(MsgKey::JwtInvalidKey, &["jwt/verify", &e.to_string()]))?;
+    let family = match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => KeyFamily::Rsa,
+        AlgorithmParameters::EllipticCurve(_) => KeyFamily::Ec,
+        AlgorithmParameters::OctetKeyPair(_) => KeyFamily::Ed,
+        AlgorithmParameters::OctetKey(_) => KeyFamily::Hmac,
+    };
+    // 署名用でないアルゴリズム（RSA-OAEP等）はエラー
+    let alg = match jwk.common.key_algorithm {
+        Some(ka) => Some(parse_algorithm(&format!("{:?}", ka)).map_err(|_| {
+            fmt_msg(
+                MsgKey::JwtInvalidKey,
+                &["jwt/verify", &format!("unsupported alg {:?}", ka)],
+            )
+        })?),
+        None => None,
+    };
+    Ok(VerifyKey {
+        key,
+        family,
+        alg,
+        kid: jwk.common.key_id.clone(),
+    })
+}
+
+/// トークンヘッダーのkidで鍵を選ぶ（kidがなければ鍵が1つの場合のみ）
+fn select_key<'a>(keys: &'a [VerifyKey], kid: Option<&str>) -> Option<&'a VerifyKey> {
+    match kid {
+        Some(kid) if keys.len() > 1 || keys.iter().any(|k| k.kid.is_some()) => {
+            keys.iter().find(|k| k.kid.as_deref() == Some(kid))
+        }
+        _ if keys.len() == 1 => keys.first(),
+        _ => None,
+    }
+}
+
+/// jwt/verifyのオプションマップを解釈する
+fn parse_verify_options(opts: &crate::HashMap<MapKey, Value>) -> Result<VerifyOptions, String> {
+    let invalid =
+        |name: &str, detail: &str| fmt_msg(MsgKey::JwtInvalidOption, &["jwt/verify", name, detail]);
+
+    // 文字列（またはキーワード）1つ、またはそのベクタ
+    let strings = |name: &str| -> Result<Option<Vec<String>>, String> {
+        let to_string = |v: &Value| match v {
+            Value::String(s) => Ok(s.clone()),
+            Value::Keyword(k) => Ok(k.to_string()),
+            _ => Err(invalid(name, "expected a string or a vector of strings")),
+        };
+        match opts.get(&kw(&name[1..])) {
+            None | Some(Value::Nil) => Ok(None),
+            Some(Value::Vector(items)) | Some(Value::List(items)) => {
+                Ok(Some(items.iter().map(to_string).collect::<Result<_, _>>()?))
+            }
+            Some(v) => Ok(Some(vec![to_string(v)?])),
+        }
+    };
+
+    let algorithms = match strings(":alg")? {
+        Some(names) if !names.is_empty() => Some(
+            names
+                .iter()
+                .map(|n| parse_algorithm(n))
+                .collect::<Result<_, _>>()?,
+        ),
+        Some(_) => return Err(invalid(":alg", "must not be empty")),
+        None => None,
+    };
+    let sub = match opts.get(&kw("sub")) {
+        None | Some(Value::Nil) => None,
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(_) => return Err(invalid(":sub", "must be a string")),
+    };
+    let leeway = match opts.get(&kw("leeway")) {
+        None | Some(Value::Nil) => None,
+        Some(Value::Integer(n)) if *n >= 0 => Some(*n as u64),
+        Some(_) => {
+            return Err(invalid(
+                ":leeway",
+                "must be a non-negative integer (seconds)",
+            ))
+        }
+    };
+
+    Ok(VerifyOptions {
+        algorithms,
+        aud: strings(":aud")?,
+        iss: strings(":iss")?,
+        sub,
+        leeway,
+        required: strings(":required")?.unwrap_or_default(),
+    })
+}
+
+/// アルゴリズム名（文字列またはキーワード）をパース
+fn parse_algorithm_value(value: &Value, fn_name: &str) -> Result<Algorithm, String> {
+    match value {
+        Value::String(s) => parse_algorithm(s),
+        Value::Keyword(k) => parse_algorithm(k),
+        _ => Err(fmt_msg(
+            MsgKey::JwtInvalidOption,
+            &[fn_name, ":alg", "must be a string"],
+        )),
+    }
+}
+
 /// アルゴリズム名をパース
 fn parse_algorithm(alg: &str) -> Result<Algorithm, String> {
     match alg.to_uppercase().as_str() {
@@ -202,6 +624,7 @@ fn qi_map_to_json(map: &crate::HashMap<MapKey, Value>) -> Result<JsonValue, Stri
             }
             MapKey::Symbol(sym) => sym.to_string(),
             MapKey::Integer(i) => i.to_string(),
+            MapKey::Char(c) => c.to_string(),
         };
 
         let json_value = qi_value_to_json(value)?;
@@ -273,11 +696,12 @@ fn json_to_qi_value(json: &JsonValue) -> Result<Value, String> {
 
 /// 登録すべき関数のリスト
 /// @qi-doc:category auth/jwt
-/// @qi-doc:functions jwt/sign, jwt/verify, jwt/decode
+/// @qi-doc:functions jwt/sign, jwt/verify, jwt/decode, jwt/jwks
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("jwt/sign", native_jwt_sign),
     ("jwt/verify", native_jwt_verify),
     ("jwt/decode", native_jwt_decode),
+    ("jwt/jwks", native_jwt_jwks),
 ];
 
 #[cfg(test)]
@@ -363,6 +787,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_jwt_verify_claim_options() {
+        let mut payload = crate::new_hashmap();
+        payload.insert(to_map_key("aud"), Value::String("api".to_string()));
+        payload.insert(to_map_key("iss"), Value::String("issuer".to_string()));
+        let secret = Value::String("my-secret-key".to_string());
+        let token = native_jwt_sign(&[Value::Map(payload), secret.clone()]).unwrap();
+
+        let verify = |opts: &[(&str, Value)]| {
+            let mut m = crate::new_hashmap();
+            for (k, v) in opts {
+                m.insert(kw(k), v.clone());
+            }
+            native_jwt_verify(&[token.clone(), secret.clone(), Value::Map(m)]).unwrap()
+        };
+        let is_error = |v: &Value| match v {
+            Value::Map(m) => m.contains_key(&crate::constants::keywords::error_mapkey()),
+            _ => false,
+        };
+
+        let ok = verify(&[
+            ("aud", Value::String("api".to_string())),
+            ("iss", Value::String("issuer".to_string())),
+        ]);
+        assert!(!is_error(&ok));
+        assert!(is_error(&verify(&[(
+            "aud",
+            Value::String("other".to_string())
+        )])));
+        assert!(is_error(&verify(&[
+            ("aud", Value::String("api".to_string())),
+            (
+                "required",
+                Value::Vector(vec![Value::Keyword("sub".into())].into())
+            ),
+        ])));
+    }
+
+    #[test]
+    fn test_jwt_verify_jwks_selects_key_by_kid() {
+        // k は "secret-a" / "secret-b" のbase64url
+        let jwk = |kid: &str, k: &str| {
+            let mut m = crate::new_hashmap();
+            m.insert(to_map_key("kty"), Value::String("oct".to_string()));
+            m.insert(to_map_key("kid"), Value::String(kid.to_string()));
+            m.insert(to_map_key("alg"), Value::String("HS256".to_string()));
+            m.insert(to_map_key("k"), Value::String(k.to_string()));
+            Value::Map(m)
+        };
+        let mut jwks = crate::new_hashmap();
+        jwks.insert(
+            to_map_key("keys"),
+            Value::Vector(vec![jwk("a", "c2VjcmV0LWE"), jwk("b", "c2VjcmV0LWI")].into()),
+        );
+
+        let mut opts = crate::new_hashmap();
+        opts.insert(kw("kid"), Value::String("b".to_string()));
+        let token = native_jwt_sign(&[
+            Value::Map(crate::new_hashmap()),
+            Value::String("secret-b".to_string()),
+            Value::Map(opts),
+        ])
+        .unwrap();
+
+        let result = native_jwt_verify(&[token, Value::Map(jwks)]).unwrap();
+        assert_eq!(result, Value::Map(crate::new_hashmap()));
+    }
+
     #[test]
     fn test_jwt_verify_invalid_token() {
         let token = Value::String("invalid.token.here".to_string());
@@ -62,6 +62,7 @@ pub fn native_mset(args: &[Value]) -> Result<Value, String> {
                     crate::value::MapKey::String(s) => s.clone(),
                     crate::value::MapKey::Symbol(sym) => sym.to_string(),
                     crate::value::MapKey::Integer(i) => i.to_string(),
+                    crate::value::MapKey::Char(c) => c.to_string(),
                 };
                 let value_str = match v {
                     Value::String(s) => s.clone(),
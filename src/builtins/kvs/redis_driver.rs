@@ -334,6 +334,7 @@ impl KvsDriver for RedisDriver {
                         crate::value::MapKey::String(s) => s.clone(),
                         crate::value::MapKey::Symbol(sym) => sym.to_string(),
                         crate::value::MapKey::Integer(i) => i.to_string(),
+                        crate::value::MapKey::Char(c) => c.to_string(),
                     };
                     pairs.push((field, s));
                 }
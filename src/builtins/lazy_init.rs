@@ -31,6 +31,25 @@ pub mod http_client {
     pub fn get_client() -> Result<&'static reqwest::blocking::Client, String> {
         CLIENT.as_ref().map_err(|e| e.clone())
     }
+
+    /// 非同期リクエスト用スレッドの上限（これを超えた分は空きが出るまで待つ）
+    pub const MAX_ASYNC_WORKERS: usize = 64;
+
+    /// 非同期リクエスト用の専用スレッドプール
+    ///
+    /// リトライの待機やレスポンス受信でブロックするため、計算用のグローバルrayonプールとは分ける
+    pub static ASYNC_POOL: Lazy<Result<rayon::ThreadPool, String>> = Lazy::new(|| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(MAX_ASYNC_WORKERS)
+            .thread_name(|i| format!("qi-http-async-{}", i))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP async pool: {}", e))
+    });
+
+    /// 非同期リクエスト用スレッドプールを取得
+    pub fn get_async_pool() -> Result<&'static rayon::ThreadPool, String> {
+        ASYNC_POOL.as_ref().map_err(|e| e.clone())
+    }
 }
 
 /// HTTPサーバーランタイムのLazy初期化
@@ -1,6 +1,6 @@
 //! リスト操作 - 集約・統計関数
 
-use crate::builtins::numeric_helpers::checked_int_add;
+use crate::builtins::numeric_helpers::{arith, compare_numbers, is_number, ArithOp};
 use crate::eval::Evaluator;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::Value;
@@ -39,7 +39,7 @@ pub fn native_sort_by(args: &[Value], evaluator: &Evaluator) -> Result<Value, St
                 (Value::Float(x), Value::Integer(y)) => x
                     .partial_cmp(&(*y as f64))
                     .unwrap_or(std::cmp::Ordering::Equal),
-                _ => std::cmp::Ordering::Equal,
+                (x, y) => compare_numbers(x, y).unwrap_or(std::cmp::Ordering::Equal),
             });
 
             let result: Vec<Value> = keyed.into_iter().map(|(_, v)| v).collect();
@@ -82,7 +82,7 @@ pub fn native_max_by(args: &[Value], evaluator: &Evaluator) -> Result<Value, Str
                     (Value::Integer(k), Value::Float(m)) => (*k as f64) > *m,
                     (Value::Float(k), Value::Integer(m)) => *k > (*m as f64),
                     (Value::String(k), Value::String(m)) => k > m,
-                    _ => false,
+                    (k, m) => compare_numbers(k, m).is_some_and(|o| o.is_gt()),
                 };
 
                 if is_greater {
@@ -130,7 +130,7 @@ pub fn native_min_by(args: &[Value], evaluator: &Evaluator) -> Result<Value, Str
                     (Value::Integer(k), Value::Float(m)) => (*k as f64) < *m,
                     (Value::Float(k), Value::Integer(m)) => *k < (*m as f64),
                     (Value::String(k), Value::String(m)) => k < m,
-                    _ => false,
+                    (k, m) => compare_numbers(k, m).is_some_and(|o| o.is_lt()),
                 };
 
                 if is_less {
@@ -162,38 +162,18 @@ pub fn native_sum_by(args: &[Value], evaluator: &Evaluator) -> Result<Value, Str
 
     match collection {
         Value::List(items) | Value::Vector(items) => {
-            let mut int_sum: i64 = 0;
-            let mut float_sum: f64 = 0.0;
-            let mut has_float = false;
+            let mut sum = Value::Integer(0);
 
             for item in items {
                 let key = evaluator.apply_function(key_fn, std::slice::from_ref(item))?;
-
-                match key {
-                    Value::Integer(n) => {
-                        if has_float {
-                            float_sum += n as f64;
-                        } else {
-                            // ⚠️ SAFETY: 整数の合計でオーバーフローする可能性があるため checked_int_add() を使用
-                            int_sum = checked_int_add(int_sum, n, "sum-by")?;
-                        }
-                    }
-                    Value::Float(f) => {
-                        if !has_float {
-                            float_sum = int_sum as f64;
-                            has_float = true;
-                        }
-                        float_sum += f;
-                    }
-                    _ => return Err(fmt_msg(MsgKey::FuncMustReturnType, &["sum-by", "numbers"])),
+                if !is_number(&key) {
+                    return Err(fmt_msg(MsgKey::FuncMustReturnType, &["sum-by", "numbers"]));
                 }
+                // 整数の合計がi64を超えた場合は多倍長整数に昇格する
+                sum = arith(ArithOp::Add, &sum, &key, "sum-by")?;
             }
 
-            if has_float {
-                Ok(Value::Float(float_sum))
-            } else {
-                Ok(Value::Integer(int_sum))
-            }
+            Ok(sum)
         }
         _ => Err(fmt_msg(
             MsgKey::MustBeListOrVector,
@@ -7,6 +7,13 @@ use crate::value::Value;
 
 /// partition-by - 連続する値を述語関数でグループ化
 pub fn native_partition_by(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    // (list/partition-by f) はトランスデューサを返す
+    if args.len() == 1 {
+        return Ok(crate::builtins::transducer::transducer(
+            "partition-by",
+            args,
+        ));
+    }
     if args.len() != 2 {
         return Err(fmt_msg(
             MsgKey::NeedNArgsDesc,
@@ -50,6 +57,9 @@ pub fn native_partition_by(args: &[Value], evaluator: &Evaluator) -> Result<Valu
 
             Ok(Value::List(result))
         }
+        Value::Stream(stream) => Ok(crate::builtins::stream::lazy_partition_by(
+            pred_fn, stream, evaluator,
+        )),
         _ => Err(fmt_msg(
             MsgKey::MustBeListOrVector,
             &["partition-by (2nd arg)", "second argument"],
@@ -69,6 +79,9 @@ pub fn native_keep(args: &[Value], evaluator: &Evaluator) -> Result<Value, Strin
     let func = &args[0];
     let collection = match &args[1] {
         Value::List(v) | Value::Vector(v) => v,
+        Value::Stream(stream) => {
+            return Ok(crate::builtins::stream::lazy_keep(func, stream, evaluator))
+        }
         _ => {
             return Err(fmt_msg(
                 MsgKey::MustBeListOrVector,
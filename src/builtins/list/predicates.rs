@@ -29,6 +29,9 @@ pub fn native_take_while(args: &[Value], evaluator: &Evaluator) -> Result<Value,
             }
             Ok(Value::List(result))
         }
+        Value::Stream(stream) => Ok(crate::builtins::stream::lazy_take_while(
+            pred, stream, evaluator,
+        )),
         _ => Err(fmt_msg(
             MsgKey::MustBeListOrVector,
             &["take-while (2nd arg)", "second argument"],
@@ -65,6 +68,9 @@ pub fn native_drop_while(args: &[Value], evaluator: &Evaluator) -> Result<Value,
             }
             Ok(Value::List(result))
         }
+        Value::Stream(stream) => Ok(crate::builtins::stream::lazy_drop_while(
+            pred, stream, evaluator,
+        )),
         _ => Err(fmt_msg(
             MsgKey::MustBeListOrVector,
             &["drop-while (2nd arg)", "second argument"],
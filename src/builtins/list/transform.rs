@@ -1,6 +1,7 @@
 //! リスト操作 - 変換関数
 
 use super::helpers::values_equal;
+use crate::builtins::stream;
 use crate::builtins::value_helpers::{to_nonnegative_usize, to_positive_usize};
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
@@ -39,6 +40,21 @@ pub fn native_split_at(args: &[Value]) -> Result<Value, String> {
 pub fn native_interleave(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "interleave");
 
+    // どちらかがストリームなら遅延ストリームを返す（もう一方はリスト/ベクタでも可）
+    if matches!(args[0], Value::Stream(_)) || matches!(args[1], Value::Stream(_)) {
+        return match (stream::as_stream(&args[0]), stream::as_stream(&args[1])) {
+            (Some(first), Some(second)) => Ok(stream::lazy_interleave(&first, &second)),
+            (None, _) => Err(fmt_msg(
+                MsgKey::MustBeListOrVector,
+                &["interleave (1st arg)", "first argument"],
+            )),
+            (_, None) => Err(fmt_msg(
+                MsgKey::MustBeListOrVector,
+                &["interleave (2nd arg)", "second argument"],
+            )),
+        };
+    }
+
     let list1 = match &args[0] {
         Value::List(items) | Value::Vector(items) => items,
         _ => {
@@ -129,6 +145,7 @@ pub fn native_chunk(args: &[Value]) -> Result<Value, String> {
 
             Ok(Value::List(result))
         }
+        Value::Stream(stream) => Ok(stream::lazy_chunk(size, stream)),
         _ => Err(fmt_msg(
             MsgKey::MustBeListOrVector,
             &["chunk (2nd arg)", "second argument"],
@@ -189,6 +206,7 @@ pub fn native_take_nth(args: &[Value]) -> Result<Value, String> {
 
     let collection = match &args[1] {
         Value::List(v) | Value::Vector(v) => v,
+        Value::Stream(s) => return Ok(stream::lazy_take_nth(n, s)),
         _ => {
             return Err(fmt_msg(
                 MsgKey::MustBeListOrVector,
@@ -210,10 +228,15 @@ pub fn native_take_nth(args: &[Value]) -> Result<Value, String> {
 
 /// dedupe - 連続する重複を除去
 pub fn native_dedupe(args: &[Value]) -> Result<Value, String> {
+    // (list/dedupe) はトランスデューサを返す
+    if args.is_empty() {
+        return Ok(crate::builtins::transducer::transducer("dedupe", &[]));
+    }
     check_args!(args, 1, "dedupe");
 
     let collection = match &args[0] {
         Value::List(v) | Value::Vector(v) => v,
+        Value::Stream(s) => return Ok(stream::lazy_dedupe(s)),
         _ => return Err(fmt_msg(MsgKey::MustBeListOrVector, &["dedupe", "argument"])),
     };
 
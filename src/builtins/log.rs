@@ -2,6 +2,7 @@
 
 use crate::builtins::value_helpers::{get_map_arg, get_string_ref};
 use crate::check_args;
+use crate::eval::dynamic;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{MapKey, Value};
 use parking_lot::RwLock;
@@ -121,6 +122,13 @@ fn log_internal(level: LogLevel, message: &str, context: Option<crate::HashMap<M
         return;
     }
 
+    // bindingされた *log-context* をマージ（明示的に渡したコンテキストが優先）
+    let context = match (dynamic::lookup(dynamic::LOG_CONTEXT), context) {
+        (Some(Value::Map(bound)), Some(explicit)) => Some(explicit.union(bound)),
+        (Some(Value::Map(bound)), None) => Some(bound),
+        (_, context) => context,
+    };
+
     // 標準ライブラリでタイムスタンプを生成（ISO8601風）
     let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(duration) => {
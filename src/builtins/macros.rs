@@ -222,7 +222,7 @@ macro_rules! map_db_err {
     };
 }
 
-/// 引数1個チェック + 文字列型チェック + 抽出を1行で実行
+/// 引数1個チェック + 文字列型チェック（Charも可） + 抽出を1行で実行
 ///
 /// 最も頻繁に使用されるパターン（引数1個かつ文字列）を1マクロで処理。
 ///
@@ -250,8 +250,10 @@ macro_rules! require_string {
                 &[$func],
             ));
         }
-        match &$args[0] {
-            $crate::value::Value::String(s) => s.as_str(),
+        // Charは1文字の文字列として扱う（str/charsの結果をそのまま渡せるように）
+        &*match &$args[0] {
+            $crate::value::Value::String(s) => std::borrow::Cow::Borrowed(s.as_str()),
+            $crate::value::Value::Char(c) => std::borrow::Cow::Owned(c.to_string()),
             _ => {
                 return Err($crate::i18n::fmt_msg(
                     $crate::i18n::MsgKey::TypeOnly,
@@ -293,7 +295,7 @@ macro_rules! require_int {
 
 /// 引数1個チェック + 数値型（Integer/Float）チェック + 抽出を1行で実行
 ///
-/// IntegerまたはFloatを受け入れ、f64として返す。
+/// 数値塔のすべての型（Integer/BigInt/Ratio/Decimal/Float）を受け入れ、f64として返す。
 ///
 /// # 使用例
 /// ```rust,ignore
@@ -308,10 +310,9 @@ macro_rules! require_number {
                 &[$func],
             ));
         }
-        match &$args[0] {
-            $crate::value::Value::Float(f) => *f,
-            $crate::value::Value::Integer(i) => *i as f64,
-            _ => {
+        match $crate::builtins::numeric_helpers::number_to_f64(&$args[0]) {
+            Some(f) => f,
+            None => {
                 return Err($crate::i18n::fmt_msg(
                     $crate::i18n::MsgKey::TypeOnly,
                     &[$func, "numbers"],
@@ -338,25 +339,29 @@ macro_rules! require_2_strings {
                 &[$func],
             ));
         }
-        let s1 = match &$args[0] {
-            $crate::value::Value::String(s) => s.as_str(),
-            _ => {
-                return Err($crate::i18n::fmt_msg(
-                    $crate::i18n::MsgKey::FirstArgMustBe,
-                    &[$func, "a string"],
-                ))
-            }
-        };
-        let s2 = match &$args[1] {
-            $crate::value::Value::String(s) => s.as_str(),
-            _ => {
-                return Err($crate::i18n::fmt_msg(
-                    $crate::i18n::MsgKey::SecondArgMustBe,
-                    &[$func, "a string"],
-                ))
-            }
-        };
-        (s1, s2)
+        // Charは1文字の文字列として扱う
+        (
+            &*match &$args[0] {
+                $crate::value::Value::String(s) => std::borrow::Cow::Borrowed(s.as_str()),
+                $crate::value::Value::Char(c) => std::borrow::Cow::Owned(c.to_string()),
+                _ => {
+                    return Err($crate::i18n::fmt_msg(
+                        $crate::i18n::MsgKey::FirstArgMustBe,
+                        &[$func, "a string"],
+                    ))
+                }
+            },
+            &*match &$args[1] {
+                $crate::value::Value::String(s) => std::borrow::Cow::Borrowed(s.as_str()),
+                $crate::value::Value::Char(c) => std::borrow::Cow::Owned(c.to_string()),
+                _ => {
+                    return Err($crate::i18n::fmt_msg(
+                        $crate::i18n::MsgKey::SecondArgMustBe,
+                        &[$func, "a string"],
+                    ))
+                }
+            },
+        )
     }};
 }
 
@@ -1,5 +1,6 @@
 //! 数学関数
 
+use crate::builtins::numeric_helpers::{is_number, power, round_exact, Rounding};
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::require_number;
@@ -9,30 +10,17 @@ use crate::value::Value;
 use rand::Rng;
 
 /// pow - べき乗
+/// 整数のべき乗がi64を超える場合は多倍長整数になる
 pub fn native_pow(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "pow");
 
-    let base_f = match &args[0] {
-        Value::Integer(i) => *i as f64,
-        Value::Float(f) => *f,
-        _ => return Err(fmt_msg(MsgKey::TypeOnly, &["pow (base)", "numbers"])),
-    };
-    let exp_f = match &args[1] {
-        Value::Integer(i) => *i as f64,
-        Value::Float(f) => *f,
-        _ => return Err(fmt_msg(MsgKey::TypeOnly, &["pow (exponent)", "numbers"])),
-    };
-
-    // 両方が整数で指数が非負の場合は整数で返す
-    if let (Value::Integer(base), Value::Integer(exp)) = (&args[0], &args[1]) {
-        if *exp >= 0 {
-            if let Some(result) = base.checked_pow(*exp as u32) {
-                return Ok(Value::Integer(result));
-            }
-        }
+    if !is_number(&args[0]) {
+        return Err(fmt_msg(MsgKey::TypeOnly, &["pow (base)", "numbers"]));
     }
-
-    Ok(Value::Float(base_f.powf(exp_f)))
+    if !is_number(&args[1]) {
+        return Err(fmt_msg(MsgKey::TypeOnly, &["pow (exponent)", "numbers"]));
+    }
+    power(&args[0], &args[1], "pow")
 }
 
 /// sqrt - 平方根
@@ -43,6 +31,11 @@ pub fn native_sqrt(args: &[Value]) -> Result<Value, String> {
 
 /// round - 四捨五入
 pub fn native_round(args: &[Value]) -> Result<Value, String> {
+    // BigInt・有理数・10進数はf64を経由せずに丸める
+    if let Some(v) = args.first().and_then(|v| round_exact(v, Rounding::Round)) {
+        check_args!(args, 1, "round");
+        return Ok(v);
+    }
     let n = require_number!(args, "round");
 
     let rounded = n.round();
@@ -60,6 +53,11 @@ pub fn native_round(args: &[Value]) -> Result<Value, String> {
 
 /// floor - 切り捨て
 pub fn native_floor(args: &[Value]) -> Result<Value, String> {
+    // BigInt・有理数・10進数はf64を経由せずに丸める
+    if let Some(v) = args.first().and_then(|v| round_exact(v, Rounding::Floor)) {
+        check_args!(args, 1, "floor");
+        return Ok(v);
+    }
     let n = require_number!(args, "floor");
 
     let floored = n.floor();
@@ -77,6 +75,11 @@ pub fn native_floor(args: &[Value]) -> Result<Value, String> {
 
 /// ceil - 切り上げ
 pub fn native_ceil(args: &[Value]) -> Result<Value, String> {
+    // BigInt・有理数・10進数はf64を経由せずに丸める
+    if let Some(v) = args.first().and_then(|v| round_exact(v, Rounding::Ceil)) {
+        check_args!(args, 1, "ceil");
+        return Ok(v);
+    }
     let n = require_number!(args, "ceil");
 
     let ceiled = n.ceil();
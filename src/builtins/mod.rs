@@ -11,7 +11,8 @@
 //! - 述語・型判定（22個）: nil?, list?, vector?, map?, string?, integer?, float?, etc.
 //! - 並行処理（5個）: go, chan, send!, recv!, close!
 //! - 論理・I/O（4個）: not, print, println, error (※ and, or は特殊形式)
-//! - 状態管理（4個）: atom, deref, swap!, reset!
+//! - 状態管理（7個）: atom, deref, swap!, reset!, add-watch, remove-watch, set-validator!
+//! - STM（5個）: ref, dosync, alter, commute, ensure
 //! - メタプログラミング（4個）: eval, uvar, variable, macro?
 //! - 型変換（3個）: to-int, to-float, to-string
 //! - 日時（3個）: now, timestamp, sleep
@@ -33,9 +34,16 @@
 //! - pipeline: パイプライン処理（5個）
 //! - stream: ストリーム処理（11個）
 //! - str: 文字列操作（62個）
-//! - json: JSON処理（3個）
+//! - json: JSON処理（4個）
+//! - jsonl: JSON Lines処理（2個）
+//! - json_path: JSONPathクエリ（1個）
+//! - json_schema: JSON Schema検証（1個）
 //! - yaml: YAML処理（3個）
-//! - csv: CSV処理（5個）
+//! - toml: TOML処理（2個）
+//! - xml: XML処理（3個）
+//! - msgpack: MessagePack（2個）
+//! - cbor: CBOR（2個）
+//! - csv: CSV処理（6個）
 //! - markdown: Markdown生成・解析（11個）
 //! - http: HTTP通信（22個）
 //! - db: データベース（17個）
@@ -55,6 +63,7 @@ pub mod core_predicates;
 pub mod core_state_meta;
 pub mod core_string;
 pub mod core_util;
+pub mod stm;
 
 // 専門モジュール
 pub mod bytes;
@@ -67,6 +76,12 @@ pub mod io;
 
 #[cfg(feature = "format-json")]
 pub mod json;
+#[cfg(feature = "format-json")]
+pub mod json_path;
+#[cfg(feature = "format-json")]
+pub mod json_schema;
+#[cfg(feature = "format-json")]
+pub mod jsonl;
 pub mod list;
 pub mod map;
 pub mod math;
@@ -74,6 +89,18 @@ pub mod path;
 #[cfg(feature = "format-yaml")]
 pub mod yaml;
 
+#[cfg(feature = "format-toml")]
+pub mod toml;
+
+#[cfg(feature = "format-xml")]
+pub mod xml;
+
+#[cfg(feature = "format-msgpack")]
+pub mod msgpack;
+
+#[cfg(feature = "format-cbor")]
+pub mod cbor;
+
 #[cfg(feature = "std-set")]
 pub mod set;
 
@@ -93,6 +120,7 @@ pub mod env;
 pub mod flow;
 pub mod log;
 pub mod table;
+pub mod transducer;
 pub mod util;
 
 #[cfg(feature = "util-zip")]
@@ -112,6 +140,7 @@ pub mod markdown;
 pub mod ds;
 pub mod profile;
 pub mod test;
+pub mod validation;
 
 #[cfg(feature = "db-sqlite")]
 pub mod db;
@@ -143,6 +172,9 @@ pub mod jwt;
 #[cfg(feature = "auth-password")]
 pub mod password;
 
+#[cfg(feature = "auth-crypto")]
+pub mod crypto;
+
 use crate::eval::Evaluator;
 use crate::value::{Env, NativeFunc, Value};
 use parking_lot::RwLock;
@@ -202,6 +234,7 @@ const CORE_MODULES: &[NativeFunctions] = &[
     core_io_logic::FUNCTIONS,
     core_functions::FUNCTIONS,
     core_state_meta::FUNCTIONS,
+    stm::FUNCTIONS,
 ];
 
 /// 標準専門モジュール一覧（feature-gatedでないもの）
@@ -226,6 +259,7 @@ const STANDARD_MODULES: &[NativeFunctions] = &[
     stream::FUNCTIONS,
     concurrency::FUNCTIONS,
     debug::FUNCTIONS,
+    validation::FUNCTIONS,
 ];
 
 /// すべての組み込み関数を環境に登録
@@ -269,9 +303,30 @@ pub fn register_all(env: &Arc<RwLock<Env>>) {
     #[cfg(feature = "format-json")]
     register_functions(&mut env_write, json::FUNCTIONS);
 
+    #[cfg(feature = "format-json")]
+    register_functions(&mut env_write, jsonl::FUNCTIONS);
+
+    #[cfg(feature = "format-json")]
+    register_functions(&mut env_write, json_path::FUNCTIONS);
+
+    #[cfg(feature = "format-json")]
+    register_functions(&mut env_write, json_schema::FUNCTIONS);
+
     #[cfg(feature = "format-yaml")]
     register_functions(&mut env_write, yaml::FUNCTIONS);
 
+    #[cfg(feature = "format-toml")]
+    register_functions(&mut env_write, toml::FUNCTIONS);
+
+    #[cfg(feature = "format-xml")]
+    register_functions(&mut env_write, xml::FUNCTIONS);
+
+    #[cfg(feature = "format-msgpack")]
+    register_functions(&mut env_write, msgpack::FUNCTIONS);
+
+    #[cfg(feature = "format-cbor")]
+    register_functions(&mut env_write, cbor::FUNCTIONS);
+
     #[cfg(feature = "http-client")]
     register_functions(&mut env_write, http::FUNCTIONS);
 
@@ -309,9 +364,15 @@ pub fn register_all(env: &Arc<RwLock<Env>>) {
     #[cfg(feature = "auth-password")]
     register_functions(&mut env_write, password::FUNCTIONS);
 
+    #[cfg(feature = "auth-crypto")]
+    register_functions(&mut env_write, crypto::FUNCTIONS);
+
     // Evaluator必要な関数をプレースホルダーとして登録
     // （実際の呼び出しはtry_eval_special_formで行われる）
     register_eval_functions(&mut env_write, table::EVAL_FUNCTIONS);
+
+    // 組み込みの動的変数（ルート値nil、bindingで再束縛）
+    env_write.set(crate::eval::dynamic::LOG_CONTEXT, Value::Nil);
 }
 
 // ========================================
@@ -331,6 +392,11 @@ pub fn filter(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     hof::native_filter(args, evaluator)
 }
 
+/// mapcat - 各要素に関数を適用して結果を連結
+pub fn mapcat(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    hof::native_mapcat(args, evaluator)
+}
+
 /// reduce - リストを畳み込み
 pub fn reduce(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     hof::native_reduce(args, evaluator)
@@ -376,6 +442,11 @@ pub fn apply(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     core_functions::native_apply(args, evaluator)
 }
 
+/// trampoline - 関数が返される間呼び出しを繰り返す
+pub fn trampoline(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    core_functions::native_trampoline(args, evaluator)
+}
+
 /// take-while - 条件を満たす間要素を取得
 pub fn take_while(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     list::native_take_while(args, evaluator)
@@ -416,6 +487,26 @@ pub fn swap(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     core_state_meta::native_swap(args, evaluator)
 }
 
+/// reset! - アトムの値をセット（バリデータ・ウォッチ対応）
+pub fn reset(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    core_state_meta::native_reset_with_evaluator(args, evaluator)
+}
+
+/// set-validator! - アトムのバリデータを設定
+pub fn set_validator(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    core_state_meta::native_set_validator(args, evaluator)
+}
+
+/// alter - トランザクション内でrefを更新
+pub fn alter(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    stm::native_alter(args, evaluator)
+}
+
+/// commute - トランザクション内でrefを可換に更新
+pub fn commute(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    stm::native_commute(args, evaluator)
+}
+
 /// eval - 式を評価
 pub fn eval(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     core_state_meta::native_eval(args, evaluator)
@@ -508,6 +599,26 @@ pub fn map_lines(args: &[Value], evaluator: &Evaluator) -> Result<Value, String>
     hof::native_map_lines(args, evaluator)
 }
 
+/// transduce - トランスデューサを通した値を畳み込む
+pub fn transduce(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    transducer::native_transduce(args, evaluator)
+}
+
+/// into - 要素をコレクションに追加（トランスデューサ指定可）
+pub fn into(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    transducer::native_into(args, evaluator)
+}
+
+/// sequence - トランスデューサを適用した結果を返す
+pub fn sequence(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    transducer::native_sequence(args, evaluator)
+}
+
+/// go/chan - チャネル作成（第2引数にトランスデューサを指定可）
+pub fn chan(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    concurrency::native_chan_with_xform(args, evaluator)
+}
+
 pub fn pipeline(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
     concurrency::native_pipeline(args, evaluator)
 }
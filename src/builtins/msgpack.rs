@@ -0,0 +1,233 @@
+//! MessagePackモジュール
+//!
+//! MessagePackのエンコード・デコードを提供:
+//! - encode: Qi値をMessagePackのバイト列に変換
+//! - decode: MessagePackのバイト列をQi値に変換
+//!
+//! 文字列キーのマップは `MapKey::String`、整数キーは `MapKey::Integer` になる。
+//! バイナリは `Value::Bytes`、拡張型は `{:ext-type 型番号 :data バイト列}` で表す。
+
+use crate::builtins::util::kw;
+use crate::check_args;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Value};
+use rmpv::Value as Mp;
+
+/// Qi値をMessagePackのバイト列に変換
+///
+/// # 引数
+/// - args[0]: 変換する値
+///
+/// # 戻り値
+/// - 成功時: バイト列
+/// - 失敗時: {:error エラーメッセージ}
+pub fn native_encode(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "msgpack/encode");
+
+    let mp = match value_to_msgpack(&args[0]) {
+        Ok(mp) => mp,
+        Err(e) => {
+            return Ok(Value::error(fmt_msg(
+                MsgKey::FormatEncodeError,
+                &["msgpack/encode", &e],
+            )))
+        }
+    };
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &mp).map_err(|e| e.to_string())?;
+    Ok(Value::Bytes(buf.into()))
+}
+
+/// MessagePackのバイト列をQi値に変換
+///
+/// # 引数
+/// - args[0]: バイト列
+///
+/// # 戻り値
+/// - 成功時: デコード結果
+/// - 失敗時: {:error エラーメッセージ}
+pub fn native_decode(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "msgpack/decode");
+
+    let bytes = match &args[0] {
+        Value::Bytes(b) => b.clone(),
+        _ => return Err(fmt_msg(MsgKey::ArgMustBeType, &["msgpack/decode", "bytes"])),
+    };
+
+    match rmpv::decode::read_value(&mut bytes.as_ref()) {
+        Ok(mp) => Ok(msgpack_to_value(mp)),
+        Err(e) => Ok(Value::error(fmt_msg(
+            MsgKey::FormatParseError,
+            &["msgpack/decode", &e.to_string()],
+        ))),
+    }
+}
+
+/// rmpv::ValueをQi Valueに変換
+fn msgpack_to_value(mp: Mp) -> Value {
+    match mp {
+        Mp::Nil => Value::Nil,
+        Mp::Boolean(b) => Value::Bool(b),
+        Mp::Integer(i) => match (i.as_i64(), i.as_u64()) {
+            (Some(n), _) => Value::Integer(n),
+            // i64に収まらないu64は多倍長整数にする
+            (None, Some(u)) => Value::BigInt(std::sync::Arc::new(num_bigint::BigInt::from(u))),
+            (None, None) => Value::Float(i.as_f64().unwrap_or(f64::NAN)),
+        },
+        Mp::F32(f) => Value::Float(f as f64),
+        Mp::F64(f) => Value::Float(f),
+        Mp::String(s) => match s.into_str() {
+            Some(s) => Value::String(s),
+            None => Value::Nil,
+        },
+        Mp::Binary(b) => Value::Bytes(b.into()),
+        Mp::Array(arr) => Value::Vector(arr.into_iter().map(msgpack_to_value).collect()),
+        Mp::Map(entries) => {
+            let mut map = crate::new_hashmap();
+            for (k, v) in entries {
+                let key = match k {
+                    Mp::String(s) => match s.into_str() {
+                        Some(s) => MapKey::String(s),
+                        None => continue,
+                    },
+                    Mp::Integer(i) => match i.as_i64() {
+                        Some(n) => MapKey::Integer(n),
+                        None => continue,
+                    },
+                    Mp::Boolean(b) => MapKey::String(b.to_string()),
+                    _ => continue, // その他のキーはスキップ
+                };
+                map.insert(key, msgpack_to_value(v));
+            }
+            Value::Map(map)
+        }
+        Mp::Ext(ty, data) => {
+            let mut map = crate::new_hashmap();
+            map.insert(kw("ext-type"), Value::Integer(ty as i64));
+            map.insert(kw("data"), Value::Bytes(data.into()));
+            Value::Map(map)
+        }
+    }
+}
+
+/// Qi Valueをrmpv::Valueに変換
+fn value_to_msgpack(value: &Value) -> Result<Mp, String> {
+    Ok(match value {
+        Value::Nil => Mp::Nil,
+        Value::Bool(b) => Mp::Boolean(*b),
+        Value::Integer(i) => Mp::from(*i),
+        Value::Float(f) => Mp::F64(*f),
+        // u64に収まらない多倍長整数は精度を失わないよう文字列で出力（json/stringifyと同じ規則）
+        Value::BigInt(n) => match num_traits::ToPrimitive::to_u64(n.as_ref()) {
+            Some(u) => Mp::from(u),
+            None => Mp::from(n.to_string()),
+        },
+        Value::Ratio(_) => {
+            Mp::F64(crate::builtins::numeric_helpers::number_to_f64(value).unwrap_or(f64::NAN))
+        }
+        // 10進数はf64で誤差なく表せる場合のみ数値、それ以外は文字列で出力
+        Value::Decimal(d) => match crate::builtins::numeric_helpers::decimal_to_exact_f64(d) {
+            Some(f) => Mp::F64(f),
+            None => Mp::from(d.to_string()),
+        },
+        Value::String(s) => Mp::from(s.as_str()),
+        Value::Char(c) => Mp::from(c.to_string()),
+        Value::Keyword(k) => Mp::from(k.as_ref()),
+        Value::Bytes(b) => Mp::Binary(b.to_vec()),
+        Value::Set(items) => {
+            return value_to_msgpack(&Value::Vector(Value::sorted_set_items(items)))
+        }
+        Value::Vector(items) | Value::List(items) => Mp::Array(
+            items
+                .iter()
+                .map(value_to_msgpack)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(m) => {
+            // {:ext-type n :data bytes} は拡張型として書き出す
+            if m.len() == 2 {
+                if let (Some(Value::Integer(ty)), Some(Value::Bytes(data))) =
+                    (m.get(&kw("ext-type")), m.get(&kw("data")))
+                {
+                    if let Ok(ty) = i8::try_from(*ty) {
+                        return Ok(Mp::Ext(ty, data.to_vec()));
+                    }
+                }
+            }
+            let mut entries = Vec::with_capacity(m.len());
+            for (k, v) in m.iter() {
+                let key = match k {
+                    MapKey::Keyword(kw) => Mp::from(kw.as_ref()),
+                    MapKey::String(s) => Mp::from(s.as_str()),
+                    MapKey::Symbol(sym) => Mp::from(sym.as_ref()),
+                    MapKey::Integer(i) => Mp::from(*i),
+                    MapKey::Char(c) => Mp::from(c.to_string()),
+                };
+                entries.push((key, value_to_msgpack(v)?));
+            }
+            Mp::Map(entries)
+        }
+        other => return Err(other.type_name().to_string()),
+    })
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category data/msgpack
+/// @qi-doc:functions encode, decode
+pub const FUNCTIONS: super::NativeFunctions = &[
+    ("msgpack/encode", native_encode),
+    ("msgpack/decode", native_decode),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut m = crate::new_hashmap();
+        m.insert(MapKey::String("id".into()), Value::Integer(-7));
+        m.insert(MapKey::Integer(1), Value::Bytes(vec![0, 255].into()));
+        m.insert(
+            MapKey::String("tags".into()),
+            Value::Vector(vec![Value::String("a".into()), Value::Float(1.5)].into()),
+        );
+        let original = Value::Map(m);
+
+        let encoded = native_encode(&[original.clone()]).unwrap();
+        assert!(matches!(encoded, Value::Bytes(_)));
+        assert_eq!(native_decode(&[encoded]).unwrap(), original);
+    }
+
+    #[test]
+    fn test_exact_numbers() {
+        use std::sync::Arc;
+        let roundtrip = |v: Value| native_decode(&[native_encode(&[v]).unwrap()]).unwrap();
+
+        let u = num_bigint::BigInt::from(u64::MAX);
+        assert_eq!(
+            roundtrip(Value::BigInt(Arc::new(u.clone()))),
+            Value::BigInt(Arc::new(u))
+        );
+        let big: num_bigint::BigInt = num_bigint::BigInt::from(i64::MAX) * 4;
+        assert_eq!(
+            roundtrip(Value::BigInt(Arc::new(big.clone()))),
+            Value::String(big.to_string())
+        );
+        assert_eq!(
+            roundtrip(Value::Decimal("1.5".parse().unwrap())),
+            Value::Float(1.5)
+        );
+        let d: rust_decimal::Decimal = "12345678901234567890.12".parse().unwrap();
+        assert_eq!(roundtrip(Value::Decimal(d)), Value::String(d.to_string()));
+        let third = num_rational::BigRational::new(1.into(), 3.into());
+        assert_eq!(
+            roundtrip(Value::Ratio(Arc::new(third))),
+            Value::Float(1.0 / 3.0)
+        );
+    }
+}
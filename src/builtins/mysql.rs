@@ -8,11 +8,13 @@
 //! このモジュールは `db-mysql` feature でコンパイルされます。
 
 use super::db::*;
-use crate::i18n::{fmt_msg, MsgKey};
+use crate::builtins::numeric_helpers::{bigint_value, number_to_f64};
+use crate::i18n::MsgKey;
 use crate::map_db_err;
 use crate::value::Value;
 use mysql_async::prelude::*;
 use mysql_async::{Conn as MyConn, Opts, Row as MyRow};
+use num_bigint::BigInt;
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -83,6 +85,10 @@ impl MysqlConnection {
             Value::Bool(b) => mysql_async::Value::Int(if *b { 1 } else { 0 }),
             Value::Integer(i) => mysql_async::Value::Int(*i),
             Value::Float(f) => mysql_async::Value::Double(*f),
+            // 10進数・多倍長整数は精度を保つため数値リテラル文字列として送信
+            Value::Decimal(d) => mysql_async::Value::Bytes(d.to_string().into_bytes()),
+            Value::BigInt(n) => mysql_async::Value::Bytes(n.to_string().into_bytes()),
+            Value::Ratio(_) => mysql_async::Value::Double(number_to_f64(value).unwrap_or(f64::NAN)),
             Value::String(s) => mysql_async::Value::Bytes(s.as_bytes().to_vec()),
             Value::Bytes(b) => mysql_async::Value::Bytes(b.as_ref().to_vec()), // BLOB型として送信
             _ => mysql_async::Value::Bytes(value.to_string().as_bytes().to_vec()),
@@ -111,16 +117,8 @@ impl MysqlConnection {
                 MySqlValue::NULL => Value::Nil,
                 MySqlValue::Int(i) => Value::Integer(*i),
                 MySqlValue::UInt(u) => {
-                    // u64 -> i64の安全な変換（Qiはi64のみサポート）
-                    if *u <= i64::MAX as u64 {
-                        Value::Integer(*u as i64)
-                    } else {
-                        // i64の範囲を超える場合はエラー（明示的にFloat型を使用すべき）
-                        return Err(DbError::new(fmt_msg(
-                            MsgKey::UnsignedIntTooLarge,
-                            &["MySQL query", &u.to_string(), "i64::MAX"],
-                        )));
-                    }
+                    // i64の範囲を超えるBIGINT UNSIGNEDは多倍長整数として保持
+                    bigint_value(BigInt::from(*u))
                 }
                 MySqlValue::Float(f) => Value::Float(*f as f64),
                 MySqlValue::Double(d) => Value::Float(*d),
@@ -129,8 +127,20 @@ impl MysqlConnection {
                     if column_type.is_numeric_type() {
                         // 数値型の場合はパース
                         if let Ok(s) = String::from_utf8(b.clone()) {
+                            let is_decimal_column = matches!(
+                                column_type,
+                                ColumnType::MYSQL_TYPE_DECIMAL | ColumnType::MYSQL_TYPE_NEWDECIMAL
+                            );
                             if let Ok(i) = s.parse::<i64>() {
                                 Value::Integer(i)
+                            } else if let Some(d) = is_decimal_column
+                                .then(|| s.parse::<rust_decimal::Decimal>().ok())
+                                .flatten()
+                            {
+                                // DECIMAL型 → Decimal（誤差なく読み込む）
+                                Value::Decimal(d)
+                            } else if let Ok(n) = s.parse::<BigInt>() {
+                                bigint_value(n)
                             } else if let Ok(f) = s.parse::<f64>() {
                                 Value::Float(f)
                             } else {
@@ -1,9 +1,21 @@
 //! 算術演算の共通ヘルパー関数
 //!
-//! オーバーフロー/アンダーフローチェック付きの整数演算を提供します。
+//! オーバーフロー/アンダーフローチェック付きの整数演算と、数値塔の演算を提供します。
+//!
+//! 数値塔は Integer → BigInt → Ratio → Decimal → Float の順に昇格します。
+//! - Integer同士の演算がi64をはみ出すとBigIntに昇格する
+//! - Integer同士の割り切れない除算はRatio（有理数）になる
+//! - Decimalが混ざると10進数で計算し、Floatが混ざると浮動小数点数で計算する
+//! - 結果は正規化する（i64に収まるBigIntはInteger、分母が1のRatioは整数）
 
-use crate::i18n::{fmt_msg, MsgKey};
+use crate::i18n::{fmt_msg, msg, MsgKey};
 use crate::value::Value;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+use std::sync::Arc;
 
 /// 整数加算のオーバーフローチェック付きヘルパー
 ///
@@ -155,3 +167,505 @@ pub fn checked_abs_value(n: i64, op_name: &str) -> Result<Value, String> {
 pub fn checked_neg_value(n: i64, op_name: &str) -> Result<Value, String> {
     checked_int_neg(n, op_name).map(Value::Integer)
 }
+
+// ========================================
+// 数値塔
+// ========================================
+
+/// 算術演算の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+/// 演算用に取り出した数値
+///
+/// 2つの数値を演算するときは `rank` の高い方に揃えてから計算する。
+#[derive(Debug, Clone)]
+pub enum Num {
+    Int(i64),
+    Big(BigInt),
+    Ratio(BigRational),
+    Decimal(Decimal),
+    Float(f64),
+}
+
+impl Num {
+    /// 数値型のValueから取り出す（数値でなければNone）
+    pub fn from_value(value: &Value) -> Option<Num> {
+        match value {
+            Value::Integer(n) => Some(Num::Int(*n)),
+            Value::BigInt(n) => Some(Num::Big((**n).clone())),
+            Value::Ratio(r) => Some(Num::Ratio((**r).clone())),
+            Value::Decimal(d) => Some(Num::Decimal(*d)),
+            Value::Float(f) => Some(Num::Float(*f)),
+            _ => None,
+        }
+    }
+
+    /// 正規化してValueに戻す
+    pub fn into_value(self) -> Value {
+        match self {
+            Num::Int(n) => Value::Integer(n),
+            Num::Big(n) => bigint_value(n),
+            Num::Ratio(r) => ratio_value(r),
+            Num::Decimal(d) => Value::Decimal(d),
+            Num::Float(f) => Value::Float(f),
+        }
+    }
+
+    /// 昇格順位（Integer < BigInt < Ratio < Decimal < Float）
+    fn rank(&self) -> u8 {
+        match self {
+            Num::Int(_) => 0,
+            Num::Big(_) => 1,
+            Num::Ratio(_) => 2,
+            Num::Decimal(_) => 3,
+            Num::Float(_) => 4,
+        }
+    }
+
+    /// 浮動小数点数に変換（精度が落ちる可能性あり）
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Num::Int(n) => *n as f64,
+            Num::Big(n) => n.to_f64().unwrap_or(f64::NAN),
+            Num::Ratio(r) => r.to_f64().unwrap_or(f64::NAN),
+            Num::Decimal(d) => d.to_f64().unwrap_or(f64::NAN),
+            Num::Float(f) => *f,
+        }
+    }
+
+    /// 誤差なく有理数に変換（Floatは対象外）
+    fn to_exact_ratio(&self) -> Option<BigRational> {
+        match self {
+            Num::Int(n) => Some(BigRational::from_integer(BigInt::from(*n))),
+            Num::Big(n) => Some(BigRational::from_integer(n.clone())),
+            Num::Ratio(r) => Some(r.clone()),
+            Num::Decimal(d) => Some(BigRational::new(
+                BigInt::from(d.mantissa()),
+                num_traits::pow(BigInt::from(10), d.scale() as usize),
+            )),
+            Num::Float(_) => None,
+        }
+    }
+
+    /// 10進数に変換（範囲外や割り切れない有理数は28桁に丸める）
+    fn to_decimal(&self, op_name: &str) -> Result<Decimal, String> {
+        let overflow = || fmt_msg(MsgKey::DecimalOverflow, &[op_name]);
+        match self {
+            Num::Int(n) => Ok(Decimal::from(*n)),
+            Num::Big(n) => n
+                .to_i128()
+                .and_then(Decimal::from_i128)
+                .ok_or_else(overflow),
+            Num::Ratio(r) => {
+                let numer = r.numer().to_i128().and_then(Decimal::from_i128);
+                let denom = r.denom().to_i128().and_then(Decimal::from_i128);
+                match (numer, denom) {
+                    (Some(n), Some(d)) => n.checked_div(d).ok_or_else(overflow),
+                    _ => Err(overflow()),
+                }
+            }
+            Num::Decimal(d) => Ok(*d),
+            Num::Float(f) => Decimal::from_f64(*f).ok_or_else(overflow),
+        }
+    }
+
+    /// 順位を `rank` まで引き上げる
+    fn promote(self, rank: u8, op_name: &str) -> Result<Num, String> {
+        if self.rank() >= rank {
+            return Ok(self);
+        }
+        Ok(match rank {
+            1 => match self {
+                Num::Int(n) => Num::Big(BigInt::from(n)),
+                other => other,
+            },
+            2 => match self.to_exact_ratio() {
+                Some(r) => Num::Ratio(r),
+                None => self,
+            },
+            3 => Num::Decimal(self.to_decimal(op_name)?),
+            _ => Num::Float(self.to_f64()),
+        })
+    }
+}
+
+/// BigIntをValueに正規化（i64に収まればInteger）
+pub fn bigint_value(n: BigInt) -> Value {
+    match n.to_i64() {
+        Some(i) => Value::Integer(i),
+        None => Value::BigInt(Arc::new(n)),
+    }
+}
+
+/// 有理数をValueに正規化（分母が1なら整数）
+pub fn ratio_value(r: BigRational) -> Value {
+    if r.is_integer() {
+        bigint_value(r.to_integer())
+    } else {
+        Value::Ratio(Arc::new(r))
+    }
+}
+
+/// 数値型かどうか（Integer/BigInt/Ratio/Decimal/Float）
+#[inline]
+pub fn is_number(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Integer(_)
+            | Value::BigInt(_)
+            | Value::Ratio(_)
+            | Value::Decimal(_)
+            | Value::Float(_)
+    )
+}
+
+/// 数値を浮動小数点数として取り出す（数値でなければNone）
+pub fn number_to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(f) => Some(*f),
+        Value::Integer(n) => Some(*n as f64),
+        _ => Num::from_value(value).map(|n| n.to_f64()),
+    }
+}
+
+/// 10進数をf64で誤差なく表せる場合のみ取り出す（シリアライズ用）
+///
+/// f64に変換して文字列に戻したときに元の値と一致しなければNone
+pub fn decimal_to_exact_f64(d: &Decimal) -> Option<f64> {
+    d.to_f64()
+        .filter(|f| f.to_string().parse::<Decimal>().ok() == Some(*d))
+}
+
+fn type_error(op_name: &str, value: &Value) -> String {
+    fmt_msg(
+        MsgKey::TypeOnlyWithDebug,
+        &[op_name, "numbers", &format!("{:?}", value)],
+    )
+}
+
+/// 2つの数値を同じ順位に揃える
+fn coerce(a: &Value, b: &Value, op_name: &str) -> Result<(Num, Num), String> {
+    let a = Num::from_value(a).ok_or_else(|| type_error(op_name, a))?;
+    let b = Num::from_value(b).ok_or_else(|| type_error(op_name, b))?;
+    let rank = a.rank().max(b.rank());
+    Ok((a.promote(rank, op_name)?, b.promote(rank, op_name)?))
+}
+
+/// 数値塔の二項演算
+///
+/// i64同士の加減乗算は高速パスで計算し、オーバーフロー時のみBigIntに昇格する。
+pub fn arith(op: ArithOp, a: &Value, b: &Value, op_name: &str) -> Result<Value, String> {
+    if let (Value::Integer(x), Value::Integer(y)) = (a, b) {
+        let fast = match op {
+            ArithOp::Add => x.checked_add(*y),
+            ArithOp::Sub => x.checked_sub(*y),
+            ArithOp::Mul => x.checked_mul(*y),
+            ArithOp::Div if *y != 0 && x.checked_rem(*y) == Some(0) => x.checked_div(*y),
+            ArithOp::Rem if *y != 0 => x.checked_rem(*y),
+            ArithOp::Div | ArithOp::Rem => None,
+        };
+        if let Some(n) = fast {
+            return Ok(Value::Integer(n));
+        }
+    }
+
+    // 高速パスで扱えなかったi64同士の演算はBigIntで計算する
+    let (a, b) = match coerce(a, b, op_name)? {
+        (Num::Int(x), Num::Int(y)) => (Num::Big(BigInt::from(x)), Num::Big(BigInt::from(y))),
+        pair => pair,
+    };
+    let division_by_zero = || msg(MsgKey::DivisionByZero).to_string();
+    let decimal_overflow = || fmt_msg(MsgKey::DecimalOverflow, &[op_name]);
+
+    let result = match (a, b) {
+        (Num::Big(x), Num::Big(y)) => match op {
+            ArithOp::Add => Num::Big(x + y),
+            ArithOp::Sub => Num::Big(x - y),
+            ArithOp::Mul => Num::Big(x * y),
+            ArithOp::Div if y.is_zero() => return Err(division_by_zero()),
+            ArithOp::Div => Num::Ratio(BigRational::new(x, y)),
+            ArithOp::Rem if y.is_zero() => return Err(division_by_zero()),
+            ArithOp::Rem => Num::Big(x % y),
+        },
+        (Num::Ratio(x), Num::Ratio(y)) => match op {
+            ArithOp::Add => Num::Ratio(x + y),
+            ArithOp::Sub => Num::Ratio(x - y),
+            ArithOp::Mul => Num::Ratio(x * y),
+            ArithOp::Div | ArithOp::Rem if y.is_zero() => return Err(division_by_zero()),
+            ArithOp::Div => Num::Ratio(x / y),
+            ArithOp::Rem => Num::Ratio(x % y),
+        },
+        (Num::Decimal(x), Num::Decimal(y)) => match op {
+            ArithOp::Div | ArithOp::Rem if y.is_zero() => return Err(division_by_zero()),
+            ArithOp::Add => Num::Decimal(x.checked_add(y).ok_or_else(decimal_overflow)?),
+            ArithOp::Sub => Num::Decimal(x.checked_sub(y).ok_or_else(decimal_overflow)?),
+            ArithOp::Mul => Num::Decimal(x.checked_mul(y).ok_or_else(decimal_overflow)?),
+            ArithOp::Div => Num::Decimal(x.checked_div(y).ok_or_else(decimal_overflow)?),
+            ArithOp::Rem => Num::Decimal(x.checked_rem(y).ok_or_else(decimal_overflow)?),
+        },
+        (Num::Float(x), Num::Float(y)) => match op {
+            ArithOp::Add => Num::Float(x + y),
+            ArithOp::Sub => Num::Float(x - y),
+            ArithOp::Mul => Num::Float(x * y),
+            ArithOp::Div | ArithOp::Rem if y == 0.0 => return Err(division_by_zero()),
+            ArithOp::Div => Num::Float(x / y),
+            ArithOp::Rem => Num::Float(x.rem_euclid(y)),
+        },
+        _ => unreachable!("coerce() returns numbers of the same rank"),
+    };
+
+    Ok(result.into_value())
+}
+
+/// 数値の符号反転
+pub fn negate(value: &Value, op_name: &str) -> Result<Value, String> {
+    match Num::from_value(value).ok_or_else(|| type_error(op_name, value))? {
+        Num::Int(n) => Ok(n
+            .checked_neg()
+            .map(Value::Integer)
+            .unwrap_or_else(|| bigint_value(-BigInt::from(n)))),
+        Num::Big(n) => Ok(bigint_value(-n)),
+        Num::Ratio(r) => Ok(ratio_value(-r)),
+        Num::Decimal(d) => Ok(Value::Decimal(-d)),
+        Num::Float(f) => Ok(Value::Float(-f)),
+    }
+}
+
+/// 数値の絶対値
+pub fn absolute(value: &Value, op_name: &str) -> Result<Value, String> {
+    match Num::from_value(value).ok_or_else(|| type_error(op_name, value))? {
+        Num::Int(n) => Ok(n
+            .checked_abs()
+            .map(Value::Integer)
+            .unwrap_or_else(|| bigint_value(BigInt::from(n).abs()))),
+        Num::Big(n) => Ok(bigint_value(n.abs())),
+        Num::Ratio(r) => Ok(ratio_value(r.abs())),
+        Num::Decimal(d) => Ok(Value::Decimal(d.abs())),
+        Num::Float(f) => Ok(Value::Float(f.abs())),
+    }
+}
+
+/// 数値の大小比較（数値でない場合やNaNを含む場合はNone）
+///
+/// Floatが含まれない限り、Decimalや有理数も誤差なく比較する。
+pub fn compare_numbers(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => return Some(x.cmp(y)),
+        (Value::Float(x), Value::Float(y)) => return x.partial_cmp(y),
+        _ => {}
+    }
+    let (a, b) = (Num::from_value(a)?, Num::from_value(b)?);
+    match (a.to_exact_ratio(), b.to_exact_ratio()) {
+        (Some(x), Some(y)) => Some(x.cmp(&y)),
+        _ => a.to_f64().partial_cmp(&b.to_f64()),
+    }
+}
+
+/// 数値を切り捨てて整数にする（Integer/BigIntを返す）
+pub fn truncate_to_integer(value: &Value, op_name: &str) -> Result<Value, String> {
+    match Num::from_value(value).ok_or_else(|| type_error(op_name, value))? {
+        Num::Int(n) => Ok(Value::Integer(n)),
+        Num::Big(n) => Ok(bigint_value(n)),
+        Num::Ratio(r) => Ok(bigint_value(r.trunc().to_integer())),
+        Num::Decimal(d) => Ok(bigint_value(BigInt::from(
+            d.trunc().to_i128().unwrap_or_default(),
+        ))),
+        Num::Float(f) => {
+            if f.is_nan() || f.is_infinite() {
+                return Err(fmt_msg(MsgKey::FloatIsNanOrInfinity, &[op_name]));
+            }
+            Ok(BigInt::from_f64(f.trunc())
+                .map(bigint_value)
+                .unwrap_or(Value::Integer(f as i64)))
+        }
+    }
+}
+
+/// 数値を10進数に変換（Floatは最短表現の10進数として扱う）
+pub fn to_decimal(value: &Value, op_name: &str) -> Result<Decimal, String> {
+    match value {
+        Value::Float(f) => {
+            if f.is_nan() || f.is_infinite() {
+                return Err(fmt_msg(
+                    MsgKey::CannotConvertToDecimal,
+                    &[op_name, &f.to_string()],
+                ));
+            }
+            // 0.1 → 0.1M のように、浮動小数点数の二進誤差を持ち込まない
+            f.to_string()
+                .parse::<Decimal>()
+                .or_else(|_| Decimal::from_f64(*f).ok_or(()))
+                .map_err(|_| fmt_msg(MsgKey::DecimalOverflow, &[op_name]))
+        }
+        _ => Num::from_value(value)
+            .ok_or_else(|| type_error(op_name, value))?
+            .to_decimal(op_name),
+    }
+}
+
+/// 整数への丸め方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Floor,
+    Ceil,
+    /// 0.5は0から遠い方へ丸める（f64::roundと同じ）
+    Round,
+}
+
+/// BigInt・有理数・10進数を誤差なく整数に丸める
+///
+/// 正確な数値型でない場合はNoneを返す（呼び出し側でf64として処理する）。
+pub fn round_exact(value: &Value, mode: Rounding) -> Option<Value> {
+    match value {
+        Value::BigInt(n) => Some(bigint_value((**n).clone())),
+        Value::Ratio(r) => Some(bigint_value(
+            match mode {
+                Rounding::Floor => r.floor(),
+                Rounding::Ceil => r.ceil(),
+                Rounding::Round => r.round(),
+            }
+            .to_integer(),
+        )),
+        Value::Decimal(d) => {
+            let rounded = match mode {
+                Rounding::Floor => d.floor(),
+                Rounding::Ceil => d.ceil(),
+                Rounding::Round => d.round_dp_with_strategy(
+                    0,
+                    rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+                ),
+            };
+            Some(bigint_value(BigInt::from(
+                rounded.to_i128().unwrap_or_default(),
+            )))
+        }
+        _ => None,
+    }
+}
+
+/// べき乗（指数が非負の整数なら正確な型のまま計算する）
+///
+/// 整数のべき乗がi64を超えた場合は多倍長整数に昇格する。
+/// 指数が負または整数でない場合、底が整数ならFloatで計算する。
+pub fn power(base: &Value, exp: &Value, op_name: &str) -> Result<Value, String> {
+    let base_num = Num::from_value(base).ok_or_else(|| type_error(op_name, base))?;
+    let exp_num = Num::from_value(exp).ok_or_else(|| type_error(op_name, exp))?;
+
+    let exact_exp = match &exp_num {
+        Num::Int(e) if *e >= 0 => u32::try_from(*e).ok(),
+        _ => None,
+    };
+    if let Some(e) = exact_exp {
+        match base_num {
+            Num::Int(b) => {
+                if let Some(result) = b.checked_pow(e) {
+                    return Ok(Value::Integer(result));
+                }
+                return Ok(bigint_value(num_traits::pow(BigInt::from(b), e as usize)));
+            }
+            Num::Big(b) => return Ok(bigint_value(num_traits::pow(b, e as usize))),
+            Num::Ratio(r) => return Ok(ratio_value(num_traits::pow(r, e as usize))),
+            Num::Decimal(d) => {
+                let mut result = Decimal::ONE;
+                for _ in 0..e {
+                    result = result
+                        .checked_mul(d)
+                        .ok_or_else(|| fmt_msg(MsgKey::DecimalOverflow, &[op_name]))?;
+                }
+                return Ok(Value::Decimal(result));
+            }
+            Num::Float(_) => {}
+        }
+    }
+    // 有理数の負の整数乗は逆数のべき乗として正確に計算
+    if let (Num::Ratio(r), Num::Int(e)) = (&base_num, &exp_num) {
+        if *e < 0 && !r.is_zero() {
+            if let Ok(e) = usize::try_from(e.unsigned_abs()) {
+                return Ok(ratio_value(num_traits::pow(r.recip(), e)));
+            }
+        }
+    }
+
+    Ok(Value::Float(base_num.to_f64().powf(exp_num.to_f64())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Value {
+        Value::Decimal(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_integer_overflow_promotes_to_bigint() {
+        let result = arith(
+            ArithOp::Add,
+            &Value::Integer(i64::MAX),
+            &Value::Integer(1),
+            "+",
+        )
+        .unwrap();
+        assert_eq!(result.to_string(), "9223372036854775808");
+        // i64に収まる結果は整数に戻る
+        let back = arith(ArithOp::Sub, &result, &Value::Integer(1), "-").unwrap();
+        assert_eq!(back, Value::Integer(i64::MAX));
+    }
+
+    #[test]
+    fn test_integer_division_yields_ratio() {
+        let third = arith(ArithOp::Div, &Value::Integer(1), &Value::Integer(3), "/").unwrap();
+        assert_eq!(third.to_string(), "1/3");
+        let whole = arith(ArithOp::Mul, &third, &Value::Integer(3), "*").unwrap();
+        assert_eq!(whole, Value::Integer(1));
+        assert!(arith(ArithOp::Div, &Value::Integer(1), &Value::Integer(0), "/").is_err());
+    }
+
+    #[test]
+    fn test_decimal_arithmetic_is_exact() {
+        let sum = arith(ArithOp::Add, &dec("0.1"), &dec("0.2"), "+").unwrap();
+        assert_eq!(sum, dec("0.3"));
+        // 整数・有理数との演算は10進数になり、Floatが混ざるとFloatになる
+        assert_eq!(
+            arith(ArithOp::Mul, &dec("1.10"), &Value::Integer(3), "*").unwrap(),
+            dec("3.30")
+        );
+        assert!(matches!(
+            arith(ArithOp::Add, &dec("0.1"), &Value::Float(0.5), "+").unwrap(),
+            Value::Float(_)
+        ));
+    }
+
+    #[test]
+    fn test_compare_across_types() {
+        let half = ratio_value(BigRational::new(1.into(), 2.into()));
+        assert_eq!(compare_numbers(&half, &dec("0.5")), Some(Ordering::Equal));
+        assert_eq!(
+            compare_numbers(&half, &Value::Integer(1)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare_numbers(&half, &Value::String("x".into())), None);
+    }
+
+    #[test]
+    fn test_power_and_rounding() {
+        let big = power(&Value::Integer(2), &Value::Integer(100), "pow").unwrap();
+        assert_eq!(big.to_string(), "1267650600228229401496703205376");
+        let five_halves = ratio_value(BigRational::new(5.into(), 2.into()));
+        assert_eq!(
+            round_exact(&five_halves, Rounding::Round),
+            Some(Value::Integer(3))
+        );
+        assert_eq!(
+            round_exact(&dec("-2.5"), Rounding::Floor),
+            Some(Value::Integer(-3))
+        );
+    }
+}
@@ -84,6 +84,14 @@ impl PostgresConnection {
             Value::Bool(b) => Box::new(*b),
             Value::Integer(i) => Box::new(*i), // i64のまま送る（PostgreSQL BIGINT型）
             Value::Float(f) => Box::new(*f),
+            Value::Decimal(d) => Box::new(*d), // NUMERIC型として誤差なく送信
+            Value::BigInt(n) => match n.to_string().parse::<rust_decimal::Decimal>() {
+                Ok(d) => Box::new(d),
+                Err(_) => Box::new(n.to_string()),
+            },
+            Value::Ratio(_) => {
+                Box::new(crate::builtins::numeric_helpers::number_to_f64(value).unwrap_or(f64::NAN))
+            }
             Value::String(s) => Box::new(s.clone()),
             Value::Bytes(b) => Box::new(b.as_ref().to_vec()), // BYTEA型として送信
             _ => Box::new(value.to_string()),
@@ -110,6 +118,9 @@ impl PostgresConnection {
                 v.map(Value::Integer).unwrap_or(Value::Nil)
             } else if let Ok(v) = row.try_get::<_, Option<f64>>(idx) {
                 v.map(Value::Float).unwrap_or(Value::Nil)
+            } else if let Ok(v) = row.try_get::<_, Option<rust_decimal::Decimal>>(idx) {
+                // NUMERIC型 → Decimal（誤差なく読み込む）
+                v.map(Value::Decimal).unwrap_or(Value::Nil)
             } else if let Ok(v) = row.try_get::<_, Option<bool>>(idx) {
                 v.map(Value::Bool).unwrap_or(Value::Nil)
             } else {
@@ -109,6 +109,7 @@ pub fn native_redis_mset(args: &[Value]) -> Result<Value, String> {
                         crate::value::MapKey::String(s) => s.trim_matches('"').to_string(),
                         crate::value::MapKey::Symbol(sym) => sym.to_string(),
                         crate::value::MapKey::Integer(i) => i.to_string(),
+                        crate::value::MapKey::Char(c) => c.to_string(),
                     };
                     Ok((clean_key, s.clone()))
                 }
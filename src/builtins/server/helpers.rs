@@ -8,7 +8,7 @@ use flate2::read::GzDecoder;
 use futures_util::StreamExt;
 use http_body_util::{combinators::BoxBody, BodyExt, Full, StreamBody};
 use hyper::body::{Bytes, Frame};
-use hyper::{Request, Response};
+use hyper::Response;
 use std::convert::Infallible;
 use std::io::Read;
 use tokio::fs::File as TokioFile;
@@ -88,72 +88,21 @@ pub(super) fn parse_query_params(query_str: &str) -> HashMap<MapKey, Value> {
         .collect()
 }
 
-/// HTTPリクエストをQi値に変換
-pub(super) async fn request_to_value(
-    req: Request<hyper::body::Incoming>,
-) -> Result<(Value, String), String> {
-    // ⚠️ SECURITY: 最大ボディサイズを10MBに制限（DoS攻撃防止）
-    const MAX_BODY_SIZE: usize = 10 * 1024 * 1024; // 10MB
-
-    let (parts, body) = req.into_parts();
-
-    // ⚠️ SECURITY: ストリーミング中にサイズ制限を適用（DoS防止）
-    // body.collect()は全データをメモリに読み込むため、サイズ制限前に実行すると
-    // 攻撃者が大量のデータを送信してメモリを使い果たすことができる
-    use http_body_util::BodyExt;
-    use http_body_util::Limited;
-
-    let limited_body = Limited::new(body, MAX_BODY_SIZE);
-    let collected = limited_body.collect().await.map_err(|e| {
-        let err_str = e.to_string();
-        if err_str.contains("length limit exceeded") {
-            fmt_msg(
-                MsgKey::ServerBodyTooLarge,
-                &[&MAX_BODY_SIZE.to_string(), &MAX_BODY_SIZE.to_string()],
-            )
-        } else {
-            fmt_msg(MsgKey::ServerFailedToReadBody, &[&err_str])
-        }
-    })?;
-    let body_bytes = collected.to_bytes();
-
-    // Content-Encodingヘッダーをチェックして解凍
-    let decompressed_bytes = if let Some(encoding) = parts.headers.get("content-encoding") {
-        if let Ok(encoding_str) = encoding.to_str() {
-            if encoding_str.to_lowercase() == "gzip" {
-                // gzip解凍
-                let decompressed = decompress_gzip(&body_bytes).map_err(|e| {
-                    fmt_msg(MsgKey::ServerFailedToDecompressGzip, &[&e.to_string()])
-                })?;
-
-                // ⚠️ SECURITY: 解凍後のサイズもチェック（zip bomb攻撃防止）
-                if decompressed.len() > MAX_BODY_SIZE {
-                    return Err(fmt_msg(
-                        MsgKey::ServerBodyTooLarge,
-                        &[&decompressed.len().to_string(), &MAX_BODY_SIZE.to_string()],
-                    ));
-                }
-
-                decompressed
-            } else {
-                body_bytes.to_vec()
-            }
-        } else {
-            body_bytes.to_vec()
-        }
-    } else {
-        body_bytes.to_vec()
-    };
-
-    // UTF-8として解釈を試み、成功すれば文字列、失敗すればBytesとして返す
-    let (body_value, body_str) = match std::str::from_utf8(&decompressed_bytes) {
-        Ok(text) => (Value::String(text.to_string()), text.to_string()),
-        Err(_) => (
-            Value::Bytes(std::sync::Arc::from(decompressed_bytes.as_slice())),
-            String::new(), // バイナリの場合は空文字列（後方互換性）
-        ),
-    };
+/// リクエストボディ読み込みエラー
+pub(super) enum BodyReadError {
+    /// サイズ上限超過（413 Payload Too Large）
+    TooLarge(String),
+    /// その他の読み込み失敗
+    Failed(String),
+}
 
+/// HTTPリクエストのヘッダー部分（メソッド・パス・クエリ・ヘッダー）をQi値に変換
+///
+/// ボディはルーティング後にルートごとのサイズ上限で読み込むため含まない
+pub(super) fn request_head_to_value(
+    parts: &hyper::http::request::Parts,
+    remote_addr: Option<std::net::SocketAddr>,
+) -> HashMap<MapKey, Value> {
     // メソッド
     let method = parts.method.as_str().to_lowercase();
 
@@ -183,9 +132,84 @@ pub(super) async fn request_to_value(
     req_map.insert(kw("query"), Value::String(query));
     req_map.insert(kw("query-params"), Value::Map(query_params));
     req_map.insert(kw("headers"), Value::Map(headers));
-    req_map.insert(kw("body"), body_value);
+    if let Some(addr) = remote_addr {
+        req_map.insert(kw("remote-addr"), Value::String(addr.ip().to_string()));
+    }
 
-    Ok((Value::Map(req_map), body_str))
+    req_map
+}
+
+/// リクエストボディを読み込んでQi値に変換
+///
+/// UTF-8として解釈できれば文字列、できなければBytesを返す
+pub(super) async fn read_body<B>(
+    parts: &hyper::http::request::Parts,
+    body: B,
+    max_body_size: usize,
+) -> Result<Value, BodyReadError>
+where
+    B: hyper::body::Body<Data = Bytes>,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    // ⚠️ SECURITY: ストリーミング中にサイズ制限を適用（DoS防止）
+    // body.collect()は全データをメモリに読み込むため、サイズ制限前に実行すると
+    // 攻撃者が大量のデータを送信してメモリを使い果たすことができる
+    use http_body_util::Limited;
+
+    let limited_body = Limited::new(body, max_body_size);
+    let collected = limited_body.collect().await.map_err(|e| {
+        let err_str = e.to_string();
+        if err_str.contains("length limit exceeded") {
+            // 上限で読み込みを打ち切るため、実サイズはContent-Lengthがあればそれを使う
+            let size = parts
+                .headers
+                .get("content-length")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!(">{}", max_body_size));
+            BodyReadError::TooLarge(fmt_msg(
+                MsgKey::ServerBodyTooLarge,
+                &[&size, &max_body_size.to_string()],
+            ))
+        } else {
+            BodyReadError::Failed(fmt_msg(MsgKey::ServerFailedToReadBody, &[&err_str]))
+        }
+    })?;
+    let body_bytes = collected.to_bytes();
+
+    // Content-Encodingヘッダーをチェックして解凍
+    let is_gzip = parts
+        .headers
+        .get("content-encoding")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("gzip"));
+
+    let decompressed_bytes = if is_gzip {
+        let decompressed = decompress_gzip(&body_bytes).map_err(|e| {
+            BodyReadError::Failed(fmt_msg(
+                MsgKey::ServerFailedToDecompressGzip,
+                &[&e.to_string()],
+            ))
+        })?;
+
+        // ⚠️ SECURITY: 解凍後のサイズもチェック（zip bomb攻撃防止）
+        if decompressed.len() > max_body_size {
+            return Err(BodyReadError::TooLarge(fmt_msg(
+                MsgKey::ServerBodyTooLarge,
+                &[&decompressed.len().to_string(), &max_body_size.to_string()],
+            )));
+        }
+
+        decompressed
+    } else {
+        body_bytes.to_vec()
+    };
+
+    // UTF-8として解釈を試み、成功すれば文字列、失敗すればBytesとして返す
+    Ok(match String::from_utf8(decompressed_bytes) {
+        Ok(text) => Value::String(text),
+        Err(e) => Value::Bytes(std::sync::Arc::from(e.into_bytes().as_slice())),
+    })
 }
 
 /// ファイルをストリーミングでレスポンスボディに変換
@@ -262,6 +286,10 @@ pub(super) async fn value_to_response(
                                 key_owned = i.to_string();
                                 &key_owned
                             }
+                            MapKey::Char(c) => {
+                                key_owned = c.to_string();
+                                &key_owned
+                            }
                         };
                         response = response.header(key_str, val.as_str());
                     }
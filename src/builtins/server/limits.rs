@@ -0,0 +1,359 @@
+//! 流量制御ミドルウェア（レート制限・タイムアウト・ボディサイズ制限）
+//!
+//! - rate-limit: トークンバケット（メモリ）または固定ウィンドウ（kvs/*）
+//! - timeout: ルート単位のタイムアウト（超過時は503）
+//! - body-limit: ルート単位のボディ上限（超過時は413）
+
+use super::response::status_response;
+use super::routing::apply_middleware;
+use crate::builtins::util::kw;
+use crate::eval::Evaluator;
+use crate::value::{MapKey, Value};
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// メモリ上のバケット数がこれを超えたら古いバケットを掃除する
+const BUCKET_PRUNE_THRESHOLD: usize = 10_000;
+
+/// タイムアウト付きハンドラーを同時に実行できるスレッド数の上限
+const MAX_TIMEOUT_WORKERS: usize = 256;
+
+/// 実行中のタイムアウト付きハンドラーのスレッド数（タイムアウト後も完了まで数える）
+static TIMEOUT_WORKERS: AtomicUsize = AtomicUsize::new(0);
+
+/// タイムアウト付きハンドラー用スレッドの枠（Drop時に返却）
+struct WorkerSlot;
+
+impl WorkerSlot {
+    /// 空きがあれば枠を確保（上限に達していればNone）
+    fn acquire() -> Option<Self> {
+        TIMEOUT_WORKERS
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < MAX_TIMEOUT_WORKERS).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| WorkerSlot)
+    }
+}
+
+impl Drop for WorkerSlot {
+    fn drop(&mut self) {
+        TIMEOUT_WORKERS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// トークンバケット
+struct Bucket {
+    /// 残りトークン数
+    tokens: f64,
+    /// 最終補充時刻
+    last: Instant,
+    /// 満タンに戻るまでの時間（これだけ使われなければ削除しても結果が変わらない）
+    idle: Duration,
+}
+
+/// メモリストアのバケット（キー: (リミッター名, クライアントキー)）
+static BUCKETS: LazyLock<DashMap<(String, String), Bucket>> = LazyLock::new(DashMap::new);
+
+/// レート制限の判定結果
+struct Decision {
+    allowed: bool,
+    limit: i64,
+    remaining: i64,
+    /// 次にリクエスト可能になるまでの秒数
+    retry_after: u64,
+}
+
+/// ミドルウェアマップから設定値を取得
+fn get<'a>(m: &'a crate::HashMap<MapKey, Value>, name: &str) -> Option<&'a Value> {
+    m.get(&MapKey::String(name.to_string()))
+}
+
+fn get_int(m: &crate::HashMap<MapKey, Value>, name: &str) -> Option<i64> {
+    match get(m, name) {
+        Some(Value::Integer(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+/// レート制限ミドルウェアを適用
+pub(super) fn apply_rate_limit(
+    m: &crate::HashMap<MapKey, Value>,
+    inner: &Value,
+    req: &Value,
+    eval: &Evaluator,
+) -> Result<Value, String> {
+    let name = match get(m, "__name__") {
+        Some(Value::String(s)) => s.clone(),
+        _ => String::new(),
+    };
+    let limit = get_int(m, "__limit__").unwrap_or(60).max(1);
+    let window = get_int(m, "__window__").unwrap_or(60).max(1);
+    let burst = get_int(m, "__burst__").unwrap_or(limit).max(1);
+
+    // クライアントキー（:key関数の戻り値、なければリモートアドレス）
+    let key = match get(m, "__key__") {
+        Some(f) => match eval.apply_function(f, std::slice::from_ref(req))? {
+            Value::String(s) => s,
+            Value::Nil => remote_addr(req),
+            v => v.to_string(),
+        },
+        None => remote_addr(req),
+    };
+
+    // :storeはkvs-redis有効時のみ設定できる（server/with-rate-limitで検査済み）
+    let decision = match get(m, "__store__") {
+        #[cfg(feature = "kvs-redis")]
+        Some(conn) => check_kvs(conn, &name, &key, limit, window),
+        _ => check_memory(&name, &key, limit, window, burst),
+    };
+
+    let mut headers = vec![
+        ("X-RateLimit-Limit", decision.limit.to_string()),
+        ("X-RateLimit-Remaining", decision.remaining.to_string()),
+    ];
+
+    if !decision.allowed {
+        headers.push(("Retry-After", decision.retry_after.to_string()));
+        return Ok(status_response(429, "Too Many Requests", &headers));
+    }
+
+    let response = apply_middleware(inner, req, eval)?;
+    Ok(add_headers(response, &headers))
+}
+
+/// リクエストからリモートアドレスを取得（取得できなければ"unknown"）
+fn remote_addr(req: &Value) -> String {
+    match req {
+        Value::Map(r) => match r.get(&kw("remote-addr")) {
+            Some(Value::String(s)) => s.clone(),
+            _ => "unknown".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+/// メモリ上のトークンバケットで判定
+///
+/// windowごとにlimit個のペースで補充し、最大burst個まで貯められる。
+/// X-RateLimit-*はkvsの固定ウィンドウと揃えてlimitを上限として報告する
+fn check_memory(name: &str, key: &str, limit: i64, window: i64, burst: i64) -> Decision {
+    let rate = limit as f64 / window as f64;
+    let capacity = burst as f64;
+    let now = Instant::now();
+
+    if BUCKETS.len() > BUCKET_PRUNE_THRESHOLD {
+        prune_idle_buckets(now);
+    }
+
+    let mut bucket = BUCKETS
+        .entry((name.to_string(), key.to_string()))
+        .or_insert(Bucket {
+            tokens: capacity,
+            last: now,
+            idle: Duration::from_secs_f64(capacity / rate),
+        });
+
+    let elapsed = now.duration_since(bucket.last).as_secs_f64();
+    bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+    bucket.last = now;
+
+    if bucket.tokens >= 1.0 {
+        bucket.tokens -= 1.0;
+        Decision {
+            allowed: true,
+            limit,
+            remaining: (bucket.tokens.floor() as i64).min(limit),
+            retry_after: 0,
+        }
+    } else {
+        Decision {
+            allowed: false,
+            limit,
+            remaining: 0,
+            retry_after: ((1.0 - bucket.tokens) / rate).ceil().max(1.0) as u64,
+        }
+    }
+}
+
+/// 満タンに戻るまでの時間が経過したバケットを削除
+///
+/// リミッターごとにウィンドウが異なるため、各バケット自身のidleで判定する
+fn prune_idle_buckets(now: Instant) {
+    BUCKETS.retain(|_, b| now.duration_since(b.last) < b.idle);
+}
+
+/// kvs/*の固定ウィンドウカウンターで判定（複数プロセス間で共有できる）
+///
+/// KVSエラー時はリクエストを通す（fail-open）
+#[cfg(feature = "kvs-redis")]
+fn check_kvs(conn: &Value, name: &str, key: &str, limit: i64, window: i64) -> Decision {
+    use crate::builtins::kvs::{native_expire, native_incr};
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let slot = now / window;
+    let retry_after = (window - now % window).max(1) as u64;
+    let redis_key = format!("qi:ratelimit:{}:{}:{}", name, key, slot);
+
+    let count = match native_incr(&[conn.clone(), Value::String(redis_key.clone())]) {
+        Ok(Value::Integer(n)) => n,
+        _ => {
+            return Decision {
+                allowed: true,
+                limit,
+                remaining: limit,
+                retry_after: 0,
+            }
+        }
+    };
+
+    if count == 1 {
+        let _ = native_expire(&[
+            conn.clone(),
+            Value::String(redis_key),
+            Value::Integer(window),
+        ]);
+    }
+
+    Decision {
+        allowed: count <= limit,
+        limit,
+        remaining: (limit - count).max(0),
+        retry_after,
+    }
+}
+
+/// タイムアウトミドルウェアを適用
+///
+/// 内部ハンドラーを別スレッドで実行し、時間内に終わらなければ503を返す。
+/// Qiの評価は中断できないため、タイムアウトしたハンドラーはバックグラウンドで完了まで実行される。
+/// スレッドは完了まで枠（MAX_TIMEOUT_WORKERS）を占有し、枠が埋まっていれば実行せずに503を返す。
+pub(super) fn apply_timeout(
+    m: &crate::HashMap<MapKey, Value>,
+    inner: &Value,
+    req: &Value,
+    eval: &Evaluator,
+) -> Result<Value, String> {
+    let millis = get_int(m, "__timeout_ms__").unwrap_or(30_000).max(1) as u64;
+
+    let Some(slot) = WorkerSlot::acquire() else {
+        return Ok(status_response(
+            503,
+            "Service Unavailable",
+            &[("Retry-After", "1".to_string())],
+        ));
+    };
+
+    let (tx, rx) = crossbeam_channel::bounded(1);
+    let inner = inner.clone();
+    let req = req.clone();
+    let eval = eval.clone();
+    std::thread::spawn(move || {
+        let _slot = slot;
+        let _ = tx.send(apply_middleware(&inner, &req, &eval));
+    });
+
+    match rx.recv_timeout(Duration::from_millis(millis)) {
+        Ok(result) => result,
+        // ハンドラー側の遅延なので408（クライアントの送信が遅い）ではなく503
+        Err(_) => Ok(status_response(503, "Service Unavailable", &[])),
+    }
+}
+
+/// ボディサイズ制限ミドルウェアを適用
+///
+/// サーバーは読み込み時点でこの上限を使うが、ミドルウェア単体で呼ばれた場合に備えて再検査する
+pub(super) fn apply_body_limit(
+    m: &crate::HashMap<MapKey, Value>,
+    inner: &Value,
+    req: &Value,
+    eval: &Evaluator,
+) -> Result<Value, String> {
+    let max = get_int(m, "__max_body_size__").unwrap_or(i64::MAX);
+    let size = match req {
+        Value::Map(r) => match r.get(&kw("body")) {
+            Some(Value::String(s)) => s.len(),
+            Some(Value::Bytes(b)) => b.len(),
+            _ => 0,
+        },
+        _ => 0,
+    };
+
+    if size as i64 > max {
+        return Ok(status_response(413, "Payload Too Large", &[]));
+    }
+    apply_middleware(inner, req, eval)
+}
+
+/// レスポンスマップにヘッダーを追加（マップ以外はそのまま返す）
+fn add_headers(response: Value, extra: &[(&str, String)]) -> Value {
+    let Value::Map(mut resp) = response else {
+        return response;
+    };
+    let mut headers = match resp.get(&kw("headers")) {
+        Some(Value::Map(h)) => h.clone(),
+        _ => crate::new_hashmap(),
+    };
+    for (name, value) in extra {
+        headers.insert(
+            MapKey::String(name.to_string()),
+            Value::String(value.clone()),
+        );
+    }
+    resp.insert(kw("headers"), Value::Map(headers));
+    Value::Map(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_allows_burst_then_denies() {
+        let name = "test-burst";
+        for i in 0..3 {
+            let d = check_memory(name, "1.2.3.4", 1, 60, 3);
+            assert!(d.allowed);
+            // 報告する上限はburstではなくlimit（kvsストアと同じ）
+            assert_eq!(d.limit, 1);
+            assert_eq!(d.remaining, (2 - i).min(1));
+        }
+        let d = check_memory(name, "1.2.3.4", 1, 60, 3);
+        assert!(!d.allowed);
+        assert_eq!(d.remaining, 0);
+        assert!(d.retry_after >= 1 && d.retry_after <= 60);
+    }
+
+    #[test]
+    fn test_token_bucket_keys_are_independent() {
+        let name = "test-keys";
+        assert!(check_memory(name, "a", 1, 60, 1).allowed);
+        assert!(!check_memory(name, "a", 1, 60, 1).allowed);
+        assert!(check_memory(name, "b", 1, 60, 1).allowed);
+        assert!(check_memory("test-keys-other", "a", 1, 60, 1).allowed);
+    }
+
+    #[test]
+    fn test_prune_uses_each_buckets_window() {
+        // 短いウィンドウのバケットだけが期限切れ（長いウィンドウのバケットは消えない）
+        let past = Instant::now() - Duration::from_secs(2);
+        for (name, idle) in [("test-prune-short", 1), ("test-prune-long", 3600)] {
+            BUCKETS.insert(
+                (name.to_string(), "k".to_string()),
+                Bucket {
+                    tokens: 0.0,
+                    last: past,
+                    idle: Duration::from_secs(idle),
+                },
+            );
+        }
+        prune_idle_buckets(Instant::now());
+        assert!(!BUCKETS.contains_key(&("test-prune-short".to_string(), "k".to_string())));
+        assert!(!check_memory("test-prune-long", "k", 1, 3600, 1).allowed);
+    }
+}
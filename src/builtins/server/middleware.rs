@@ -480,3 +480,174 @@ pub fn native_server_with_cache_control(args: &[Value]) -> Result<Value, String>
 
     Ok(Value::Map(metadata))
 }
+
+// ========================================
+// 流量制御ミドルウェア
+// ========================================
+
+/// 名前未指定のレートリミッターに割り当てるIDカウンター
+static RATE_LIMITER_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// server/with-rate-limit - レート制限ミドルウェア
+/// クライアントごとにトークンバケットでリクエスト数を制限し、超過したら429（Retry-After付き）を返す
+/// オプション: {:limit 60 :window 60 :burst 60 :key (fn [req] ...) :store kvs-conn :name "api"}
+pub fn native_server_with_rate_limit(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err(fmt_msg(MsgKey::Need1Arg, &["server/with-rate-limit"]));
+    }
+
+    let handler = args[0].clone();
+
+    let opts = if args.len() > 1 {
+        match &args[1] {
+            Value::Map(m) => m.clone(),
+            _ => {
+                return Err(fmt_msg(
+                    MsgKey::SecondArgMustBe,
+                    &["server/with-rate-limit", "a map"],
+                ))
+            }
+        }
+    } else {
+        crate::new_hashmap()
+    };
+
+    // 数値オプションは正の整数のみ受け付ける
+    let positive = |name: &str, default: i64| -> Result<i64, String> {
+        match opts.get(&kw(name)) {
+            None => Ok(default),
+            Some(Value::Integer(n)) if *n > 0 => Ok(*n),
+            Some(_) => Err(fmt_msg(
+                MsgKey::MustBePositiveInteger,
+                &["server/with-rate-limit", &format!(":{}", name)],
+            )),
+        }
+    };
+    let limit = positive("limit", 60)?;
+    let window = positive("window", 60)?;
+    let burst = positive("burst", limit)?;
+
+    // kvs-redisなしでは:storeを使えない（黙ってメモリにフォールバックしない）
+    #[cfg(not(feature = "kvs-redis"))]
+    if opts.contains_key(&kw("store")) {
+        return Err(fmt_msg(MsgKey::RedisSupportNotEnabled, &[]));
+    }
+
+    // 名前が同じリミッター同士はバケットを共有する
+    let name = match opts.get(&kw("name")) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Keyword(k)) => k.to_string(),
+        _ => format!(
+            "limiter:{}",
+            RATE_LIMITER_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        ),
+    };
+
+    let mut metadata = crate::new_hashmap();
+    metadata.insert(
+        crate::value::MapKey::String("__middleware__".to_string()),
+        Value::String("rate-limit".to_string()),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__handler__".to_string()),
+        handler,
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__name__".to_string()),
+        Value::String(name),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__limit__".to_string()),
+        Value::Integer(limit),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__window__".to_string()),
+        Value::Integer(window),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__burst__".to_string()),
+        Value::Integer(burst),
+    );
+    if let Some(key_fn) = opts.get(&kw("key")) {
+        metadata.insert(
+            crate::value::MapKey::String("__key__".to_string()),
+            key_fn.clone(),
+        );
+    }
+    if let Some(store) = opts.get(&kw("store")) {
+        metadata.insert(
+            crate::value::MapKey::String("__store__".to_string()),
+            store.clone(),
+        );
+    }
+
+    Ok(Value::Map(metadata))
+}
+
+/// server/with-timeout - ルート単位のタイムアウトミドルウェア
+/// 指定ミリ秒以内にハンドラーが終わらなければ503を返す
+pub fn native_server_with_timeout(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(fmt_msg(MsgKey::Need2Args, &["server/with-timeout"]));
+    }
+
+    let millis = match &args[1] {
+        Value::Integer(n) if *n > 0 => *n,
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::MustBePositiveInteger,
+                &["server/with-timeout", "timeout (ms)"],
+            ))
+        }
+    };
+
+    let mut metadata = crate::new_hashmap();
+    metadata.insert(
+        crate::value::MapKey::String("__middleware__".to_string()),
+        Value::String("timeout".to_string()),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__handler__".to_string()),
+        args[0].clone(),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__timeout_ms__".to_string()),
+        Value::Integer(millis),
+    );
+
+    Ok(Value::Map(metadata))
+}
+
+/// server/with-body-limit - ルート単位のボディサイズ制限ミドルウェア
+/// サーバー全体の:max-body-sizeの代わりに指定バイト数を上限とし、超過したら413を返す
+pub fn native_server_with_body_limit(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(fmt_msg(MsgKey::Need2Args, &["server/with-body-limit"]));
+    }
+
+    let max_size = match &args[1] {
+        Value::Integer(n) if *n > 0 => *n,
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::MustBePositiveInteger,
+                &["server/with-body-limit", "max size (bytes)"],
+            ))
+        }
+    };
+
+    let mut metadata = crate::new_hashmap();
+    metadata.insert(
+        crate::value::MapKey::String("__middleware__".to_string()),
+        Value::String("body-limit".to_string()),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__handler__".to_string()),
+        args[0].clone(),
+    );
+    metadata.insert(
+        crate::value::MapKey::String("__max_body_size__".to_string()),
+        Value::Integer(max_size),
+    );
+
+    Ok(Value::Map(metadata))
+}
@@ -1,11 +1,14 @@
 //! サーバーモジュール
 //!
 //! HTTPサーバー機能（Flow-Oriented）:
-//! - serve: サーバー起動（ルーター対応）
+//! - serve/stop/port: サーバー起動（ルーター対応）・停止・ポート取得
 //! - ok/json/not-found/no-content: レスポンスヘルパー
-//! - router: ルーティング定義
+//! - router/group/url-for: ルーティング定義（トライ木）・ルートグループ・URL逆引き
 //! - with-logging/with-cors/with-json-body: ミドルウェア
+//! - with-rate-limit/with-timeout/with-body-limit: 流量制御ミドルウェア
+//! - with-openapi: OpenAPI定義によるリクエスト検証
 //! - static-file/static-dir: 静的ファイル配信
+//! - test-request: インプロセステストクライアント
 //!
 //! このモジュールは `http-server` feature でコンパイルされます。
 
@@ -22,23 +25,37 @@ pub const DEFAULT_HTTP_HOST: &str = "127.0.0.1";
 /// デフォルトHTTPサーバータイムアウト（秒）
 pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
+/// デフォルトのシャットダウン時の処理中リクエスト待機時間（秒）
+pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+
+/// デフォルトのリクエストボディ上限（バイト）
+///
+/// ⚠️ SECURITY: DoS攻撃防止のため、ルートごとに引き上げる場合も上限を設けること
+pub const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024; // 10MB
+
 // ========================================
 // サブモジュール
 // ========================================
 
 mod helpers;
+mod limits;
 mod middleware;
+mod openapi;
 mod response;
+mod route_trie;
 mod routing;
 mod serve;
 mod static_files;
+mod test_client;
 
 // 公開エクスポート
 pub use middleware::*;
+pub use openapi::native_server_with_openapi;
 pub use response::*;
-pub use routing::native_server_router;
-pub use serve::native_server_serve;
+pub use routing::{native_server_group, native_server_router, native_server_url_for};
+pub use serve::{native_server_port, native_server_serve, native_server_stop};
 pub use static_files::{native_server_static_dir, native_server_static_file};
+pub use test_client::native_server_test_request;
 
 // ========================================
 // 関数登録テーブル
@@ -46,10 +63,14 @@ pub use static_files::{native_server_static_dir, native_server_static_file};
 
 /// 登録すべき関数のリスト（Evaluator不要な関数のみ）
 /// @qi-doc:category server
-/// @qi-doc:functions serve, router, ok, json, response, not-found, no-content, with-logging, with-cors, with-json-body, static-file, static-dir
+/// @qi-doc:functions serve, stop, port, router, group, url-for, ok, json, response, not-found, no-content, with-logging, with-cors, with-json-body, with-rate-limit, with-timeout, with-body-limit, with-openapi, static-file, static-dir, test-request
 pub const FUNCTIONS: super::NativeFunctions = &[
     ("server/serve", native_server_serve),
+    ("server/stop", native_server_stop),
+    ("server/port", native_server_port),
     ("server/router", native_server_router),
+    ("server/group", native_server_group),
+    ("server/url-for", native_server_url_for),
     ("server/ok", native_server_ok),
     ("server/json", native_server_json),
     ("server/response", native_server_response),
@@ -66,6 +87,11 @@ pub const FUNCTIONS: super::NativeFunctions = &[
         "server/with-cache-control",
         native_server_with_cache_control,
     ),
+    ("server/with-rate-limit", native_server_with_rate_limit),
+    ("server/with-timeout", native_server_with_timeout),
+    ("server/with-body-limit", native_server_with_body_limit),
+    ("server/with-openapi", native_server_with_openapi),
     ("server/static-file", native_server_static_file),
     ("server/static-dir", native_server_static_dir),
+    ("server/test-request", native_server_test_request),
 ];
@@ -0,0 +1,601 @@
+//! OpenAPIミドルウェア（server/with-openapi）
+//!
+//! OpenAPI 3のオペレーション定義（parameters / requestBody / responses）で
+//! リクエストを検証する。スキーマの各制約は `validate` のスキーマに変換して検査し、
+//! エラーはすべて集めて400で返す。開発時は `:check-responses` でレスポンスも検査できる。
+
+use super::response::native_server_json;
+use super::routing::apply_middleware;
+use crate::builtins::json::json_to_value;
+use crate::builtins::util::kw;
+use crate::builtins::validation::native_validate;
+use crate::eval::Evaluator;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Value};
+
+/// server/with-openapi - OpenAPIのオペレーション定義でリクエストを検証するミドルウェア
+///
+/// 引数:
+/// - handler: ハンドラー
+/// - operation: OpenAPIのオペレーション定義（register-apiに渡すspecと同じ形）
+/// - opts: オプション（省略可）
+///   - :check-responses - trueならJSONレスポンスもresponsesのスキーマで検査（開発用）
+///
+/// 検証に通るとリクエストに :json（パース済みボディ）を追加してハンドラーを呼ぶ。
+/// 失敗時は400と {"error" "Bad Request" "errors" [{"in" "name" "code" "message"} ...]} を返す。
+pub fn native_server_with_openapi(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["server/with-openapi"]));
+    }
+
+    if !matches!(args[1], Value::Map(_)) {
+        return Err(fmt_msg(
+            MsgKey::SecondArgMustBe,
+            &["server/with-openapi", "a map"],
+        ));
+    }
+
+    let check_responses = match args.get(2) {
+        Some(Value::Map(opts)) => {
+            matches!(opts.get(&kw("check-responses")), Some(Value::Bool(true)))
+        }
+        Some(Value::Nil) | None => false,
+        Some(_) => {
+            return Err(fmt_msg(
+                MsgKey::ThirdArgMustBe,
+                &["server/with-openapi", "a map"],
+            ))
+        }
+    };
+
+    let mut metadata = crate::new_hashmap();
+    metadata.insert(
+        MapKey::String("__middleware__".to_string()),
+        Value::String("openapi".to_string()),
+    );
+    metadata.insert(MapKey::String("__handler__".to_string()), args[0].clone());
+    metadata.insert(MapKey::String("__operation__".to_string()), args[1].clone());
+    metadata.insert(
+        MapKey::String("__check_responses__".to_string()),
+        Value::Bool(check_responses),
+    );
+
+    Ok(Value::Map(metadata))
+}
+
+/// OpenAPIミドルウェアを適用
+pub(super) fn apply_openapi(
+    m: &crate::HashMap<MapKey, Value>,
+    inner: &Value,
+    req: &Value,
+    eval: &Evaluator,
+) -> Result<Value, String> {
+    let (Some(Value::Map(op)), Value::Map(req_map)) =
+        (m.get(&MapKey::String("__operation__".to_string())), req)
+    else {
+        return apply_middleware(inner, req, eval);
+    };
+
+    let mut errors = Vec::new();
+    check_parameters(op, req_map, &mut errors)?;
+    let json = check_request_body(op, req_map, &mut errors)?;
+
+    if !errors.is_empty() {
+        return error_json(400, "Bad Request", errors);
+    }
+
+    let req = match json {
+        Some(json) => {
+            let mut new_req = req_map.clone();
+            new_req.insert(kw("json"), json);
+            Value::Map(new_req)
+        }
+        None => req.clone(),
+    };
+
+    let response = apply_middleware(inner, &req, eval)?;
+
+    if matches!(
+        m.get(&MapKey::String("__check_responses__".to_string())),
+        Some(Value::Bool(true))
+    ) {
+        let errors = check_response(op, &response)?;
+        if !errors.is_empty() {
+            let method = match req_map.get(&kw("method")) {
+                Some(Value::Keyword(k)) => k.to_uppercase(),
+                _ => String::new(),
+            };
+            let path = match req_map.get(&kw("path")) {
+                Some(Value::String(p)) => p.clone(),
+                _ => String::new(),
+            };
+            let summary: Vec<String> = errors.iter().map(describe_error).collect();
+            eprintln!(
+                "{}",
+                fmt_msg(
+                    MsgKey::ServerOpenApiResponseMismatch,
+                    &[&method, &path, &summary.join("; ")],
+                )
+            );
+            return error_json(500, "Response does not match the OpenAPI schema", errors);
+        }
+    }
+
+    Ok(response)
+}
+
+/// parameters（path / query / header）を検証
+fn check_parameters(
+    op: &crate::HashMap<MapKey, Value>,
+    req: &crate::HashMap<MapKey, Value>,
+    errors: &mut Vec<Value>,
+) -> Result<(), String> {
+    let Some(Value::Vector(params) | Value::List(params)) = field(op, "parameters") else {
+        return Ok(());
+    };
+
+    for param in params.iter() {
+        let Value::Map(param) = param else { continue };
+        let (Some(Value::String(name)), Some(location)) =
+            (field(param, "name"), field(param, "in").and_then(as_str))
+        else {
+            continue;
+        };
+
+        let raw = match location {
+            "path" => lookup(
+                req,
+                "params",
+                MapKey::Keyword(crate::intern::intern_keyword(name)),
+            ),
+            "query" => lookup(req, "query-params", MapKey::String(name.clone())),
+            "header" => lookup(req, "headers", MapKey::String(name.to_lowercase())),
+            _ => continue,
+        };
+
+        let required =
+            location == "path" || matches!(field(param, "required"), Some(Value::Bool(true)));
+        let schema = field(param, "schema");
+
+        match raw {
+            None | Some(Value::Nil) => {
+                if required {
+                    errors.push(error_entry(
+                        location,
+                        name,
+                        "required",
+                        fmt_msg(MsgKey::ValidateRequired, &[]),
+                    ));
+                }
+            }
+            Some(value) => {
+                if let Some(schema) = schema {
+                    let value = coerce(schema, value);
+                    check_schema(schema, &value, location, name, errors)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// requestBody（application/json）を検証し、パース済みのJSONを返す
+fn check_request_body(
+    op: &crate::HashMap<MapKey, Value>,
+    req: &crate::HashMap<MapKey, Value>,
+    errors: &mut Vec<Value>,
+) -> Result<Option<Value>, String> {
+    let Some(Value::Map(body_spec)) = field(op, "requestBody") else {
+        return Ok(None);
+    };
+    let Some(Value::Map(media)) = field(body_spec, "content")
+        .and_then(as_map)
+        .and_then(|c| field(c, "application/json"))
+    else {
+        return Ok(None);
+    };
+
+    let required = matches!(field(body_spec, "required"), Some(Value::Bool(true)));
+    let body = match req.get(&kw("body")) {
+        Some(Value::String(s)) if !s.trim().is_empty() => s.clone(),
+        Some(Value::Bytes(_)) => {
+            errors.push(error_entry(
+                "body",
+                "",
+                "invalid-json",
+                fmt_msg(MsgKey::ValidateInvalidJson, &["not UTF-8"]),
+            ));
+            return Ok(None);
+        }
+        _ => {
+            if required {
+                errors.push(error_entry(
+                    "body",
+                    "",
+                    "required",
+                    fmt_msg(MsgKey::ValidateRequired, &[]),
+                ));
+            }
+            return Ok(None);
+        }
+    };
+
+    let json = match serde_json::from_str::<serde_json::Value>(&body) {
+        Ok(json) => json_to_value(json),
+        Err(e) => {
+            errors.push(error_entry(
+                "body",
+                "",
+                "invalid-json",
+                fmt_msg(MsgKey::ValidateInvalidJson, &[&e.to_string()]),
+            ));
+            return Ok(None);
+        }
+    };
+
+    if let Some(schema) = field(media, "schema") {
+        check_schema(schema, &json, "body", "", errors)?;
+    }
+
+    Ok(Some(json))
+}
+
+/// レスポンスをresponsesのスキーマで検証（JSONボディのみ）
+fn check_response(
+    op: &crate::HashMap<MapKey, Value>,
+    response: &Value,
+) -> Result<Vec<Value>, String> {
+    let mut errors = Vec::new();
+    let Value::Map(resp) = response else {
+        return Ok(errors);
+    };
+    let Some(Value::Map(responses)) = field(op, "responses") else {
+        return Ok(errors);
+    };
+
+    let status = match resp.get(&kw("status")) {
+        Some(Value::Integer(s)) => *s,
+        _ => 200,
+    };
+    let spec = responses
+        .get(&MapKey::Integer(status))
+        .or_else(|| field(responses, &status.to_string()))
+        .or_else(|| field(responses, "default"));
+    let Some(Value::Map(spec)) = spec else {
+        return Ok(errors);
+    };
+    let Some(schema) = field(spec, "content")
+        .and_then(as_map)
+        .and_then(|c| field(c, "application/json"))
+        .and_then(as_map)
+        .and_then(|media| field(media, "schema"))
+    else {
+        return Ok(errors);
+    };
+
+    let Some(Value::String(body)) = resp.get(&kw("body")) else {
+        return Ok(errors);
+    };
+    let json = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(json) => json_to_value(json),
+        Err(e) => {
+            errors.push(error_entry(
+                "response",
+                "",
+                "invalid-json",
+                fmt_msg(MsgKey::ValidateInvalidJson, &[&e.to_string()]),
+            ));
+            return Ok(errors);
+        }
+    };
+
+    check_schema(schema, &json, "response", "", &mut errors)?;
+    Ok(errors)
+}
+
+/// OpenAPIスキーマで値を検証し、エラーをすべて集める
+///
+/// 各ノードの制約はvalidateで検査し、properties / itemsは再帰的にたどる
+fn check_schema(
+    schema: &Value,
+    value: &Value,
+    location: &str,
+    name: &str,
+    errors: &mut Vec<Value>,
+) -> Result<(), String> {
+    let Value::Map(schema) = schema else {
+        return Ok(());
+    };
+    if matches!(value, Value::Nil) {
+        return Ok(());
+    }
+
+    if let Value::Map(result) = native_validate(&[to_validate_schema(schema), value.clone()])? {
+        if let Some(Value::Map(err)) = result.get(&kw("error")) {
+            let code = match err.get(&kw("code")) {
+                Some(Value::String(c)) => c.as_str(),
+                _ => "invalid",
+            };
+            let message = match err.get(&kw("message")) {
+                Some(Value::String(msg)) => msg.clone(),
+                _ => String::new(),
+            };
+            errors.push(error_entry(location, name, code, message));
+            return Ok(());
+        }
+    }
+
+    match value {
+        Value::Map(data) => {
+            if let Some(Value::Vector(required) | Value::List(required)) = field(schema, "required")
+            {
+                for key in required.iter().filter_map(as_str) {
+                    if matches!(field(data, key), None | Some(Value::Nil)) {
+                        errors.push(error_entry(
+                            location,
+                            &join_path(name, key),
+                            "required",
+                            fmt_msg(MsgKey::ValidateRequired, &[]),
+                        ));
+                    }
+                }
+            }
+            if let Some(Value::Map(props)) = field(schema, "properties") {
+                for (key, prop_schema) in props.iter() {
+                    let key = key_name(key);
+                    if let Some(prop) = field(data, &key) {
+                        check_schema(prop_schema, prop, location, &join_path(name, &key), errors)?;
+                    }
+                }
+            }
+        }
+        Value::Vector(items) | Value::List(items) => {
+            if let Some(item_schema) = field(schema, "items") {
+                for (i, item) in items.iter().enumerate() {
+                    check_schema(
+                        item_schema,
+                        item,
+                        location,
+                        &format!("{}[{}]", name, i),
+                        errors,
+                    )?;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// OpenAPIスキーマの1ノード分の制約をvalidateのスキーマに変換
+fn to_validate_schema(schema: &crate::HashMap<MapKey, Value>) -> Value {
+    let mut out = crate::new_hashmap();
+
+    if let Some(ty) = field(schema, "type").and_then(as_str) {
+        let ty = match ty {
+            "object" => "map",
+            "array" => "vector",
+            other => other,
+        };
+        if matches!(
+            ty,
+            "string" | "integer" | "number" | "boolean" | "map" | "vector"
+        ) {
+            out.insert(kw("type"), Value::String(ty.to_string()));
+        }
+    }
+
+    for (from, to) in [
+        ("enum", "enum"),
+        ("minLength", "min-length"),
+        ("maxLength", "max-length"),
+        ("pattern", "pattern"),
+        ("minimum", "min"),
+        ("maximum", "max"),
+        ("minItems", "min-items"),
+        ("maxItems", "max-items"),
+    ] {
+        if let Some(v) = field(schema, from) {
+            out.insert(kw(to), v.clone());
+        }
+    }
+
+    Value::Map(out)
+}
+
+/// パラメータ文字列をスキーマの型に変換（変換できなければそのまま返し、型エラーにする）
+fn coerce(schema: &Value, value: &Value) -> Value {
+    let Value::Map(schema) = schema else {
+        return value.clone();
+    };
+    let ty = field(schema, "type").and_then(as_str).unwrap_or("");
+
+    match (ty, value) {
+        ("array", Value::String(s)) => {
+            let items: Vec<Value> = s
+                .split(',')
+                .map(|part| Value::String(part.to_string()))
+                .collect();
+            coerce(&Value::Map(schema.clone()), &Value::Vector(items.into()))
+        }
+        ("array", Value::Vector(items)) => match field(schema, "items") {
+            Some(item_schema) => {
+                Value::Vector(items.iter().map(|v| coerce(item_schema, v)).collect())
+            }
+            None => value.clone(),
+        },
+        ("integer", Value::String(s)) => s
+            .parse::<i64>()
+            .map(Value::Integer)
+            .unwrap_or_else(|_| value.clone()),
+        ("number", Value::String(s)) => match s.parse::<i64>() {
+            Ok(n) => Value::Integer(n),
+            Err(_) => s
+                .parse::<f64>()
+                .map(Value::Float)
+                .unwrap_or_else(|_| value.clone()),
+        },
+        ("boolean", Value::String(s)) => match s.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+/// 400/500のJSONエラーレスポンスを生成
+fn error_json(status: i64, message: &str, errors: Vec<Value>) -> Result<Value, String> {
+    let mut body = crate::new_hashmap();
+    body.insert(kw("error"), Value::String(message.to_string()));
+    body.insert(kw("errors"), Value::Vector(errors.into()));
+
+    let mut opts = crate::new_hashmap();
+    opts.insert(kw("status"), Value::Integer(status));
+    native_server_json(&[Value::Map(body), Value::Map(opts)])
+}
+
+/// エラー項目 {:in :name :code :message} を生成
+fn error_entry(location: &str, name: &str, code: &str, message: String) -> Value {
+    let mut entry = crate::new_hashmap();
+    entry.insert(kw("in"), Value::String(location.to_string()));
+    entry.insert(kw("name"), Value::String(name.to_string()));
+    entry.insert(kw("code"), Value::String(code.to_string()));
+    entry.insert(kw("message"), Value::String(message));
+    Value::Map(entry)
+}
+
+/// ログ用にエラー項目を1行にまとめる
+fn describe_error(error: &Value) -> String {
+    let Value::Map(e) = error else {
+        return error.to_string();
+    };
+    let get = |k: &str| match e.get(&kw(k)) {
+        Some(Value::String(s)) => s.clone(),
+        _ => String::new(),
+    };
+    format!("{} {}: {}", get("in"), get("name"), get("message"))
+}
+
+/// OpenAPI定義のフィールドを取得（キーワードキー・文字列キーの両方に対応）
+fn field<'a>(m: &'a crate::HashMap<MapKey, Value>, name: &str) -> Option<&'a Value> {
+    m.get(&kw(name))
+        .or_else(|| m.get(&MapKey::String(name.to_string())))
+}
+
+/// リクエストのサブマップ（:params / :query-params / :headers）から値を取得
+fn lookup<'a>(
+    req: &'a crate::HashMap<MapKey, Value>,
+    section: &str,
+    key: MapKey,
+) -> Option<&'a Value> {
+    match req.get(&kw(section)) {
+        Some(Value::Map(m)) => m.get(&key),
+        _ => None,
+    }
+}
+
+fn as_str(v: &Value) -> Option<&str> {
+    match v {
+        Value::String(s) => Some(s.as_str()),
+        Value::Keyword(k) => Some(k.as_ref()),
+        _ => None,
+    }
+}
+
+fn as_map(v: &Value) -> Option<&crate::HashMap<MapKey, Value>> {
+    match v {
+        Value::Map(m) => Some(m),
+        _ => None,
+    }
+}
+
+fn key_name(key: &MapKey) -> String {
+    match key {
+        MapKey::Keyword(s) | MapKey::Symbol(s) => s.to_string(),
+        MapKey::String(s) => s.clone(),
+        MapKey::Integer(i) => i.to_string(),
+        MapKey::Char(c) => c.to_string(),
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(entries: Vec<(&str, Value)>) -> crate::HashMap<MapKey, Value> {
+        entries.into_iter().map(|(k, v)| (kw(k), v)).collect()
+    }
+
+    fn s(v: &str) -> Value {
+        Value::String(v.to_string())
+    }
+
+    #[test]
+    fn test_check_schema_collects_nested_errors() {
+        let schema = Value::Map(map(vec![
+            ("type", s("object")),
+            ("required", Value::Vector(vec![s("name"), s("age")].into())),
+            (
+                "properties",
+                Value::Map(map(vec![
+                    (
+                        "name",
+                        Value::Map(map(vec![
+                            ("type", s("string")),
+                            ("minLength", Value::Integer(2)),
+                        ])),
+                    ),
+                    (
+                        "tags",
+                        Value::Map(map(vec![
+                            ("type", s("array")),
+                            ("items", Value::Map(map(vec![("type", s("string"))]))),
+                        ])),
+                    ),
+                ])),
+            ),
+        ]));
+        let data = Value::Map(map(vec![
+            ("name", s("a")),
+            (
+                "tags",
+                Value::Vector(vec![s("ok"), Value::Integer(1)].into()),
+            ),
+        ]));
+
+        let mut errors = Vec::new();
+        check_schema(&schema, &data, "body", "", &mut errors).unwrap();
+        let described: Vec<String> = errors.iter().map(describe_error).collect();
+        assert_eq!(errors.len(), 3, "{:?}", described);
+        assert!(described.iter().any(|e| e.starts_with("body age:")));
+        assert!(described.iter().any(|e| e.starts_with("body name:")));
+        assert!(described.iter().any(|e| e.starts_with("body tags[1]:")));
+    }
+
+    #[test]
+    fn test_coerce_parameter_strings() {
+        let int = Value::Map(map(vec![("type", s("integer"))]));
+        assert_eq!(coerce(&int, &s("42")), Value::Integer(42));
+        assert_eq!(coerce(&int, &s("x")), s("x"));
+
+        let arr = Value::Map(map(vec![("type", s("array")), ("items", int.clone())]));
+        assert_eq!(
+            coerce(&arr, &s("1,2")),
+            Value::Vector(vec![Value::Integer(1), Value::Integer(2)].into())
+        );
+
+        let boolean = Value::Map(map(vec![("type", s("boolean"))]));
+        assert_eq!(coerce(&boolean, &s("true")), Value::Bool(true));
+    }
+}
@@ -141,6 +141,54 @@ pub fn native_server_not_found(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Map(resp))
 }
 
+/// 405 Method Not Allowedレスポンスを作成（ルーター内部用）
+///
+/// allowed: Allowヘッダーに列挙するメソッド（大文字）
+pub(super) fn method_not_allowed(allowed: &[String]) -> Result<Value, String> {
+    let mut resp = crate::new_hashmap();
+    resp.insert(kw("status"), Value::Integer(405));
+    resp.insert(kw("body"), Value::String("Method Not Allowed".to_string()));
+
+    let mut headers = crate::new_hashmap();
+    headers.insert(
+        crate::value::MapKey::String("Content-Type".to_string()),
+        Value::String("text/plain; charset=utf-8".to_string()),
+    );
+    headers.insert(
+        crate::value::MapKey::String("Allow".to_string()),
+        Value::String(allowed.join(", ")),
+    );
+    resp.insert(kw("headers"), Value::Map(headers));
+
+    Ok(Value::Map(resp))
+}
+
+/// ステータスとテキスト本文だけのレスポンスを作成（ミドルウェアの拒否応答用）
+pub(super) fn status_response(
+    status: i64,
+    message: &str,
+    extra_headers: &[(&str, String)],
+) -> Value {
+    let mut resp = crate::new_hashmap();
+    resp.insert(kw("status"), Value::Integer(status));
+    resp.insert(kw("body"), Value::String(message.to_string()));
+
+    let mut headers = crate::new_hashmap();
+    headers.insert(
+        crate::value::MapKey::String("Content-Type".to_string()),
+        Value::String("text/plain; charset=utf-8".to_string()),
+    );
+    for (name, value) in extra_headers {
+        headers.insert(
+            crate::value::MapKey::String(name.to_string()),
+            Value::String(value.clone()),
+        );
+    }
+    resp.insert(kw("headers"), Value::Map(headers));
+
+    Value::Map(resp)
+}
+
 /// server/no-content - 204 No Contentレスポンスを作成
 pub fn native_server_no_content(_args: &[Value]) -> Result<Value, String> {
     let mut resp = crate::new_hashmap();
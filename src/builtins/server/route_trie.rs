@@ -0,0 +1,615 @@
+//! ルーティング用トライ木
+//!
+//! `server/router` に渡されたルート定義をパスセグメント単位のトライ木にコンパイルし、
+//! リクエストごとの線形探索を避ける。
+//!
+//! サポートするセグメント:
+//! - 固定セグメント: `/users`
+//! - パラメータ: `/users/:id`
+//! - 制約付きパラメータ: `/users/:id<int>`（int, float, uuid, alpha, alnum, slug, または正規表現）
+//! - キャッチオール: `/files/*path`（最後のセグメントのみ）
+//!
+//! マッチングの優先順位は 固定 > パラメータ（定義順）> キャッチオール > 静的ディレクトリ。
+
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Value};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// パラメータ制約
+#[derive(Debug, Clone)]
+pub(super) enum ParamConstraint {
+    Int,
+    Float,
+    Uuid,
+    Alpha,
+    Alnum,
+    Slug,
+    Regex(Regex),
+}
+
+impl ParamConstraint {
+    fn parse(spec: &str) -> Result<Self, String> {
+        Ok(match spec {
+            "int" => ParamConstraint::Int,
+            "float" => ParamConstraint::Float,
+            "uuid" => ParamConstraint::Uuid,
+            "alpha" => ParamConstraint::Alpha,
+            "alnum" => ParamConstraint::Alnum,
+            "slug" => ParamConstraint::Slug,
+            _ => {
+                // 既知の型名以外は正規表現として扱う（セグメント全体にアンカー）
+                let re = Regex::new(&format!("^(?:{})$", spec)).map_err(|e| e.to_string())?;
+                ParamConstraint::Regex(re)
+            }
+        })
+    }
+
+    /// 制約を検証し、型変換済みの値を返す
+    fn convert(&self, raw: &str) -> Option<Value> {
+        match self {
+            ParamConstraint::Int => raw.parse::<i64>().ok().map(Value::Integer),
+            ParamConstraint::Float => raw
+                .parse::<f64>()
+                .ok()
+                .filter(|f| f.is_finite())
+                .map(Value::Float),
+            ParamConstraint::Uuid => is_uuid(raw).then(|| Value::String(raw.to_string())),
+            ParamConstraint::Alpha => (!raw.is_empty() && raw.chars().all(|c| c.is_alphabetic()))
+                .then(|| Value::String(raw.to_string())),
+            ParamConstraint::Alnum => (!raw.is_empty() && raw.chars().all(|c| c.is_alphanumeric()))
+                .then(|| Value::String(raw.to_string())),
+            ParamConstraint::Slug => (!raw.is_empty()
+                && raw
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .then(|| Value::String(raw.to_string())),
+            ParamConstraint::Regex(re) => re.is_match(raw).then(|| Value::String(raw.to_string())),
+        }
+    }
+}
+
+/// UUID形式（8-4-4-4-12の16進数）かチェック
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    let lens = [8, 4, 4, 4, 12];
+    groups.len() == lens.len()
+        && groups
+            .iter()
+            .zip(lens.iter())
+            .all(|(g, len)| g.len() == *len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// パースされたパスセグメント
+#[derive(Debug, Clone)]
+enum Segment {
+    Static(String),
+    Param {
+        name: Arc<str>,
+        constraint: Option<Arc<ParamConstraint>>,
+        spec: Option<String>,
+    },
+    CatchAll(Option<Arc<str>>),
+}
+
+/// パスパターンをセグメントに分解
+fn parse_pattern(pattern: &str) -> Result<Vec<Segment>, String> {
+    let parts: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let mut segments = Vec::with_capacity(parts.len());
+
+    for (i, part) in parts.iter().enumerate() {
+        if let Some(rest) = part.strip_prefix('*') {
+            if i != parts.len() - 1 {
+                return Err(fmt_msg(
+                    MsgKey::ServerInvalidRoute,
+                    &[pattern, "catch-all segment must be last"],
+                ));
+            }
+            let name = (!rest.is_empty()).then(|| crate::intern::intern_symbol(rest));
+            segments.push(Segment::CatchAll(name));
+        } else if let Some(rest) = part
+            .strip_prefix(':')
+            .or_else(|| part.strip_prefix('{').and_then(|p| p.strip_suffix('}')))
+        {
+            // `:id` とOpenAPI形式の `{id}` を同じパラメータとして扱う
+            let (name, spec) = match rest.split_once('<') {
+                Some((name, spec)) => {
+                    let spec = spec.strip_suffix('>').ok_or_else(|| {
+                        fmt_msg(MsgKey::ServerInvalidRouteConstraint, &[pattern, rest])
+                    })?;
+                    (name, Some(spec.to_string()))
+                }
+                None => (rest, None),
+            };
+            if name.is_empty() {
+                return Err(fmt_msg(
+                    MsgKey::ServerInvalidRoute,
+                    &[pattern, "parameter name is empty"],
+                ));
+            }
+            let constraint = match &spec {
+                Some(s) => Some(Arc::new(ParamConstraint::parse(s).map_err(|e| {
+                    fmt_msg(MsgKey::ServerInvalidRouteConstraint, &[pattern, &e])
+                })?)),
+                None => None,
+            };
+            segments.push(Segment::Param {
+                name: crate::intern::intern_symbol(name),
+                constraint,
+                spec,
+            });
+        } else {
+            segments.push(Segment::Static(part.to_string()));
+        }
+    }
+
+    Ok(segments)
+}
+
+/// 1つのルートパターンに登録されたハンドラー群
+#[derive(Debug, Clone, Default)]
+struct RouteEntry {
+    /// メソッド名（小文字） → ハンドラー
+    handlers: HashMap<Arc<str>, Value>,
+}
+
+impl RouteEntry {
+    /// メソッドに対応するハンドラーを取得（HEADはGETにフォールバック）
+    fn handler_for(&self, method: &str) -> Option<&Value> {
+        self.handlers.get(method).or_else(|| {
+            if method == "head" {
+                self.handlers.get("get")
+            } else {
+                None
+            }
+        })
+    }
+
+    fn collect_allowed(&self, allowed: &mut BTreeSet<String>) {
+        for method in self.handlers.keys() {
+            allowed.insert(method.to_uppercase());
+            if method.as_ref() == "get" {
+                allowed.insert("HEAD".to_string());
+            }
+        }
+    }
+}
+
+/// パラメータセグメントの子ノード
+#[derive(Debug)]
+struct ParamNode {
+    name: Arc<str>,
+    spec: Option<String>,
+    constraint: Option<Arc<ParamConstraint>>,
+    node: RouteNode,
+}
+
+/// トライ木のノード
+#[derive(Debug, Default)]
+struct RouteNode {
+    statics: HashMap<String, RouteNode>,
+    params: Vec<ParamNode>,
+    catch_all: Option<(Option<Arc<str>>, RouteEntry)>,
+    /// static-dirハンドラー（このノード以下すべてにプレフィックスマッチ）
+    prefix: Option<RouteEntry>,
+    entry: Option<RouteEntry>,
+}
+
+/// マッチ結果
+pub(super) enum RouteMatch<'a> {
+    Found {
+        handler: &'a Value,
+        params: Vec<(Arc<str>, Value)>,
+    },
+    MethodNotAllowed(Vec<String>),
+    NotFound,
+}
+
+/// 名前付きルート（URL逆引き用）
+#[derive(Debug, Clone)]
+struct NamedRoute {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+/// コンパイル済みルーター
+#[derive(Debug, Default)]
+pub(super) struct RouteTrie {
+    root: RouteNode,
+    named: HashMap<String, NamedRoute>,
+}
+
+impl RouteTrie {
+    /// ルートを追加
+    ///
+    /// `is_static_dir` が真の場合はプレフィックスマッチとして登録する
+    pub(super) fn insert(
+        &mut self,
+        pattern: &str,
+        method: Arc<str>,
+        handler: Value,
+        is_static_dir: bool,
+    ) -> Result<(), String> {
+        let segments = parse_pattern(pattern)?;
+        let mut node = &mut self.root;
+
+        for seg in &segments {
+            match seg {
+                Segment::Static(s) => {
+                    node = node.statics.entry(s.clone()).or_default();
+                }
+                Segment::Param {
+                    name,
+                    constraint,
+                    spec,
+                } => {
+                    // 同じ名前・同じ制約のパラメータノードは共有する
+                    let pos = node
+                        .params
+                        .iter()
+                        .position(|p| &p.name == name && &p.spec == spec);
+                    let idx = match pos {
+                        Some(idx) => idx,
+                        None => {
+                            node.params.push(ParamNode {
+                                name: name.clone(),
+                                spec: spec.clone(),
+                                constraint: constraint.clone(),
+                                node: RouteNode::default(),
+                            });
+                            node.params.len() - 1
+                        }
+                    };
+                    node = &mut node.params[idx].node;
+                }
+                Segment::CatchAll(name) => {
+                    // parse_patternで最後のセグメントであることを検証済み
+                    let (_, entry) = node
+                        .catch_all
+                        .get_or_insert_with(|| (name.clone(), RouteEntry::default()));
+                    entry.handlers.insert(method, handler);
+                    return Ok(());
+                }
+            }
+        }
+
+        let entry = if is_static_dir {
+            node.prefix.get_or_insert_with(RouteEntry::default)
+        } else {
+            node.entry.get_or_insert_with(RouteEntry::default)
+        };
+        entry.handlers.insert(method, handler);
+        Ok(())
+    }
+
+    /// 名前付きルートを登録
+    pub(super) fn add_name(&mut self, name: &str, pattern: &str) -> Result<(), String> {
+        if self.named.contains_key(name) {
+            return Err(fmt_msg(MsgKey::ServerDuplicateRouteName, &[name]));
+        }
+        let segments = parse_pattern(pattern)?;
+        self.named.insert(
+            name.to_string(),
+            NamedRoute {
+                pattern: pattern.to_string(),
+                segments,
+            },
+        );
+        Ok(())
+    }
+
+    /// パスとメソッドからハンドラーを検索
+    pub(super) fn lookup<'a>(&'a self, method: &str, path: &str) -> RouteMatch<'a> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut params = Vec::new();
+        let mut allowed = BTreeSet::new();
+
+        match find(&self.root, &segments, method, &mut params, &mut allowed) {
+            Some(handler) => RouteMatch::Found { handler, params },
+            None if !allowed.is_empty() => {
+                RouteMatch::MethodNotAllowed(allowed.into_iter().collect())
+            }
+            None => RouteMatch::NotFound,
+        }
+    }
+
+    /// 名前付きルートからURLを生成
+    ///
+    /// パスに使われなかったパラメータはクエリ文字列として付加する
+    pub(super) fn url_for(
+        &self,
+        name: &str,
+        params: &crate::HashMap<MapKey, Value>,
+    ) -> Result<String, String> {
+        let route = self
+            .named
+            .get(name)
+            .ok_or_else(|| fmt_msg(MsgKey::ServerRouteNotFound, &[name]))?;
+
+        let lookup = |key: &str| -> Option<&Value> {
+            params
+                .get(&MapKey::Keyword(crate::intern::intern_keyword(key)))
+                .or_else(|| params.get(&MapKey::String(key.to_string())))
+        };
+        let mut used: BTreeSet<&str> = BTreeSet::new();
+        let mut path = String::new();
+
+        for seg in &route.segments {
+            path.push('/');
+            match seg {
+                Segment::Static(s) => path.push_str(s),
+                Segment::Param {
+                    name: pname,
+                    constraint,
+                    spec,
+                } => {
+                    let value = lookup(pname).ok_or_else(|| {
+                        fmt_msg(MsgKey::ServerMissingRouteParam, &[pname, &route.pattern])
+                    })?;
+                    let raw = param_to_string(value);
+                    if let Some(c) = constraint {
+                        if c.convert(&raw).is_none() {
+                            return Err(fmt_msg(
+                                MsgKey::ServerRouteParamMismatch,
+                                &[pname, spec.as_deref().unwrap_or(""), &raw],
+                            ));
+                        }
+                    }
+                    path.push_str(&urlencoding::encode(&raw));
+                    used.insert(pname);
+                }
+                Segment::CatchAll(Some(pname)) => {
+                    let raw = lookup(pname).map(param_to_string).unwrap_or_default();
+                    let encoded: Vec<String> = raw
+                        .split('/')
+                        .filter(|s| !s.is_empty())
+                        .map(|s| urlencoding::encode(s).into_owned())
+                        .collect();
+                    path.push_str(&encoded.join("/"));
+                    used.insert(pname);
+                }
+                Segment::CatchAll(None) => {}
+            }
+        }
+
+        if path.is_empty() {
+            path.push('/');
+        }
+
+        // 残りのパラメータはクエリ文字列へ（キー順で安定化）
+        let mut query: Vec<(String, String)> = params
+            .iter()
+            .filter_map(|(k, v)| {
+                let key = match k {
+                    MapKey::Keyword(s) | MapKey::Symbol(s) => s.to_string(),
+                    MapKey::String(s) => s.clone(),
+                    MapKey::Integer(i) => i.to_string(),
+                    MapKey::Char(c) => c.to_string(),
+                };
+                (!used.contains(key.as_str())).then(|| (key, param_to_string(v)))
+            })
+            .collect();
+        query.sort();
+
+        if !query.is_empty() {
+            let qs: Vec<String> = query
+                .iter()
+                .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+                .collect();
+            path.push('?');
+            path.push_str(&qs.join("&"));
+        }
+
+        Ok(path)
+    }
+}
+
+/// URL生成用にパラメータ値を文字列化
+fn param_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Keyword(k) => k.to_string(),
+        v => format!("{}", v),
+    }
+}
+
+/// トライ木を再帰的に探索（マッチしなかった分岐はバックトラック）
+fn find<'a>(
+    node: &'a RouteNode,
+    segments: &[&str],
+    method: &str,
+    params: &mut Vec<(Arc<str>, Value)>,
+    allowed: &mut BTreeSet<String>,
+) -> Option<&'a Value> {
+    match segments.split_first() {
+        None => {
+            if let Some(entry) = &node.entry {
+                if let Some(h) = entry.handler_for(method) {
+                    return Some(h);
+                }
+                entry.collect_allowed(allowed);
+            }
+        }
+        Some((head, rest)) => {
+            // 1. 固定セグメント
+            if let Some(child) = node.statics.get(*head) {
+                if let Some(h) = find(child, rest, method, params, allowed) {
+                    return Some(h);
+                }
+            }
+
+            // 2. パラメータ（定義順）
+            if !node.params.is_empty() {
+                // percent-encodingをデコード（例: %E3%81%82 → あ）
+                let decoded = urlencoding::decode(head)
+                    .unwrap_or(std::borrow::Cow::Borrowed(head))
+                    .to_string();
+                for param in &node.params {
+                    let value = match &param.constraint {
+                        Some(c) => match c.convert(&decoded) {
+                            Some(v) => v,
+                            None => continue,
+                        },
+                        None => Value::String(decoded.clone()),
+                    };
+                    params.push((param.name.clone(), value));
+                    if let Some(h) = find(&param.node, rest, method, params, allowed) {
+                        return Some(h);
+                    }
+                    params.pop();
+                }
+            }
+        }
+    }
+
+    // 3. キャッチオール（残り0個以上のセグメント）
+    if let Some((name, entry)) = &node.catch_all {
+        if let Some(h) = entry.handler_for(method) {
+            if let Some(name) = name {
+                let decoded: Vec<String> = segments
+                    .iter()
+                    .map(|s| {
+                        urlencoding::decode(s)
+                            .unwrap_or(std::borrow::Cow::Borrowed(s))
+                            .to_string()
+                    })
+                    .collect();
+                params.push((name.clone(), Value::String(decoded.join("/"))));
+            }
+            return Some(h);
+        }
+        entry.collect_allowed(allowed);
+    }
+
+    // 4. 静的ディレクトリ（プレフィックスマッチ）
+    if let Some(entry) = &node.prefix {
+        if let Some(h) = entry.handler_for(method) {
+            return Some(h);
+        }
+        entry.collect_allowed(allowed);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h(name: &str) -> Value {
+        Value::String(name.to_string())
+    }
+
+    fn trie(routes: &[(&str, &str, &str)]) -> RouteTrie {
+        let mut t = RouteTrie::default();
+        for (pattern, method, name) in routes {
+            t.insert(pattern, Arc::from(*method), h(name), false)
+                .unwrap();
+        }
+        t
+    }
+
+    fn found(t: &RouteTrie, method: &str, path: &str) -> Option<(Value, Vec<(Arc<str>, Value)>)> {
+        match t.lookup(method, path) {
+            RouteMatch::Found { handler, params } => Some((handler.clone(), params)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_static_beats_param() {
+        let t = trie(&[("/users/:id", "get", "show"), ("/users/new", "get", "new")]);
+        assert_eq!(found(&t, "get", "/users/new").unwrap().0, h("new"));
+        let (handler, params) = found(&t, "get", "/users/42").unwrap();
+        assert_eq!(handler, h("show"));
+        assert_eq!(params[0].1, h("42"));
+    }
+
+    #[test]
+    fn test_typed_params() {
+        let t = trie(&[
+            ("/items/:id<int>", "get", "by-id"),
+            ("/items/:slug", "get", "by-slug"),
+        ]);
+        let (handler, params) = found(&t, "get", "/items/7").unwrap();
+        assert_eq!(handler, h("by-id"));
+        assert_eq!(params[0].1, Value::Integer(7));
+        assert_eq!(found(&t, "get", "/items/abc").unwrap().0, h("by-slug"));
+    }
+
+    #[test]
+    fn test_openapi_style_params() {
+        let t = trie(&[
+            ("/posts/{slug}", "get", "post"),
+            ("/posts/:slug", "put", "update"),
+        ]);
+        let (handler, params) = found(&t, "get", "/posts/hello").unwrap();
+        assert_eq!(handler, h("post"));
+        assert_eq!(&*params[0].0, "slug");
+        // 同じ名前のパラメータノードを共有する
+        assert_eq!(found(&t, "put", "/posts/hello").unwrap().0, h("update"));
+    }
+
+    #[test]
+    fn test_catch_all() {
+        let t = trie(&[("/files/*path", "get", "files")]);
+        let (_, params) = found(&t, "get", "/files/a/b%20c.txt").unwrap();
+        assert_eq!(params[0].1, h("a/b c.txt"));
+    }
+
+    #[test]
+    fn test_method_not_allowed() {
+        let t = trie(&[("/users", "get", "list"), ("/users", "post", "create")]);
+        match t.lookup("delete", "/users") {
+            RouteMatch::MethodNotAllowed(allowed) => {
+                assert_eq!(allowed, vec!["GET", "HEAD", "POST"]);
+            }
+            _ => panic!("expected 405"),
+        }
+        assert!(matches!(t.lookup("get", "/nope"), RouteMatch::NotFound));
+        assert_eq!(found(&t, "head", "/users").unwrap().0, h("list"));
+    }
+
+    #[test]
+    fn test_backtracking_across_methods() {
+        let t = trie(&[
+            ("/users/new", "get", "new"),
+            ("/users/:id", "delete", "destroy"),
+        ]);
+        assert_eq!(found(&t, "delete", "/users/new").unwrap().0, h("destroy"));
+    }
+
+    #[test]
+    fn test_url_for() {
+        let mut t = RouteTrie::default();
+        t.add_name("user", "/users/:id<int>/posts/:slug").unwrap();
+        let mut params = crate::new_hashmap();
+        params.insert(MapKey::Keyword(Arc::from("id")), Value::Integer(3));
+        params.insert(MapKey::Keyword(Arc::from("slug")), h("hello world"));
+        params.insert(MapKey::String("page".to_string()), Value::Integer(2));
+        assert_eq!(
+            t.url_for("user", &params).unwrap(),
+            "/users/3/posts/hello%20world?page=2"
+        );
+
+        params.insert(MapKey::Keyword(Arc::from("id")), h("x"));
+        assert!(t.url_for("user", &params).is_err());
+        assert!(t.url_for("missing", &params).is_err());
+        assert!(t.add_name("user", "/other").is_err());
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let mut t = RouteTrie::default();
+        assert!(t
+            .insert("/a/*rest/b", Arc::from("get"), h("x"), false)
+            .is_err());
+        assert!(t
+            .insert("/a/:id<[>", Arc::from("get"), h("x"), false)
+            .is_err());
+        assert!(t
+            .insert("/a/:id<int", Arc::from("get"), h("x"), false)
+            .is_err());
+    }
+}
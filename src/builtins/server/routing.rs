@@ -1,19 +1,22 @@
 //! ルーティング機能
 
+use super::limits::{apply_body_limit, apply_rate_limit, apply_timeout};
 use super::middleware::{
     apply_bearer_middleware, apply_compression_middleware, apply_cors_middleware,
     apply_json_body_middleware, apply_logging_middleware,
 };
-use super::response::native_server_not_found;
+use super::openapi::apply_openapi;
+use super::response::{method_not_allowed, native_server_not_found};
+use super::route_trie::{RouteMatch, RouteTrie};
 use super::static_files::serve_static_file;
 use crate::builtins::util::kw;
 use crate::eval::Evaluator;
 use crate::i18n::{fmt_msg, MsgKey};
-use crate::value::Value;
-use std::sync::LazyLock;
+use crate::value::{Handle, MapKey, Value};
+use std::sync::{Arc, LazyLock};
 
 /// グローバルEvaluatorインスタンス（高速化：リクエストごとにclone）
-static GLOBAL_EVALUATOR: LazyLock<Evaluator> = LazyLock::new(Evaluator::new);
+pub(super) static GLOBAL_EVALUATOR: LazyLock<Evaluator> = LazyLock::new(Evaluator::new);
 
 // HTTPヘッダー定数
 const HEADER_CACHE_CONTROL: &str = "Cache-Control";
@@ -25,124 +28,391 @@ const CACHE_DIRECTIVE_NO_STORE: &str = "no-store";
 const CACHE_DIRECTIVE_MUST_REVALIDATE: &str = "must-revalidate";
 const CACHE_DIRECTIVE_IMMUTABLE: &str = "immutable";
 
+/// マーカーマップのキーを作成
+fn marker(name: &str) -> MapKey {
+    MapKey::String(name.to_string())
+}
+
 /// server/router - HTTPルーターを作成
 ///
 /// 引数:
-/// - routes: ルート定義（[[path {:get handler, :post handler}], ...] の形式）
+/// - routes: ルート定義のベクタ
+///   - `[path {:get handler, :post handler}]`
+///   - `[path {:get handler} {:name :route-name}]`（名前付きルート、server/url-forで使用）
+///   - `(server/group prefix ...)` で作成したルートグループ
 ///
-/// 戻り値: ルーター定義
+/// パスには `:id`（パラメータ）、`:id<int>`（制約付きパラメータ）、
+/// `*path`（キャッチオール）を使用可能。
+///
+/// 戻り値: ルーター（`{"__router__" handle "__routes__" routes}` 形式のマップ、
+/// handleはコンパイル済みトライ木を持つ不透明なハンドル）
 pub fn native_server_router(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["server/router", "1"]));
     }
 
-    // ルート定義をそのまま返す（後でserveで使用）
-    // ルートは [[path {:get handler, :post handler}], ...] の形式
-    Ok(args[0].clone())
+    let routes = match &args[0] {
+        Value::Vector(v) | Value::List(v) => v.clone(),
+        v => {
+            return Err(fmt_msg(
+                MsgKey::FirstArgMustBe,
+                &[
+                    "server/router",
+                    &format!("a vector of routes, got {}", v.type_name()),
+                ],
+            ))
+        }
+    };
+
+    // ルート定義をトライ木にコンパイル（不正な定義はここでエラーにする）
+    let mut trie = RouteTrie::default();
+    compile_routes(&mut trie, "", &routes, &[])?;
+
+    // トライ木はハンドルとして値に直接持たせる（ルーターと一緒に解放される）
+    let handle = Handle::new("router", trie);
+
+    // 元のルート定義も保持（データとして検査可能にする）
+    let mut router = crate::new_hashmap();
+    router.insert(marker("__router__"), Value::Handle(Arc::new(handle)));
+    router.insert(marker("__routes__"), Value::Vector(routes));
+    Ok(Value::Map(router))
 }
 
-/// server/serve - HTTPサーバーを起動
+/// server/group - 共通プレフィックスとミドルウェアを持つルートグループを作成
 ///
-/// ## オプション
+/// 引数:
+/// - prefix: パスプレフィックス（例: "/api/v1"）
+/// - opts: オプション（省略可）
+///   - :middleware - ミドルウェア関数のベクタ（先頭が最も外側）
+/// - routes: ルート定義のベクタ（ネストしたグループも可）
 ///
-/// - `:timeout` - リクエストタイムアウト（秒）
-///   - 最小: 1秒
-///   - 最大: 300秒（5分）
-///   - デフォルト: 30秒
-pub(super) fn route_request(req: &Value, routes: &im::Vector<Value>) -> Result<Value, String> {
-    let method_key = kw("method");
-    let path_key = kw("path");
-
-    let method = match req {
-        Value::Map(m) => match m.get(&method_key) {
-            Some(Value::Keyword(k)) => k.clone(),
-            _ => {
-                return Err(fmt_msg(
-                    MsgKey::RequestMustHave,
-                    &["request", ":method keyword"],
-                ))
-            }
-        },
-        _ => return Err(fmt_msg(MsgKey::RequestMustBe, &["request", "a map"])),
+/// 戻り値: ルートグループ（server/routerのルート定義内で使用）
+pub fn native_server_group(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["server/group"]));
+    }
+
+    let prefix = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::FirstArgMustBe,
+                &["server/group", "a string"],
+            ))
+        }
+    };
+
+    let (opts, routes) = if args.len() == 3 {
+        match &args[1] {
+            Value::Map(m) => (m.clone(), &args[2]),
+            _ => return Err(fmt_msg(MsgKey::SecondArgMustBe, &["server/group", "a map"])),
+        }
+    } else {
+        (crate::new_hashmap(), &args[1])
     };
 
-    let path = match req {
-        Value::Map(m) => match m.get(&path_key) {
-            Some(Value::String(p)) => p.clone(),
+    let routes = match routes {
+        Value::Vector(v) | Value::List(v) => v.clone(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::MustBeListOrVector,
+                &["server/group", "routes"],
+            ))
+        }
+    };
+
+    let middleware = match opts.get(&kw("middleware")) {
+        Some(Value::Vector(v)) | Some(Value::List(v)) => v.clone(),
+        Some(v @ (Value::Function(_) | Value::NativeFunc(_))) => im::vector![v.clone()],
+        None | Some(Value::Nil) => im::Vector::new(),
+        Some(_) => {
+            return Err(fmt_msg(
+                MsgKey::MustBeListOrVector,
+                &["server/group", ":middleware"],
+            ))
+        }
+    };
+
+    let mut group = crate::new_hashmap();
+    group.insert(marker("__group__"), Value::String(prefix));
+    group.insert(marker("__routes__"), Value::Vector(routes));
+    group.insert(marker("__group_middleware__"), Value::Vector(middleware));
+    Ok(Value::Map(group))
+}
+
+/// server/url-for - 名前付きルートからURLを生成
+///
+/// 引数:
+/// - router: server/routerで作成したルーター
+/// - name: ルート名（キーワードまたは文字列）
+/// - params: パスパラメータのマップ（省略可、パスで使われないものはクエリ文字列になる）
+///
+/// 戻り値: URL文字列
+pub fn native_server_url_for(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 || args.len() > 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["server/url-for"]));
+    }
+
+    let trie = match &args[0] {
+        Value::Vector(routes) => {
+            let mut trie = RouteTrie::default();
+            compile_routes(&mut trie, "", routes, &[])?;
+            Arc::new(trie)
+        }
+        router => lookup_router(router)
+            .ok_or_else(|| fmt_msg(MsgKey::FirstArgMustBe, &["server/url-for", "a router"]))??,
+    };
+
+    let name = match &args[1] {
+        Value::Keyword(k) => k.to_string(),
+        Value::String(s) => s.clone(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::SecondArgMustBe,
+                &["server/url-for", "a keyword or string"],
+            ))
+        }
+    };
+
+    let params = match args.get(2) {
+        Some(Value::Map(m)) => m.clone(),
+        None | Some(Value::Nil) => crate::new_hashmap(),
+        Some(_) => {
+            return Err(fmt_msg(
+                MsgKey::ThirdArgMustBe,
+                &["server/url-for", "a map"],
+            ))
+        }
+    };
+
+    trie.url_for(&name, &params).map(Value::String)
+}
+
+/// ルーターマップからコンパイル済みトライ木を取得
+///
+/// 戻り値: ルーターでなければNone、ハンドルがルーターでなければSome(Err)
+pub(super) fn lookup_router(value: &Value) -> Option<Result<Arc<RouteTrie>, String>> {
+    let Value::Map(m) = value else {
+        return None;
+    };
+    let Some(Value::Handle(handle)) = m.get(&marker("__router__")) else {
+        return None;
+    };
+    Some(
+        handle
+            .downcast::<RouteTrie>()
+            .ok_or_else(|| fmt_msg(MsgKey::ServerRouterNotFound, &[handle.kind])),
+    )
+}
+
+/// ルート定義を再帰的にトライ木へ登録
+fn compile_routes(
+    trie: &mut RouteTrie,
+    prefix: &str,
+    routes: &im::Vector<Value>,
+    middleware: &[Value],
+) -> Result<(), String> {
+    for route in routes {
+        match route {
+            // ルートグループ
+            Value::Map(m) if m.contains_key(&marker("__group__")) => {
+                let group_prefix = match m.get(&marker("__group__")) {
+                    Some(Value::String(p)) => p.as_str(),
+                    _ => "",
+                };
+                let group_routes = match m.get(&marker("__routes__")) {
+                    Some(Value::Vector(v)) => v.clone(),
+                    _ => im::Vector::new(),
+                };
+                // 外側のグループのミドルウェアが外側になるよう連結
+                let mut group_middleware = middleware.to_vec();
+                if let Some(Value::Vector(mw)) = m.get(&marker("__group_middleware__")) {
+                    group_middleware.extend(mw.iter().cloned());
+                }
+                compile_routes(
+                    trie,
+                    &join_paths(prefix, group_prefix),
+                    &group_routes,
+                    &group_middleware,
+                )?;
+            }
+            Value::Vector(def) if def.len() == 2 || def.len() == 3 => {
+                let (Value::String(pattern), Value::Map(handlers)) = (&def[0], &def[1]) else {
+                    return Err(fmt_msg(
+                        MsgKey::ServerInvalidRoute,
+                        &[&format!("{}", route), "expected [path {:method handler}]"],
+                    ));
+                };
+                let full_pattern = join_paths(prefix, pattern);
+
+                for (method_key, handler) in handlers {
+                    let method = match method_key {
+                        MapKey::Keyword(k) | MapKey::Symbol(k) => k.to_lowercase(),
+                        MapKey::String(s) => s.to_lowercase(),
+                        MapKey::Integer(_) | MapKey::Char(_) => {
+                            return Err(fmt_msg(
+                                MsgKey::ServerInvalidRoute,
+                                &[&full_pattern, "method must be a keyword"],
+                            ))
+                        }
+                    };
+                    let is_static_dir = matches!(handler, Value::Map(hm)
+                        if hm.contains_key(&marker("__static_dir__")));
+                    let wrapped = wrap_middleware(handler.clone(), middleware)?;
+                    trie.insert(
+                        &full_pattern,
+                        crate::intern::intern_symbol(&method),
+                        wrapped,
+                        is_static_dir,
+                    )?;
+                }
+
+                // 名前付きルート
+                if let Some(Value::Map(opts)) = def.get(2) {
+                    match opts.get(&kw("name")) {
+                        Some(Value::Keyword(name)) => trie.add_name(name, &full_pattern)?,
+                        Some(Value::String(name)) => trie.add_name(name, &full_pattern)?,
+                        _ => {}
+                    }
+                }
+            }
             _ => {
                 return Err(fmt_msg(
-                    MsgKey::RequestMustHave,
-                    &["request", ":path string"],
+                    MsgKey::ServerInvalidRoute,
+                    &[
+                        &format!("{}", route),
+                        "expected [path {:method handler}] or a group",
+                    ],
                 ))
             }
-        },
-        _ => return Err(fmt_msg(MsgKey::RequestMustBe, &["request", "a map"])),
+        }
+    }
+    Ok(())
+}
+
+/// グループのミドルウェアでハンドラーをラップ（先頭が最も外側）
+fn wrap_middleware(handler: Value, middleware: &[Value]) -> Result<Value, String> {
+    let eval = GLOBAL_EVALUATOR.clone();
+    middleware
+        .iter()
+        .rev()
+        .try_fold(handler, |h, mw| eval.apply_function(mw, &[h]))
+}
+
+/// プレフィックスとパスを連結
+fn join_paths(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        if prefix.is_empty() {
+            "/".to_string()
+        } else {
+            prefix.to_string()
+        }
+    } else {
+        format!("{}/{}", prefix, path)
+    }
+}
+
+/// リクエストをハンドラー（関数・ミドルウェア・ルーター）にディスパッチ
+pub(super) fn dispatch_request(req: &Value, handler: &Value) -> Result<Value, String> {
+    match handler {
+        Value::Map(_) => {
+            let eval = GLOBAL_EVALUATOR.clone();
+            apply_middleware(handler, req, &eval)
+        }
+        Value::Function(_) | Value::NativeFunc(_) => {
+            let eval = GLOBAL_EVALUATOR.clone();
+            eval.apply_function(handler, std::slice::from_ref(req))
+        }
+        _ => Err(fmt_msg(
+            MsgKey::ServerHandlerMustBeFunction,
+            &[handler.type_name()],
+        )),
+    }
+}
+
+/// ルーターでリクエストを処理
+///
+/// パスがマッチしてもメソッドが異なる場合は405（Allowヘッダー付き）、
+/// パスがマッチしない場合は404を返す。
+pub(super) fn route_request(req: &Value, router: &RouteTrie) -> Result<Value, String> {
+    let Value::Map(req_map) = req else {
+        return Err(fmt_msg(MsgKey::RequestMustBe, &["request", "a map"]));
     };
 
-    // ルートを探索
-    for route in routes {
-        if let Value::Vector(route_def) = route {
-            if route_def.len() == 2 {
-                if let (Value::String(pattern), Value::Map(handlers)) =
-                    (&route_def[0], &route_def[1])
-                {
-                    // メソッドに対応するハンドラーを取得
-                    // ルート定義は {:get handler :post handler} のようにキーワードキーを使用
-                    if let Some(handler) =
-                        handlers.get(&crate::value::MapKey::Keyword(method.clone()))
-                    {
-                        // 静的ファイルハンドラーの場合はプレフィックスマッチング
-                        if let Value::Map(m) = handler {
-                            if m.contains_key(&crate::value::MapKey::String(
-                                "__static_dir__".to_string(),
-                            )) {
-                                // プレフィックスマッチング（パスがパターンで始まっているか）
-                                let pattern_normalized = if pattern == "/" {
-                                    "/"
-                                } else {
-                                    pattern.trim_end_matches('/')
-                                };
-                                let path_normalized = path.trim_end_matches('/');
-
-                                if path_normalized == pattern_normalized
-                                    || (pattern_normalized == "/" && !path.is_empty())
-                                    || path.starts_with(&format!("{}/", pattern_normalized))
-                                {
-                                    // 静的ファイルハンドラーを実行（グローバルEvaluatorをclone）
-                                    let eval = GLOBAL_EVALUATOR.clone();
-                                    return apply_middleware(handler, req, &eval);
-                                }
-                                continue;
-                            }
-                        }
+    let method = match req_map.get(&kw("method")) {
+        Some(Value::Keyword(k)) => k.clone(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::RequestMustHave,
+                &["request", ":method keyword"],
+            ))
+        }
+    };
 
-                        // 通常のパスパラメータ対応のパターンマッチング
-                        if let Some(params) = match_route_pattern(pattern, &path) {
-                            // パラメータをリクエストに追加
-                            let mut req_with_params = match req {
-                                Value::Map(m) => m.clone(),
-                                _ => crate::new_hashmap(),
-                            };
-                            let params_key = kw("params");
-                            req_with_params.insert(
-                                params_key,
-                                Value::Map(crate::builtins::util::convert_string_map_to_mapkey(
-                                    params,
-                                )),
-                            );
+    let path = match req_map.get(&kw("path")) {
+        Some(Value::String(p)) => p.clone(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::RequestMustHave,
+                &["request", ":path string"],
+            ))
+        }
+    };
 
-                            // ミドルウェアを適用してハンドラーを実行（グローバルEvaluatorをclone）
-                            let eval = GLOBAL_EVALUATOR.clone();
-                            return apply_middleware(handler, &Value::Map(req_with_params), &eval);
-                        }
-                    }
-                }
+    match router.lookup(&method, &path) {
+        RouteMatch::Found { handler, params } => {
+            // パラメータをリクエストに追加
+            let mut req_with_params = req_map.clone();
+            let params_map: crate::HashMap<MapKey, Value> = params
+                .into_iter()
+                .map(|(k, v)| (MapKey::Keyword(crate::intern::intern_keyword(&k)), v))
+                .collect();
+            req_with_params.insert(kw("params"), Value::Map(params_map));
+
+            // ミドルウェアを適用してハンドラーを実行（グローバルEvaluatorをclone）
+            let eval = GLOBAL_EVALUATOR.clone();
+            apply_middleware(handler, &Value::Map(req_with_params), &eval)
+        }
+        RouteMatch::MethodNotAllowed(allowed) => method_not_allowed(&allowed),
+        RouteMatch::NotFound => native_server_not_found(&[]),
+    }
+}
+
+/// リクエストに適用されるボディ上限を取得（server/with-body-limit）
+///
+/// ボディを読み込む前に呼ばれるため、リクエストヘッダー部分だけでルーティングを辿る。
+/// 指定がなければNone（サーバー全体の上限を使う）。
+pub(super) fn body_limit_for(req: &Value, handler: &Value) -> Option<usize> {
+    let Value::Map(m) = handler else {
+        return None;
+    };
+
+    if let Some(Value::String(middleware_type)) = m.get(&marker("__middleware__")) {
+        if middleware_type == "body-limit" {
+            if let Some(Value::Integer(n)) = m.get(&marker("__max_body_size__")) {
+                return Some(*n as usize);
             }
         }
+        return m
+            .get(&marker("__handler__"))
+            .and_then(|inner| body_limit_for(req, inner));
     }
 
-    // ルートが見つからない
-    native_server_not_found(&[])
+    let router = lookup_router(handler)?.ok()?;
+    let Value::Map(req_map) = req else {
+        return None;
+    };
+    let (Some(Value::Keyword(method)), Some(Value::String(path))) =
+        (req_map.get(&kw("method")), req_map.get(&kw("path")))
+    else {
+        return None;
+    };
+    match router.lookup(method, path) {
+        RouteMatch::Found { handler, .. } => body_limit_for(req, handler),
+        _ => None,
+    }
 }
 
 /// ミドルウェアを適用してハンドラーを実行
@@ -167,6 +437,15 @@ pub(super) fn apply_middleware(
             if let Some(inner_handler) =
                 m.get(&crate::value::MapKey::String("__handler__".to_string()))
             {
+                // 流量制御（内部ハンドラーの実行自体を制御する）
+                match middleware_type.as_str() {
+                    "rate-limit" => return apply_rate_limit(m, inner_handler, req, eval),
+                    "timeout" => return apply_timeout(m, inner_handler, req, eval),
+                    "body-limit" => return apply_body_limit(m, inner_handler, req, eval),
+                    "openapi" => return apply_openapi(m, inner_handler, req, eval),
+                    _ => {}
+                }
+
                 // Basic Auth検証
                 if middleware_type == "basic-auth" {
                     if let Value::Map(req_map) = req {
@@ -397,42 +676,12 @@ pub(super) fn apply_middleware(
         }
     }
 
-    // ミドルウェアでない場合、直接ハンドラーを実行
-    eval.apply_function(handler, std::slice::from_ref(req))
-}
-
-/// パスパターンマッチング - /users/:id のような形式をサポート
-/// 戻り値: マッチした場合はパラメータマップ、マッチしない場合はNone
-fn match_route_pattern(
-    pattern: &str,
-    path: &str,
-) -> Option<std::collections::HashMap<String, Value>> {
-    let pattern_parts: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
-    let path_parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
-
-    // パート数が異なる場合はマッチしない
-    if pattern_parts.len() != path_parts.len() {
-        return None;
-    }
-
-    let mut params = std::collections::HashMap::new();
-
-    // 各パートを比較
-    for (pattern_part, path_part) in pattern_parts.iter().zip(path_parts.iter()) {
-        if let Some(param_name) = pattern_part.strip_prefix(':') {
-            // パラメータ部分 - パラメータ名を抽出
-            // percent-encodingをデコード（例: %E3%81%82 → あ）
-            // URLのパーセントデコードは基本機能なので無条件で有効
-            let decoded_value = urlencoding::decode(path_part)
-                .unwrap_or(std::borrow::Cow::Borrowed(path_part))
-                .to_string();
-
-            params.insert(param_name.to_string(), Value::String(decoded_value));
-        } else if pattern_part != path_part {
-            // 固定部分が一致しない
-            return None;
-        }
+    // ミドルウェアでラップされたルーター
+    if let Some(router) = lookup_router(handler) {
+        let router = router?;
+        return route_request(req, &router);
     }
 
-    Some(params)
+    // ミドルウェアでない場合、直接ハンドラーを実行
+    eval.apply_function(handler, std::slice::from_ref(req))
 }
@@ -1,25 +1,35 @@
 //! サーバー起動機能
 
-use super::helpers::{error_response, request_to_value, value_to_response};
-use super::routing::route_request;
+use super::helpers::{
+    error_response, read_body, request_head_to_value, value_to_response, BodyReadError,
+};
+use super::routing::{body_limit_for, dispatch_request, native_server_router, GLOBAL_EVALUATOR};
 use crate::builtins::util::kw;
 use crate::builtins::value_helpers::validate_port;
-use crate::eval::Evaluator;
+use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
-use crate::value::{MapKey, Value};
+use crate::value::Value;
 use http_body_util::combinators::BoxBody;
 use hyper::body::Bytes;
 use hyper::server::conn::http1;
 use hyper::service::service_fn;
 use hyper::{Request, Response};
 use hyper_util::rt::TokioIo;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::convert::Infallible;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::sync::{oneshot, watch, Semaphore};
+use tokio::task::JoinSet;
 
-use super::{DEFAULT_HTTP_HOST, DEFAULT_HTTP_PORT, DEFAULT_TIMEOUT_SECS};
+use super::{
+    DEFAULT_DRAIN_TIMEOUT_SECS, DEFAULT_HTTP_HOST, DEFAULT_HTTP_PORT, DEFAULT_MAX_BODY_SIZE,
+    DEFAULT_TIMEOUT_SECS,
+};
 
 /// HTTPサーバータイムアウトの最小値（秒）
 const MIN_TIMEOUT_SECS: u64 = 1;
@@ -27,22 +37,49 @@ const MIN_TIMEOUT_SECS: u64 = 1;
 /// HTTPサーバータイムアウトの最大値（秒）
 const MAX_TIMEOUT_SECS: u64 = 300; // 5分
 
+/// 起動中サーバーのID生成カウンター
+static SERVER_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// `:block false` で起動したサーバーのレジストリ（キー: サーバーID）
+static SERVERS: LazyLock<Mutex<HashMap<String, RunningServer>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// バックグラウンドで動作中のサーバー
+struct RunningServer {
+    /// 実際にバインドされたポート（:port 0 の場合はOSが割り当てたポート）
+    port: u16,
+    /// 停止要求の送信側
+    stop_tx: oneshot::Sender<()>,
+    /// サーバースレッド（停止後のドレインとフック実行の完了待ちに使う）
+    thread: std::thread::JoinHandle<()>,
+}
+
 /// server/serve - HTTPサーバーを起動
 ///
 /// 引数:
 /// - handler: リクエストハンドラー（関数またはルーター定義）
 /// - opts: オプション（マップ、省略可）
 ///   - :host - バインドホスト（デフォルト: "127.0.0.1"）
-///   - :port - バインドポート（デフォルト: 3000）
+///   - :port - バインドポート（デフォルト: 3000、0ならOSが空きポートを割り当てる）
 ///   - :timeout - タイムアウト（秒、デフォルト: 30、最小: 1、最大: 300）
+///   - :max-body-size - リクエストボディの上限（バイト、デフォルト: 10MB）
+///     ルートごとの上限は server/with-body-limit で指定する
+///   - :max-concurrent - 同時処理リクエスト数の上限（超過時は503、省略時は無制限）
+///   - :block - falseならバックグラウンドで起動してサーバーハンドルを返す（デフォルト: true）
+///   - :on-shutdown - 停止時に呼ぶ関数（または関数のベクタ）。処理中リクエストの完了後に呼ばれる
+///   - :drain-timeout - 停止時に処理中リクエストを待つ秒数（デフォルト: 30）
 ///
-/// 戻り値: nil（サーバー起動後は戻らない）
+/// 戻り値: :block true ならnil（停止するまで戻らない）、falseならサーバーハンドル
 pub fn native_server_serve(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["server/serve", "1"]));
     }
 
-    let handler = args[0].clone();
+    // ルート定義のベクタが直接渡された場合は起動時に一度だけコンパイル
+    let handler = match &args[0] {
+        Value::Vector(_) => native_server_router(&args[..1])?,
+        h => h.clone(),
+    };
 
     // オプション引数
     let opts = if args.len() > 1 {
@@ -59,6 +96,8 @@ pub fn native_server_serve(args: &[Value]) -> Result<Value, String> {
     let timeout_key = kw("timeout");
 
     let port = match opts.get(&port_key) {
+        // 0はエフェメラルポート（server/portで実際のポートを取得）
+        Some(Value::Integer(0)) => 0,
         Some(val) => validate_port(val, "server/serve")?,
         None => DEFAULT_HTTP_PORT,
     };
@@ -91,145 +130,392 @@ pub fn native_server_serve(args: &[Value]) -> Result<Value, String> {
         _ => DEFAULT_TIMEOUT_SECS,
     };
 
+    let max_body_size = match opts.get(&kw("max-body-size")) {
+        Some(Value::Integer(n)) if *n > 0 => *n as usize,
+        Some(Value::Integer(_)) => {
+            return Err(fmt_msg(
+                MsgKey::MustBePositive,
+                &["server/serve", ":max-body-size"],
+            ))
+        }
+        _ => DEFAULT_MAX_BODY_SIZE,
+    };
+
+    let max_concurrent = match opts.get(&kw("max-concurrent")) {
+        Some(Value::Integer(n)) if *n > 0 => Some(*n as usize),
+        Some(Value::Integer(_)) => {
+            return Err(fmt_msg(
+                MsgKey::MustBePositive,
+                &["server/serve", ":max-concurrent"],
+            ))
+        }
+        _ => None,
+    };
+
+    let drain_timeout_secs = match opts.get(&kw("drain-timeout")) {
+        Some(Value::Integer(n)) if *n >= 0 => *n as u64,
+        Some(Value::Integer(_)) => {
+            return Err(fmt_msg(
+                MsgKey::MustBePositive,
+                &["server/serve", ":drain-timeout"],
+            ))
+        }
+        _ => DEFAULT_DRAIN_TIMEOUT_SECS,
+    };
+
+    let on_shutdown = match opts.get(&kw("on-shutdown")) {
+        None | Some(Value::Nil) => Vec::new(),
+        Some(f @ (Value::Function(_) | Value::NativeFunc(_))) => vec![f.clone()],
+        Some(Value::Vector(fs))
+            if fs
+                .iter()
+                .all(|f| matches!(f, Value::Function(_) | Value::NativeFunc(_))) =>
+        {
+            fs.iter().cloned().collect()
+        }
+        Some(_) => return Err(fmt_msg(MsgKey::ServerInvalidOnShutdown, &[])),
+    };
+
+    let block = !matches!(opts.get(&kw("block")), Some(Value::Bool(false)));
+
+    let config = ServerConfig {
+        timeout: Duration::from_secs(timeout_secs),
+        max_body_size,
+        concurrency: max_concurrent.map(|n| Arc::new(Semaphore::new(n))),
+        drain_timeout: Duration::from_secs(drain_timeout_secs),
+        on_shutdown,
+    };
+
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| fmt_msg(MsgKey::ServerFailedToCreateRuntime, &[&e.to_string()]))?;
+
+    // 先にバインドして、エラーを呼び出し元に返し、実際のポートを確定させる
+    let bind_addr = format!("{}:{}", host, port);
+    let listener = rt
+        .block_on(TcpListener::bind(&bind_addr))
+        .map_err(|e| fmt_msg(MsgKey::ServerFailedToBind, &[&bind_addr, &e.to_string()]))?;
+    let local_port = listener
+        .local_addr()
+        .map(|a| a.port())
+        .map_err(|e| fmt_msg(MsgKey::ServerFailedToBind, &[&bind_addr, &e.to_string()]))?;
+
     println!(
         "HTTP server started on http://{}:{} (timeout: {}s)",
-        host, port, timeout_secs
+        host, local_port, timeout_secs
     );
 
-    // ブロッキングで実行（Ctrl+Cでシャットダウンするまで待つ）
-    let rt = tokio::runtime::Runtime::new()
-        .map_err(|e| fmt_msg(MsgKey::ServerFailedToCreateRuntime, &[&e.to_string()]))?;
+    if block {
+        // ブロッキングで実行（Ctrl+C / SIGTERMでシャットダウンするまで待つ）
+        rt.block_on(async move {
+            if let Err(e) = run_server(listener, handler, config, shutdown_signal()).await {
+                eprintln!("Server error: {}", e);
+            }
+        });
+        return Ok(Value::Nil);
+    }
 
-    rt.block_on(async move {
-        if let Err(e) = run_server(&host, port, handler, timeout_secs).await {
-            eprintln!("Server error: {}", e);
-        }
+    // バックグラウンドで実行（server/stopで停止）
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let thread = std::thread::spawn(move || {
+        rt.block_on(async move {
+            let stop = async {
+                let _ = stop_rx.await;
+            };
+            if let Err(e) = run_server(listener, handler, config, stop).await {
+                eprintln!("Server error: {}", e);
+            }
+        });
     });
 
+    let id = format!("Server:{}", SERVER_COUNTER.fetch_add(1, Ordering::Relaxed));
+    SERVERS.lock().insert(
+        id.clone(),
+        RunningServer {
+            port: local_port,
+            stop_tx,
+            thread,
+        },
+    );
+
+    Ok(Value::String(id))
+}
+
+/// server/stop - バックグラウンドで起動したサーバーを停止
+///
+/// 新規接続の受付を止め、処理中のリクエストを:drain-timeoutまで待ってから
+/// :on-shutdownフックを実行する。すべて完了するまで戻らない。
+///
+/// 引数:
+/// - server: server/serve（:block false）が返したサーバーハンドル
+///
+/// 戻り値: nil
+pub fn native_server_stop(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "server/stop");
+
+    let id = server_id(&args[0], "server/stop")?;
+    let server = SERVERS
+        .lock()
+        .remove(id)
+        .ok_or_else(|| fmt_msg(MsgKey::ServerNotRunning, &["server/stop", id]))?;
+
+    // 受信側が既に終了していても（サーバーエラー等）スレッドの終了を待つ
+    let _ = server.stop_tx.send(());
+    if server.thread.join().is_err() {
+        eprintln!("Server thread panicked during shutdown");
+    }
+
     Ok(Value::Nil)
 }
 
-/// サーバー実行
-async fn run_server(
-    host: &str,
-    port: u16,
-    handler: Value,
-    timeout_secs: u64,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let addr: SocketAddr = format!("{}:{}", host, port).parse()?;
-    let listener = TcpListener::bind(addr).await?;
+/// server/port - サーバーが待ち受けているポート番号を取得
+///
+/// `:port 0` で起動した場合にOSが割り当てたポートを知るために使う。
+///
+/// 引数:
+/// - server: server/serve（:block false）が返したサーバーハンドル
+///
+/// 戻り値: ポート番号（整数）
+pub fn native_server_port(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "server/port");
 
-    // ハンドラーをArcで共有
-    let handler = Arc::new(handler);
-    let timeout_duration = Duration::from_secs(timeout_secs);
+    let id = server_id(&args[0], "server/port")?;
+    SERVERS
+        .lock()
+        .get(id)
+        .map(|s| Value::Integer(s.port as i64))
+        .ok_or_else(|| fmt_msg(MsgKey::ServerNotRunning, &["server/port", id]))
+}
 
-    // グレースフルシャットダウン用のシグナル
-    let shutdown = Arc::new(tokio::sync::Notify::new());
-    let shutdown_clone = shutdown.clone();
+/// サーバーハンドルからIDを取り出す
+fn server_id<'a>(value: &'a Value, func_name: &str) -> Result<&'a str, String> {
+    match value {
+        Value::String(s) if s.starts_with("Server:") => Ok(s),
+        _ => Err(fmt_msg(
+            MsgKey::FirstArgMustBe,
+            &[func_name, "a server handle"],
+        )),
+    }
+}
 
-    // シグナルハンドラー（Ctrl+C と SIGTERM）
-    tokio::spawn(async move {
-        #[cfg(unix)]
-        {
-            use tokio::signal::unix::{signal, SignalKind};
-            let mut sigterm =
-                signal(SignalKind::terminate()).expect("failed to create SIGTERM signal handler");
-            let mut sigint =
-                signal(SignalKind::interrupt()).expect("failed to create SIGINT signal handler");
-
-            tokio::select! {
-                _ = sigterm.recv() => {
-                    println!("\nReceived SIGTERM, gracefully shutting down...");
-                }
-                _ = sigint.recv() => {
-                    println!("\nReceived SIGINT (Ctrl+C), gracefully shutting down...");
-                }
+/// サーバー全体の設定（全リクエストで共有）
+struct ServerConfig {
+    /// リクエストタイムアウト
+    timeout: Duration,
+    /// ルートで指定がない場合のボディ上限（バイト）
+    max_body_size: usize,
+    /// 同時処理数の制限（Noneなら無制限）
+    concurrency: Option<Arc<Semaphore>>,
+    /// 停止時に処理中リクエストを待つ時間
+    drain_timeout: Duration,
+    /// 停止時に呼ぶ関数
+    on_shutdown: Vec<Value>,
+}
+
+/// OSのシャットダウンシグナル（Ctrl+C と SIGTERM）を待つ
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut sigterm =
+            signal(SignalKind::terminate()).expect("failed to create SIGTERM signal handler");
+        let mut sigint =
+            signal(SignalKind::interrupt()).expect("failed to create SIGINT signal handler");
+
+        tokio::select! {
+            _ = sigterm.recv() => {
+                println!("\nReceived SIGTERM, gracefully shutting down...");
+            }
+            _ = sigint.recv() => {
+                println!("\nReceived SIGINT (Ctrl+C), gracefully shutting down...");
             }
         }
-        #[cfg(not(unix))]
-        {
-            // Windows環境ではCtrl+Cのみ対応
-            match tokio::signal::ctrl_c().await {
-                Ok(()) => {
-                    println!("\nReceived shutdown signal, gracefully shutting down...");
-                }
-                Err(err) => {
-                    eprintln!("Error listening for shutdown signal: {}", err);
-                    return;
-                }
+    }
+    #[cfg(not(unix))]
+    {
+        // Windows環境ではCtrl+Cのみ対応
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => {
+                println!("\nReceived shutdown signal, gracefully shutting down...");
+            }
+            Err(err) => {
+                eprintln!("Error listening for shutdown signal: {}", err);
+                // シグナルを受け取れない場合は停止しない
+                std::future::pending::<()>().await;
             }
         }
-        shutdown_clone.notify_waiters();
-    });
+    }
+}
+
+/// サーバー実行
+///
+/// shutdownが完了したら新規接続の受付を止め、処理中の接続をdrain_timeoutまで待ってから
+/// on_shutdownフックを実行する。
+async fn run_server(
+    listener: TcpListener,
+    handler: Value,
+    config: ServerConfig,
+    shutdown: impl std::future::Future<Output = ()>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // ハンドラーと設定をArcで共有
+    let handler = Arc::new(handler);
+    let config = Arc::new(config);
+
+    // 接続タスクへの停止通知と、処理中の接続の追跡
+    let (drain_tx, drain_rx) = watch::channel(false);
+    let mut connections = JoinSet::new();
+
+    tokio::pin!(shutdown);
 
     loop {
         tokio::select! {
             // 新規接続を受け付ける
             result = listener.accept() => {
-                let (stream, _) = result?;
+                let (stream, remote_addr) = result?;
                 let io = TokioIo::new(stream);
                 let handler = handler.clone();
-                let timeout = timeout_duration;
+                let config = config.clone();
+                let mut drain_rx = drain_rx.clone();
 
-                tokio::task::spawn(async move {
+                connections.spawn(async move {
                     let service = service_fn(move |req| {
                         let handler = handler.clone();
-                        let timeout = timeout;
+                        let config = config.clone();
                         async move {
-                            handle_request(req, handler, timeout).await
+                            handle_request(req, handler, config, remote_addr).await
                         }
                     });
 
-                    if let Err(err) = http1::Builder::new()
-                        .serve_connection(io, service)
-                        .await
-                    {
+                    let conn = http1::Builder::new().serve_connection(io, service);
+                    tokio::pin!(conn);
+
+                    let result = tokio::select! {
+                        res = conn.as_mut() => res,
+                        _ = drain_rx.changed() => {
+                            // 処理中のリクエストを完了させてから接続を閉じる
+                            conn.as_mut().graceful_shutdown();
+                            conn.as_mut().await
+                        }
+                    };
+                    if let Err(err) = result {
                         eprintln!("Error serving connection: {:?}", err);
                     }
                 });
             }
-            // シャットダウンシグナルを受信
-            _ = shutdown.notified() => {
-                println!("Server stopped");
-                return Ok(());
+            // 終了した接続タスクを回収
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+            // シャットダウン要求を受信
+            _ = &mut shutdown => break,
+        }
+    }
+
+    // 新規接続の受付を止め、処理中の接続を待つ
+    drop(listener);
+    let _ = drain_tx.send(true);
+    let drained = tokio::time::timeout(config.drain_timeout, async {
+        while connections.join_next().await.is_some() {}
+    })
+    .await;
+    if drained.is_err() {
+        eprintln!(
+            "Drain timeout: closing {} connection(s) still in flight",
+            connections.len()
+        );
+        connections.abort_all();
+    }
+
+    // シャットダウンフック（DBプールのクローズ等）
+    if !config.on_shutdown.is_empty() {
+        let hooks = config.on_shutdown.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let eval = GLOBAL_EVALUATOR.clone();
+            for hook in &hooks {
+                if let Err(e) = eval.apply_function(hook, &[]) {
+                    eprintln!("on-shutdown hook error: {}", e);
+                }
             }
+        })
+        .await;
+        if let Err(e) = result {
+            eprintln!("on-shutdown hook error: {}", e);
         }
     }
+
+    println!("Server stopped");
+    Ok(())
 }
 
 /// リクエスト処理
 async fn handle_request(
     req: Request<hyper::body::Incoming>,
     handler: Arc<Value>,
-    timeout: Duration,
+    config: Arc<ServerConfig>,
+    remote_addr: SocketAddr,
 ) -> Result<Response<BoxBody<Bytes, std::io::Error>>, Infallible> {
+    // 同時処理数の制限（上限に達していれば待たずに503を返す）
+    let _permit = match &config.concurrency {
+        Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                let mut resp = error_response(503, "Service Unavailable");
+                resp.headers_mut()
+                    .insert("Retry-After", hyper::header::HeaderValue::from_static("1"));
+                return Ok(resp);
+            }
+        },
+        None => None,
+    };
+
+    Ok(process_request(
+        req,
+        handler,
+        config.timeout,
+        config.max_body_size,
+        remote_addr,
+    )
+    .await)
+}
+
+/// リクエストをハンドラーで処理してHTTPレスポンスを作る
+///
+/// サーバーとテストクライアント（server/test-request）で共通の経路
+pub(super) async fn process_request<B>(
+    req: Request<B>,
+    handler: Arc<Value>,
+    timeout: Duration,
+    max_body_size: usize,
+    remote_addr: SocketAddr,
+) -> Response<BoxBody<Bytes, std::io::Error>>
+where
+    B: hyper::body::Body<Data = Bytes>,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
     // タイムアウト付きで処理
     let result = tokio::time::timeout(timeout, async {
-        // リクエストをQi値に変換
-        let (req_value, _body) = request_to_value(req).await?;
+        let (parts, body) = req.into_parts();
+        let mut req_map = request_head_to_value(&parts, Some(remote_addr));
 
-        // HEADリクエストの場合はボディを削除（RFC 7231 §4.3.2準拠）
-        let is_head = match &req_value {
-            Value::Map(m) => {
-                let method_key = MapKey::Keyword(crate::intern::intern_keyword("method"));
-                matches!(m.get(&method_key), Some(Value::Keyword(s)) if s.as_ref() == "head")
-            }
-            _ => false,
+        // ルートごとのボディ上限を決定してから読み込む
+        let body_limit =
+            body_limit_for(&Value::Map(req_map.clone()), &handler).unwrap_or(max_body_size);
+        let body_value = match read_body(&parts, body, body_limit).await {
+            Ok(v) => v,
+            // クライアント側のエラーなので標準エラーには出さず、理由をボディで返す
+            Err(BodyReadError::TooLarge(e)) => return Ok(error_response(413, &e)),
+            Err(BodyReadError::Failed(e)) => return Err(e),
         };
+        req_map.insert(kw("body"), body_value);
+        let req_value = Value::Map(req_map);
 
-        // ハンドラーがルーター（Vector）の場合、ルーティング処理
-        let resp_value = match handler.as_ref() {
-            Value::Vector(routes) => route_request(&req_value, routes),
-            Value::Function(_) => {
-                // 直接関数を呼び出す
-                let eval = Evaluator::new();
-                eval.apply_function(handler.as_ref(), &[req_value])
-            }
-            _ => Err(fmt_msg(
-                MsgKey::ServerHandlerMustBeFunction,
-                &[handler.type_name()],
-            )),
-        };
+        // HEADリクエストの場合はボディを削除（RFC 7231 §4.3.2準拠）
+        let is_head = parts.method == hyper::Method::HEAD;
+
+        // ハンドラー（関数・ミドルウェア・ルーター）にディスパッチ
+        // Qiの評価は同期処理のため、ブロッキングスレッドで実行してタイムアウトを効かせる
+        let dispatch_handler = handler.clone();
+        let resp_value =
+            tokio::task::spawn_blocking(move || dispatch_request(&req_value, &dispatch_handler))
+                .await
+                .map_err(|e| fmt_msg(MsgKey::ServerHandlerError, &[&e.to_string()]))?;
 
         // Qi値をHTTPレスポンスに変換（async）
         match resp_value {
@@ -237,11 +523,8 @@ async fn handle_request(
                 // HEADリクエストの場合、レスポンスから:bodyと:body-fileを削除
                 if is_head {
                     if let Value::Map(m) = &mut v {
-                        let body_key = MapKey::Keyword(crate::intern::intern_keyword("body"));
-                        let body_file_key =
-                            MapKey::Keyword(crate::intern::intern_keyword("body-file"));
-                        m.remove(&body_key);
-                        m.remove(&body_file_key);
+                        m.remove(&kw("body"));
+                        m.remove(&kw("body-file"));
                     }
                 }
                 value_to_response(v).await
@@ -255,14 +538,14 @@ async fn handle_request(
     .await;
 
     match result {
-        Ok(Ok(resp)) => Ok(resp),
+        Ok(Ok(resp)) => resp,
         Ok(Err(e)) => {
             eprintln!("Error: {}", e);
-            Ok(error_response(500, "Internal Server Error"))
+            error_response(500, "Internal Server Error")
         }
         Err(_) => {
             eprintln!("Request timeout");
-            Ok(error_response(408, "Request Timeout"))
+            error_response(408, "Request Timeout")
         }
     }
 }
@@ -0,0 +1,214 @@
+//! インプロセステストクライアント（server/test-request）
+//!
+//! ポートをバインドせず、サーバーと同じ経路（ボディ読み込み → ルーティング →
+//! ミドルウェア → レスポンス変換）でリクエストを処理する。
+
+use super::routing::native_server_router;
+use super::serve::process_request;
+use super::{DEFAULT_MAX_BODY_SIZE, DEFAULT_TIMEOUT_SECS};
+use crate::builtins::util::kw;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Value};
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::Request;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// server/test-request - ハンドラーにリクエストを送ってレスポンスを取得（テスト用）
+///
+/// 引数:
+/// - handler: ハンドラー関数・ルーター・ルート定義のベクタ（server/serveと同じ）
+/// - request: リクエストマップ
+///   - :method - HTTPメソッド（キーワードまたは文字列、デフォルト: :get）
+///   - :path - パス（クエリ文字列を含めてもよい、デフォルト: "/"）
+///   - :query - クエリパラメータのマップ（:pathのクエリ文字列に追加）
+///   - :headers - ヘッダーのマップ
+///   - :cookies - Cookieのマップ（Cookieヘッダーに変換）
+///   - :body - ボディ（文字列またはバイト列）
+///   - :json - JSONボディ（エンコードしてContent-Type: application/jsonを付ける）
+///   - :remote-addr - クライアントIP（デフォルト: "127.0.0.1"）
+///
+/// 戻り値: {:status 200 :headers {"content-type" "..."} :body "..."}
+///         （ヘッダー名は小文字、ボディはUTF-8なら文字列、それ以外はバイト列）
+pub fn native_server_test_request(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(fmt_msg(MsgKey::Need2Args, &["server/test-request"]));
+    }
+
+    // ルート定義のベクタはその場でコンパイル（グローバルには登録されない）
+    let handler = match &args[0] {
+        Value::Vector(_) => native_server_router(&args[..1])?,
+        h => h.clone(),
+    };
+
+    let req_map = match &args[1] {
+        Value::Map(m) => m,
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::SecondArgMustBe,
+                &["server/test-request", "a map"],
+            ))
+        }
+    };
+
+    let (request, remote_addr) = build_request(req_map)?;
+
+    // 呼び出しごとにランタイムを作らず、共有サーバーランタイムを使う
+    let rt = crate::builtins::lazy_init::http_server::get_runtime()
+        .map_err(|e| fmt_msg(MsgKey::ServerFailedToCreateRuntime, &[&e]))?;
+
+    rt.block_on(async move {
+        let response = process_request(
+            request,
+            Arc::new(handler),
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            DEFAULT_MAX_BODY_SIZE,
+            remote_addr,
+        )
+        .await;
+        response_to_value(response).await
+    })
+}
+
+/// リクエストマップからhyperのリクエストを組み立てる
+fn build_request(
+    req: &crate::HashMap<MapKey, Value>,
+) -> Result<(Request<Full<Bytes>>, SocketAddr), String> {
+    let invalid =
+        |e: &dyn std::fmt::Display| fmt_msg(MsgKey::ServerInvalidTestRequest, &[&e.to_string()]);
+
+    let method = match req.get(&kw("method")) {
+        None => "GET".to_string(),
+        Some(Value::Keyword(k)) => k.to_uppercase(),
+        Some(Value::String(s)) => s.to_uppercase(),
+        Some(v) => return Err(invalid(&format!(":method {}", v))),
+    };
+
+    let mut uri = match req.get(&kw("path")) {
+        None => "/".to_string(),
+        Some(Value::String(p)) => p.clone(),
+        Some(v) => return Err(invalid(&format!(":path {}", v))),
+    };
+    if let Some(Value::Map(query)) = req.get(&kw("query")) {
+        let mut pairs: Vec<(String, String)> = query
+            .iter()
+            .map(|(k, v)| (key_to_string(k), value_to_string(v)))
+            .collect();
+        pairs.sort();
+        let encoded: Vec<String> = pairs
+            .iter()
+            .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+            .collect();
+        if !encoded.is_empty() {
+            uri.push(if uri.contains('?') { '&' } else { '?' });
+            uri.push_str(&encoded.join("&"));
+        }
+    }
+
+    let mut builder = Request::builder().method(method.as_str()).uri(uri);
+
+    let mut has_content_type = false;
+    if let Some(Value::Map(headers)) = req.get(&kw("headers")) {
+        for (k, v) in headers {
+            let name = key_to_string(k);
+            has_content_type |= name.eq_ignore_ascii_case("content-type");
+            builder = builder.header(name, value_to_string(v));
+        }
+    }
+
+    if let Some(Value::Map(cookies)) = req.get(&kw("cookies")) {
+        let mut pairs: Vec<String> = cookies
+            .iter()
+            .map(|(k, v)| format!("{}={}", key_to_string(k), value_to_string(v)))
+            .collect();
+        pairs.sort();
+        if !pairs.is_empty() {
+            builder = builder.header("cookie", pairs.join("; "));
+        }
+    }
+
+    let body = match (req.get(&kw("body")), req.get(&kw("json"))) {
+        (Some(Value::String(s)), _) => Bytes::from(s.clone()),
+        (Some(Value::Bytes(b)), _) => Bytes::copy_from_slice(b),
+        (Some(Value::Nil) | None, Some(json)) => {
+            let encoded = match crate::builtins::json::native_stringify(std::slice::from_ref(json))?
+            {
+                Value::String(s) => s,
+                _ => return Err(fmt_msg(MsgKey::JsonStringifyError, &[])),
+            };
+            if !has_content_type {
+                builder = builder.header("content-type", "application/json");
+            }
+            Bytes::from(encoded)
+        }
+        (Some(Value::Nil) | None, None) => Bytes::new(),
+        (Some(v), _) => return Err(invalid(&format!(":body {}", v.type_name()))),
+    };
+
+    let ip = match req.get(&kw("remote-addr")) {
+        Some(Value::String(s)) => s
+            .parse::<IpAddr>()
+            .map_err(|e| invalid(&format!(":remote-addr {}", e)))?,
+        _ => IpAddr::V4(Ipv4Addr::LOCALHOST),
+    };
+
+    let request = builder.body(Full::new(body)).map_err(|e| invalid(&e))?;
+    Ok((request, SocketAddr::new(ip, 0)))
+}
+
+/// hyperのレスポンスをQiのマップに変換（ボディはすべて読み込む）
+async fn response_to_value(
+    response: hyper::Response<http_body_util::combinators::BoxBody<Bytes, std::io::Error>>,
+) -> Result<Value, String> {
+    let (parts, body) = response.into_parts();
+
+    // 同名ヘッダーが複数ある場合は", "で連結
+    let mut headers: crate::HashMap<MapKey, Value> = crate::new_hashmap();
+    for name in parts.headers.keys() {
+        let values: Vec<&str> = parts
+            .headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect();
+        headers.insert(
+            MapKey::String(name.as_str().to_string()),
+            Value::String(values.join(", ")),
+        );
+    }
+
+    let bytes = body
+        .collect()
+        .await
+        .map_err(|e| fmt_msg(MsgKey::ServerFailedToReadBody, &[&e.to_string()]))?
+        .to_bytes();
+    let body_value = match std::str::from_utf8(&bytes) {
+        Ok(text) => Value::String(text.to_string()),
+        Err(_) => Value::Bytes(Arc::from(bytes.as_ref())),
+    };
+
+    let mut resp = crate::new_hashmap();
+    resp.insert(kw("status"), Value::Integer(parts.status.as_u16() as i64));
+    resp.insert(kw("headers"), Value::Map(headers));
+    resp.insert(kw("body"), body_value);
+    Ok(Value::Map(resp))
+}
+
+fn key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Keyword(s) | MapKey::Symbol(s) => s.to_string(),
+        MapKey::String(s) => s.clone(),
+        MapKey::Integer(i) => i.to_string(),
+        MapKey::Char(c) => c.to_string(),
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Keyword(k) => k.to_string(),
+        v => v.to_string(),
+    }
+}
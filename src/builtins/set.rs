@@ -1,5 +1,7 @@
 //! 集合演算関数
 //!
+//! List/Vector/Setを受け付け、引数がすべてSetの場合は結果もSetで返す。
+//!
 //! このモジュールは `std-set` feature でコンパイルされます。
 
 // ValueはArc<RwLock<Env>>を含むため、clippyがmutable_key_typeを警告する。
@@ -26,6 +28,7 @@ fn check_hashable(items: &im::Vector<Value>) -> Result<(), String> {
             | Value::NativeFunc(_)
             | Value::Macro(_)
             | Value::Atom(_)
+            | Value::Ref(_)
             | Value::Channel(_)
             | Value::Scope(_)
             | Value::Stream(_)
@@ -44,30 +47,51 @@ fn check_hashable(items: &im::Vector<Value>) -> Result<(), String> {
     Ok(())
 }
 
+/// 引数の要素を取り出す（List/Vector/Set）
+fn elements(value: &Value, name: &str) -> Result<im::Vector<Value>, String> {
+    match value {
+        Value::List(items) | Value::Vector(items) => {
+            check_hashable(items)?;
+            Ok(items.clone())
+        }
+        Value::Set(set) => Ok(set.iter().cloned().collect()),
+        _ => Err(fmt_msg(
+            MsgKey::AllElementsMustBe,
+            &[name, "lists, vectors or sets"],
+        )),
+    }
+}
+
+/// 全引数がSetならSetのスライスとして返す（Set同士の演算はSetを返すため）
+fn all_sets(args: &[Value]) -> Option<Vec<&crate::HashSet<Value>>> {
+    args.iter()
+        .map(|arg| match arg {
+            Value::Set(set) => Some(set),
+            _ => None,
+        })
+        .collect()
+}
+
 /// union - 和集合
 pub fn native_union(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Ok(Value::List(vec![].into()));
     }
 
+    if let Some(sets) = all_sets(args) {
+        let result = sets
+            .into_iter()
+            .fold(crate::new_hashset(), |acc, set| acc.union(set.clone()));
+        return Ok(Value::Set(result));
+    }
+
     let mut seen = HashSet::new();
     let mut result = Vec::new();
 
     for arg in args {
-        match arg {
-            Value::List(items) | Value::Vector(items) => {
-                check_hashable(items)?;
-                for item in items {
-                    if seen.insert(item.clone()) {
-                        result.push(item.clone());
-                    }
-                }
-            }
-            _ => {
-                return Err(fmt_msg(
-                    MsgKey::AllElementsMustBe,
-                    &["union", "lists or vectors"],
-                ))
+        for item in elements(arg, "union")? {
+            if seen.insert(item.clone()) {
+                result.push(item);
             }
         }
     }
@@ -81,35 +105,22 @@ pub fn native_intersect(args: &[Value]) -> Result<Value, String> {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["intersect", "2"]));
     }
 
-    // 最初のリストをベースにする
-    let first = match &args[0] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["intersect", "lists or vectors"],
-            ))
+    if let Some(sets) = all_sets(args) {
+        let mut result = sets[0].clone();
+        for set in &sets[1..] {
+            result.retain(|item| set.contains(item));
         }
-    };
+        return Ok(Value::Set(result));
+    }
 
-    check_hashable(first)?;
+    // 最初のリストをベースにする
+    let first = elements(&args[0], "intersect")?;
     let mut result: HashSet<Value> = first.iter().cloned().collect();
 
     // 他のリストとの積集合を取る
     for arg in &args[1..] {
-        match arg {
-            Value::List(items) | Value::Vector(items) => {
-                check_hashable(items)?;
-                let set: HashSet<Value> = items.iter().cloned().collect();
-                result.retain(|k| set.contains(k));
-            }
-            _ => {
-                return Err(fmt_msg(
-                    MsgKey::AllElementsMustBe,
-                    &["intersect", "lists or vectors"],
-                ))
-            }
-        }
+        let set: HashSet<Value> = elements(arg, "intersect")?.into_iter().collect();
+        result.retain(|k| set.contains(k));
     }
 
     // 元の値を復元（順序を保持）
@@ -128,33 +139,18 @@ pub fn native_difference(args: &[Value]) -> Result<Value, String> {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["difference", "2"]));
     }
 
-    let first = match &args[0] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["difference", "lists or vectors"],
-            ))
+    if let Some(sets) = all_sets(args) {
+        let mut result = sets[0].clone();
+        for set in &sets[1..] {
+            result.retain(|item| !set.contains(item));
         }
-    };
+        return Ok(Value::Set(result));
+    }
 
-    check_hashable(first)?;
+    let first = elements(&args[0], "difference")?;
     let mut exclude = HashSet::new();
     for arg in &args[1..] {
-        match arg {
-            Value::List(items) | Value::Vector(items) => {
-                check_hashable(items)?;
-                for item in items {
-                    exclude.insert(item.clone());
-                }
-            }
-            _ => {
-                return Err(fmt_msg(
-                    MsgKey::AllElementsMustBe,
-                    &["difference", "lists or vectors"],
-                ))
-            }
-        }
+        exclude.extend(elements(arg, "difference")?);
     }
 
     let values: Vec<Value> = first
@@ -170,32 +166,12 @@ pub fn native_difference(args: &[Value]) -> Result<Value, String> {
 pub fn native_subset(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "subset?");
 
-    let subset = match &args[0] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["subset?", "lists or vectors"],
-            ))
-        }
-    };
-
-    let superset = match &args[1] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["subset?", "lists or vectors"],
-            ))
-        }
-    };
-
-    check_hashable(subset)?;
-    check_hashable(superset)?;
+    let subset = elements(&args[0], "subset?")?;
+    let superset = elements(&args[1], "subset?")?;
 
-    let superset_set: HashSet<Value> = superset.iter().cloned().collect();
+    let superset_set: HashSet<Value> = superset.into_iter().collect();
 
-    for item in subset {
+    for item in &subset {
         if !superset_set.contains(item) {
             return Ok(Value::Bool(false));
         }
@@ -216,32 +192,12 @@ pub fn native_superset(args: &[Value]) -> Result<Value, String> {
 pub fn native_disjoint(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "disjoint?");
 
-    let set1 = match &args[0] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["disjoint?", "lists or vectors"],
-            ))
-        }
-    };
-
-    let set2 = match &args[1] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["disjoint?", "lists or vectors"],
-            ))
-        }
-    };
+    let set1 = elements(&args[0], "disjoint?")?;
+    let set2 = elements(&args[1], "disjoint?")?;
 
-    check_hashable(set1)?;
-    check_hashable(set2)?;
+    let set1_hash: HashSet<Value> = set1.into_iter().collect();
 
-    let set1_hash: HashSet<Value> = set1.iter().cloned().collect();
-
-    for item in set2 {
+    for item in &set2 {
         if set1_hash.contains(item) {
             return Ok(Value::Bool(false));
         }
@@ -254,28 +210,8 @@ pub fn native_disjoint(args: &[Value]) -> Result<Value, String> {
 pub fn native_symmetric_difference(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "symmetric-difference");
 
-    let set1 = match &args[0] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["symmetric-difference", "lists or vectors"],
-            ))
-        }
-    };
-
-    let set2 = match &args[1] {
-        Value::List(items) | Value::Vector(items) => items,
-        _ => {
-            return Err(fmt_msg(
-                MsgKey::AllElementsMustBe,
-                &["symmetric-difference", "lists or vectors"],
-            ))
-        }
-    };
-
-    check_hashable(set1)?;
-    check_hashable(set2)?;
+    let set1 = elements(&args[0], "symmetric-difference")?;
+    let set2 = elements(&args[1], "symmetric-difference")?;
 
     let set1_hash: HashSet<Value> = set1.iter().cloned().collect();
     let set2_hash: HashSet<Value> = set2.iter().cloned().collect();
@@ -284,19 +220,23 @@ pub fn native_symmetric_difference(args: &[Value]) -> Result<Value, String> {
     let mut seen = HashSet::new();
 
     // set1にのみ存在する要素
-    for item in set1 {
+    for item in &set1 {
         if !set2_hash.contains(item) && seen.insert(item.clone()) {
             result.push(item.clone());
         }
     }
 
     // set2にのみ存在する要素
-    for item in set2 {
+    for item in &set2 {
         if !set1_hash.contains(item) && seen.insert(item.clone()) {
             result.push(item.clone());
         }
     }
 
+    if matches!((&args[0], &args[1]), (Value::Set(_), Value::Set(_))) {
+        return Value::set_from(result);
+    }
+
     Ok(Value::List(result.into()))
 }
 
@@ -8,6 +8,7 @@
 //! このモジュールは `db-sqlite` feature でコンパイルされます。
 
 use super::db::*;
+use crate::builtins::numeric_helpers::{number_to_f64, to_decimal};
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::map_db_err;
 use crate::value::Value;
@@ -95,6 +96,12 @@ impl SqliteConnection {
             }
             Value::Integer(i) => ToSqlOutput::Owned(rusqlite::types::Value::Integer(*i)),
             Value::Float(f) => ToSqlOutput::Owned(rusqlite::types::Value::Real(*f)),
+            // 10進数・多倍長整数はMサフィックスなしの数値文字列として送信
+            Value::Decimal(d) => ToSqlOutput::Owned(rusqlite::types::Value::Text(d.to_string())),
+            Value::BigInt(n) => ToSqlOutput::Owned(rusqlite::types::Value::Text(n.to_string())),
+            Value::Ratio(_) => ToSqlOutput::Owned(rusqlite::types::Value::Real(
+                number_to_f64(value).unwrap_or(f64::NAN),
+            )),
             Value::String(s) => ToSqlOutput::Borrowed(ValueRef::Text(s.as_bytes())),
             Value::Bytes(b) => ToSqlOutput::Borrowed(ValueRef::Blob(b.as_ref())), // BLOB型として送信
             _ => ToSqlOutput::Owned(rusqlite::types::Value::Text(value.to_string())),
@@ -105,6 +112,18 @@ impl SqliteConnection {
     fn row_to_hashmap(row: &SqliteRow) -> DbResult<Row> {
         let mut map = crate::new_hashmap();
         let column_count = row.as_ref().column_count();
+        // DECIMAL/NUMERIC と宣言された列は10進数として読み戻す
+        let decimal_columns: Vec<bool> = row
+            .as_ref()
+            .columns()
+            .iter()
+            .map(|c| {
+                c.decl_type().is_some_and(|t| {
+                    let t = t.to_ascii_uppercase();
+                    t.starts_with("DECIMAL") || t.starts_with("NUMERIC")
+                })
+            })
+            .collect();
 
         for i in 0..column_count {
             let column_name = map_db_err!(
@@ -113,12 +132,25 @@ impl SqliteConnection {
             )?
             .to_string();
 
+            let is_decimal_column = decimal_columns.get(i).copied().unwrap_or(false);
             let value = match row.get_ref(i) {
                 Ok(rusqlite::types::ValueRef::Null) => Value::Nil,
                 Ok(rusqlite::types::ValueRef::Integer(i)) => Value::Integer(i),
+                Ok(rusqlite::types::ValueRef::Real(f)) if is_decimal_column => {
+                    to_decimal(&Value::Float(f), "db/query")
+                        .map(Value::Decimal)
+                        .unwrap_or(Value::Float(f))
+                }
                 Ok(rusqlite::types::ValueRef::Real(f)) => Value::Float(f),
                 Ok(rusqlite::types::ValueRef::Text(t)) => {
-                    Value::String(String::from_utf8_lossy(t).to_string())
+                    let s = String::from_utf8_lossy(t).to_string();
+                    match is_decimal_column
+                        .then(|| s.parse::<rust_decimal::Decimal>().ok())
+                        .flatten()
+                    {
+                        Some(d) => Value::Decimal(d),
+                        None => Value::String(s),
+                    }
                 }
                 Ok(rusqlite::types::ValueRef::Blob(b)) => {
                     // BLOB型 → Bytes
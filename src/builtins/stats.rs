@@ -2,11 +2,24 @@
 //!
 //! このモジュールは `std-stats` feature でコンパイルされます。
 
+use crate::builtins::numeric_helpers::{arith, compare_numbers, is_number, number_to_f64, ArithOp};
 use crate::check_args;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::Value;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+/// 10進数または有理数を含み、Floatを含まないコレクションか
+///
+/// この場合、平均・中央値は誤差なく計算して正確な型で返す。
+/// 整数だけのコレクションは従来どおりFloatを返す。
+fn is_exact_collection(items: &im::Vector<Value>) -> bool {
+    items
+        .iter()
+        .any(|v| matches!(v, Value::Decimal(_) | Value::Ratio(_)))
+        && !items.iter().any(|v| matches!(v, Value::Float(_)))
+}
+
 /// mean - 平均値
 pub fn native_mean(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "stats/mean");
@@ -20,29 +33,29 @@ pub fn native_mean(args: &[Value]) -> Result<Value, String> {
                 ));
             }
 
-            let mut sum = 0.0;
-            let mut count = 0;
+            if !items.iter().all(is_number) {
+                return Err(fmt_msg(
+                    MsgKey::AllElementsMustBe,
+                    &["stats/mean", "numbers"],
+                ));
+            }
 
-            for item in items {
-                match item {
-                    Value::Integer(n) => {
-                        sum += *n as f64;
-                        count += 1;
-                    }
-                    Value::Float(f) => {
-                        sum += f;
-                        count += 1;
-                    }
-                    _ => {
-                        return Err(fmt_msg(
-                            MsgKey::AllElementsMustBe,
-                            &["stats/mean", "numbers"],
-                        ))
-                    }
+            // 10進数・有理数の平均は誤差なく計算する
+            if is_exact_collection(items) {
+                let mut sum = Value::Integer(0);
+                for item in items {
+                    sum = arith(ArithOp::Add, &sum, item, "stats/mean")?;
                 }
+                return arith(
+                    ArithOp::Div,
+                    &sum,
+                    &Value::Integer(items.len() as i64),
+                    "stats/mean",
+                );
             }
 
-            Ok(Value::Float(sum / count as f64))
+            let sum: f64 = items.iter().filter_map(number_to_f64).sum();
+            Ok(Value::Float(sum / items.len() as f64))
         }
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
@@ -64,10 +77,35 @@ pub fn native_median(args: &[Value]) -> Result<Value, String> {
                 ));
             }
 
+            if is_exact_collection(items) {
+                if !items.iter().all(is_number) {
+                    return Err(fmt_msg(
+                        MsgKey::AllElementsMustBe,
+                        &["stats/median", "numbers"],
+                    ));
+                }
+                // 10進数・有理数は誤差なく並べ替えて中央値を求める
+                let mut sorted: Vec<&Value> = items.iter().collect();
+                sorted.sort_by(|a, b| compare_numbers(a, b).unwrap_or(Ordering::Equal));
+                let len = sorted.len();
+                if !len.is_multiple_of(2) {
+                    return Ok(sorted[len / 2].clone());
+                }
+                let sum = arith(
+                    ArithOp::Add,
+                    sorted[len / 2 - 1],
+                    sorted[len / 2],
+                    "stats/median",
+                )?;
+                return arith(ArithOp::Div, &sum, &Value::Integer(2), "stats/median");
+            }
+
             let mut numbers: Vec<f64> = Vec::new();
             for item in items {
                 match item {
-                    Value::Integer(n) => numbers.push(*n as f64),
+                    Value::Integer(_) | Value::BigInt(_) => {
+                        numbers.push(number_to_f64(item).unwrap_or_default())
+                    }
                     Value::Float(f) => {
                         if f.is_nan() {
                             return Err(fmt_msg(
@@ -123,7 +161,11 @@ pub fn native_mode(args: &[Value]) -> Result<Value, String> {
 
             for item in items {
                 match item {
-                    Value::Integer(_) | Value::Float(_) => {
+                    Value::Integer(_)
+                    | Value::BigInt(_)
+                    | Value::Ratio(_)
+                    | Value::Decimal(_)
+                    | Value::Float(_) => {
                         let key = format!("{:?}", item);
                         freq.entry(key)
                             .and_modify(|(count, _)| *count += 1)
@@ -174,7 +216,9 @@ pub fn native_variance(args: &[Value]) -> Result<Value, String> {
             let mut numbers: Vec<f64> = Vec::new();
             for item in items {
                 match item {
-                    Value::Integer(n) => numbers.push(*n as f64),
+                    Value::Integer(_) | Value::BigInt(_) | Value::Ratio(_) | Value::Decimal(_) => {
+                        numbers.push(number_to_f64(item).unwrap_or_default())
+                    }
                     Value::Float(f) => {
                         if f.is_nan() {
                             return Err(fmt_msg(
@@ -259,7 +303,9 @@ pub fn native_percentile(args: &[Value]) -> Result<Value, String> {
             let mut numbers: Vec<f64> = Vec::new();
             for item in items {
                 match item {
-                    Value::Integer(n) => numbers.push(*n as f64),
+                    Value::Integer(_) | Value::BigInt(_) | Value::Ratio(_) | Value::Decimal(_) => {
+                        numbers.push(number_to_f64(item).unwrap_or_default())
+                    }
                     Value::Float(f) => {
                         if f.is_nan() {
                             return Err(fmt_msg(
@@ -0,0 +1,290 @@
+//! STM（ソフトウェアトランザクショナルメモリ） - ref / dosync / alter / commute / ensure
+//!
+//! 複数の共有状態（口座間の在庫移動など）をロックなしで協調的に更新します。
+//!
+//! - `dosync` のボディ内で `alter` / `commute` した値はトランザクション内にだけ見え、
+//!   コミット時にまとめて反映されます
+//! - 読み取った（deref・alter・ensureした）refがコミットまでに他のトランザクションで
+//!   更新されていた場合は、ボディを最初から再実行します（副作用は繰り返される可能性あり）
+//! - `commute` は競合を起こさず、コミット時に最新の値へ関数を再適用します
+//!
+//! コミット時は関係するrefをID順にロックし、デッドロックを避けます。
+
+use crate::check_args;
+use crate::eval::Evaluator;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{Ref, RefState, Value};
+use parking_lot::RwLock;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// refのID採番用カウンター
+static REF_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// コミットできない場合のリトライ上限
+const MAX_RETRIES: usize = 10_000;
+
+/// 実行中のトランザクション
+#[derive(Default)]
+struct Transaction {
+    /// トランザクション内の値（refのID → (ref, 値, 最初に複製した時点のバージョン)）
+    values: HashMap<u64, (Arc<Ref>, Value, u64)>,
+    /// 読み取った時点のバージョン（コミット時に変更されていないか検証）
+    reads: HashMap<u64, u64>,
+    /// alterで更新したrefのID
+    altered: HashSet<u64>,
+    /// commuteの操作（コミット時に最新の値へ再適用）
+    commutes: Vec<(Arc<Ref>, Value, Vec<Value>)>,
+}
+
+// dosyncはスレッド単位（go/run等で起動したタスクは外側のトランザクションに参加しない）
+thread_local! {
+    static TXN: RefCell<Option<Transaction>> = const { RefCell::new(None) };
+}
+
+/// RAIIガード: Drop時に必ずトランザクションを破棄（ボディがエラーで抜けた場合も含む）
+struct TxnGuard;
+
+impl TxnGuard {
+    fn begin() -> Self {
+        TXN.with(|t| *t.borrow_mut() = Some(Transaction::default()));
+        TxnGuard
+    }
+
+    /// トランザクションを取り出して終了
+    fn finish(self) -> Transaction {
+        TXN.with(|t| t.borrow_mut().take()).unwrap_or_default()
+    }
+}
+
+impl Drop for TxnGuard {
+    fn drop(&mut self) {
+        TXN.with(|t| *t.borrow_mut() = None);
+    }
+}
+
+fn in_transaction() -> bool {
+    TXN.with(|t| t.borrow().is_some())
+}
+
+/// トランザクション内の値を取得（トランザクション外ならNone）
+///
+/// record_readがtrueなら読み取り時のバージョンを記録し、コミット時の競合検出対象にする
+fn txn_value(r: &Arc<Ref>, record_read: bool) -> Option<Value> {
+    TXN.with(|t| {
+        let mut t = t.borrow_mut();
+        let txn = t.as_mut()?;
+        let value = match txn.values.get(&r.id) {
+            Some((_, v, version)) => {
+                // 値を複製した時点のバージョンで記録する（現在のバージョンを記録すると、
+                // その後に他でコミットされた更新を見落とす）
+                if record_read && !txn.reads.contains_key(&r.id) {
+                    txn.reads.insert(r.id, *version);
+                }
+                v.clone()
+            }
+            None => {
+                let state = r.state.read();
+                if record_read {
+                    txn.reads.insert(r.id, state.version);
+                }
+                txn.values
+                    .insert(r.id, (Arc::clone(r), state.value.clone(), state.version));
+                state.value.clone()
+            }
+        };
+        Some(value)
+    })
+}
+
+/// トランザクション内の値を更新（txn_valueで複製済みのエントリの値だけを差し替える）
+fn set_txn_value(r: &Arc<Ref>, value: Value, update: impl FnOnce(&mut Transaction)) {
+    TXN.with(|t| {
+        if let Some(txn) = t.borrow_mut().as_mut() {
+            if let Some(entry) = txn.values.get_mut(&r.id) {
+                entry.1 = value;
+            }
+            update(txn);
+        }
+    });
+}
+
+fn expect_ref<'a>(value: &'a Value, name: &str) -> Result<&'a Arc<Ref>, String> {
+    match value {
+        Value::Ref(r) => Ok(r),
+        _ => Err(fmt_msg(MsgKey::FirstArgMustBe, &[name, "a ref"])),
+    }
+}
+
+/// refの値を取得（dosync内ではトランザクション内の値）
+pub(crate) fn deref_ref(r: &Arc<Ref>) -> Value {
+    txn_value(r, true).unwrap_or_else(|| r.state.read().value.clone())
+}
+
+/// commuteを最新の値へ再適用した結果を計算（refID → (新しい値, 元にしたバージョン)）
+///
+/// 関数内でrefを参照してもデッドロックしないよう、ロックを取らずに複製した値で計算する
+/// （alterしたrefはトランザクション内の値をそのまま使うため対象外）
+fn compute_commutes(
+    txn: &Transaction,
+    evaluator: &Evaluator,
+) -> Result<HashMap<u64, (Value, u64)>, String> {
+    let mut commuted: HashMap<u64, (Value, u64)> = HashMap::new();
+    for (r, func, extra) in &txn.commutes {
+        if txn.altered.contains(&r.id) {
+            continue;
+        }
+        let (current, version) = commuted.remove(&r.id).unwrap_or_else(|| {
+            let state = r.state.read();
+            (state.value.clone(), state.version)
+        });
+        let mut func_args = vec![current];
+        func_args.extend(extra.iter().cloned());
+        commuted.insert(r.id, (evaluator.apply_function(func, &func_args)?, version));
+    }
+    Ok(commuted)
+}
+
+/// トランザクションをコミット（競合を検出した場合はfalse）
+fn commit(txn: Transaction, evaluator: &Evaluator) -> Result<bool, String> {
+    let mut refs: Vec<Arc<Ref>> = txn.values.values().map(|(r, _, _)| Arc::clone(r)).collect();
+    refs.sort_by_key(|r| r.id);
+    let positions: HashMap<u64, usize> = refs.iter().enumerate().map(|(i, r)| (r.id, i)).collect();
+    let index = |id: u64| positions[&id];
+
+    for _ in 0..MAX_RETRIES {
+        let commuted = compute_commutes(&txn, evaluator)?;
+        let mut guards: Vec<_> = refs.iter().map(|r| r.state.write()).collect();
+
+        // 読み取ったrefが他のトランザクションで更新されていれば再実行
+        for (id, version) in &txn.reads {
+            if guards[index(*id)].version != *version {
+                return Ok(false);
+            }
+        }
+
+        // commuteの計算後に更新されていれば、ロックを解放して計算し直す
+        if commuted
+            .iter()
+            .any(|(id, (_, version))| guards[index(*id)].version != *version)
+        {
+            drop(guards);
+            std::thread::yield_now();
+            continue;
+        }
+
+        for id in &txn.altered {
+            let state = &mut guards[index(*id)];
+            state.value = txn.values[id].1.clone();
+            state.version += 1;
+        }
+        for (id, (value, _)) in commuted {
+            let state = &mut guards[index(id)];
+            state.value = value;
+            state.version += 1;
+        }
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+/// dosync - ボディをトランザクション内で実行し、競合時はリトライ
+///
+/// 入れ子のdosyncは外側のトランザクションに参加する
+pub fn dosync(
+    evaluator: &Evaluator,
+    mut body: impl FnMut() -> Result<Value, String>,
+) -> Result<Value, String> {
+    if in_transaction() {
+        return body();
+    }
+
+    for _ in 0..MAX_RETRIES {
+        let guard = TxnGuard::begin();
+        let result = body()?;
+        let txn = guard.finish();
+        if commit(txn, evaluator)? {
+            return Ok(result);
+        }
+        std::thread::yield_now();
+    }
+    Err(fmt_msg(
+        MsgKey::TransactionRetryLimit,
+        &[&MAX_RETRIES.to_string()],
+    ))
+}
+
+/// ref - STM参照を作成
+pub fn native_ref(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "ref");
+
+    Ok(Value::Ref(Arc::new(Ref {
+        id: REF_COUNTER.fetch_add(1, Ordering::SeqCst),
+        state: RwLock::new(RefState {
+            value: args[0].clone(),
+            version: 0,
+        }),
+    })))
+}
+
+/// alter - トランザクション内でrefに関数を適用して更新
+///
+/// 読み取った値がコミットまでに他で更新されていればトランザクションを再実行
+pub fn native_alter(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["alter", "2"]));
+    }
+    let r = expect_ref(&args[0], "alter")?;
+    let current = txn_value(r, true).ok_or_else(|| fmt_msg(MsgKey::NoTransaction, &["alter"]))?;
+
+    let mut func_args = vec![current];
+    func_args.extend_from_slice(&args[2..]);
+    let new_value = evaluator.apply_function(&args[1], &func_args)?;
+
+    set_txn_value(r, new_value.clone(), |txn| {
+        txn.altered.insert(r.id);
+    });
+    Ok(new_value)
+}
+
+/// commute - 順序に依存しない更新（+ や conj など）をトランザクション内で適用
+///
+/// 競合を起こさず、コミット時に最新の値へ関数を再適用する
+pub fn native_commute(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["commute", "2"]));
+    }
+    let r = expect_ref(&args[0], "commute")?;
+    let current =
+        txn_value(r, false).ok_or_else(|| fmt_msg(MsgKey::NoTransaction, &["commute"]))?;
+
+    let mut func_args = vec![current];
+    func_args.extend_from_slice(&args[2..]);
+    let new_value = evaluator.apply_function(&args[1], &func_args)?;
+
+    let func = args[1].clone();
+    let extra = args[2..].to_vec();
+    set_txn_value(r, new_value.clone(), |txn| {
+        txn.commutes.push((Arc::clone(r), func, extra));
+    });
+    Ok(new_value)
+}
+
+/// ensure - refを更新せずに読み取りとして保護（コミットまでに他で更新されたら再実行）
+///
+/// 書き込まないrefに依存した判断（残高チェック等）の書き込みスキューを防ぐ
+pub fn native_ensure(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "ensure");
+
+    let r = expect_ref(&args[0], "ensure")?;
+    txn_value(r, true).ok_or_else(|| fmt_msg(MsgKey::NoTransaction, &["ensure"]))
+}
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category core/stm
+/// @qi-doc:functions ref, ensure, alter, commute, dosync
+///
+/// 注意: alter, commute, dosyncはEvaluatorが必要なため、mod.rsで別途登録されます
+pub const FUNCTIONS: super::NativeFunctions = &[("ref", native_ref), ("ensure", native_ensure)];
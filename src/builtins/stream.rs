@@ -1,5 +1,6 @@
 //! ストリーム（遅延評価）- メモリ内の無限データ構造
 
+use crate::builtins::value_helpers::values_equal;
 use crate::check_args;
 use crate::eval::Evaluator;
 use crate::i18n::{fmt_msg, MsgKey};
@@ -318,6 +319,384 @@ pub fn native_realize(args: &[Value]) -> Result<Value, String> {
     Ok(Value::List(result.into()))
 }
 
+// ========================================
+// コアシーケンス関数の遅延版
+// ========================================
+//
+// take-while / mapcat / dedupe 等のコア関数にStreamが渡された場合、
+// ここで遅延Streamを構築して返す（|> パイプラインを realize まで遅延させる）。
+// 関数適用でのエラーは黙って捨てず、{:error ...} 要素としてストリームに流す。
+
+/// next_fnからストリーム値を作成
+fn make_stream(next_fn: impl Fn() -> Option<Value> + Send + Sync + 'static) -> Value {
+    Value::Stream(Arc::new(RwLock::new(Stream {
+        next_fn: Box::new(next_fn),
+    })))
+}
+
+/// ストリームから次の要素を1つ取り出す
+fn pull(source: &Arc<RwLock<Stream>>) -> Option<Value> {
+    let stream = source.read();
+    (stream.next_fn)()
+}
+
+/// map（遅延版）- エラーになった要素は{:error}要素に置き換えて続行
+pub(crate) fn lazy_map(func: &Value, source: &Arc<RwLock<Stream>>, evaluator: &Evaluator) -> Value {
+    let func = func.clone();
+    let source = source.clone();
+    let evaluator = evaluator.clone();
+
+    make_stream(move || {
+        let v = pull(&source)?;
+        Some(
+            evaluator
+                .apply_function(&func, &[v])
+                .unwrap_or_else(Value::error),
+        )
+    })
+}
+
+/// filter（遅延版）- 述語がエラーになった要素は{:error}要素に置き換えて続行
+pub(crate) fn lazy_filter(
+    pred: &Value,
+    source: &Arc<RwLock<Stream>>,
+    evaluator: &Evaluator,
+) -> Value {
+    let pred = pred.clone();
+    let source = source.clone();
+    let evaluator = evaluator.clone();
+
+    make_stream(move || loop {
+        let v = pull(&source)?;
+        match evaluator.apply_function(&pred, std::slice::from_ref(&v)) {
+            Ok(result) if result.is_truthy() => return Some(v),
+            Ok(_) => {}
+            Err(e) => return Some(Value::error(e)),
+        }
+    })
+}
+
+/// take-while（遅延版）- 述語が偽になった時点で終端（エラーは{:error}要素を返して終端）
+pub(crate) fn lazy_take_while(
+    pred: &Value,
+    source: &Arc<RwLock<Stream>>,
+    evaluator: &Evaluator,
+) -> Value {
+    let pred = pred.clone();
+    let source = source.clone();
+    let evaluator = evaluator.clone();
+    let done = Arc::new(RwLock::new(false));
+
+    make_stream(move || {
+        let mut done = done.write();
+        if *done {
+            return None;
+        }
+        let v = pull(&source)?;
+        match evaluator.apply_function(&pred, std::slice::from_ref(&v)) {
+            Ok(result) if result.is_truthy() => Some(v),
+            Ok(_) => {
+                *done = true;
+                None
+            }
+            Err(e) => {
+                *done = true;
+                Some(Value::error(e))
+            }
+        }
+    })
+}
+
+/// drop-while（遅延版）- 述語が真の間の先頭要素をスキップ（エラーは{:error}要素を返してスキップを終える）
+pub(crate) fn lazy_drop_while(
+    pred: &Value,
+    source: &Arc<RwLock<Stream>>,
+    evaluator: &Evaluator,
+) -> Value {
+    let pred = pred.clone();
+    let source = source.clone();
+    let evaluator = evaluator.clone();
+    let dropping = Arc::new(RwLock::new(true));
+
+    make_stream(move || {
+        let mut dropping = dropping.write();
+        loop {
+            let v = pull(&source)?;
+            if *dropping {
+                match evaluator.apply_function(&pred, std::slice::from_ref(&v)) {
+                    Ok(result) if result.is_truthy() => continue,
+                    Ok(_) => *dropping = false,
+                    Err(e) => {
+                        *dropping = false;
+                        return Some(Value::error(e));
+                    }
+                }
+            }
+            return Some(v);
+        }
+    })
+}
+
+/// mapcat（遅延版）- 関数が返したリスト/ベクタを平坦化
+pub(crate) fn lazy_mapcat(
+    func: &Value,
+    source: &Arc<RwLock<Stream>>,
+    evaluator: &Evaluator,
+) -> Value {
+    let func = func.clone();
+    let source = source.clone();
+    let evaluator = evaluator.clone();
+    let pending = Arc::new(RwLock::new(im::Vector::new()));
+
+    make_stream(move || {
+        let mut pending = pending.write();
+        loop {
+            if let Some(v) = pending.pop_front() {
+                return Some(v);
+            }
+            let v = pull(&source)?;
+            match evaluator.apply_function(&func, &[v]) {
+                Ok(Value::List(items) | Value::Vector(items)) => *pending = items,
+                Ok(Value::Nil) => {}
+                Ok(_) => {
+                    return Some(Value::error(fmt_msg(
+                        MsgKey::FuncMustReturnType,
+                        &["mapcat", "a list or vector"],
+                    )))
+                }
+                Err(e) => return Some(Value::error(e)),
+            }
+        }
+    })
+}
+
+/// keep（遅延版）- 関数を適用し、nilの結果を除外
+pub(crate) fn lazy_keep(
+    func: &Value,
+    source: &Arc<RwLock<Stream>>,
+    evaluator: &Evaluator,
+) -> Value {
+    let func = func.clone();
+    let source = source.clone();
+    let evaluator = evaluator.clone();
+
+    make_stream(move || loop {
+        let v = pull(&source)?;
+        match evaluator.apply_function(&func, &[v]) {
+            Ok(Value::Nil) => {}
+            Ok(result) => return Some(result),
+            Err(e) => return Some(Value::error(e)),
+        }
+    })
+}
+
+/// partition（遅延版）- 述語が真の要素と偽の要素の2つのストリームに分ける
+///
+/// 戻り値は `[真のストリーム 偽のストリーム]`。一方を読み進めると、もう一方に
+/// 振り分けた要素は読まれるまでバッファに残る。述語のエラーは両方に{:error}要素として流す。
+pub(crate) fn lazy_partition(
+    pred: &Value,
+    source: &Arc<RwLock<Stream>>,
+    evaluator: &Evaluator,
+) -> Value {
+    type Queues = (
+        std::collections::VecDeque<Value>,
+        std::collections::VecDeque<Value>,
+    );
+    let queues: Arc<parking_lot::Mutex<Queues>> = Arc::default();
+
+    let side = |truthy: bool| {
+        let pred = pred.clone();
+        let source = source.clone();
+        let evaluator = evaluator.clone();
+        let queues = queues.clone();
+
+        make_stream(move || {
+            let mut queues = queues.lock();
+            loop {
+                let own = if truthy { &mut queues.0 } else { &mut queues.1 };
+                if let Some(v) = own.pop_front() {
+                    return Some(v);
+                }
+                let v = pull(&source)?;
+                match evaluator.apply_function(&pred, std::slice::from_ref(&v)) {
+                    Ok(result) if result.is_truthy() == truthy => return Some(v),
+                    Ok(_) if truthy => queues.1.push_back(v),
+                    Ok(_) => queues.0.push_back(v),
+                    Err(e) => {
+                        let err = Value::error(e);
+                        if truthy {
+                            queues.1.push_back(err.clone());
+                        } else {
+                            queues.0.push_back(err.clone());
+                        }
+                        return Some(err);
+                    }
+                }
+            }
+        })
+    };
+
+    Value::Vector(im::vector![side(true), side(false)])
+}
+
+/// partition-by（遅延版）- 関数の結果が変わるごとにグループ（リスト）を生成
+///
+/// 関数のエラーは、それまでのグループに続けて{:error}要素を返して終端
+pub(crate) fn lazy_partition_by(
+    func: &Value,
+    source: &Arc<RwLock<Stream>>,
+    evaluator: &Evaluator,
+) -> Value {
+    /// 次の呼び出しで返すもの
+    enum Next {
+        /// ソースから読む
+        Pull,
+        /// 次のグループの先頭として先読みした (要素, 関数の結果)
+        Group(Value, Value),
+        /// 関数のエラー（返したあと終端）
+        Error(Value),
+        Done,
+    }
+
+    let func = func.clone();
+    let source = source.clone();
+    let evaluator = evaluator.clone();
+    let state = Arc::new(RwLock::new(Next::Pull));
+
+    make_stream(move || {
+        let mut state = state.write();
+        let apply = |v: &Value| {
+            evaluator
+                .apply_function(&func, std::slice::from_ref(v))
+                .map_err(Value::error)
+        };
+
+        let (first, key) = match std::mem::replace(&mut *state, Next::Pull) {
+            Next::Group(v, key) => (v, key),
+            Next::Pull => {
+                let v = pull(&source)?;
+                match apply(&v) {
+                    Ok(key) => (v, key),
+                    Err(err) => {
+                        *state = Next::Done;
+                        return Some(err);
+                    }
+                }
+            }
+            Next::Error(err) => {
+                *state = Next::Done;
+                return Some(err);
+            }
+            Next::Done => {
+                *state = Next::Done;
+                return None;
+            }
+        };
+
+        let mut group = im::vector![first];
+        while let Some(v) = pull(&source) {
+            match apply(&v) {
+                Ok(k) if values_equal(&k, &key) => group.push_back(v),
+                Ok(k) => {
+                    *state = Next::Group(v, k);
+                    break;
+                }
+                Err(err) => {
+                    *state = Next::Error(err);
+                    break;
+                }
+            }
+        }
+        Some(Value::List(group))
+    })
+}
+
+/// chunk（遅延版）- size個ずつのリストに分割（最後は端数）
+pub(crate) fn lazy_chunk(size: usize, source: &Arc<RwLock<Stream>>) -> Value {
+    let source = source.clone();
+
+    make_stream(move || {
+        let mut chunk = im::Vector::new();
+        while chunk.len() < size {
+            match pull(&source) {
+                Some(v) => chunk.push_back(v),
+                None => break,
+            }
+        }
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(Value::List(chunk))
+        }
+    })
+}
+
+/// take-nth（遅延版）- 先頭から n 個ごとの要素を取得
+pub(crate) fn lazy_take_nth(n: usize, source: &Arc<RwLock<Stream>>) -> Value {
+    let source = source.clone();
+    let started = Arc::new(RwLock::new(false));
+
+    make_stream(move || {
+        let mut started = started.write();
+        if *started {
+            for _ in 1..n {
+                pull(&source)?;
+            }
+        }
+        *started = true;
+        pull(&source)
+    })
+}
+
+/// dedupe（遅延版）- 連続する重複を除去
+pub(crate) fn lazy_dedupe(source: &Arc<RwLock<Stream>>) -> Value {
+    let source = source.clone();
+    let last: Arc<RwLock<Option<Value>>> = Arc::new(RwLock::new(None));
+
+    make_stream(move || {
+        let mut last = last.write();
+        loop {
+            let v = pull(&source)?;
+            if last.as_ref().is_some_and(|prev| values_equal(prev, &v)) {
+                continue;
+            }
+            *last = Some(v.clone());
+            return Some(v);
+        }
+    })
+}
+
+/// interleave（遅延版）- 2つのストリームから交互に取り出す（短い方で終端）
+pub(crate) fn lazy_interleave(first: &Arc<RwLock<Stream>>, second: &Arc<RwLock<Stream>>) -> Value {
+    let first = first.clone();
+    let second = second.clone();
+    // 2番目のストリームから先に取り出した要素
+    let buffered: Arc<RwLock<Option<Value>>> = Arc::new(RwLock::new(None));
+
+    make_stream(move || {
+        let mut buffered = buffered.write();
+        if let Some(v) = buffered.take() {
+            return Some(v);
+        }
+        let a = pull(&first)?;
+        *buffered = Some(pull(&second)?);
+        Some(a)
+    })
+}
+
+/// コレクションまたはストリームをストリームとして取得（interleave等の混在引数用）
+pub(crate) fn as_stream(value: &Value) -> Option<Arc<RwLock<Stream>>> {
+    match value {
+        Value::Stream(s) => Some(s.clone()),
+        Value::List(_) | Value::Vector(_) => match native_stream(std::slice::from_ref(value)) {
+            Ok(Value::Stream(s)) => Some(s),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 // ========================================
 // 関数登録テーブル
 // ========================================
@@ -18,6 +18,9 @@ use uuid::Uuid;
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 // ========================================
 // Regexキャッシュ（グローバル）
@@ -27,7 +30,7 @@ use regex::Regex;
 static REGEX_CACHE: Lazy<DashMap<String, Regex>> = Lazy::new(DashMap::new);
 
 /// Regexキャッシュから取得または新規コンパイル
-fn get_or_compile_regex(pattern: &str) -> Result<Regex, regex::Error> {
+pub(crate) fn get_or_compile_regex(pattern: &str) -> Result<Regex, regex::Error> {
     // キャッシュヒット時はcloneして返す
     if let Some(re) = REGEX_CACHE.get(pattern) {
         return Ok(re.clone());
@@ -354,148 +357,80 @@ pub fn native_upper_p(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Bool(has_alpha && all_upper))
 }
 
-/// pad-left - 左側に文字を詰める
-pub fn native_pad_left(args: &[Value]) -> Result<Value, String> {
-    if args.len() < 2 || args.len() > 3 {
-        return Err(fmt_msg(MsgKey::Need2Or3Args, &["pad-left"]));
-    }
-    match (&args[0], &args[1]) {
-        (Value::String(s), Value::Integer(width)) => {
-            let pad_char = if args.len() == 3 {
-                match &args[2] {
-                    Value::String(ch) if ch.chars().count() == 1 => {
-                        // count() == 1 をチェック済みのため安全
-                        #[allow(clippy::expect_used)]
-                        ch.chars().next().expect("checked by count == 1 above")
-                    }
-                    _ => {
-                        return Err(fmt_msg(
-                            MsgKey::TypeOnly,
-                            &["pad-left (3rd arg)", "single character"],
-                        ))
-                    }
-                }
-            } else {
-                ' '
-            };
-            let width = (*width).max(0) as usize;
-            let char_count = s.chars().count();
-
-            if char_count >= width {
-                return Ok(Value::String(s.clone()));
-            }
+/// パディングの配置
+#[derive(Clone, Copy)]
+enum PadAlign {
+    Left,
+    Right,
+    Center,
+}
 
-            let pad_count = width - char_count;
-            // String::with_capacity + push_strで効率的に構築
-            let mut result = String::with_capacity(width * pad_char.len_utf8());
-            for _ in 0..pad_count {
-                result.push(pad_char);
-            }
-            result.push_str(s);
-            Ok(Value::String(result))
-        }
-        _ => Err(fmt_msg(
-            MsgKey::TypeOnly,
-            &["pad-left", "string and integer"],
-        )),
-    }
+/// 表示幅（East Asian Widthを考慮し、全角文字・絵文字は2桁）
+fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
 }
 
-/// pad-right - 右側に文字を詰める
-pub fn native_pad_right(args: &[Value]) -> Result<Value, String> {
+/// 表示幅で文字列を詰める（pad-left / pad-right / pad の共通処理）
+///
+/// 詰め文字は文字または1文字（書記素）の文字列。全角の詰め文字で端数が出る場合は空白で埋める
+fn pad_to_width(args: &[Value], name: &str, align: PadAlign) -> Result<Value, String> {
     if args.len() < 2 || args.len() > 3 {
-        return Err(fmt_msg(MsgKey::Need2Or3Args, &["pad-right"]));
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &[name]));
     }
     match (&args[0], &args[1]) {
         (Value::String(s), Value::Integer(width)) => {
-            let pad_char = if args.len() == 3 {
+            let pad = if args.len() == 3 {
                 match &args[2] {
-                    Value::String(ch) if ch.chars().count() == 1 => {
-                        // count() == 1 をチェック済みのため安全
-                        #[allow(clippy::expect_used)]
-                        ch.chars().next().expect("checked by count == 1 above")
-                    }
+                    Value::Char(c) => c.to_string(),
+                    Value::String(ch) if ch.graphemes(true).count() == 1 => ch.clone(),
                     _ => {
                         return Err(fmt_msg(
                             MsgKey::TypeOnly,
-                            &["pad-right (3rd arg)", "single character"],
+                            &[&format!("{} (3rd arg)", name), "single character"],
                         ))
                     }
                 }
             } else {
-                ' '
+                " ".to_string()
             };
+            let pad_width = display_width(&pad).max(1);
             let width = (*width).max(0) as usize;
-            let char_count = s.chars().count();
+            let current = display_width(s);
 
-            if char_count >= width {
+            if current >= width {
                 return Ok(Value::String(s.clone()));
             }
 
-            let pad_count = width - char_count;
-            // String::with_capacity + push_strで効率的に構築
-            let mut result = String::with_capacity(width * pad_char.len_utf8());
-            result.push_str(s);
-            for _ in 0..pad_count {
-                result.push(pad_char);
-            }
-            Ok(Value::String(result))
+            let fill = |cols: usize| {
+                let mut out = pad.repeat(cols / pad_width);
+                out.push_str(&" ".repeat(cols % pad_width));
+                out
+            };
+            let total = width - current;
+            let (left, right) = match align {
+                PadAlign::Left => (total, 0),
+                PadAlign::Right => (0, total),
+                PadAlign::Center => (total / 2, total - total / 2),
+            };
+            Ok(Value::String(format!("{}{}{}", fill(left), s, fill(right))))
         }
-        _ => Err(fmt_msg(
-            MsgKey::TypeOnly,
-            &["pad-right", "string and integer"],
-        )),
+        _ => Err(fmt_msg(MsgKey::TypeOnly, &[name, "string and integer"])),
     }
 }
 
-/// pad - 中央揃え
-pub fn native_pad(args: &[Value]) -> Result<Value, String> {
-    if args.len() < 2 || args.len() > 3 {
-        return Err(fmt_msg(MsgKey::Need2Or3Args, &["pad"]));
-    }
-    match (&args[0], &args[1]) {
-        (Value::String(s), Value::Integer(width)) => {
-            let pad_char = if args.len() == 3 {
-                match &args[2] {
-                    Value::String(ch) if ch.chars().count() == 1 => {
-                        // count() == 1 をチェック済みのため安全
-                        #[allow(clippy::expect_used)]
-                        ch.chars().next().expect("checked by count == 1 above")
-                    }
-                    _ => {
-                        return Err(fmt_msg(
-                            MsgKey::TypeOnly,
-                            &["pad (3rd arg)", "single character"],
-                        ))
-                    }
-                }
-            } else {
-                ' '
-            };
-            let width = (*width).max(0) as usize;
-            let char_count = s.chars().count();
-
-            if char_count >= width {
-                return Ok(Value::String(s.clone()));
-            }
+/// pad-left - 左側に文字を詰める（表示幅基準）
+pub fn native_pad_left(args: &[Value]) -> Result<Value, String> {
+    pad_to_width(args, "pad-left", PadAlign::Left)
+}
 
-            let total_pad = width - char_count;
-            let left_pad = total_pad / 2;
-            let right_pad = total_pad - left_pad;
+/// pad-right - 右側に文字を詰める（表示幅基準）
+pub fn native_pad_right(args: &[Value]) -> Result<Value, String> {
+    pad_to_width(args, "pad-right", PadAlign::Right)
+}
 
-            // String::with_capacity + push_strで効率的に構築
-            let mut result = String::with_capacity(width * pad_char.len_utf8());
-            for _ in 0..left_pad {
-                result.push(pad_char);
-            }
-            result.push_str(s);
-            for _ in 0..right_pad {
-                result.push(pad_char);
-            }
-            Ok(Value::String(result))
-        }
-        _ => Err(fmt_msg(MsgKey::TypeOnly, &["pad", "string and integer"])),
-    }
+/// pad - 中央揃え（表示幅基準）
+pub fn native_pad(args: &[Value]) -> Result<Value, String> {
+    pad_to_width(args, "pad", PadAlign::Center)
 }
 
 /// squish - 連続空白を1つに、前後trim
@@ -541,20 +476,66 @@ pub fn native_title(args: &[Value]) -> Result<Value, String> {
     Ok(Value::String(result))
 }
 
-/// reverse - 文字列を反転
+/// reverse - 文字列を反転（書記素クラスタ単位のため絵文字・結合文字も崩れない）
 pub fn native_reverse(args: &[Value]) -> Result<Value, String> {
     let s = require_string!(args, "reverse");
-    let reversed: String = s.chars().rev().collect();
+    let reversed: String = s.graphemes(true).rev().collect();
     Ok(Value::String(reversed))
 }
 
-/// chars - 文字列を文字のリストに分割
+/// chars - 文字列を文字（Unicodeスカラー値）のベクタに分割
 pub fn native_chars(args: &[Value]) -> Result<Value, String> {
     let s = require_string!(args, "chars");
-    let chars = im::Vector::from_iter(s.chars().map(|c| Value::String(c.to_string())));
+    let chars = im::Vector::from_iter(s.chars().map(Value::Char));
     Ok(Value::Vector(chars))
 }
 
+/// graphemes - 文字列を書記素クラスタ（見た目の1文字）のベクタに分割
+pub fn native_graphemes(args: &[Value]) -> Result<Value, String> {
+    let s = require_string!(args, "graphemes");
+    let graphemes = im::Vector::from_iter(s.graphemes(true).map(|g| Value::String(g.to_string())));
+    Ok(Value::Vector(graphemes))
+}
+
+/// width - 表示幅（全角文字・絵文字は2桁）
+pub fn native_width(args: &[Value]) -> Result<Value, String> {
+    let s = require_string!(args, "width");
+    Ok(Value::Integer(display_width(s) as i64))
+}
+
+/// normalize - Unicode正規化（デフォルトはNFC）
+pub fn native_normalize(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["normalize"]));
+    }
+    let Value::String(s) = &args[0] else {
+        return Err(fmt_msg(MsgKey::TypeOnly, &["normalize", "strings"]));
+    };
+    let form = match args.get(1) {
+        None => "nfc",
+        Some(Value::Keyword(k)) => k.as_ref(),
+        Some(other) => {
+            return Err(fmt_msg(
+                MsgKey::InvalidNormalizationForm,
+                &["str/normalize", &other.to_string()],
+            ))
+        }
+    };
+    let normalized: String = match form {
+        "nfc" => s.nfc().collect(),
+        "nfd" => s.nfd().collect(),
+        "nfkc" => s.nfkc().collect(),
+        "nfkd" => s.nfkd().collect(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::InvalidNormalizationForm,
+                &["str/normalize", &format!(":{}", form)],
+            ))
+        }
+    };
+    Ok(Value::String(normalized))
+}
+
 // ケース変換のヘルパー関数
 fn split_words(s: &str) -> Vec<String> {
     let mut words = Vec::new();
@@ -654,7 +635,7 @@ pub fn native_split_camel(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Vector(result.into()))
 }
 
-/// truncate - 指定長で切り詰め
+/// truncate - 指定した表示幅で切り詰め（suffixの幅を含む）
 pub fn native_truncate(args: &[Value]) -> Result<Value, String> {
     if args.len() < 2 || args.len() > 3 {
         return Err(fmt_msg(MsgKey::Need2Or3Args, &["truncate"]));
@@ -670,18 +651,24 @@ pub fn native_truncate(args: &[Value]) -> Result<Value, String> {
                 "...".to_string()
             };
 
-            let max_len = (*max_len).max(0) as usize;
-            let chars: Vec<char> = s.chars().collect();
+            // 表示幅で判定し、書記素クラスタの途中では切らない
+            let max_width = (*max_len).max(0) as usize;
+            if display_width(s) <= max_width {
+                return Ok(Value::String(s.clone()));
+            }
 
-            if chars.len() <= max_len {
-                Ok(Value::String(s.clone()))
-            } else {
-                let truncated: String = chars
-                    .iter()
-                    .take(max_len.saturating_sub(suffix.len()))
-                    .collect();
-                Ok(Value::String(truncated + &suffix))
+            let budget = max_width.saturating_sub(display_width(&suffix));
+            let mut truncated = String::with_capacity(s.len());
+            let mut used = 0;
+            for g in s.graphemes(true) {
+                let w = display_width(g);
+                if used + w > budget {
+                    break;
+                }
+                truncated.push_str(g);
+                used += w;
             }
+            Ok(Value::String(truncated + &suffix))
         }
         _ => Err(fmt_msg(
             MsgKey::TypeOnly,
@@ -821,25 +808,42 @@ pub fn native_indent(args: &[Value]) -> Result<Value, String> {
     }
 }
 
-/// wrap - 指定幅で改行
+/// wrap - 指定した表示幅で改行
+///
+/// 空白で区切られた単語単位で折り返し、幅を超える単語（空白のない日本語の文など）は
+/// 書記素クラスタ単位で分割する
 pub fn native_wrap(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 2, "wrap");
     match (&args[0], &args[1]) {
         (Value::String(s), Value::Integer(width)) => {
             let width = (*width).max(1) as usize;
-            let words: Vec<&str> = s.split_whitespace().collect();
             let mut lines = Vec::new();
             let mut current_line = String::new();
+            let mut current_width = 0;
 
-            for word in words {
-                if current_line.is_empty() {
+            for word in s.split_whitespace() {
+                let word_width = display_width(word);
+                if current_line.is_empty() && word_width <= width {
                     current_line.push_str(word);
-                } else if current_line.len() + 1 + word.len() <= width {
+                    current_width = word_width;
+                } else if !current_line.is_empty() && current_width + 1 + word_width <= width {
                     current_line.push(' ');
                     current_line.push_str(word);
+                    current_width += 1 + word_width;
                 } else {
-                    lines.push(current_line.clone());
-                    current_line = word.to_string();
+                    if !current_line.is_empty() {
+                        lines.push(std::mem::take(&mut current_line));
+                        current_width = 0;
+                    }
+                    for g in word.graphemes(true) {
+                        let w = display_width(g);
+                        if current_width + w > width && !current_line.is_empty() {
+                            lines.push(std::mem::take(&mut current_line));
+                            current_width = 0;
+                        }
+                        current_line.push_str(g);
+                        current_width += w;
+                    }
                 }
             }
 
@@ -1279,6 +1283,10 @@ pub fn native_format(args: &[Value]) -> Result<Value, String> {
             Value::String(s) => s.clone(),
             Value::Integer(n) => n.to_string(),
             Value::Float(f) => f.to_string(),
+            Value::BigInt(n) => n.to_string(),
+            Value::Ratio(r) => r.to_string(),
+            Value::Decimal(d) => d.to_string(),
+            Value::Char(c) => c.to_string(),
             Value::Bool(b) => b.to_string(),
             Value::Nil => "nil".to_string(),
             _ => format!("{:?}", arg),
@@ -1483,6 +1491,9 @@ pub const FUNCTIONS: super::NativeFunctions = &[
     ("str/title", native_title),
     ("str/reverse", native_reverse),
     ("str/chars", native_chars),
+    ("str/graphemes", native_graphemes),
+    ("str/width", native_width),
+    ("str/normalize", native_normalize),
     ("str/snake", native_snake),
     ("str/camel", native_camel),
     ("str/kebab", native_kebab),
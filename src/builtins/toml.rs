@@ -0,0 +1,202 @@
+//! TOMLモジュール
+//!
+//! TOML処理関数を提供:
+//! - parse: TOML文字列をパース
+//! - stringify: マップをTOML文字列に変換
+//!
+//! 日時（オフセット付き日時・ローカル日時・日付・時刻）はRFC 3339形式の文字列になる。
+
+use crate::check_args;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Value};
+
+/// TOML文字列をパースしてQi値に変換
+///
+/// # 引数
+/// - args[0]: TOML文字列
+///
+/// # 戻り値
+/// - 成功時: パース結果のマップ（キーは文字列）
+/// - 失敗時: {:error エラーメッセージ}
+pub fn native_parse(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "toml/parse");
+
+    let toml_str = match &args[0] {
+        Value::String(s) => s,
+        _ => return Err(fmt_msg(MsgKey::ArgMustBeType, &["toml/parse", "a string"])),
+    };
+
+    match toml_str.parse::<::toml::Table>() {
+        Ok(table) => Ok(toml_to_value(::toml::Value::Table(table))),
+        Err(e) => Ok(Value::error(fmt_msg(
+            MsgKey::FormatParseError,
+            &["toml/parse", e.message()],
+        ))),
+    }
+}
+
+/// マップをTOML文字列に変換
+///
+/// # 引数
+/// - args[0]: 変換するマップ（TOMLのトップレベルはテーブルのみ）
+///
+/// # 戻り値
+/// - 成功時: TOML文字列
+/// - 失敗時: {:error エラーメッセージ}
+///
+/// # 注意
+/// TOMLにはnullがないため、マップ内のnilは出力しない（ベクタ内のnilはエラー）
+pub fn native_stringify(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "toml/stringify");
+
+    if !matches!(args[0], Value::Map(_)) {
+        return Err(fmt_msg(MsgKey::MustBeMap, &["toml/stringify", "argument"]));
+    }
+
+    let result = value_to_toml(&args[0]).and_then(|v| match v {
+        Some(toml) => ::toml::to_string(&toml).map_err(|e| e.to_string()),
+        None => Ok(String::new()),
+    });
+    match result {
+        Ok(s) => Ok(Value::String(s)),
+        Err(e) => Ok(Value::error(fmt_msg(
+            MsgKey::FormatEncodeError,
+            &["toml/stringify", &e],
+        ))),
+    }
+}
+
+/// toml::ValueをQi Valueに変換
+fn toml_to_value(toml: ::toml::Value) -> Value {
+    match toml {
+        ::toml::Value::String(s) => Value::String(s),
+        ::toml::Value::Integer(i) => Value::Integer(i),
+        ::toml::Value::Float(f) => Value::Float(f),
+        ::toml::Value::Boolean(b) => Value::Bool(b),
+        ::toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        ::toml::Value::Array(arr) => Value::Vector(arr.into_iter().map(toml_to_value).collect()),
+        ::toml::Value::Table(table) => {
+            let mut map = crate::new_hashmap();
+            for (k, v) in table {
+                // 外部データなのでMapKey::String
+                map.insert(MapKey::String(k), toml_to_value(v));
+            }
+            Value::Map(map)
+        }
+    }
+}
+
+/// Qi Valueをtoml::Valueに変換（nilはNone）
+fn value_to_toml(value: &Value) -> Result<Option<::toml::Value>, String> {
+    Ok(Some(match value {
+        Value::Nil => return Ok(None),
+        Value::Bool(b) => ::toml::Value::Boolean(*b),
+        Value::Integer(i) => ::toml::Value::Integer(*i),
+        Value::Float(f) => ::toml::Value::Float(*f),
+        // TOMLの整数はi64なので、i64に収まらない多倍長整数は文字列で出力
+        Value::BigInt(n) => ::toml::Value::String(n.to_string()),
+        Value::Ratio(_) => ::toml::Value::Float(
+            crate::builtins::numeric_helpers::number_to_f64(value).unwrap_or(f64::NAN),
+        ),
+        // 10進数はf64で誤差なく表せる場合のみ数値、それ以外は文字列で出力
+        Value::Decimal(d) => match crate::builtins::numeric_helpers::decimal_to_exact_f64(d) {
+            Some(f) => ::toml::Value::Float(f),
+            None => ::toml::Value::String(d.to_string()),
+        },
+        Value::String(s) => ::toml::Value::String(s.clone()),
+        Value::Char(c) => ::toml::Value::String(c.to_string()),
+        Value::Keyword(k) => ::toml::Value::String(k.to_string()),
+        Value::Set(items) => return value_to_toml(&Value::Vector(Value::sorted_set_items(items))),
+        Value::Vector(items) | Value::List(items) => {
+            let mut arr = Vec::with_capacity(items.len());
+            for item in items {
+                arr.push(value_to_toml(item)?.ok_or_else(|| "nil in array".to_string())?);
+            }
+            ::toml::Value::Array(arr)
+        }
+        Value::Map(m) => {
+            let mut table = ::toml::Table::new();
+            for (k, v) in m.iter() {
+                let key = match k {
+                    MapKey::Keyword(kw) => kw.to_string(),
+                    MapKey::String(s) => s.clone(),
+                    MapKey::Symbol(sym) => sym.to_string(),
+                    MapKey::Integer(i) => i.to_string(),
+                    MapKey::Char(c) => c.to_string(),
+                };
+                if let Some(v) = value_to_toml(v)? {
+                    table.insert(key, v);
+                }
+            }
+            ::toml::Value::Table(table)
+        }
+        other => return Err(other.type_name().to_string()),
+    }))
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category data/toml
+/// @qi-doc:functions parse, stringify
+pub const FUNCTIONS: super::NativeFunctions = &[
+    ("toml/parse", native_parse),
+    ("toml/stringify", native_stringify),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let src = "title = \"qi\"\nborn = 1979-05-27T07:32:00Z\n\n[server]\nports = [8000, 8001]\n";
+        let parsed = native_parse(&[Value::String(src.into())]).unwrap();
+        let Value::Map(m) = &parsed else {
+            panic!("expected map, got {}", parsed);
+        };
+        assert_eq!(
+            m.get(&MapKey::String("born".into())),
+            Some(&Value::String("1979-05-27T07:32:00Z".into()))
+        );
+
+        let Value::String(out) = native_stringify(&[parsed.clone()]).unwrap() else {
+            panic!("expected string");
+        };
+        assert_eq!(native_parse(&[Value::String(out)]).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_exact_numbers() {
+        let big: num_bigint::BigInt = num_bigint::BigInt::from(i64::MAX) * 2;
+        let d: rust_decimal::Decimal = "12345678901234567890.12".parse().unwrap();
+        let mut m = crate::new_hashmap();
+        m.insert(
+            MapKey::String("big".into()),
+            Value::BigInt(std::sync::Arc::new(big.clone())),
+        );
+        m.insert(MapKey::String("price".into()), Value::Decimal(d));
+        m.insert(
+            MapKey::String("half".into()),
+            Value::Decimal("1.5".parse().unwrap()),
+        );
+        let out = native_stringify(&[Value::Map(m)]).unwrap();
+        let Value::Map(parsed) = native_parse(&[out]).unwrap() else {
+            panic!("expected map");
+        };
+        assert_eq!(
+            parsed.get(&MapKey::String("big".into())),
+            Some(&Value::String(big.to_string()))
+        );
+        assert_eq!(
+            parsed.get(&MapKey::String("price".into())),
+            Some(&Value::String(d.to_string()))
+        );
+        assert_eq!(
+            parsed.get(&MapKey::String("half".into())),
+            Some(&Value::Float(1.5))
+        );
+    }
+}
@@ -0,0 +1,468 @@
+//! トランスデューサ - 中間コレクションを作らない変換の合成
+//!
+//! `(map f)` `(filter pred)` `(take n)` `(list/partition-by f)` `(list/dedupe)` のように
+//! コレクションを省略して呼ぶとトランスデューサを返します。`comp` で合成し、
+//! `transduce` / `into` / `sequence` や `(go/chan n xform)` で1パスで適用します。
+//!
+//! comp・partialと同様に特殊処理フラグ付きのFunctionとして表現し、
+//! 変換ステップ列（`[:map f]` 等のベクタ）を環境に保持します。
+
+use crate::builtins::value_helpers::values_equal;
+use crate::eval::{dynamic, hof_keys, Evaluator};
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{Channel, Env, Expr, Function, Pattern, Stream, Value};
+use crossbeam_channel::{bounded, unbounded};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// ステップ列からトランスデューサを作成
+fn make_transducer(steps: im::Vector<Value>) -> Value {
+    let mut env = Env::new();
+    env.set(hof_keys::TRANSDUCER_STEPS.to_string(), Value::List(steps));
+    Value::Function(Arc::new(Function {
+        params: vec![Pattern::Var(crate::intern::intern_symbol("rf"))],
+        body: Arc::new(Expr::symbol_dummy("__transducer_placeholder__")),
+        env: Arc::new(RwLock::new(env)),
+        is_variadic: false,
+        has_special_processing: true,
+    }))
+}
+
+/// 単一ステップのトランスデューサを作成（kind: "map", "filter", "take", "partition-by", "dedupe"）
+pub fn transducer(kind: &str, args: &[Value]) -> Value {
+    let mut step = im::vector![Value::Keyword(crate::intern::intern_keyword(kind))];
+    step.extend(args.iter().cloned());
+    make_transducer(im::vector![Value::Vector(step)])
+}
+
+/// トランスデューサのステップ列を取得（トランスデューサでなければNone）
+pub fn transducer_steps(value: &Value) -> Option<im::Vector<Value>> {
+    match value {
+        Value::Function(f) if f.has_special_processing => {
+            match f.env.read().get(hof_keys::TRANSDUCER_STEPS) {
+                Some(Value::List(steps)) => Some(steps),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 全てトランスデューサなら、左から順に適用する1つのトランスデューサに合成（comp用）
+pub fn compose(xforms: &[Value]) -> Option<Value> {
+    let mut steps = im::Vector::new();
+    for xf in xforms {
+        steps.append(transducer_steps(xf)?);
+    }
+    Some(make_transducer(steps))
+}
+
+/// 変換ステップの実行時状態
+enum Stage {
+    Map(Value),
+    Filter(Value),
+    Take(usize),
+    PartitionBy {
+        func: Value,
+        key: Option<Value>,
+        group: im::Vector<Value>,
+    },
+    Dedupe(Option<Value>),
+}
+
+/// トランスデューサ1回分の実行状態（takeの残数、partition-byのグループ等）
+///
+/// 各ステップは1入力につき高々1つの値しか出力しないため、
+/// 要素ごとにステップ列を先頭から順に通すだけで処理できる。
+pub struct Pipeline {
+    stages: Vec<Stage>,
+    done: bool,
+}
+
+impl Pipeline {
+    /// トランスデューサから実行状態を作成
+    pub fn new(xform: &Value, name: &str) -> Result<Self, String> {
+        let not_transducer = || fmt_msg(MsgKey::ArgMustBeType, &[name, "a transducer"]);
+        let steps = transducer_steps(xform).ok_or_else(not_transducer)?;
+
+        let mut stages = Vec::with_capacity(steps.len());
+        for step in &steps {
+            let Value::Vector(step) = step else {
+                return Err(not_transducer());
+            };
+            let stage = match (step.get(0), step.get(1)) {
+                (Some(Value::Keyword(k)), Some(f)) if &**k == "map" => Stage::Map(f.clone()),
+                (Some(Value::Keyword(k)), Some(f)) if &**k == "filter" => Stage::Filter(f.clone()),
+                (Some(Value::Keyword(k)), Some(Value::Integer(n))) if &**k == "take" => {
+                    Stage::Take(usize::try_from(*n).unwrap_or(0))
+                }
+                (Some(Value::Keyword(k)), Some(f)) if &**k == "partition-by" => {
+                    Stage::PartitionBy {
+                        func: f.clone(),
+                        key: None,
+                        group: im::Vector::new(),
+                    }
+                }
+                (Some(Value::Keyword(k)), None) if &**k == "dedupe" => Stage::Dedupe(None),
+                _ => return Err(not_transducer()),
+            };
+            stages.push(stage);
+        }
+
+        // (take 0) があれば何も通過しない
+        let done = stages.iter().any(|s| matches!(s, Stage::Take(0)));
+        Ok(Self { stages, done })
+    }
+
+    /// takeが上限に達し、これ以上入力が不要か
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// 1要素を流し、最後のステップまで通過した値を返す
+    pub fn push(&mut self, item: Value, evaluator: &Evaluator) -> Result<Option<Value>, String> {
+        if self.done {
+            return Ok(None);
+        }
+        self.run_from(0, item, evaluator)
+    }
+
+    /// 入力終了時に、partition-byが保持しているグループを流し出す
+    pub fn finish(&mut self, evaluator: &Evaluator) -> Result<Vec<Value>, String> {
+        let mut out = Vec::new();
+        for i in 0..self.stages.len() {
+            let pending = match &mut self.stages[i] {
+                Stage::PartitionBy { key, group, .. } if !group.is_empty() => {
+                    *key = None;
+                    Some(Value::List(std::mem::take(group)))
+                }
+                _ => None,
+            };
+            if let Some(group) = pending {
+                if let Some(v) = self.run_from(i + 1, group, evaluator)? {
+                    out.push(v);
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn run_from(
+        &mut self,
+        start: usize,
+        mut item: Value,
+        evaluator: &Evaluator,
+    ) -> Result<Option<Value>, String> {
+        let Pipeline { stages, done } = self;
+        for stage in stages[start..].iter_mut() {
+            match stage {
+                Stage::Map(func) => item = evaluator.apply_function(func, &[item])?,
+                Stage::Filter(pred) => {
+                    if !evaluator
+                        .apply_function(pred, std::slice::from_ref(&item))?
+                        .is_truthy()
+                    {
+                        return Ok(None);
+                    }
+                }
+                Stage::Take(remaining) => {
+                    if *remaining == 0 {
+                        return Ok(None);
+                    }
+                    *remaining -= 1;
+                    if *remaining == 0 {
+                        *done = true;
+                    }
+                }
+                Stage::Dedupe(last) => {
+                    if last.as_ref().is_some_and(|prev| values_equal(prev, &item)) {
+                        return Ok(None);
+                    }
+                    *last = Some(item.clone());
+                }
+                Stage::PartitionBy { func, key, group } => {
+                    let k = evaluator.apply_function(func, std::slice::from_ref(&item))?;
+                    let changed = key.as_ref().is_some_and(|prev| !values_equal(prev, &k));
+                    *key = Some(k);
+                    if !changed {
+                        group.push_back(item);
+                        return Ok(None);
+                    }
+                    // 結果が変わったら前のグループを次のステップへ流す
+                    item = Value::List(std::mem::replace(group, im::vector![item]));
+                }
+            }
+        }
+        Ok(Some(item))
+    }
+}
+
+/// 入力の要素を1つずつfに渡す（fがfalseを返したら中断）
+fn for_each_item(
+    source: &Value,
+    name: &str,
+    mut f: impl FnMut(Value) -> Result<bool, String>,
+) -> Result<(), String> {
+    match source {
+        Value::List(items) | Value::Vector(items) => {
+            for item in items {
+                if !f(item.clone())? {
+                    break;
+                }
+            }
+        }
+        Value::Set(items) => {
+            for item in items {
+                if !f(item.clone())? {
+                    break;
+                }
+            }
+        }
+        Value::Stream(stream) => loop {
+            let next_val = {
+                let s = stream.read();
+                (s.next_fn)()
+            };
+            match next_val {
+                Some(item) => {
+                    if !f(item)? {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        },
+        // チャネルはクローズされるまで受信する
+        Value::Channel(ch) => {
+            while let Ok(item) = ch.receiver.recv() {
+                if !f(item)? {
+                    break;
+                }
+            }
+        }
+        Value::Nil => {}
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::TypeOnly,
+                &[name, "lists, vectors, sets, streams or channels"],
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// 入力をトランスデューサに通し、出力値ごとにfを呼ぶ（xformがNoneならそのまま渡す）
+fn run(
+    xform: Option<&Value>,
+    source: &Value,
+    name: &str,
+    evaluator: &Evaluator,
+    mut f: impl FnMut(Value) -> Result<(), String>,
+) -> Result<(), String> {
+    let Some(xform) = xform else {
+        return for_each_item(source, name, |item| f(item).map(|_| true));
+    };
+
+    let mut pipeline = Pipeline::new(xform, name)?;
+    if pipeline.is_done() {
+        return Ok(());
+    }
+    for_each_item(source, name, |item| {
+        if let Some(v) = pipeline.push(item, evaluator)? {
+            f(v)?;
+        }
+        Ok(!pipeline.is_done())
+    })?;
+    for v in pipeline.finish(evaluator)? {
+        f(v)?;
+    }
+    Ok(())
+}
+
+/// transduce - トランスデューサを通した値を畳み込む
+///
+/// `(transduce xform f init coll)` または `(transduce xform f coll)`（初期値は `(f)`）
+pub fn native_transduce(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(fmt_msg(
+            MsgKey::NeedNArgsDesc,
+            &["transduce", "3 or 4", "(xform, f, [init], coll)"],
+        ));
+    }
+
+    let rf = &args[1];
+    let (mut acc, coll) = if args.len() == 4 {
+        (args[2].clone(), &args[3])
+    } else {
+        (evaluator.apply_function(rf, &[])?, &args[2])
+    };
+
+    run(Some(&args[0]), coll, "transduce", evaluator, |v| {
+        acc = evaluator.apply_function(rf, &[acc.clone(), v])?;
+        Ok(())
+    })?;
+    Ok(acc)
+}
+
+/// into - 要素をコレクションに追加（トランスデューサ指定時は変換しながら追加）
+///
+/// `(into to from)` または `(into to xform from)`。
+/// リスト・ベクタは末尾に順序を保って追加し、マップには `[キー 値]` のペアを追加します。
+pub fn native_into(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    if args.len() != 2 && args.len() != 3 {
+        return Err(fmt_msg(MsgKey::Need2Or3Args, &["into"]));
+    }
+
+    let (xform, from) = if args.len() == 3 {
+        (Some(&args[1]), &args[2])
+    } else {
+        (None, &args[1])
+    };
+
+    let mut result = args[0].clone();
+    if !matches!(
+        result,
+        Value::List(_) | Value::Vector(_) | Value::Set(_) | Value::Map(_)
+    ) {
+        return Err(fmt_msg(
+            MsgKey::TypeOnly,
+            &["into (1st arg)", "lists, vectors, sets or maps"],
+        ));
+    }
+
+    run(xform, from, "into", evaluator, |v| {
+        match &mut result {
+            Value::List(items) | Value::Vector(items) => items.push_back(v),
+            Value::Set(set) => {
+                if !v.is_hashable() {
+                    return Err(fmt_msg(MsgKey::SetElementNotHashable, &[v.type_name()]));
+                }
+                set.insert(v);
+            }
+            Value::Map(map) => match &v {
+                Value::List(pair) | Value::Vector(pair) if pair.len() == 2 => {
+                    map.insert(pair[0].to_map_key()?, pair[1].clone());
+                }
+                _ => return Err(fmt_msg(MsgKey::MapEntryMustBePair, &["into"])),
+            },
+            _ => unreachable!(),
+        }
+        Ok(())
+    })?;
+    Ok(result)
+}
+
+/// sequence - トランスデューサを適用した結果を返す
+///
+/// コレクションは1パスで処理してリストを返し、
+/// ストリーム・チャネルは遅延ストリームを返します。
+pub fn native_sequence(args: &[Value], evaluator: &Evaluator) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(fmt_msg(MsgKey::Need2Args, &["sequence"]));
+    }
+
+    let pull: Box<dyn Fn() -> Option<Value> + Send + Sync> = match &args[1] {
+        Value::Stream(stream) => {
+            let stream = stream.clone();
+            Box::new(move || {
+                let s = stream.read();
+                (s.next_fn)()
+            })
+        }
+        Value::Channel(ch) => {
+            let receiver = ch.receiver.clone();
+            Box::new(move || receiver.recv().ok())
+        }
+        coll => {
+            let mut result = im::Vector::new();
+            run(Some(&args[0]), coll, "sequence", evaluator, |v| {
+                result.push_back(v);
+                Ok(())
+            })?;
+            return Ok(Value::List(result));
+        }
+    };
+
+    // ストリーム・チャネル: 要求されるたびに入力を引き出して変換する
+    // （stream/map等と同様、関数適用でのエラーはストリームの終端として扱う）
+    let pipeline = Arc::new(RwLock::new(Pipeline::new(&args[0], "sequence")?));
+    let flushed = Arc::new(RwLock::new(None::<std::vec::IntoIter<Value>>));
+    let evaluator = evaluator.clone();
+
+    let stream = Stream {
+        next_fn: Box::new(move || {
+            let mut flushed = flushed.write();
+            if let Some(rest) = flushed.as_mut() {
+                return rest.next();
+            }
+            let mut pipeline = pipeline.write();
+            while !pipeline.is_done() {
+                let Some(item) = pull() else {
+                    break;
+                };
+                match pipeline.push(item, &evaluator) {
+                    Ok(Some(v)) => return Some(v),
+                    Ok(None) => {}
+                    Err(_) => return None,
+                }
+            }
+            let rest = flushed.insert(pipeline.finish(&evaluator).ok()?.into_iter());
+            rest.next()
+        }),
+    };
+
+    Ok(Value::Stream(Arc::new(RwLock::new(stream))))
+}
+
+/// go/chan にトランスデューサを指定した場合のチャネル作成
+///
+/// 送信された値をワーカースレッドでトランスデューサに通し、結果だけを受信側に流す。
+/// 送信側がcloseされるとpartition-by等の残りを流してから受信側もcloseされる。
+pub fn native_chan_with_transducer(
+    capacity: Option<usize>,
+    xform: &Value,
+    evaluator: &Evaluator,
+) -> Result<Value, String> {
+    let mut pipeline = Pipeline::new(xform, "go/chan")?;
+
+    let (in_sender, in_receiver) = match capacity {
+        Some(cap) => bounded(cap),
+        None => unbounded(),
+    };
+    let (out_sender, out_receiver) = match capacity {
+        Some(cap) => bounded(cap),
+        None => unbounded(),
+    };
+
+    let evaluator = evaluator.clone();
+    let bindings = dynamic::capture();
+    std::thread::spawn(move || {
+        let _guard = dynamic::install(bindings);
+        while !pipeline.is_done() {
+            let Ok(value) = in_receiver.recv() else {
+                break;
+            };
+            match pipeline.push(value, &evaluator) {
+                Ok(Some(v)) => {
+                    if out_sender.send(v).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => {}
+                Err(_) => return,
+            }
+        }
+        if let Ok(rest) = pipeline.finish(&evaluator) {
+            for v in rest {
+                if out_sender.send(v).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(Value::Channel(Arc::new(Channel {
+        sender: Arc::new(parking_lot::Mutex::new(Some(in_sender))),
+        receiver: out_receiver,
+    })))
+}
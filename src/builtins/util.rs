@@ -50,6 +50,7 @@ pub fn normalize_map_keys_to_string(
                 MapKey::Keyword(s) | MapKey::Symbol(s) => s.to_string(),
                 MapKey::String(s) => s.clone(),
                 MapKey::Integer(i) => i.to_string(),
+                MapKey::Char(c) => c.to_string(),
             };
             (MapKey::String(string_key), v.clone())
         })
@@ -8,6 +8,7 @@
 //! - 数値範囲チェック（:min, :max）
 //! - 文字列長チェック（:min-length, :max-length）
 //! - パターンマッチ（:pattern）
+//! - 列挙値チェック（:enum）
 //!
 //! ## 使用例
 //! ```qi
@@ -17,7 +18,6 @@
 //! ```
 
 use crate::check_args;
-use crate::constants::keywords::ERROR_KEY;
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{MapKey, Value};
 use crate::HashMap;
@@ -45,7 +45,10 @@ fn validate_value(schema: &Value, data: &Value, field_name: Option<&str>) -> Res
     };
 
     // :required チェック
-    let is_required = matches!(schema_map.get(&MapKey::Keyword(Arc::from("required"))), Some(Value::Bool(true)));
+    let is_required = matches!(
+        schema_map.get(&MapKey::Keyword(Arc::from("required"))),
+        Some(Value::Bool(true))
+    );
 
     if matches!(data, Value::Nil) {
         if is_required {
@@ -57,10 +60,7 @@ fn validate_value(schema: &Value, data: &Value, field_name: Option<&str>) -> Res
         } else {
             // オプショナルフィールドでnilの場合は成功
             let mut result_map = crate::new_hashmap();
-            result_map.insert(
-                MapKey::Keyword(std::sync::Arc::from("ok")),
-                data.clone(),
-            );
+            result_map.insert(MapKey::Keyword(std::sync::Arc::from("ok")), data.clone());
             return Ok(Value::Map(result_map));
         }
     }
@@ -72,6 +72,20 @@ fn validate_value(schema: &Value, data: &Value, field_name: Option<&str>) -> Res
         }
     }
 
+    // :enum チェック（許可された値のいずれかであること）
+    if let Some(Value::Vector(allowed) | Value::List(allowed)) =
+        schema_map.get(&MapKey::Keyword(Arc::from("enum")))
+    {
+        if !allowed.iter().any(|v| v == data) {
+            let choices: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+            return Ok(error_result(
+                field_name,
+                "enum",
+                fmt_msg(MsgKey::ValidateEnum, &[&choices.join(", ")]),
+            ));
+        }
+    }
+
     // 型別の詳細バリデーション
     match data {
         Value::String(s) => {
@@ -343,7 +357,8 @@ fn validate_map(
     if let Some(Value::Map(fields_schema)) = schema.get(&MapKey::Keyword(Arc::from("fields"))) {
         for (field_key, field_schema) in fields_schema.iter() {
             let field_data = data.get(field_key).unwrap_or(&Value::Nil);
-            let result = validate_value(field_schema, field_data, Some(field_key))?;
+            let field_name = field_key.to_string();
+            let result = validate_value(field_schema, field_data, Some(&field_name))?;
 
             // エラーがあれば即座に返す
             if let Value::Map(m) = &result {
@@ -377,10 +392,7 @@ fn error_result(field: Option<&str>, code: &str, message: String) -> Value {
     }
 
     let mut result_map = crate::new_hashmap();
-    result_map.insert(
-        MapKey::Keyword(Arc::from("error")),
-        Value::Map(error_map),
-    );
+    result_map.insert(MapKey::Keyword(Arc::from("error")), Value::Map(error_map));
     Value::Map(result_map)
 }
 
@@ -392,14 +404,14 @@ pub const FUNCTIONS: super::NativeFunctions = &[("validate", native_validate)];
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::builtins::util::kw;
     use crate::value::Value;
-    use im::HashMap;
 
     /// ヘルパー関数: スキーママップを作成
     fn make_schema(entries: Vec<(&str, Value)>) -> Value {
         let mut map = crate::new_hashmap();
         for (key, val) in entries {
-            map.insert(key.to_string(), val);
+            map.insert(kw(key.trim_start_matches(':')), val);
         }
         Value::Map(map)
     }
@@ -407,7 +419,7 @@ mod tests {
     /// ヘルパー関数: 成功結果かどうかをチェック
     fn is_ok(result: &Value) -> bool {
         if let Value::Map(m) = result {
-            m.contains_key(":ok")
+            m.contains_key(&kw("ok"))
         } else {
             false
         }
@@ -425,8 +437,9 @@ mod tests {
     /// ヘルパー関数: エラーコードを取得
     fn get_error_code(result: &Value) -> Option<String> {
         if let Value::Map(m) = result {
-            if let Some(Value::Map(error_map)) = m.get(&crate::constants::keywords::error_mapkey()) {
-                if let Some(Value::String(code)) = error_map.get(":code") {
+            if let Some(Value::Map(error_map)) = m.get(&crate::constants::keywords::error_mapkey())
+            {
+                if let Some(Value::String(code)) = error_map.get(&kw("code")) {
                     return Some(code.clone());
                 }
             }
@@ -606,18 +619,18 @@ mod tests {
     fn test_validate_nested_map() {
         // ネストしたマップのスキーマ
         let mut name_schema = crate::new_hashmap();
-        name_schema.insert(":type".to_string(), Value::String("string".to_string()));
-        name_schema.insert(":required".to_string(), Value::Bool(true));
-        name_schema.insert(":min-length".to_string(), Value::Integer(1));
+        name_schema.insert(kw("type"), Value::String("string".to_string()));
+        name_schema.insert(kw("required"), Value::Bool(true));
+        name_schema.insert(kw("min-length"), Value::Integer(1));
 
         let mut age_schema = crate::new_hashmap();
-        age_schema.insert(":type".to_string(), Value::String("integer".to_string()));
-        age_schema.insert(":min".to_string(), Value::Integer(0));
-        age_schema.insert(":max".to_string(), Value::Integer(150));
+        age_schema.insert(kw("type"), Value::String("integer".to_string()));
+        age_schema.insert(kw("min"), Value::Integer(0));
+        age_schema.insert(kw("max"), Value::Integer(150));
 
         let mut fields = crate::new_hashmap();
-        fields.insert(":name".to_string(), Value::Map(name_schema));
-        fields.insert(":age".to_string(), Value::Map(age_schema));
+        fields.insert(kw("name"), Value::Map(name_schema));
+        fields.insert(kw("age"), Value::Map(age_schema));
 
         let schema = make_schema(vec![
             (":type", Value::String("map".to_string())),
@@ -626,22 +639,22 @@ mod tests {
 
         // 正常なデータ
         let mut valid_data = crate::new_hashmap();
-        valid_data.insert(":name".to_string(), Value::String("太郎".to_string()));
-        valid_data.insert(":age".to_string(), Value::Integer(25));
+        valid_data.insert(kw("name"), Value::String("太郎".to_string()));
+        valid_data.insert(kw("age"), Value::Integer(25));
         let result = native_validate(&[schema.clone(), Value::Map(valid_data)]).unwrap();
         assert!(is_ok(&result), "Expected success for valid nested map");
 
         // nameが空文字列（min-lengthエラー）
         let mut invalid_name = crate::new_hashmap();
-        invalid_name.insert(":name".to_string(), Value::String("".to_string()));
-        invalid_name.insert(":age".to_string(), Value::Integer(25));
+        invalid_name.insert(kw("name"), Value::String("".to_string()));
+        invalid_name.insert(kw("age"), Value::Integer(25));
         let result = native_validate(&[schema.clone(), Value::Map(invalid_name)]).unwrap();
         assert!(is_error(&result), "Expected error for empty name");
         assert_eq!(get_error_code(&result), Some("min-length".to_string()));
 
         // nameがない（requiredエラー）
         let mut missing_name = crate::new_hashmap();
-        missing_name.insert(":age".to_string(), Value::Integer(25));
+        missing_name.insert(kw("age"), Value::Integer(25));
         let result = native_validate(&[schema.clone(), Value::Map(missing_name)]).unwrap();
         assert!(
             is_error(&result),
@@ -651,8 +664,8 @@ mod tests {
 
         // ageが範囲外（max-valueエラー）
         let mut invalid_age = crate::new_hashmap();
-        invalid_age.insert(":name".to_string(), Value::String("太郎".to_string()));
-        invalid_age.insert(":age".to_string(), Value::Integer(200));
+        invalid_age.insert(kw("name"), Value::String("太郎".to_string()));
+        invalid_age.insert(kw("age"), Value::Integer(200));
         let result = native_validate(&[schema, Value::Map(invalid_age)]).unwrap();
         assert!(is_error(&result), "Expected error for age above max");
         assert_eq!(get_error_code(&result), Some("max-value".to_string()));
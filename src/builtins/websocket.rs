@@ -7,30 +7,39 @@
 //! ## クライアントサイド
 //! - `ws/connect` - WebSocket接続（接続IDを返す）
 //! - `ws/send` - メッセージ送信
-//! - `ws/receive` - メッセージ受信
+//! - `ws/receive` - メッセージ受信（タイムアウト指定可）
+//! - `ws/channel` - 受信メッセージをチャネルとして取得（`go/select!` と組み合わせる）
 //! - `ws/close` - 接続クローズ
+//!
+//! 接続ごとにバックグラウンドタスクがソケットを所有し、受信メッセージをキューに積む。
+//! 送信はキュー経由でタスクに渡すため、受信待ちの間も送信できる。
+//! Pingの定期送信と、切断時のバックオフ付き再接続もタスクが行う。
 
+use crate::builtins::util::kw;
 use crate::builtins::value_helpers::{get_int_arg, get_string_ref};
 use crate::i18n::{fmt_msg, MsgKey};
-use crate::value::{MapKey, Value};
+use crate::value::{Channel, MapKey, Value};
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
+use std::collections::VecDeque;
 use std::sync::{
     atomic::{AtomicI64, Ordering},
     Arc,
 };
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
 
 /// ネイティブ関数の型エイリアス
 type NativeFn = fn(&[Value]) -> Result<Value, String>;
 
 /// @qi-doc:category net/websocket
-/// @qi-doc:functions ws/connect, ws/send, ws/receive, ws/close
+/// @qi-doc:functions ws/connect, ws/send, ws/receive, ws/channel, ws/close
 /// @qi-doc:note WebSocket通信（クライアント）
 pub const FUNCTIONS: &[(&str, NativeFn)] = &[
     ("ws/connect", native_ws_connect as NativeFn),
     ("ws/send", native_ws_send as NativeFn),
     ("ws/receive", native_ws_receive as NativeFn),
+    ("ws/channel", native_ws_channel as NativeFn),
     ("ws/close", native_ws_close as NativeFn),
 ];
 
@@ -39,10 +48,29 @@ use futures_util::{SinkExt, StreamExt};
 #[cfg(feature = "websocket")]
 use tokio_tungstenite::{
     connect_async,
-    tungstenite::{protocol::CloseFrame, Message},
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{HeaderName, HeaderValue},
+        protocol::CloseFrame,
+        Message,
+    },
     MaybeTlsStream, WebSocketStream,
 };
 
+/// 接続タイムアウトのデフォルト（ミリ秒）
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 30_000;
+
+/// 再接続の初期待機時間のデフォルト（ミリ秒）
+const DEFAULT_RECONNECT_INITIAL_DELAY_MS: u64 = 500;
+
+/// 再接続の最大待機時間のデフォルト（ミリ秒）
+const DEFAULT_RECONNECT_MAX_DELAY_MS: u64 = 30_000;
+
+/// ws/close がタスクの終了を待つ最大時間
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
 // ========================================
 // グローバルTokioランタイム
 // ========================================
@@ -52,140 +80,381 @@ static TOKIO_RT: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
     tokio::runtime::Runtime::new().expect("Failed to create tokio runtime for WebSocket")
 });
 
+// ========================================
+// 接続オプション
+// ========================================
+
+/// ws/connect のオプション
+#[derive(Clone)]
+struct ConnectOptions {
+    /// ハンドシェイクに付けるヘッダー
+    headers: Vec<(HeaderName, HeaderValue)>,
+    /// 要求するサブプロトコル（Sec-WebSocket-Protocol）
+    protocols: Vec<String>,
+    /// Pingの送信間隔（この2倍の間なにも受信しなければ切断とみなす）
+    ping_interval: Option<Duration>,
+    /// 接続（ハンドシェイク）のタイムアウト
+    connect_timeout: Duration,
+    /// 切断時の再接続設定
+    reconnect: Option<ReconnectPolicy>,
+}
+
+/// 再接続の設定（指数バックオフ）
+#[derive(Clone)]
+struct ReconnectPolicy {
+    /// 連続した再接続の最大試行回数（Noneなら無制限）
+    max_attempts: Option<u32>,
+    initial_delay: Duration,
+    max_delay: Duration,
+}
+
+impl ReconnectPolicy {
+    /// n回目（1始まり）の試行前の待機時間
+    fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+}
+
+/// オプションマップを解釈する
+fn parse_connect_options(opts: &crate::HashMap<MapKey, Value>) -> Result<ConnectOptions, String> {
+    let invalid =
+        |name: &str, detail: &str| fmt_msg(MsgKey::WsInvalidOption, &["ws/connect", name, detail]);
+    let millis = |name: &str, key: &str| -> Result<Option<Duration>, String> {
+        match opts.get(&kw(key)) {
+            None | Some(Value::Nil) => Ok(None),
+            Some(Value::Integer(n)) if *n > 0 => Ok(Some(Duration::from_millis(*n as u64))),
+            Some(_) => Err(invalid(name, "must be a positive integer (ms)")),
+        }
+    };
+
+    let mut headers = Vec::new();
+    match opts.get(&kw("headers")) {
+        None | Some(Value::Nil) => {}
+        Some(Value::Map(m)) => {
+            for (k, v) in m.iter() {
+                let name = match k {
+                    MapKey::String(s) => s.clone(),
+                    MapKey::Keyword(s) | MapKey::Symbol(s) => s.to_string(),
+                    MapKey::Integer(i) => i.to_string(),
+                    MapKey::Char(c) => c.to_string(),
+                };
+                let value = match v {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|e| invalid(":headers", &e.to_string()))?;
+                let value = HeaderValue::from_str(&value)
+                    .map_err(|e| invalid(":headers", &e.to_string()))?;
+                headers.push((name, value));
+            }
+        }
+        Some(_) => return Err(invalid(":headers", "must be a map")),
+    }
+
+    let protocols = match opts.get(&kw("protocols")) {
+        None | Some(Value::Nil) => Vec::new(),
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Vector(items)) | Some(Value::List(items)) => items
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(invalid(":protocols", "must be strings")),
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => {
+            return Err(invalid(
+                ":protocols",
+                "must be a string or a vector of strings",
+            ))
+        }
+    };
+
+    let reconnect = match opts.get(&kw("reconnect")) {
+        None | Some(Value::Nil) | Some(Value::Bool(false)) => None,
+        Some(Value::Bool(true)) => Some(ReconnectPolicy {
+            max_attempts: None,
+            initial_delay: Duration::from_millis(DEFAULT_RECONNECT_INITIAL_DELAY_MS),
+            max_delay: Duration::from_millis(DEFAULT_RECONNECT_MAX_DELAY_MS),
+        }),
+        Some(Value::Map(m)) => {
+            let millis = |key: &str, default: u64| match m.get(&kw(key)) {
+                None | Some(Value::Nil) => Ok(Duration::from_millis(default)),
+                Some(Value::Integer(n)) if *n >= 0 => Ok(Duration::from_millis(*n as u64)),
+                Some(_) => Err(invalid(":reconnect", &format!(":{} must be ms", key))),
+            };
+            let max_attempts = match m.get(&kw("max-attempts")) {
+                None | Some(Value::Nil) => None,
+                Some(Value::Integer(n)) if *n >= 0 => Some(*n as u32),
+                Some(_) => {
+                    return Err(invalid(
+                        ":reconnect",
+                        ":max-attempts must be a non-negative integer",
+                    ))
+                }
+            };
+            Some(ReconnectPolicy {
+                max_attempts,
+                initial_delay: millis("initial-delay", DEFAULT_RECONNECT_INITIAL_DELAY_MS)?,
+                max_delay: millis("max-delay", DEFAULT_RECONNECT_MAX_DELAY_MS)?,
+            })
+        }
+        Some(_) => return Err(invalid(":reconnect", "must be a boolean or a map")),
+    };
+
+    Ok(ConnectOptions {
+        headers,
+        protocols,
+        ping_interval: millis(":ping-interval", "ping-interval")?,
+        connect_timeout: millis(":connect-timeout", "connect-timeout")?
+            .unwrap_or(Duration::from_millis(DEFAULT_CONNECT_TIMEOUT_MS)),
+        reconnect,
+    })
+}
+
 // ========================================
 // WebSocket接続の管理
 // ========================================
 
+/// バックグラウンドタスクへの指示
+enum Command {
+    Send(Message),
+    /// 接続を閉じてタスクを終了する（完了を通知）
+    Close(oneshot::Sender<()>),
+}
+
 /// WebSocket接続（IDベース管理）
 pub struct WebSocketConnection {
-    stream: Arc<Mutex<Option<WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>>>>,
+    /// バックグラウンドタスクへの送信キュー
+    commands: mpsc::UnboundedSender<Command>,
+    /// 受信メッセージのキュー（タスク終了で切断される）
+    incoming: crossbeam_channel::Receiver<Value>,
 }
 
-impl WebSocketConnection {
-    /// 新しい接続を作成
-    pub fn new(stream: WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>) -> Self {
-        Self {
-            stream: Arc::new(Mutex::new(Some(stream))),
-        }
+/// 切断の理由
+enum Disconnect {
+    /// ws/close による切断（再接続しない）
+    Closed,
+    /// 相手側からの切断・エラー（最後に通知するイベント）
+    Lost(Value),
+}
+
+/// ハンドシェイクを行って接続する
+async fn open(url: &str, opts: &ConnectOptions) -> Result<WsStream, String> {
+    let failed = |e: String| fmt_msg(MsgKey::WsFailedToConnect, &[&e]);
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| failed(e.to_string()))?;
+    for (name, value) in &opts.headers {
+        request.headers_mut().insert(name.clone(), value.clone());
     }
+    if !opts.protocols.is_empty() {
+        let value =
+            HeaderValue::from_str(&opts.protocols.join(", ")).map_err(|e| failed(e.to_string()))?;
+        request
+            .headers_mut()
+            .insert("Sec-WebSocket-Protocol", value);
+    }
+    match tokio::time::timeout(opts.connect_timeout, connect_async(request)).await {
+        Ok(Ok((stream, _))) => Ok(stream),
+        Ok(Err(e)) => Err(failed(e.to_string())),
+        Err(_) => Err(failed("connection timed out".to_string())),
+    }
+}
 
-    /// メッセージを送信
-    pub async fn send(&self, data: &str) -> Result<(), String> {
-        let mut stream_guard = self.stream.lock().await;
-        if let Some(stream) = stream_guard.as_mut() {
-            stream
-                .send(Message::Text(data.to_string()))
-                .await
-                .map_err(|e| fmt_msg(MsgKey::WsFailedToSend, &[&e.to_string()]))?;
-            Ok(())
-        } else {
-            Err(fmt_msg(MsgKey::WsConnectionClosed, &[]))
+/// 接続を維持するタスク本体（切断時は設定に従って再接続する）
+async fn run_connection(
+    mut stream: WsStream,
+    url: String,
+    opts: ConnectOptions,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+    incoming: crossbeam_channel::Sender<Value>,
+) {
+    // 再接続待ちの間に送られたメッセージ（再接続後に送信）
+    let mut pending = VecDeque::new();
+    loop {
+        let event = match serve(&mut stream, &opts, &mut commands, &incoming, &mut pending).await {
+            Disconnect::Closed => return,
+            Disconnect::Lost(event) => event,
+        };
+        let Some(policy) = &opts.reconnect else {
+            let _ = incoming.send(event);
+            return;
+        };
+
+        let mut attempt = 0;
+        stream = loop {
+            attempt += 1;
+            if policy.max_attempts.is_some_and(|max| attempt > max) {
+                let _ = incoming.send(event);
+                return;
+            }
+            let delay = policy.delay(attempt);
+            let _ = incoming.send(event_map(
+                "reconnecting",
+                &[
+                    ("attempt", Value::Integer(attempt as i64)),
+                    ("delay", Value::Integer(delay.as_millis() as i64)),
+                ],
+            ));
+
+            // 待機中もws/closeと送信を受け付ける
+            let sleep = tokio::time::sleep(delay);
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    cmd = commands.recv() => match cmd {
+                        Some(Command::Send(msg)) => pending.push_back(msg),
+                        Some(Command::Close(done)) => {
+                            let _ = done.send(());
+                            return;
+                        }
+                        None => return,
+                    },
+                }
+            }
+
+            if let Ok(s) = open(&url, &opts).await {
+                let _ = incoming.send(event_map(
+                    "reconnected",
+                    &[("attempt", Value::Integer(attempt as i64))],
+                ));
+                break s;
+            }
+        };
+    }
+}
+
+/// 1回の接続を処理する（切断されるまで送受信とPingを行う）
+async fn serve(
+    stream: &mut WsStream,
+    opts: &ConnectOptions,
+    commands: &mut mpsc::UnboundedReceiver<Command>,
+    incoming: &crossbeam_channel::Sender<Value>,
+    pending: &mut VecDeque<Message>,
+) -> Disconnect {
+    while let Some(msg) = pending.pop_front() {
+        if let Err(e) = stream.send(msg.clone()).await {
+            pending.push_front(msg);
+            return Disconnect::Lost(error_event(&e.to_string()));
         }
     }
 
-    /// メッセージを受信
-    pub async fn receive(&self) -> Result<Value, String> {
-        loop {
-            let mut stream_guard = self.stream.lock().await;
-            if let Some(stream) = stream_guard.as_mut() {
-                match stream.next().await {
-                    Some(Ok(Message::Text(text))) => {
-                        let mut result = crate::new_hashmap();
-                        result.insert(
-                            MapKey::String("type".to_string()),
-                            Value::String("message".to_string()),
-                        );
-                        result.insert(MapKey::String("data".to_string()), Value::String(text));
-                        return Ok(Value::Map(result));
-                    }
-                    Some(Ok(Message::Binary(data))) => {
-                        let mut result = crate::new_hashmap();
-                        result.insert(
-                            MapKey::String("type".to_string()),
-                            Value::String("binary".to_string()),
-                        );
-                        #[cfg(feature = "string-encoding")]
-                        {
-                            use base64::{engine::general_purpose, Engine as _};
-                            let encoded = general_purpose::STANDARD.encode(&data);
-                            result
-                                .insert(MapKey::String("data".to_string()), Value::String(encoded));
-                        }
-                        #[cfg(not(feature = "string-encoding"))]
-                        {
-                            result.insert(
-                                MapKey::String("data".to_string()),
-                                Value::String(format!("<binary data {} bytes>", data.len())),
-                            );
+    let mut ping = opts.ping_interval.map(|d| {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + d, d);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        (interval, d)
+    });
+    let mut last_seen = tokio::time::Instant::now();
+    // 相手から受け取ったCloseフレーム（ハンドシェイク完了まで読み続ける）
+    let mut close_event = None;
+
+    loop {
+        tokio::select! {
+            msg = stream.next() => {
+                last_seen = tokio::time::Instant::now();
+                match msg {
+                    Some(Ok(Message::Close(frame))) => close_event = Some(close_event_map(frame)),
+                    Some(Ok(msg)) => {
+                        if let Some(event) = message_event(msg) {
+                            let _ = incoming.send(event);
                         }
-                        return Ok(Value::Map(result));
-                    }
-                    Some(Ok(Message::Close(frame))) => {
-                        let mut result = crate::new_hashmap();
-                        result.insert(
-                            MapKey::String("type".to_string()),
-                            Value::String("close".to_string()),
-                        );
-                        if let Some(CloseFrame { code, reason }) = frame {
-                            result.insert(
-                                MapKey::String("code".to_string()),
-                                Value::Integer(u16::from(code) as i64),
-                            );
-                            result.insert(
-                                MapKey::String("reason".to_string()),
-                                Value::String(reason.to_string()),
-                            );
-                        }
-                        return Ok(Value::Map(result));
-                    }
-                    Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => {
-                        // Ping/Pongは自動的に処理されるのでスキップして次を受信
-                        drop(stream_guard);
-                        continue;
-                    }
-                    Some(Ok(Message::Frame(_))) => {
-                        // Frameは通常ユーザーが直接扱わない
-                        return Err(fmt_msg(MsgKey::WsUnexpectedFrame, &[]));
                     }
                     Some(Err(e)) => {
-                        let mut result = crate::new_hashmap();
-                        result.insert(
-                            MapKey::String("type".to_string()),
-                            Value::String("error".to_string()),
-                        );
-                        result.insert(
-                            MapKey::String("error".to_string()),
-                            Value::String(e.to_string()),
-                        );
-                        return Ok(Value::Map(result));
+                        return Disconnect::Lost(
+                            close_event.unwrap_or_else(|| error_event(&e.to_string())),
+                        )
                     }
                     None => {
-                        let mut result = crate::new_hashmap();
-                        result.insert(
-                            MapKey::String("type".to_string()),
-                            Value::String("close".to_string()),
-                        );
-                        return Ok(Value::Map(result));
+                        return Disconnect::Lost(
+                            close_event.unwrap_or_else(|| close_event_map(None)),
+                        )
+                    }
+                }
+            }
+            cmd = commands.recv() => match cmd {
+                Some(Command::Send(msg)) => {
+                    if let Err(e) = stream.send(msg.clone()).await {
+                        pending.push_back(msg);
+                        return Disconnect::Lost(error_event(&e.to_string()));
                     }
                 }
-            } else {
-                return Err(fmt_msg(MsgKey::WsConnectionClosed, &[]));
+                Some(Command::Close(done)) => {
+                    let _ = stream.close(None).await;
+                    let _ = done.send(());
+                    return Disconnect::Closed;
+                }
+                None => {
+                    let _ = stream.close(None).await;
+                    return Disconnect::Closed;
+                }
+            },
+            _ = async {
+                match ping.as_mut() {
+                    Some((interval, _)) => {
+                        interval.tick().await;
+                    }
+                    None => std::future::pending::<()>().await,
+                }
+            } => {
+                let Some((_, period)) = ping else { continue };
+                if last_seen.elapsed() > period * 2 {
+                    return Disconnect::Lost(error_event("ping timeout"));
+                }
+                if let Err(e) = stream.send(Message::Ping(Vec::new())).await {
+                    return Disconnect::Lost(error_event(&e.to_string()));
+                }
             }
         }
     }
+}
+
+/// {:type ...} 形式のイベントマップを作成
+fn event_map(kind: &str, fields: &[(&str, Value)]) -> Value {
+    let mut result = crate::new_hashmap();
+    result.insert(kw("type"), Value::String(kind.to_string()));
+    for (k, v) in fields {
+        result.insert(kw(k), v.clone());
+    }
+    Value::Map(result)
+}
+
+fn error_event(message: &str) -> Value {
+    event_map("error", &[("error", Value::String(message.to_string()))])
+}
+
+fn close_event_map(frame: Option<CloseFrame>) -> Value {
+    match frame {
+        Some(CloseFrame { code, reason }) => event_map(
+            "close",
+            &[
+                ("code", Value::Integer(u16::from(code) as i64)),
+                ("reason", Value::String(reason.to_string())),
+            ],
+        ),
+        None => event_map("close", &[]),
+    }
+}
 
-    /// 接続をクローズ
-    pub async fn close(&self) -> Result<(), String> {
-        let mut stream_guard = self.stream.lock().await;
-        if let Some(mut stream) = stream_guard.take() {
-            stream
-                .close(None)
-                .await
-                .map_err(|e| fmt_msg(MsgKey::WsFailedToClose, &[&e.to_string()]))?;
-            Ok(())
-        } else {
-            Err(fmt_msg(MsgKey::WsConnectionAlreadyClosed, &[]))
+/// 受信したデータフレームをイベントマップに変換（Ping/Pongは自動処理されるのでNone）
+fn message_event(msg: Message) -> Option<Value> {
+    match msg {
+        Message::Text(text) => Some(event_map("message", &[("data", Value::String(text))])),
+        Message::Binary(data) => {
+            #[cfg(feature = "string-encoding")]
+            let data = {
+                use base64::{engine::general_purpose, Engine as _};
+                Value::String(general_purpose::STANDARD.encode(&data))
+            };
+            #[cfg(not(feature = "string-encoding"))]
+            let data = Value::String(format!("<binary data {} bytes>", data.len()));
+            Some(event_map("binary", &[("data", data)]))
         }
+        Message::Ping(_) | Message::Pong(_) | Message::Close(_) | Message::Frame(_) => None,
     }
 }
 
@@ -195,74 +464,144 @@ static WS_CONNECTIONS: Lazy<DashMap<i64, Arc<WebSocketConnection>>> = Lazy::new(
 /// 次の接続ID
 static NEXT_WS_ID: AtomicI64 = AtomicI64::new(1);
 
+/// 接続IDから接続を取得
+fn get_connection(args: &[Value], fn_name: &str) -> Result<Arc<WebSocketConnection>, String> {
+    let conn_id = get_int_arg(args, 0, fn_name)?;
+    WS_CONNECTIONS
+        .get(&conn_id)
+        .map(|c| c.clone())
+        .ok_or_else(|| fmt_msg(MsgKey::WsConnectionNotFound, &[]))
+}
+
 // ========================================
 // クライアントサイド関数
 // ========================================
 
 /// ws/connect - WebSocketサーバーに接続
 ///
+/// 引数: URL、オプション（省略可）
+///   :headers - ハンドシェイクに付けるヘッダー（例: {"authorization" "Bearer ..."}）
+///   :protocols - 要求するサブプロトコル（文字列またはベクタ）
+///   :ping-interval - Pingの送信間隔（ミリ秒、この2倍の間無応答なら切断とみなす）
+///   :connect-timeout - 接続のタイムアウト（ミリ秒、デフォルト: 30000）
+///   :reconnect - 切断時に再接続する（true または {:max-attempts :initial-delay :max-delay}）
+///
 /// 接続IDを返す（Integer）
 pub fn native_ws_connect(args: &[Value]) -> Result<Value, String> {
-    if args.is_empty() {
+    if args.is_empty() || args.len() > 2 {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["ws/connect", "1"]));
     }
 
-    let url = get_string_ref(args, 0, "ws/connect")?;
+    let url = get_string_ref(args, 0, "ws/connect")?.to_string();
+    let opts = match args.get(1) {
+        None | Some(Value::Nil) => parse_connect_options(&crate::new_hashmap())?,
+        Some(Value::Map(m)) => parse_connect_options(m)?,
+        Some(_) => return Err(fmt_msg(MsgKey::MustBeMap, &["ws/connect", "options"])),
+    };
+
+    // 最初の接続は同期的に行い、失敗はエラーとして返す
+    let stream = TOKIO_RT.block_on(open(&url, &opts))?;
 
-    // グローバルランタイムで非同期実行
-    let url_owned = url.to_string();
-    let connection = TOKIO_RT.block_on(async move {
-        let (ws_stream, _) = connect_async(&url_owned)
-            .await
-            .map_err(|e| fmt_msg(MsgKey::WsFailedToConnect, &[&e.to_string()]))?;
-        Ok::<_, String>(Arc::new(WebSocketConnection::new(ws_stream)))
-    })?;
+    let (command_tx, command_rx) = mpsc::unbounded_channel();
+    let (incoming_tx, incoming_rx) = crossbeam_channel::unbounded();
+    TOKIO_RT.spawn(run_connection(stream, url, opts, command_rx, incoming_tx));
 
     // 接続IDを生成してマップに保存
     let conn_id = NEXT_WS_ID.fetch_add(1, Ordering::SeqCst);
 
-    WS_CONNECTIONS.insert(conn_id, connection);
+    WS_CONNECTIONS.insert(
+        conn_id,
+        Arc::new(WebSocketConnection {
+            commands: command_tx,
+            incoming: incoming_rx,
+        }),
+    );
 
     Ok(Value::Integer(conn_id))
 }
 
 /// ws/send - WebSocketでメッセージを送信
+///
+/// 文字列はテキスト、バイト列はバイナリとして送信する。
+/// 再接続待ちの間に送ったメッセージは再接続後に送信される。
 pub fn native_ws_send(args: &[Value]) -> Result<Value, String> {
     if args.len() < 2 {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["ws/send", "2"]));
     }
 
-    let conn_id = get_int_arg(args, 0, "ws/send")?;
-
-    let connection = WS_CONNECTIONS
-        .get(&conn_id)
-        .ok_or_else(|| fmt_msg(MsgKey::WsConnectionNotFound, &[]))?;
+    let connection = get_connection(args, "ws/send")?;
 
     let message = match &args[1] {
-        Value::String(s) => s.clone(),
-        v => format!("{}", v),
+        Value::String(s) => Message::Text(s.clone()),
+        Value::Bytes(b) => Message::Binary(b.to_vec()),
+        v => Message::Text(format!("{}", v)),
     };
 
-    // グローバルランタイムで非同期実行
-    TOKIO_RT.block_on(async move { connection.send(&message).await })?;
+    connection
+        .commands
+        .send(Command::Send(message))
+        .map_err(|_| fmt_msg(MsgKey::WsConnectionClosed, &[]))?;
 
     Ok(Value::Nil)
 }
 
 /// ws/receive - WebSocketからメッセージを受信
+///
+/// 引数: 接続ID、:timeout ミリ秒（省略可）
+/// 戻り値: メッセージマップ（タイムアウト時はnil）
 pub fn native_ws_receive(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["ws/receive", "1"]));
     }
 
-    let conn_id = get_int_arg(args, 0, "ws/receive")?;
+    let connection = get_connection(args, "ws/receive")?;
 
-    let connection = WS_CONNECTIONS
-        .get(&conn_id)
-        .ok_or_else(|| fmt_msg(MsgKey::WsConnectionNotFound, &[]))?;
+    let timeout = match &args[1..] {
+        [] => None,
+        [Value::Keyword(k), Value::Integer(ms)] if &**k == "timeout" && *ms >= 0 => {
+            Some(Duration::from_millis(*ms as u64))
+        }
+        [Value::Keyword(k), _] if &**k == "timeout" => {
+            return Err(fmt_msg(MsgKey::TimeoutMustBeMs, &["ws/receive"]))
+        }
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::ExpectedKeyword,
+                &["ws/receive", ":timeout"],
+            ))
+        }
+    };
 
-    // グローバルランタイムで非同期実行
-    TOKIO_RT.block_on(async move { connection.receive().await })
+    match timeout {
+        None => connection
+            .incoming
+            .recv()
+            .map_err(|_| fmt_msg(MsgKey::WsConnectionClosed, &[])),
+        Some(timeout) => match connection.incoming.recv_timeout(timeout) {
+            Ok(v) => Ok(v),
+            Err(crossbeam_channel::RecvTimeoutError::Timeout) => Ok(Value::Nil),
+            Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                Err(fmt_msg(MsgKey::WsConnectionClosed, &[]))
+            }
+        },
+    }
+}
+
+/// ws/channel - 受信メッセージを受け取るチャネルを取得
+///
+/// ws/receive と同じキューを共有する受信専用チャネル。接続が終了するとクローズされる。
+/// 例: (go/select! [[(ws/channel conn) (fn [msg] ...)] [:timeout 1000 (fn [] ...)]])
+pub fn native_ws_channel(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(fmt_msg(MsgKey::NeedAtLeastNArgs, &["ws/channel", "1"]));
+    }
+
+    let connection = get_connection(args, "ws/channel")?;
+
+    Ok(Value::Channel(Arc::new(Channel {
+        sender: Arc::new(parking_lot::Mutex::new(None)),
+        receiver: connection.incoming.clone(),
+    })))
 }
 
 /// ws/close - WebSocket接続をクローズ
@@ -273,15 +612,99 @@ pub fn native_ws_close(args: &[Value]) -> Result<Value, String> {
 
     let conn_id = get_int_arg(args, 0, "ws/close")?;
 
-    let connection = WS_CONNECTIONS
-        .get(&conn_id)
+    let (_, connection) = WS_CONNECTIONS
+        .remove(&conn_id)
         .ok_or_else(|| fmt_msg(MsgKey::WsConnectionNotFound, &[]))?;
 
-    // グローバルランタイムで非同期実行
-    TOKIO_RT.block_on(async move { connection.close().await })?;
-
-    // マップから削除
-    WS_CONNECTIONS.remove(&conn_id);
+    // タスクが既に終了している（相手側から切断済み）場合はそのまま成功
+    let (done_tx, done_rx) = oneshot::channel();
+    if connection.commands.send(Command::Close(done_tx)).is_ok() {
+        let _ = TOKIO_RT.block_on(async { tokio::time::timeout(CLOSE_TIMEOUT, done_rx).await });
+    }
 
     Ok(Value::Nil)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 受信したテキストをそのまま返すサーバー（最初の接続は1メッセージで切断する）
+    fn spawn_echo_server() -> String {
+        let listener = TOKIO_RT
+            .block_on(async { tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap() });
+        let addr = listener.local_addr().unwrap();
+        TOKIO_RT.spawn(async move {
+            let mut first = true;
+            while let Ok((tcp, _)) = listener.accept().await {
+                let drop_after_one = std::mem::take(&mut first);
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                    while let Some(Ok(msg)) = ws.next().await {
+                        if msg.is_text() {
+                            let _ = ws.send(msg).await;
+                            if drop_after_one {
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        format!("ws://{}", addr)
+    }
+
+    fn event_type(v: &Value) -> String {
+        match v {
+            Value::Map(m) => match m.get(&kw("type")) {
+                Some(Value::String(s)) => s.clone(),
+                _ => String::new(),
+            },
+            _ => String::new(),
+        }
+    }
+
+    #[test]
+    fn test_receive_timeout_and_reconnect() {
+        let url = spawn_echo_server();
+        let mut reconnect = crate::new_hashmap();
+        reconnect.insert(kw("initial-delay"), Value::Integer(10));
+        let mut opts = crate::new_hashmap();
+        opts.insert(kw("reconnect"), Value::Map(reconnect));
+        let conn = native_ws_connect(&[Value::String(url), Value::Map(opts)]).unwrap();
+        let timeout = |ms| {
+            vec![
+                conn.clone(),
+                Value::Keyword("timeout".into()),
+                Value::Integer(ms),
+            ]
+        };
+
+        // 何も届いていなければnil
+        assert_eq!(native_ws_receive(&timeout(50)).unwrap(), Value::Nil);
+
+        native_ws_send(&[conn.clone(), Value::String("hello".into())]).unwrap();
+        assert_eq!(
+            event_type(&native_ws_receive(&timeout(2000)).unwrap()),
+            "message"
+        );
+
+        // サーバーが切断したので再接続し、その後も送受信できる
+        let mut seen = Vec::new();
+        while seen.last().map(String::as_str) != Some("reconnected") {
+            seen.push(event_type(&native_ws_receive(&timeout(2000)).unwrap()));
+            assert!(seen.len() < 10, "unexpected events: {:?}", seen);
+        }
+        assert!(seen.contains(&"reconnecting".to_string()));
+
+        native_ws_send(&[conn.clone(), Value::String("again".into())]).unwrap();
+        let channel = native_ws_channel(std::slice::from_ref(&conn)).unwrap();
+        let Value::Channel(ch) = channel else {
+            panic!("expected channel");
+        };
+        let msg = ch.receiver.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(event_type(&msg), "message");
+
+        native_ws_close(&[conn]).unwrap();
+    }
+}
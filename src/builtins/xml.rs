@@ -0,0 +1,453 @@
+//! XMLモジュール
+//!
+//! XML処理関数を提供:
+//! - parse: XML文字列をパース
+//! - stringify: 要素マップをXML文字列に変換
+//! - read-stream: XMLファイルから指定タグの要素をストリームとして読み込み
+//!
+//! 要素は次のマップで表す:
+//! `{:tag "item" :attrs {"id" "1"} :content ["テキスト" {:tag "b" ...}]}`
+//! - :attrs は属性名（文字列）→値（文字列）のマップ（属性がなければ空）
+//! - :content は子要素マップとテキスト（文字列）のベクタ（子がなければ空）
+//! - 空白だけのテキストは捨て、隣接するテキストとCDATAは1つの文字列にまとめる
+//! - XML宣言・コメント・処理命令・DOCTYPEは読み飛ばす
+
+use crate::builtins::util::kw;
+use crate::check_args;
+use crate::i18n::{fmt_msg, MsgKey};
+use crate::value::{MapKey, Stream, Value};
+use parking_lot::{Mutex, RwLock};
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// 要素のネストの最大深さ（深すぎる入力でスタックを使い果たさないように）
+const MAX_DEPTH: usize = 128;
+
+/// XML文字列をパースしてルート要素のマップに変換
+///
+/// # 引数
+/// - args[0]: XML文字列
+///
+/// # 戻り値
+/// - 成功時: ルート要素のマップ
+/// - 失敗時: {:error エラーメッセージ}
+pub fn native_parse(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 1, "xml/parse");
+
+    let xml_str = match &args[0] {
+        Value::String(s) => s,
+        _ => return Err(fmt_msg(MsgKey::ArgMustBeType, &["xml/parse", "a string"])),
+    };
+
+    let mut reader = Reader::from_str(xml_str);
+    match read_root(&mut reader) {
+        Ok(root) => Ok(root),
+        Err(e) => Ok(Value::error(fmt_msg(
+            MsgKey::FormatParseError,
+            &["xml/parse", &e],
+        ))),
+    }
+}
+
+/// 要素マップをXML文字列に変換
+///
+/// # 引数
+/// - args[0]: 要素マップ
+/// - args[1]: オプション（省略可）
+///   - :indent - インデント幅（省略時は改行なし）
+///   - :declaration - trueなら `<?xml version="1.0" encoding="UTF-8"?>` を先頭に付ける
+///
+/// # 戻り値
+/// - XML文字列
+pub fn native_stringify(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(fmt_msg(MsgKey::Need1Or2Args, &["xml/stringify"]));
+    }
+
+    let (indent, declaration) = match args.get(1) {
+        None | Some(Value::Nil) => (None, false),
+        Some(Value::Map(opts)) => {
+            let indent = match opts.get(&kw("indent")) {
+                None | Some(Value::Nil) => None,
+                Some(Value::Integer(n)) if *n >= 0 => Some(*n as usize),
+                Some(_) => {
+                    return Err(fmt_msg(
+                        MsgKey::MustBeNonNegative,
+                        &["xml/stringify", ":indent"],
+                    ))
+                }
+            };
+            let declaration = matches!(opts.get(&kw("declaration")), Some(Value::Bool(true)));
+            (indent, declaration)
+        }
+        Some(_) => return Err(fmt_msg(MsgKey::MustBeMap, &["xml/stringify", "options"])),
+    };
+
+    let mut writer = match indent {
+        Some(n) => Writer::new_with_indent(Vec::new(), b' ', n),
+        None => Writer::new(Vec::new()),
+    };
+    if declaration {
+        writer
+            .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+            .map_err(|e| e.to_string())?;
+        // インデント時はWriterが改行を入れる
+        if indent.is_none() {
+            writer.get_mut().push(b'\n');
+        }
+    }
+    write_node(&mut writer, &args[0])?;
+
+    String::from_utf8(writer.into_inner())
+        .map(Value::String)
+        .map_err(|e| e.to_string())
+}
+
+/// xml/read-stream - XMLファイルから指定タグの要素をストリームとして読み込み
+///
+/// ファイル全体をメモリに載せず、`tag` に一致する要素（深さは問わない）を
+/// 見つけるたびにその部分木だけをマップにして返す。RSSの`item`や巨大なデータダンプ向け。
+/// パースエラーは `{:error "xml/read-stream: ..."}` 要素として返し、そこでストリームを終える。
+///
+/// # 引数
+/// - args[0]: ファイルパス
+/// - args[1]: タグ名（名前空間接頭辞を含む名前と一致させる）
+pub fn native_read_stream(args: &[Value]) -> Result<Value, String> {
+    check_args!(args, 2, "xml/read-stream");
+
+    let path = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => return Err(fmt_msg(MsgKey::TypeOnly, &["xml/read-stream", "string"])),
+    };
+    let tag = match &args[1] {
+        Value::String(s) => s.clone(),
+        Value::Keyword(k) => k.to_string(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::SecondArgMustBe,
+                &["xml/read-stream", "a tag name string"],
+            ))
+        }
+    };
+
+    let file = std::fs::File::open(&path)
+        .map_err(|e| fmt_msg(MsgKey::FileReadError, &[&path, &e.to_string()]))?;
+    // エラー後はNoneにしてストリームを終える
+    let reader = Mutex::new(Some(Reader::from_reader(std::io::BufReader::new(file))));
+
+    let stream = Stream {
+        next_fn: Box::new(move || {
+            let mut guard = reader.lock();
+            match next_element(guard.as_mut()?, &tag) {
+                Ok(Some(value)) => Some(value),
+                Ok(None) => {
+                    *guard = None;
+                    None
+                }
+                Err(e) => {
+                    *guard = None;
+                    Some(Value::error(fmt_msg(
+                        MsgKey::FormatParseError,
+                        &["xml/read-stream", &e],
+                    )))
+                }
+            }
+        }),
+    };
+
+    Ok(Value::Stream(Arc::new(RwLock::new(stream))))
+}
+
+/// 次に現れる`tag`要素を読み取る（文書の終わりならNone）
+fn next_element<R: BufRead>(reader: &mut Reader<R>, tag: &str) -> Result<Option<Value>, String> {
+    let mut buf = Vec::new();
+    loop {
+        match reader
+            .read_event_into(&mut buf)
+            .map_err(|e| e.to_string())?
+        {
+            Event::Start(e) if e.name().as_ref() == tag.as_bytes() => {
+                let e = e.into_owned();
+                let content = read_content(reader, 1)?;
+                return element(&e, content).map(Some);
+            }
+            Event::Empty(e) if e.name().as_ref() == tag.as_bytes() => {
+                return element(&e, Vec::new()).map(Some);
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// ルート要素を読み取る
+fn read_root<R: BufRead>(reader: &mut Reader<R>) -> Result<Value, String> {
+    let mut buf = Vec::new();
+    loop {
+        match reader
+            .read_event_into(&mut buf)
+            .map_err(|e| e.to_string())?
+        {
+            Event::Start(e) => {
+                let e = e.into_owned();
+                let content = read_content(reader, 1)?;
+                return element(&e, content);
+            }
+            Event::Empty(e) => return element(&e, Vec::new()),
+            Event::Text(t) if t.iter().all(u8::is_ascii_whitespace) => {}
+            Event::Text(_) | Event::CData(_) => {
+                return Err("text outside the root element".to_string())
+            }
+            Event::Eof => return Err("no root element".to_string()),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// 開始タグの直後から対応する終了タグまでの子ノードを読み取る
+///
+/// depthは読み取り中の要素の深さ（ルートが1）
+fn read_content<R: BufRead>(reader: &mut Reader<R>, depth: usize) -> Result<Vec<Value>, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("elements nested too deeply (max {})", MAX_DEPTH));
+    }
+
+    let mut content = Vec::new();
+    let mut text = String::new();
+    let mut buf = Vec::new();
+
+    fn flush(text: &mut String, content: &mut Vec<Value>) {
+        if !text.trim().is_empty() {
+            content.push(Value::String(std::mem::take(text)));
+        }
+        text.clear();
+    }
+
+    loop {
+        match reader
+            .read_event_into(&mut buf)
+            .map_err(|e| e.to_string())?
+        {
+            Event::Start(e) => {
+                let e = e.into_owned();
+                flush(&mut text, &mut content);
+                let children = read_content(reader, depth + 1)?;
+                content.push(element(&e, children)?);
+            }
+            Event::Empty(e) => {
+                flush(&mut text, &mut content);
+                content.push(element(&e, Vec::new())?);
+            }
+            Event::Text(t) => text.push_str(&t.unescape().map_err(|e| e.to_string())?),
+            Event::CData(c) => text.push_str(&c.decode().map_err(|e| e.to_string())?),
+            Event::End(_) => {
+                flush(&mut text, &mut content);
+                return Ok(content);
+            }
+            Event::Eof => return Err("unexpected end of document".to_string()),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// 開始タグと子ノードから要素マップを作る
+fn element(start: &BytesStart, content: Vec<Value>) -> Result<Value, String> {
+    let mut attrs = crate::new_hashmap();
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| e.to_string())?;
+        let value = attr.unescape_value().map_err(|e| e.to_string())?;
+        attrs.insert(
+            MapKey::String(String::from_utf8_lossy(attr.key.as_ref()).into_owned()),
+            Value::String(value.into_owned()),
+        );
+    }
+
+    let mut map = crate::new_hashmap();
+    map.insert(
+        kw("tag"),
+        Value::String(String::from_utf8_lossy(start.name().as_ref()).into_owned()),
+    );
+    map.insert(kw("attrs"), Value::Map(attrs));
+    map.insert(kw("content"), Value::Vector(content.into()));
+    Ok(Value::Map(map))
+}
+
+/// 要素マップまたはテキストを書き出す
+fn write_node(writer: &mut Writer<Vec<u8>>, node: &Value) -> Result<(), String> {
+    let io_err = |e: std::io::Error| e.to_string();
+    let m = match node {
+        Value::Map(m) => m,
+        Value::Nil => return Ok(()),
+        Value::String(s) => {
+            return writer
+                .write_event(Event::Text(BytesText::new(s)))
+                .map_err(io_err)
+        }
+        Value::Integer(_) | Value::Float(_) | Value::Bool(_) | Value::Keyword(_) => {
+            let text = match node {
+                Value::Keyword(k) => k.to_string(),
+                other => other.to_string(),
+            };
+            return writer
+                .write_event(Event::Text(BytesText::new(&text)))
+                .map_err(io_err);
+        }
+        other => {
+            return Err(fmt_msg(
+                MsgKey::XmlInvalidElement,
+                &["xml/stringify", &other.to_string()],
+            ))
+        }
+    };
+
+    let tag = match m.get(&kw("tag")) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Keyword(k)) => k.to_string(),
+        _ => {
+            return Err(fmt_msg(
+                MsgKey::XmlInvalidElement,
+                &["xml/stringify", "missing :tag"],
+            ))
+        }
+    };
+
+    let mut start = BytesStart::new(tag.as_str());
+    match m.get(&kw("attrs")) {
+        None | Some(Value::Nil) => {}
+        Some(Value::Map(attrs)) => {
+            // 出力が安定するよう属性名順に並べる
+            let mut pairs: Vec<(String, String)> = attrs
+                .iter()
+                .filter(|(_, v)| !matches!(v, Value::Nil))
+                .map(|(k, v)| {
+                    let name = match k {
+                        MapKey::Keyword(s) | MapKey::Symbol(s) => s.to_string(),
+                        MapKey::String(s) => s.clone(),
+                        MapKey::Integer(i) => i.to_string(),
+                        MapKey::Char(c) => c.to_string(),
+                    };
+                    let value = match v {
+                        Value::String(s) => s.clone(),
+                        Value::Keyword(k) => k.to_string(),
+                        other => other.to_string(),
+                    };
+                    (name, value)
+                })
+                .collect();
+            pairs.sort();
+            for (name, value) in &pairs {
+                start.push_attribute((name.as_str(), value.as_str()));
+            }
+        }
+        Some(_) => {
+            return Err(fmt_msg(
+                MsgKey::XmlInvalidElement,
+                &["xml/stringify", ":attrs must be a map"],
+            ))
+        }
+    }
+
+    let content: Vec<Value> = match m.get(&kw("content")) {
+        None | Some(Value::Nil) => Vec::new(),
+        Some(Value::Vector(items)) | Some(Value::List(items)) => items.iter().cloned().collect(),
+        Some(single) => vec![single.clone()],
+    };
+
+    if content.is_empty() {
+        return writer.write_event(Event::Empty(start)).map_err(io_err);
+    }
+    writer.write_event(Event::Start(start)).map_err(io_err)?;
+    for child in &content {
+        write_node(writer, child)?;
+    }
+    writer
+        .write_event(Event::End(BytesEnd::new(tag.as_str())))
+        .map_err(io_err)
+}
+
+// ========================================
+// 関数登録テーブル
+// ========================================
+
+/// 登録すべき関数のリスト（Evaluator不要な関数のみ）
+/// @qi-doc:category data/xml
+/// @qi-doc:functions parse, stringify, read-stream
+pub const FUNCTIONS: super::NativeFunctions = &[
+    ("xml/parse", native_parse),
+    ("xml/stringify", native_stringify),
+    ("xml/read-stream", native_read_stream),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_stringify() {
+        let src = r#"<?xml version="1.0"?>
+<feed lang="en">
+  <!-- comment -->
+  <item id="1">Fish &amp; <b>chips</b><![CDATA[ <raw> ]]></item>
+  <empty/>
+</feed>"#;
+        let root = native_parse(&[Value::String(src.into())]).unwrap();
+        let Value::Map(m) = &root else {
+            panic!("expected map, got {}", root);
+        };
+        let Some(Value::Vector(children)) = m.get(&kw("content")) else {
+            panic!("expected content");
+        };
+        assert_eq!(children.len(), 2);
+        let Value::Map(item) = &children[0] else {
+            panic!("expected element");
+        };
+        let Some(Value::Vector(item_content)) = item.get(&kw("content")) else {
+            panic!("expected content");
+        };
+        assert_eq!(item_content[0], Value::String("Fish & ".into()));
+        assert_eq!(item_content[2], Value::String(" <raw> ".into()));
+
+        let Value::String(out) = native_stringify(&[root.clone()]).unwrap() else {
+            panic!("expected string");
+        };
+        assert_eq!(
+            out,
+            r#"<feed lang="en"><item id="1">Fish &amp; <b>chips</b> &lt;raw&gt; </item><empty/></feed>"#
+        );
+        assert_eq!(native_parse(&[Value::String(out)]).unwrap(), root);
+    }
+
+    #[test]
+    fn test_depth_limit_and_stream_errors() {
+        let deep = format!(
+            "{}{}",
+            "<a>".repeat(MAX_DEPTH + 1),
+            "</a>".repeat(MAX_DEPTH + 1)
+        );
+        assert!(native_parse(&[Value::String(deep)]).unwrap().is_error());
+        let ok = format!("{}{}", "<a>".repeat(MAX_DEPTH), "</a>".repeat(MAX_DEPTH));
+        assert!(!native_parse(&[Value::String(ok)]).unwrap().is_error());
+
+        // 壊れた箇所は{:error}要素になり、そこでストリームが終わる
+        let path = std::env::temp_dir().join(format!("qi-xml-test-{}.xml", std::process::id()));
+        std::fs::write(&path, "<r><item>1</item><item>2</oops><item>3</item></r>").unwrap();
+        let Value::Stream(stream) = native_read_stream(&[
+            Value::String(path.to_str().unwrap().to_string()),
+            Value::String("item".into()),
+        ])
+        .unwrap() else {
+            panic!("expected stream");
+        };
+        let values: Vec<Value> = std::iter::from_fn(|| (stream.read().next_fn)()).collect();
+        assert_eq!(values.len(), 2);
+        assert!(!values[0].is_error());
+        assert!(values[1].is_error());
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -75,6 +75,9 @@ fn yaml_to_value(yaml: serde_yaml::Value) -> Value {
         serde_yaml::Value::Number(n) => {
             if let Some(i) = n.as_i64() {
                 Value::Integer(i)
+            } else if let Some(u) = n.as_u64() {
+                // 2^63以上の整数は多倍長整数として保持（データロスを防ぐ）
+                Value::BigInt(std::sync::Arc::new(num_bigint::BigInt::from(u)))
             } else if let Some(f) = n.as_f64() {
                 Value::Float(f)
             } else {
@@ -110,6 +113,17 @@ fn value_to_yaml(value: &Value) -> serde_yaml::Value {
         Value::Bool(b) => serde_yaml::Value::Bool(*b),
         Value::Integer(i) => serde_yaml::Value::Number((*i).into()),
         Value::Float(f) => serde_yaml::Value::Number(serde_yaml::Number::from(*f)),
+        // u64に収まらない多倍長整数は精度を失わないよう文字列で出力（json/stringifyと同じ規則）
+        Value::BigInt(n) => num_traits::ToPrimitive::to_u64(n.as_ref())
+            .map(|u| serde_yaml::Value::Number(u.into()))
+            .unwrap_or_else(|| serde_yaml::Value::String(n.to_string())),
+        Value::Ratio(_) => crate::builtins::numeric_helpers::number_to_f64(value)
+            .map(|f| serde_yaml::Value::Number(f.into()))
+            .unwrap_or(serde_yaml::Value::Null),
+        // 10進数はf64で誤差なく表せる場合のみ数値、それ以外は文字列で出力
+        Value::Decimal(d) => crate::builtins::numeric_helpers::decimal_to_exact_f64(d)
+            .map(|f| serde_yaml::Value::Number(f.into()))
+            .unwrap_or_else(|| serde_yaml::Value::String(d.to_string())),
         Value::String(s) => serde_yaml::Value::String(s.clone()),
         Value::Bytes(b) => {
             // バイナリデータはBase64エンコードして文字列として出力
@@ -129,6 +143,7 @@ fn value_to_yaml(value: &Value) -> serde_yaml::Value {
             }
         }
         Value::Keyword(s) => serde_yaml::Value::String(s.to_string()),
+        Value::Char(c) => serde_yaml::Value::String(c.to_string()),
         Value::Vector(v) => {
             // サイズが分かっているので事前確保
             let mut seq = Vec::with_capacity(v.len());
@@ -145,6 +160,8 @@ fn value_to_yaml(value: &Value) -> serde_yaml::Value {
             }
             serde_yaml::Value::Sequence(seq)
         }
+        // セットはシーケンスに変換（出力を安定させるため要素は表示順に並べる）
+        Value::Set(items) => value_to_yaml(&Value::Vector(Value::sorted_set_items(items))),
         Value::Map(m) => {
             let mut mapping = serde_yaml::Mapping::new();
             for (k, v) in m.iter() {
@@ -154,6 +171,7 @@ fn value_to_yaml(value: &Value) -> serde_yaml::Value {
                     crate::value::MapKey::String(s) => s.clone(),
                     crate::value::MapKey::Symbol(sym) => sym.to_string(),
                     crate::value::MapKey::Integer(i) => i.to_string(),
+                    crate::value::MapKey::Char(c) => c.to_string(),
                 };
                 mapping.insert(serde_yaml::Value::String(yaml_key), value_to_yaml(v));
             }
@@ -175,3 +193,52 @@ pub const FUNCTIONS: super::NativeFunctions = &[
     ("yaml/stringify", native_stringify),
     ("yaml/pretty", native_pretty),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn roundtrip(value: Value) -> Value {
+        let mut m = crate::new_hashmap();
+        m.insert(crate::value::MapKey::String("a".into()), value);
+        let yaml = native_stringify(&[Value::Map(m)]).unwrap();
+        match native_parse(&[yaml]).unwrap() {
+            Value::Map(m) => m
+                .get(&crate::value::MapKey::String("a".into()))
+                .cloned()
+                .unwrap(),
+            other => panic!("expected map, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_exact_numbers_roundtrip() {
+        // u64に収まる多倍長整数は数値、それより大きいものは文字列
+        let u = num_bigint::BigInt::from(u64::MAX);
+        assert_eq!(
+            roundtrip(Value::BigInt(Arc::new(u.clone()))),
+            Value::BigInt(Arc::new(u))
+        );
+        let big: num_bigint::BigInt = num_bigint::BigInt::from(i64::MAX) * 4;
+        assert_eq!(
+            roundtrip(Value::BigInt(Arc::new(big.clone()))),
+            Value::String(big.to_string())
+        );
+
+        // 10進数は誤差なく表せれば数値、そうでなければ文字列
+        assert_eq!(
+            roundtrip(Value::Decimal("1.5".parse().unwrap())),
+            Value::Float(1.5)
+        );
+        let d: rust_decimal::Decimal = "12345678901234567890.12".parse().unwrap();
+        assert_eq!(roundtrip(Value::Decimal(d)), Value::String(d.to_string()));
+
+        // 有理数は浮動小数点数
+        let third = num_rational::BigRational::new(1.into(), 3.into());
+        assert_eq!(
+            roundtrip(Value::Ratio(Arc::new(third))),
+            Value::Float(1.0 / 3.0)
+        );
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! try_eval_special_form, eval_call, apply_function, apply_func等の
 //! 関数呼び出しに関する評価ロジックを提供します。
+//!
+//! 末尾呼び出し最適化: 末尾位置（if/let/do/when/matchの末尾を辿った先）にある
+//! ユーザー定義関数の呼び出しは、その場で適用せずに `TailCall::Call` として返し、
+//! apply_funcのループで実行します。これにより自己再帰・相互再帰がスタックを消費しません。
 
 use crate::i18n::{fmt_msg, MsgKey};
 use crate::value::{Env, Expr, Pattern, Value};
@@ -14,6 +18,14 @@ use super::helpers::qerr;
 use super::hof_keys;
 use super::Evaluator;
 
+/// 末尾位置の評価結果
+pub(super) enum TailCall {
+    /// 評価済みの値
+    Value(Value),
+    /// 未適用のユーザー定義関数呼び出し（関数と評価済み引数）
+    Call(Value, Box<SmallVec<[Value; 4]>>),
+}
+
 /// マップキーを作成
 fn to_map_key(key: &str) -> crate::value::MapKey {
     let key_str = key.strip_prefix(':').unwrap_or(key);
@@ -29,12 +41,15 @@ impl Evaluator {
         env: Arc<RwLock<Env>>,
     ) -> Option<Result<Value, String>> {
         if let Expr::Symbol { name, .. } = func {
-            match &**name {
+            // core/ 修飾された組み込みの特殊形式（core/map 等）も同じく扱う
+            let name = name.strip_prefix("core/").unwrap_or(name);
+            match name {
                 "_railway-pipe" => Some(self.eval_railway_pipe(args, env)),
                 "and" => Some(self.eval_and(args, env)),
                 "apply" => Some(self.eval_apply(args, env)),
                 "comment" => Some(Ok(Value::Nil)),
                 "go/catch" => Some(self.eval_catch(args, env)),
+                "go/chan" => Some(self.eval_all_args(args, env, builtins::chan)),
                 "go/parallel-do" => Some(self.eval_parallel_do(args, env)),
                 "go/pfilter" => Some(self.eval_pfilter(args, env)),
                 "go/preduce" => Some(self.eval_preduce(args, env)),
@@ -45,12 +60,26 @@ impl Evaluator {
                 "go/with-scope" => Some(self.eval_with_scope(args, env)),
                 "branch" => Some(self.eval_branch(args, env)),
                 "comp" => Some(self.eval_comp(args, env)),
+                "alter" => Some(self.eval_all_args(args, env, builtins::alter)),
+                "commute" => Some(self.eval_all_args(args, env, builtins::commute)),
+                "dosync" => Some(self.eval_dosync(args, env)),
+                "binding" => Some(self.eval_binding(args, env)),
+                "defdynamic" => Some(self.eval_defdynamic(args, env)),
+                "ns" => Some(self.eval_ns(args, env)),
+                "ns-publics" => {
+                    Some(self.eval_all_args(args, env, |vals, ev| ev.eval_ns_publics(vals)))
+                }
+                "ns-resolve" => {
+                    Some(self.eval_all_args(args, env, |vals, ev| ev.eval_ns_resolve(vals)))
+                }
                 "drop-while" => Some(self.eval_drop_while(args, env)),
                 "eval" => Some(self.eval_eval(args, env)),
+                "macroexpand-all" => Some(self.eval_macroexpand_all(args, env)),
                 "source" => Some(self.eval_source(args, env)),
                 "list/every?" => Some(self.eval_every(args, env)),
                 "filter" => Some(self.eval_filter(args, env)),
                 "find" => Some(self.eval_find(args, env)),
+                "into" => Some(self.eval_all_args(args, env, builtins::into)),
                 "list/chunk" => Some(self.eval_chunk(args, env)),
                 "list/count-by" => Some(self.eval_count_by(args, env)),
                 "list/drop-last" => Some(self.eval_drop_last(args, env)),
@@ -66,6 +95,7 @@ impl Evaluator {
                 "list/sum-by" => Some(self.eval_sum_by(args, env)),
                 "map-lines" => Some(self.eval_map_lines(args, env)),
                 "map" => Some(self.eval_map(args, env)),
+                "mapcat" => Some(self.eval_mapcat(args, env)),
                 "map/update-keys" => Some(self.eval_update_keys(args, env)),
                 "map/update-vals" => Some(self.eval_update_vals(args, env)),
                 "map/filter-vals" => Some(self.eval_map_filter_vals(args, env)),
@@ -79,15 +109,20 @@ impl Evaluator {
                 "quote" => Some(self.eval_quote(args)),
                 "reduce" => Some(self.eval_reduce(args, env)),
                 "list/some?" => Some(self.eval_some(args, env)),
+                "sequence" => Some(self.eval_all_args(args, env, builtins::sequence)),
                 "stream/filter" => Some(self.eval_stream_filter(args, env)),
                 "stream/iterate" => Some(self.eval_iterate(args, env)),
                 "stream/map" => Some(self.eval_stream_map(args, env)),
+                "reset!" => Some(self.eval_all_args(args, env, builtins::reset)),
+                "set-validator!" => Some(self.eval_all_args(args, env, builtins::set_validator)),
                 "swap!" => Some(self.eval_swap(args, env)),
                 "take-while" => Some(self.eval_take_while(args, env)),
+                "trampoline" => Some(self.eval_all_args(args, env, builtins::trampoline)),
                 "tap" => Some(self.eval_tap(args, env)),
                 "test/assert-throws" => Some(self.eval_test_assert_throws(args, env)),
                 "test/run" => Some(self.eval_test_run(args, env)),
                 "time" => Some(self.eval_time(args, env)),
+                "transduce" => Some(self.eval_all_args(args, env, builtins::transduce)),
                 "update-in" => Some(self.eval_update_in(args, env)),
                 "update" => Some(self.eval_update(args, env)),
                 "table/where" => Some(self.eval_table_where(args, env)),
@@ -108,17 +143,114 @@ impl Evaluator {
         args: &[Expr],
         env: Arc<RwLock<Env>>,
     ) -> Result<Value, String> {
+        let tail = self.eval_call_tail(func, args, env)?;
+        self.finish_tail(tail)
+    }
+
+    /// 末尾位置の式を評価
+    ///
+    /// if/let/do/when/matchの末尾位置を辿り、ユーザー定義関数の呼び出しに到達したら
+    /// 適用せずに `TailCall::Call` を返す。それ以外の式は通常どおり評価する
+    pub(super) fn eval_tail(&self, expr: &Expr, env: Arc<RwLock<Env>>) -> Result<TailCall, String> {
+        match expr {
+            Expr::If {
+                test,
+                then,
+                otherwise,
+                ..
+            } => {
+                let test_val = self.eval_with_env(test, Arc::clone(&env))?;
+                if test_val.is_truthy() {
+                    self.eval_tail(then, env)
+                } else if let Some(otherwise) = otherwise {
+                    self.eval_tail(otherwise, env)
+                } else {
+                    Ok(TailCall::Value(Value::Nil))
+                }
+            }
+
+            Expr::Let { bindings, body, .. } => {
+                // let*セマンティクス: 順次束縛（各束縛が前の束縛を参照できる）
+                let new_env = Arc::new(RwLock::new(Env::with_parent(Arc::clone(&env))));
+                for (pattern, expr) in bindings {
+                    // 現在の環境で評価（既に追加された束縛が見える）
+                    let value = self.eval_with_env(expr, new_env.clone())?;
+                    self.bind_fn_param(pattern, &value, &mut new_env.write())?;
+                }
+                self.eval_tail(body, new_env)
+            }
+
+            Expr::Do { exprs, .. } => self.eval_do_tail(exprs, env),
+
+            Expr::When {
+                condition, body, ..
+            } => {
+                let cond_val = self.eval_with_env(condition, Arc::clone(&env))?;
+                if cond_val.is_truthy() {
+                    self.eval_do_tail(body, env)
+                } else {
+                    Ok(TailCall::Value(Value::Nil))
+                }
+            }
+
+            Expr::Match { expr, arms, .. } => {
+                let value = self.eval_with_env(expr, Arc::clone(&env))?;
+                self.eval_match_tail(&value, arms, env)
+            }
+
+            Expr::Call { func, args, .. } => {
+                // 末尾位置の呼び出しでもブレークポイントを確認する
+                #[cfg(feature = "dap-server")]
+                self.check_breakpoint(expr, &env);
+                self.eval_call_tail(func, args, env)
+            }
+
+            _ => self.eval_with_env(expr, env).map(TailCall::Value),
+        }
+    }
+
+    /// 末尾位置の評価結果を値に確定させる（未適用の呼び出しはここで実行）
+    pub(super) fn finish_tail(&self, tail: TailCall) -> Result<Value, String> {
+        match tail {
+            TailCall::Value(value) => Ok(value),
+            TailCall::Call(func, args) => self.apply_func(&func, *args),
+        }
+    }
+
+    /// デバッガ・プロファイラの計測対象として関数本体を評価する
+    ///
+    /// 末尾呼び出しもこの場で完了させるため、呼び出し先はスタックフレームと計測時間に
+    /// 含まれる（計測中は末尾呼び出し最適化を行わない）
+    fn eval_body_instrumented(
+        &self,
+        body: &Expr,
+        env: Arc<RwLock<Env>>,
+    ) -> Result<TailCall, String> {
+        let tail = self.eval_tail(body, env)?;
+        self.finish_tail(tail).map(TailCall::Value)
+    }
+
+    /// 関数呼び出しを末尾位置として評価
+    ///
+    /// ユーザー定義関数は適用せずに `TailCall::Call` を返し、
+    /// それ以外（特殊形式、ネイティブ関数、キーワード関数等）はその場で評価する
+    fn eval_call_tail(
+        &self,
+        func: &Expr,
+        args: &[Expr],
+        env: Arc<RwLock<Env>>,
+    ) -> Result<TailCall, String> {
         // 特殊形式のチェック
         if let Some(result) = self.try_eval_special_form(func, args, Arc::clone(&env)) {
-            return result;
+            return result.map(TailCall::Value);
         }
 
         let func_val = self.eval_with_env(func, Arc::clone(&env))?;
 
-        // マクロの場合は展開してから評価
+        // マクロの場合は展開してから評価（展開結果も末尾位置）
         if let Value::Macro(mac) = &func_val {
             let expanded = self.expand_macro(mac, args, Arc::clone(&env))?;
-            return self.eval_with_env(&expanded, env);
+            return self.eval_tail(&expanded, env);
         }
 
         let arg_vals: Result<SmallVec<[Value; 4]>, _> = args
@@ -127,11 +259,11 @@ impl Evaluator {
             .collect();
         let arg_vals = arg_vals?;
 
-        match func_val {
+        let result = match func_val {
             Value::NativeFunc(nf) => (nf.func)(&arg_vals),
             Value::Function(_) => {
-                // apply_funcを使って関数を適用（complementやjuxtの特殊処理を含む）
-                self.apply_func(&func_val, arg_vals)
+                // 呼び出し元（apply_funcのループ）で適用する
+                return Ok(TailCall::Call(func_val, Box::new(arg_vals)));
             }
             Value::Keyword(key) => {
                 // キーワードを関数として使う: (:name map) => (get map :name)
@@ -170,7 +302,8 @@ impl Evaluator {
                 "{} は呼び出し可能ではありません",
                 func_val.type_name()
             )),
-        }
+        };
+        result.map(TailCall::Value)
     }
 
     /// 関数を適用するヘルパー（builtinsモジュールから使用）
@@ -298,9 +431,11 @@ impl Evaluator {
             | Pattern::Integer(_)
             | Pattern::Float(_)
             | Pattern::String(_)
+            | Pattern::Char(_)
             | Pattern::Keyword(_)
             | Pattern::Transform(_, _)
-            | Pattern::Or(_) => Err(fmt_msg(MsgKey::PatternErrorNotAllowed, &[])),
+            | Pattern::Or(_)
+            | Pattern::Set(_) => Err(fmt_msg(MsgKey::PatternErrorNotAllowed, &[])),
         }
     }
 
@@ -310,266 +445,298 @@ impl Evaluator {
         func: &Value,
         args: SmallVec<[Value; 4]>,
     ) -> Result<Value, String> {
-        // トレース機能: 関数呼び出しをログ出力
-        // デッドロック防止: 関数名取得前にロックを解放
-        let should_trace = {
-            let traced_funcs = crate::builtins::debug::TRACED_FUNCTIONS.read();
-            !traced_funcs.is_empty()
-        };
-
-        if should_trace {
-            // 関数名を取得（global_envのロックを取得）
-            if let Some(func_name) = self.get_function_name(func) {
-                // 再度ロックを取得してチェック
+        let mut func = func.clone();
+        let mut args = args;
+
+        // 末尾呼び出しはループで処理し、Rustのスタックを消費しない
+        loop {
+            // トレース機能: 関数呼び出しをログ出力
+            // デッドロック防止: 関数名取得前にロックを解放
+            let should_trace = {
                 let traced_funcs = crate::builtins::debug::TRACED_FUNCTIONS.read();
-                if traced_funcs.contains(&func_name) {
-                    let args_str: Vec<String> = args.iter().map(|a| format!("{:?}", a)).collect();
-                    let trace_msg = format!("→ {}({})", func_name, args_str.join(", "));
-
-                    #[cfg(feature = "repl")]
-                    {
-                        use colored::Colorize;
-                        eprintln!("{}", trace_msg.cyan());
+                !traced_funcs.is_empty()
+            };
+
+            if should_trace {
+                // 関数名を取得（global_envのロックを取得）
+                if let Some(func_name) = self.get_function_name(&func) {
+                    // 再度ロックを取得してチェック
+                    let traced_funcs = crate::builtins::debug::TRACED_FUNCTIONS.read();
+                    if traced_funcs.contains(&func_name) {
+                        let args_str: Vec<String> =
+                            args.iter().map(|a| format!("{:?}", a)).collect();
+                        let trace_msg = format!("→ {}({})", func_name, args_str.join(", "));
+
+                        #[cfg(feature = "repl")]
+                        {
+                            use colored::Colorize;
+                            eprintln!("{}", trace_msg.cyan());
+                        }
+                        #[cfg(not(feature = "repl"))]
+                        eprintln!("{}", trace_msg);
                     }
-                    #[cfg(not(feature = "repl"))]
-                    eprintln!("{}", trace_msg);
                 }
             }
-        }
 
-        // 関数を実行
-        match func {
-            Value::NativeFunc(nf) => (nf.func)(&args),
-            Value::Function(f) => {
-                // 特殊処理フラグがtrueの場合のみ環境ルックアップ（99.9%の通常関数で高速化）
-                if f.has_special_processing {
-                    // ロックは一度だけ取得し、必要な値をローカルにclone（ロック競合を削減）
-                    let env_guard = f.env.read();
-                    let complement_func = env_guard.get(hof_keys::COMPLEMENT_FUNC);
-                    let juxt_funcs = env_guard.get(hof_keys::JUXT_FUNCS);
-                    let tap_func = env_guard.get(hof_keys::TAP_FUNC);
-                    let partial_func = env_guard.get(hof_keys::PARTIAL_FUNC);
-                    let partial_args = env_guard.get(hof_keys::PARTIAL_ARGS);
-                    let comp_funcs = env_guard.get(hof_keys::COMP_FUNCS);
-                    drop(env_guard); // 明示的に解放
-
-                    // complement特殊処理 - 実行前にチェック
-                    if let Some(complement_func) = complement_func {
-                        let result = self.apply_func(&complement_func, args)?;
-                        return Ok(Value::Bool(!result.is_truthy()));
-                    }
+            // 関数を実行
+            let tail = match &func {
+                Value::NativeFunc(nf) => return (nf.func)(&args),
+                Value::Function(f) => {
+                    // 特殊処理フラグがtrueの場合のみ環境ルックアップ（99.9%の通常関数で高速化）
+                    if f.has_special_processing {
+                        // ロックは一度だけ取得し、必要な値をローカルにclone（ロック競合を削減）
+                        let env_guard = f.env.read();
+                        let complement_func = env_guard.get(hof_keys::COMPLEMENT_FUNC);
+                        let juxt_funcs = env_guard.get(hof_keys::JUXT_FUNCS);
+                        let tap_func = env_guard.get(hof_keys::TAP_FUNC);
+                        let partial_func = env_guard.get(hof_keys::PARTIAL_FUNC);
+                        let partial_args = env_guard.get(hof_keys::PARTIAL_ARGS);
+                        let comp_funcs = env_guard.get(hof_keys::COMP_FUNCS);
+                        let transducer_steps = env_guard.get(hof_keys::TRANSDUCER_STEPS);
+                        drop(env_guard); // 明示的に解放
+
+                        // トランスデューサは transduce/into/sequence 経由でのみ使用する
+                        if transducer_steps.is_some() {
+                            return Err(fmt_msg(MsgKey::TransducerNotCallable, &[]));
+                        }
 
-                    // juxt特殊処理 - 実行前にチェック
-                    if let Some(Value::List(juxt_funcs)) = juxt_funcs {
-                        let mut results: SmallVec<[Value; 4]> =
-                            SmallVec::with_capacity(juxt_funcs.len());
-                        for jfunc in &juxt_funcs {
-                            let result = self.apply_func(jfunc, args.clone())?;
-                            results.push(result);
+                        // complement特殊処理 - 実行前にチェック
+                        if let Some(complement_func) = complement_func {
+                            let result = self.apply_func(&complement_func, args)?;
+                            return Ok(Value::Bool(!result.is_truthy()));
                         }
-                        return Ok(Value::Vector(results.into_iter().collect()));
-                    }
 
-                    // tap>特殊処理 - 副作用を実行してから値を返す
-                    if let Some(tap_func) = tap_func {
-                        if args.len() == 1 {
-                            let value = args[0].clone();
-                            // 副作用関数を実行（結果は無視）
-                            let _ = self.apply_func(&tap_func, smallvec![value.clone()]);
-                            // 元の値をそのまま返す
-                            return Ok(value);
+                        // juxt特殊処理 - 実行前にチェック
+                        if let Some(Value::List(juxt_funcs)) = juxt_funcs {
+                            let mut results: SmallVec<[Value; 4]> =
+                                SmallVec::with_capacity(juxt_funcs.len());
+                            for jfunc in &juxt_funcs {
+                                let result = self.apply_func(jfunc, args.clone())?;
+                                results.push(result);
+                            }
+                            return Ok(Value::Vector(results.into_iter().collect()));
                         }
-                    }
 
-                    // partial特殊処理 - 部分適用された引数と新しい引数を結合
-                    if let Some(partial_func) = partial_func {
-                        if let Some(Value::List(partial_args)) = partial_args {
-                            // 部分適用された引数と新しい引数を結合
-                            let mut combined_args: SmallVec<[Value; 4]> =
-                                SmallVec::with_capacity(partial_args.len() + args.len());
-                            combined_args.extend(partial_args.iter().cloned());
-                            combined_args.extend(args.iter().cloned());
-                            return self.apply_func(&partial_func, combined_args);
+                        // tap>特殊処理 - 副作用を実行してから値を返す
+                        if let Some(tap_func) = tap_func {
+                            if args.len() == 1 {
+                                let value = args[0].clone();
+                                // 副作用関数を実行（結果は無視）
+                                let _ = self.apply_func(&tap_func, smallvec![value.clone()]);
+                                // 元の値をそのまま返す
+                                return Ok(value);
+                            }
                         }
-                    }
 
-                    // comp特殊処理 - 関数合成（右から左に適用）
-                    if let Some(Value::List(comp_funcs)) = comp_funcs {
-                        if args.len() != 1 {
-                            return Err(fmt_msg(
-                                MsgKey::ArgCountMismatch,
-                                &["1", &args.len().to_string()],
-                            ));
+                        // partial特殊処理 - 部分適用された引数と新しい引数を結合
+                        if let Some(partial_func) = partial_func {
+                            if let Some(Value::List(partial_args)) = partial_args {
+                                // 部分適用された引数と新しい引数を結合
+                                let mut combined_args: SmallVec<[Value; 4]> =
+                                    SmallVec::with_capacity(partial_args.len() + args.len());
+                                combined_args.extend(partial_args.iter().cloned());
+                                combined_args.extend(args.iter().cloned());
+                                return self.apply_func(&partial_func, combined_args);
+                            }
                         }
-                        let mut result = args[0].clone();
-                        // 右から左に順番に適用
-                        for func in comp_funcs.iter().rev() {
-                            result = self.apply_func(func, smallvec![result])?;
+
+                        // comp特殊処理 - 関数合成（右から左に適用）
+                        if let Some(Value::List(comp_funcs)) = comp_funcs {
+                            if args.len() != 1 {
+                                return Err(fmt_msg(
+                                    MsgKey::ArgCountMismatch,
+                                    &["1", &args.len().to_string()],
+                                ));
+                            }
+                            let mut result = args[0].clone();
+                            // 右から左に順番に適用
+                            for func in comp_funcs.iter().rev() {
+                                result = self.apply_func(func, smallvec![result])?;
+                            }
+                            return Ok(result);
                         }
-                        return Ok(result);
                     }
-                }
 
-                // 通常の関数処理
-                let parent_env = Arc::clone(&f.env);
-                let mut new_env = Env::with_parent(parent_env);
+                    // 通常の関数処理
+                    let parent_env = Arc::clone(&f.env);
+                    let mut new_env = Env::with_parent(parent_env);
 
-                if f.is_variadic {
-                    // 可変長引数関数は最低1つのパラメータが必要（可変長引数自体）
-                    if f.params.is_empty() {
-                        return Err(
-                            "内部エラー: 可変長引数関数にパラメータがありません".to_string()
-                        );
-                    }
+                    if f.is_variadic {
+                        // 可変長引数関数は最低1つのパラメータが必要（可変長引数自体）
+                        if f.params.is_empty() {
+                            return Err(
+                                "内部エラー: 可変長引数関数にパラメータがありません".to_string()
+                            );
+                        }
 
-                    // 固定引数の数（可変長引数を除く）
-                    let fixed_param_count = f.params.len() - 1;
+                        // 固定引数の数（可変長引数を除く）
+                        let fixed_param_count = f.params.len() - 1;
 
-                    // 引数の数が固定引数の数より少ない場合はエラー
-                    if args.len() < fixed_param_count {
-                        return Err(fmt_msg(
-                            MsgKey::ArgCountMismatch,
-                            &[
-                                &format!("{}以上", fixed_param_count),
-                                &args.len().to_string(),
-                            ],
-                        ));
-                    }
+                        // 引数の数が固定引数の数より少ない場合はエラー
+                        if args.len() < fixed_param_count {
+                            return Err(fmt_msg(
+                                MsgKey::ArgCountMismatch,
+                                &[
+                                    &format!("{}以上", fixed_param_count),
+                                    &args.len().to_string(),
+                                ],
+                            ));
+                        }
 
-                    // 固定引数をバインド
-                    for (param, arg) in f.params.iter().take(fixed_param_count).zip(args.iter()) {
-                        self.bind_fn_param(param, arg, &mut new_env)?;
-                    }
+                        // 固定引数をバインド
+                        for (param, arg) in f.params.iter().take(fixed_param_count).zip(args.iter())
+                        {
+                            self.bind_fn_param(param, arg, &mut new_env)?;
+                        }
 
-                    // 残りの引数を可変長引数にバインド
-                    let variadic_param = &f.params[fixed_param_count];
-                    let remaining_args: SmallVec<[Value; 4]> =
-                        args.iter().skip(fixed_param_count).cloned().collect();
+                        // 残りの引数を可変長引数にバインド
+                        let variadic_param = &f.params[fixed_param_count];
+                        let remaining_args: SmallVec<[Value; 4]> =
+                            args.iter().skip(fixed_param_count).cloned().collect();
 
-                    if let crate::value::Pattern::Var(name) = variadic_param {
-                        new_env.set(
-                            name.to_string(),
-                            Value::List(remaining_args.into_iter().collect()),
-                        );
+                        if let crate::value::Pattern::Var(name) = variadic_param {
+                            new_env.set(
+                                name.to_string(),
+                                Value::List(remaining_args.into_iter().collect()),
+                            );
+                        } else {
+                            return Err("内部エラー: variadic引数がSimpleパターンではありません"
+                                .to_string());
+                        }
                     } else {
-                        return Err(
-                            "内部エラー: variadic引数がSimpleパターンではありません".to_string()
-                        );
-                    }
-                } else {
-                    if f.params.len() != args.len() {
-                        return Err(fmt_msg(
-                            MsgKey::ArgCountMismatch,
-                            &[&f.params.len().to_string(), &args.len().to_string()],
-                        ));
-                    }
-                    for (param, arg) in f.params.iter().zip(args.iter()) {
-                        self.bind_fn_param(param, arg, &mut new_env)?;
+                        if f.params.len() != args.len() {
+                            return Err(fmt_msg(
+                                MsgKey::ArgCountMismatch,
+                                &[&f.params.len().to_string(), &args.len().to_string()],
+                            ));
+                        }
+                        for (param, arg) in f.params.iter().zip(args.iter()) {
+                            self.bind_fn_param(param, arg, &mut new_env)?;
+                        }
                     }
-                }
 
-                // デバッガが有効な場合、関数呼び出しを記録
-                #[cfg(feature = "dap-server")]
-                {
-                    let debugger_enabled = crate::debugger::GLOBAL_DEBUGGER.read().is_some();
-                    if debugger_enabled {
-                        // 関数名を取得
-                        let func_name = self
-                            .get_function_name(func)
-                            .unwrap_or_else(|| "<anonymous>".to_string());
-
-                        // ファイル名を取得
-                        let file_name = self
-                            .source_name
-                            .read()
-                            .as_ref()
-                            .unwrap_or(&"<input>".to_string())
-                            .clone();
-
-                        // 関数本体のspan情報を取得
-                        let span = f.body.span();
-
-                        // デバッガに関数呼び出しを通知
-                        let should_wait = {
-                            let mut guard = crate::debugger::GLOBAL_DEBUGGER.write();
-                            if let Some(ref mut dbg) = *guard {
-                                dbg.enter_function(&func_name, &file_name, span.line, span.column);
-
-                                // ブレークポイントチェック（new_envをArcでラップして渡す）
-                                dbg.check_breakpoint(
-                                    &file_name,
-                                    span.line,
-                                    span.column,
-                                    Some(Arc::new(RwLock::new(new_env.clone()))),
-                                )
-                            } else {
-                                false
+                    // デバッガが有効な場合、関数呼び出しを記録
+                    #[cfg(feature = "dap-server")]
+                    {
+                        let debugger_enabled = crate::debugger::GLOBAL_DEBUGGER.read().is_some();
+                        if debugger_enabled {
+                            // 関数名を取得
+                            let func_name = self
+                                .get_function_name(&func)
+                                .unwrap_or_else(|| "<anonymous>".to_string());
+
+                            // ファイル名を取得
+                            let file_name = self
+                                .source_name
+                                .read()
+                                .as_ref()
+                                .unwrap_or(&"<input>".to_string())
+                                .clone();
+
+                            // 関数本体のspan情報を取得
+                            let span = f.body.span();
+
+                            // デバッガに関数呼び出しを通知
+                            let should_wait = {
+                                let mut guard = crate::debugger::GLOBAL_DEBUGGER.write();
+                                if let Some(ref mut dbg) = *guard {
+                                    dbg.enter_function(
+                                        &func_name,
+                                        &file_name,
+                                        span.line,
+                                        span.column,
+                                    );
+
+                                    // ブレークポイントチェック（new_envをArcでラップして渡す）
+                                    dbg.check_breakpoint(
+                                        &file_name,
+                                        span.line,
+                                        span.column,
+                                        Some(Arc::new(RwLock::new(new_env.clone()))),
+                                    )
+                                } else {
+                                    false
+                                }
+                            };
+
+                            // ロックを解放してから待機
+                            if should_wait {
+                                crate::debugger::wait_if_paused_global();
                             }
-                        };
 
-                        // ロックを解放してから待機
-                        if should_wait {
-                            crate::debugger::wait_if_paused_global();
-                        }
+                            // 関数本体を実行（末尾呼び出しも終了通知の前に完了させる）
+                            let result = if builtins::profile::is_enabled() {
+                                let start = std::time::Instant::now();
+                                let r = self.eval_body_instrumented(
+                                    &f.body,
+                                    Arc::new(RwLock::new(new_env)),
+                                );
+                                let duration = start.elapsed();
+                                builtins::profile::record_call(&func_name, duration);
+                                r
+                            } else {
+                                self.eval_body_instrumented(&f.body, Arc::new(RwLock::new(new_env)))
+                            };
 
-                        // 関数本体を実行
-                        let result = if builtins::profile::is_enabled() {
-                            let start = std::time::Instant::now();
-                            let r = self.eval_with_env(&f.body, Arc::new(RwLock::new(new_env)));
-                            let duration = start.elapsed();
-                            builtins::profile::record_call(&func_name, duration);
-                            r
-                        } else {
-                            self.eval_with_env(&f.body, Arc::new(RwLock::new(new_env)))
-                        };
+                            // デバッガに関数終了を通知
+                            if let Some(ref mut dbg) = *crate::debugger::GLOBAL_DEBUGGER.write() {
+                                dbg.exit_function();
+                            }
 
-                        // デバッガに関数終了を通知
-                        if let Some(ref mut dbg) = *crate::debugger::GLOBAL_DEBUGGER.write() {
-                            dbg.exit_function();
+                            result
+                        } else {
+                            // デバッガ無効時（プロファイリングのみ）
+                            if builtins::profile::is_enabled() {
+                                let start = std::time::Instant::now();
+                                let result = self.eval_body_instrumented(
+                                    &f.body,
+                                    Arc::new(RwLock::new(new_env)),
+                                );
+                                let duration = start.elapsed();
+
+                                // 関数名を取得（環境から逆引き）
+                                let func_name = self
+                                    .get_function_name(&func)
+                                    .unwrap_or_else(|| "<anonymous>".to_string());
+                                builtins::profile::record_call(&func_name, duration);
+
+                                result
+                            } else {
+                                self.eval_tail(&f.body, Arc::new(RwLock::new(new_env)))
+                            }
                         }
-
-                        result
-                    } else {
+                    }
+                    #[cfg(not(feature = "dap-server"))]
+                    {
                         // デバッガ無効時（プロファイリングのみ）
                         if builtins::profile::is_enabled() {
                             let start = std::time::Instant::now();
-                            let result =
-                                self.eval_with_env(&f.body, Arc::new(RwLock::new(new_env)));
+                            let result = self
+                                .eval_body_instrumented(&f.body, Arc::new(RwLock::new(new_env)));
                             let duration = start.elapsed();
 
                             // 関数名を取得（環境から逆引き）
                             let func_name = self
-                                .get_function_name(func)
+                                .get_function_name(&func)
                                 .unwrap_or_else(|| "<anonymous>".to_string());
                             builtins::profile::record_call(&func_name, duration);
 
                             result
                         } else {
-                            self.eval_with_env(&f.body, Arc::new(RwLock::new(new_env)))
+                            self.eval_tail(&f.body, Arc::new(RwLock::new(new_env)))
                         }
                     }
                 }
-                #[cfg(not(feature = "dap-server"))]
-                {
-                    // デバッガ無効時（プロファイリングのみ）
-                    if builtins::profile::is_enabled() {
-                        let start = std::time::Instant::now();
-                        let result = self.eval_with_env(&f.body, Arc::new(RwLock::new(new_env)));
-                        let duration = start.elapsed();
-
-                        // 関数名を取得（環境から逆引き）
-                        let func_name = self
-                            .get_function_name(func)
-                            .unwrap_or_else(|| "<anonymous>".to_string());
-                        builtins::profile::record_call(&func_name, duration);
-
-                        result
-                    } else {
-                        self.eval_with_env(&f.body, Arc::new(RwLock::new(new_env)))
-                    }
+                _ => return Err(format!("{} は呼び出し可能ではありません", func.type_name())),
+            };
+
+            match tail? {
+                TailCall::Value(value) => return Ok(value),
+                TailCall::Call(next_func, next_args) => {
+                    func = next_func;
+                    args = *next_args;
                 }
             }
-            _ => Err(format!("{} は呼び出し可能ではありません", func.type_name())),
         }
     }
 }
@@ -71,9 +71,8 @@ pub fn native_print(args: &[Value]) -> Result<Value, String> {
 /// number? - 数値かどうか判定
 pub fn native_is_number(args: &[Value]) -> Result<Value, String> {
     check_args!(args, 1, "number?");
-    Ok(Value::Bool(matches!(
-        args[0],
-        Value::Integer(_) | Value::Float(_)
+    Ok(Value::Bool(crate::builtins::numeric_helpers::is_number(
+        &args[0],
     )))
 }
 
@@ -113,6 +112,11 @@ impl Evaluator {
                         Value::String(s) => s.clone(),
                         Value::Integer(n) => n.to_string(),
                         Value::Float(f) => f.to_string(),
+                        Value::BigInt(n) => n.to_string(),
+                        Value::Ratio(r) => r.to_string(),
+                        // 埋め込み時はMサフィックスを付けない（金額表示用）
+                        Value::Decimal(d) => d.to_string(),
+                        Value::Char(c) => c.to_string(),
                         Value::Bool(b) => b.to_string(),
                         Value::Nil => "nil".to_string(),
                         Value::Keyword(k) => format!(":{}", k),
@@ -143,13 +147,19 @@ impl Evaluator {
                                 m.iter().map(|(k, v)| format!("{} {}", k, v)).collect();
                             format!("{{{}}}", strs.join(" "))
                         }
+                        Value::Set(items) => {
+                            let strs: Vec<_> = items.iter().map(|v| format!("{}", v)).collect();
+                            format!("#{{{}}}", strs.join(" "))
+                        }
                         Value::Function(_) => "<function>".to_string(),
                         Value::NativeFunc(nf) => format!("<native-fn:{}>", nf.name),
                         Value::Macro(m) => format!("<macro:{}>", m.name),
-                        Value::Atom(a) => format!("<atom:{}>", a.read()),
+                        Value::Atom(a) => format!("<atom:{}>", a.value.read()),
+                        Value::Ref(r) => format!("<ref:{}>", r.state.read().value),
                         Value::Channel(_) => "<channel>".to_string(),
                         Value::Scope(_) => "<scope>".to_string(),
                         Value::Stream(_) => "<stream>".to_string(),
+                        Value::Handle(h) => format!("<{}>", h.kind),
                         Value::Uvar(id) => format!("<uvar:{}>", id),
                     };
                     result.push_str(&s);
@@ -0,0 +1,85 @@
+//! 動的変数（defdynamic / binding）
+//!
+//! Envはレキシカルスコープのみのため、リクエストID・現在のユーザー・DBトランザクション等の
+//! コンテキストを深い呼び出し階層へ渡すための動的スコープを別に持つ。
+//!
+//! - ルート値はグローバル環境に通常の変数として定義する
+//! - `binding` による再束縛はスレッドローカルで、他スレッドからは見えない
+//! - go/run・pmap・スコープタスク等は起動時の束縛を子スレッドへ引き継ぐ
+
+use crate::value::Value;
+use dashmap::DashSet;
+use std::cell::RefCell;
+use std::sync::{Arc, LazyLock};
+
+/// log/* が自動でコンテキストに含める動的変数
+pub const LOG_CONTEXT: &str = "*log-context*";
+
+/// defdynamicで宣言された変数名
+static DYNAMIC_VARS: LazyLock<DashSet<Arc<str>>> = LazyLock::new(|| {
+    let vars = DashSet::new();
+    vars.insert(Arc::from(LOG_CONTEXT));
+    vars
+});
+
+/// 動的束縛の集合（子スレッドへ安価に複製できるよう永続マップを使用）
+pub type Bindings = im::HashMap<Arc<str>, Value>;
+
+thread_local! {
+    static BINDINGS: RefCell<Bindings> = RefCell::new(Bindings::new());
+}
+
+/// 動的変数として宣言
+pub fn declare(name: Arc<str>) {
+    DYNAMIC_VARS.insert(name);
+}
+
+/// 動的変数として宣言されているか
+pub fn is_dynamic(name: &str) -> bool {
+    DYNAMIC_VARS.contains(name)
+}
+
+/// 現在のスレッドでbindingされている値（束縛がなければNone）
+#[inline]
+pub fn lookup(name: &str) -> Option<Value> {
+    BINDINGS.with(|b| {
+        let b = b.borrow();
+        if b.is_empty() {
+            None
+        } else {
+            b.get(name).cloned()
+        }
+    })
+}
+
+/// 現在の束縛を取得（子スレッドへの引き継ぎ用）
+pub fn capture() -> Bindings {
+    BINDINGS.with(|b| b.borrow().clone())
+}
+
+/// 束縛を差し替え、ガードのDrop時に元の束縛へ戻す
+pub fn install(bindings: Bindings) -> BindingGuard {
+    let previous = BINDINGS.with(|b| std::mem::replace(&mut *b.borrow_mut(), bindings));
+    BindingGuard {
+        previous: Some(previous),
+    }
+}
+
+/// 捕捉した束縛のもとでfを実行（スレッドプールのタスク等、スレッドを使い回す場合用）
+pub fn with_bindings<R>(bindings: &Bindings, f: impl FnOnce() -> R) -> R {
+    let _guard = install(bindings.clone());
+    f()
+}
+
+/// RAIIガード: Drop時にbinding前の束縛へ戻す（エラーで抜けた場合も含む）
+pub struct BindingGuard {
+    previous: Option<Bindings>,
+}
+
+impl Drop for BindingGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            BINDINGS.with(|b| *b.borrow_mut() = previous);
+        }
+    }
+}
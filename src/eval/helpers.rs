@@ -251,7 +251,11 @@ impl Evaluator {
             Pattern::Integer(n) => Value::Integer(*n),
             Pattern::Float(f) => Value::Float(*f),
             Pattern::String(s) => Value::String(s.clone()),
+            Pattern::Char(c) => Value::Char(*c),
             Pattern::Keyword(k) => Value::Keyword(crate::intern::intern_keyword(k)),
+            Pattern::Set(patterns) => {
+                Value::Set(patterns.iter().map(|p| self.fn_param_to_value(p)).collect())
+            }
             Pattern::Transform(var, expr) => {
                 // (:transform var expr) の形式で表現
                 Value::List(
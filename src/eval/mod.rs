@@ -14,8 +14,11 @@ use std::sync::Arc;
 
 pub mod call;
 pub mod core;
+pub mod dynamic;
 pub mod helpers;
 mod modules;
+
+use modules::CORE_NS;
 mod patterns;
 mod special_forms;
 
@@ -46,6 +49,7 @@ pub mod hof_keys {
     pub const COMP_FUNCS: &str = "__comp_funcs__";
     pub const CONSTANTLY_VALUE: &str = "__constantly_value__";
     pub const PARTIAL_PLACEHOLDER: &str = "__partial_placeholder__";
+    pub const TRANSDUCER_STEPS: &str = "__transducer_steps__";
 }
 
 // ========================================
@@ -58,11 +62,12 @@ pub struct Evaluator {
     modules: Arc<RwLock<HashMap<Arc<str>, Arc<Module>>>>, // ロード済みモジュール（Arc<str>で統一、後方互換性のため残す）
     module_states: Arc<dashmap::DashMap<Arc<str>, crate::value::ModuleState>>, // モジュール状態管理（スレッド間の循環検出、アトミック操作）
     current_module: Arc<RwLock<Option<Arc<str>>>>, // 現在評価中のモジュール名
-    loading_modules: Arc<RwLock<Vec<Arc<str>>>>,   // exportキー用スタック（スレッドローカル）
+    current_ns: Arc<RwLock<Option<Arc<str>>>>, // nsで宣言された現在の名前空間（定義はデフォルト非公開）
+    loading_modules: Arc<RwLock<Vec<Arc<str>>>>, // exportキー用スタック（スレッドローカル）
     #[allow(dead_code)]
     call_stack: Arc<RwLock<Vec<String>>>, // 関数呼び出しスタック（スタックトレース用）
-    source_name: Arc<RwLock<Option<String>>>,      // ソースファイル名または入力名
-    source_code: Arc<RwLock<Option<String>>>,      // ソースコード全体
+    source_name: Arc<RwLock<Option<String>>>,  // ソースファイル名または入力名
+    source_code: Arc<RwLock<Option<String>>>,  // ソースコード全体
 }
 
 impl Clone for Evaluator {
@@ -81,6 +86,7 @@ impl Clone for Evaluator {
             defer_stack: Arc::new(RwLock::new(Default::default())),
             loading_modules: Arc::new(RwLock::new(Default::default())), // スレッドローカル（意図的）
             current_module: Arc::new(RwLock::new(Default::default())),
+            current_ns: Arc::new(RwLock::new(Default::default())),
             call_stack: Arc::new(RwLock::new(Default::default())),
 
             // ソース情報を保持（エラーメッセージのファイル名・行番号を保持）
@@ -157,12 +163,25 @@ impl Evaluator {
             }),
         );
 
+        // 組み込み関数のスナップショットをcore名前空間として登録
+        // （ユーザー定義でmap等を上書きしても core/map で参照できる）
+        let core_name: Arc<str> = Arc::from(CORE_NS);
+        let core_module = Module {
+            name: core_name.clone(),
+            file_path: None,
+            env: Arc::new(RwLock::new(env_rc.read().clone())),
+            exports: None,
+        };
+        let mut modules = HashMap::new();
+        modules.insert(core_name, Arc::new(core_module));
+
         let evaluator = Evaluator {
             global_env: env_rc.clone(),
             defer_stack: Arc::new(RwLock::new(SmallVec::new())),
-            modules: Arc::new(RwLock::new(HashMap::new())),
+            modules: Arc::new(RwLock::new(modules)),
             module_states: Arc::new(dashmap::DashMap::new()),
             current_module: Arc::new(RwLock::new(None)),
+            current_ns: Arc::new(RwLock::new(None)),
             loading_modules: Arc::new(RwLock::new(Vec::new())),
             call_stack: Arc::new(RwLock::new(Vec::new())),
             source_name: Arc::new(RwLock::new(None)),
@@ -235,81 +254,88 @@ impl Evaluator {
         Some(self.global_env.clone())
     }
 
-    pub fn eval_with_env(&self, expr: &Expr, env: Arc<RwLock<Env>>) -> Result<Value, String> {
-        // ブレークポイントチェック (dap-server feature が有効な場合のみ)
-        // リスト（関数呼び出し）の場合のみチェック
-        #[cfg(feature = "dap-server")]
-        {
-            let should_check = matches!(expr, Expr::List { .. } | Expr::Call { .. });
-
-            if should_check {
-                let span = expr.span();
-                let should_wait = {
-                    let file_name = self
-                        .source_name
-                        .read()
-                        .as_ref()
-                        .unwrap_or(&"<input>".to_string())
-                        .clone();
-
-                    let mut guard = crate::debugger::GLOBAL_DEBUGGER.write();
-                    if let Some(ref mut dbg) = *guard {
-                        let result = dbg.check_breakpoint(
-                            &file_name,
-                            span.line,
-                            span.column,
-                            Some(env.clone()),
-                        );
-                        if result {
-                            let log_msg =
-                                format!("[EVAL] Breakpoint hit: {}:{}\n", file_name, span.line);
-                            std::fs::OpenOptions::new()
-                                .create(true)
-                                .append(true)
-                                .open("/tmp/qi-dap.log")
-                                .and_then(|mut f| {
-                                    std::io::Write::write_all(&mut f, log_msg.as_bytes())
-                                })
-                                .ok();
-                        }
-                        result
-                    } else {
-                        let log_msg = "[EVAL] WARNING: GLOBAL_DEBUGGER is None\n";
+    /// ブレークポイントチェック
+    ///
+    /// リスト（関数呼び出し）の場合のみチェックし、ヒットしたら再開まで待機する
+    #[cfg(feature = "dap-server")]
+    pub(super) fn check_breakpoint(&self, expr: &Expr, env: &Arc<RwLock<Env>>) {
+        let should_check = matches!(expr, Expr::List { .. } | Expr::Call { .. });
+
+        if should_check {
+            let span = expr.span();
+            let should_wait = {
+                let file_name = self
+                    .source_name
+                    .read()
+                    .as_ref()
+                    .unwrap_or(&"<input>".to_string())
+                    .clone();
+
+                let mut guard = crate::debugger::GLOBAL_DEBUGGER.write();
+                if let Some(ref mut dbg) = *guard {
+                    let result = dbg.check_breakpoint(
+                        &file_name,
+                        span.line,
+                        span.column,
+                        Some(Arc::clone(env)),
+                    );
+                    if result {
+                        let log_msg =
+                            format!("[EVAL] Breakpoint hit: {}:{}\n", file_name, span.line);
                         std::fs::OpenOptions::new()
                             .create(true)
                             .append(true)
                             .open("/tmp/qi-dap.log")
                             .and_then(|mut f| std::io::Write::write_all(&mut f, log_msg.as_bytes()))
                             .ok();
-                        false
                     }
-                };
-
-                if should_wait {
-                    let log_msg = "[EVAL] Waiting for debugger resume...\n";
-                    std::fs::OpenOptions::new()
-                        .create(true)
-                        .append(true)
-                        .open("/tmp/qi-dap.log")
-                        .and_then(|mut f| std::io::Write::write_all(&mut f, log_msg.as_bytes()))
-                        .ok();
-                    crate::debugger::wait_if_paused_global();
-                    let log_msg = "[EVAL] Debugger resumed\n";
+                    result
+                } else {
+                    let log_msg = "[EVAL] WARNING: GLOBAL_DEBUGGER is None\n";
                     std::fs::OpenOptions::new()
                         .create(true)
                         .append(true)
                         .open("/tmp/qi-dap.log")
                         .and_then(|mut f| std::io::Write::write_all(&mut f, log_msg.as_bytes()))
                         .ok();
+                    false
                 }
+            };
+
+            if should_wait {
+                let log_msg = "[EVAL] Waiting for debugger resume...\n";
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open("/tmp/qi-dap.log")
+                    .and_then(|mut f| std::io::Write::write_all(&mut f, log_msg.as_bytes()))
+                    .ok();
+                crate::debugger::wait_if_paused_global();
+                let log_msg = "[EVAL] Debugger resumed\n";
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open("/tmp/qi-dap.log")
+                    .and_then(|mut f| std::io::Write::write_all(&mut f, log_msg.as_bytes()))
+                    .ok();
             }
         }
+    }
+
+    pub fn eval_with_env(&self, expr: &Expr, env: Arc<RwLock<Env>>) -> Result<Value, String> {
+        // ブレークポイントチェック (dap-server feature が有効な場合のみ)
+        #[cfg(feature = "dap-server")]
+        self.check_breakpoint(expr, &env);
 
         match expr {
             Expr::Nil { .. } => Ok(Value::Nil),
             Expr::Bool { value, .. } => Ok(Value::Bool(*value)),
             Expr::Integer { value, .. } => Ok(Value::Integer(*value)),
             Expr::Float { value, .. } => Ok(Value::Float(*value)),
+            Expr::BigInt { value, .. } => Ok(Value::BigInt(value.clone())),
+            Expr::Ratio { value, .. } => Ok(Value::Ratio(value.clone())),
+            Expr::Decimal { value, .. } => Ok(Value::Decimal(*value)),
+            Expr::Char { value, .. } => Ok(Value::Char(*value)),
             Expr::String { value, .. } => Ok(Value::String(value.clone())),
             Expr::FString { parts, .. } => self.eval_fstring(parts, Arc::clone(&env)),
             Expr::Keyword { name, .. } => {
@@ -318,20 +344,33 @@ impl Evaluator {
             }
 
             Expr::Symbol { name, span } => {
-                let env_read = env.read();
-                env_read.get(name).ok_or_else(|| {
-                    // 類似した変数名を検索（最大編集距離3、最大3件）
-                    let suggestions = find_similar_names(&env_read, name, 3, 3);
-                    let msg = if suggestions.is_empty() {
-                        qerr(MsgKey::UndefinedVar, &[name])
-                    } else {
-                        fmt_msg(
-                            MsgKey::UndefinedVarWithSuggestions,
-                            &[name, &suggestions.join(", ")],
-                        )
-                    };
-                    self.format_error_with_span(msg, span)
-                })
+                // bindingで再束縛された動的変数（ローカル変数によるシャドウを優先）
+                if let Some(v) = dynamic::lookup(name) {
+                    if !env.read().is_bound_locally(name) {
+                        return Ok(v);
+                    }
+                }
+                if let Some(v) = env.read().get(name) {
+                    return Ok(v);
+                }
+                // 名前空間修飾されたシンボル（app.core/foo, core/map）
+                if let Some(v) = self
+                    .resolve_qualified(name)
+                    .map_err(|e| self.format_error_with_span(e, span))?
+                {
+                    return Ok(v);
+                }
+                // 類似した変数名を検索（最大編集距離3、最大3件）
+                let suggestions = find_similar_names(&env.read(), name, 3, 3);
+                let msg = if suggestions.is_empty() {
+                    qerr(MsgKey::UndefinedVar, &[name])
+                } else {
+                    fmt_msg(
+                        MsgKey::UndefinedVarWithSuggestions,
+                        &[name, &suggestions.join(", ")],
+                    )
+                };
+                Err(self.format_error_with_span(msg, span))
             }
 
             Expr::List { items, .. } => {
@@ -366,6 +405,14 @@ impl Evaluator {
                 Ok(Value::Map(map))
             }
 
+            Expr::Set { items, .. } => {
+                let mut values = Vec::with_capacity(items.len());
+                for item in items {
+                    values.push(self.eval_with_env(item, Arc::clone(&env))?);
+                }
+                Value::set_from(values)
+            }
+
             Expr::Def {
                 name,
                 value,
@@ -390,8 +437,8 @@ impl Evaluator {
                 }
 
                 let val = self.eval_with_env(value, Arc::clone(&env))?;
-                // 現在の環境に定義（プライベートフラグに応じて）
-                if *is_private {
+                // 現在の環境に定義（プライベートフラグに応じて、ns内ではexportされていなければ非公開）
+                if *is_private || self.is_private_in_ns(name) {
                     env.write().set_private(name.clone(), val.clone());
                 } else {
                     env.write().set(name.clone(), val.clone());
@@ -412,44 +459,14 @@ impl Evaluator {
                 has_special_processing: false,
             }))),
 
-            Expr::Let { bindings, body, .. } => {
-                // let*セマンティクス: 順次束縛（各束縛が前の束縛を参照できる）
-                let new_env = Arc::new(RwLock::new(Env::with_parent(Arc::clone(&env))));
-                for (pattern, expr) in bindings {
-                    // 現在の環境で評価（既に追加された束縛が見える）
-                    let value = self.eval_with_env(expr, new_env.clone())?;
-                    self.bind_fn_param(pattern, &value, &mut new_env.write())?;
-                }
-                self.eval_with_env(body, new_env)
-            }
-
-            Expr::If {
-                test,
-                then,
-                otherwise,
-                ..
-            } => {
-                let test_val = self.eval_with_env(test, Arc::clone(&env))?;
-                if test_val.is_truthy() {
-                    self.eval_with_env(then, env)
-                } else if let Some(otherwise) = otherwise {
-                    self.eval_with_env(otherwise, env)
-                } else {
-                    Ok(Value::Nil)
-                }
-            }
-
-            Expr::Do { exprs, .. } => self.eval_do(exprs, env),
-
-            Expr::When {
-                condition, body, ..
-            } => {
-                let cond_val = self.eval_with_env(condition, Arc::clone(&env))?;
-                if cond_val.is_truthy() {
-                    self.eval_do(body, env)
-                } else {
-                    Ok(Value::Nil)
-                }
+            // 末尾位置を持つ形式（末尾の関数呼び出しはここで確定させる）
+            Expr::Let { .. }
+            | Expr::If { .. }
+            | Expr::Do { .. }
+            | Expr::When { .. }
+            | Expr::Match { .. } => {
+                let tail = self.eval_tail(expr, env)?;
+                self.finish_tail(tail)
             }
 
             Expr::While {
@@ -520,11 +537,6 @@ impl Evaluator {
                 }
             }
 
-            Expr::Match { expr, arms, .. } => {
-                let value = self.eval_with_env(expr, Arc::clone(&env))?;
-                self.eval_match(&value, arms, env)
-            }
-
             Expr::Try { expr, .. } => self.eval_try(expr, env),
 
             Expr::Defer { expr, .. } => self.eval_defer(expr.as_ref()),
@@ -557,11 +569,13 @@ impl Evaluator {
 
             Expr::Export { symbols, .. } => {
                 // 現在ロード中のファイル名を取得（フォールバック用）
+                // （nsを宣言していればメインファイルでも使用可能）
                 let file_path = self
                     .loading_modules
                     .read()
                     .last()
                     .cloned()
+                    .or_else(|| self.current_ns.read().clone())
                     .ok_or_else(|| msg(MsgKey::ExportOnlyInModule).to_string())?;
 
                 // モジュール名をキーとして優先、なければファイルパスを使用
@@ -582,6 +596,13 @@ impl Evaluator {
                         ));
                     }
                 }
+                // ns内でデフォルト非公開として定義されたものを公開に切り替える
+                {
+                    let mut env_write = env.write();
+                    for symbol in symbols {
+                        env_write.mark_public(symbol);
+                    }
+                }
 
                 // 既存のモジュールを取得または新規作成
                 let mut modules = self.modules.write();
@@ -622,20 +643,50 @@ impl Evaluator {
 
     /// 特殊形式（高階関数、演算子など）のディスパッチ
     ///
-    /// map関数の実装: (map f coll)
+    /// map関数の実装: (map f coll)、(map f) はトランスデューサ
     fn eval_map(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
         let func = self.eval_with_env(&args[0], Arc::clone(&env))?;
+        if args.len() == 1 {
+            return builtins::map(&[func], self);
+        }
         let coll = self.eval_with_env(&args[1], Arc::clone(&env))?;
         builtins::map(&[func, coll], self)
     }
 
-    /// filter関数の実装: (filter pred coll)
+    /// filter関数の実装: (filter pred coll)、(filter pred) はトランスデューサ
     fn eval_filter(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
         let pred = self.eval_with_env(&args[0], Arc::clone(&env))?;
+        if args.len() == 1 {
+            return builtins::filter(&[pred], self);
+        }
         let coll = self.eval_with_env(&args[1], Arc::clone(&env))?;
         builtins::filter(&[pred, coll], self)
     }
 
+    /// mapcat関数の実装: (mapcat f coll)
+    fn eval_mapcat(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
+        if args.len() != 2 {
+            return Err(fmt_msg(MsgKey::Need2Args, &["mapcat"]));
+        }
+        let func = self.eval_with_env(&args[0], Arc::clone(&env))?;
+        let coll = self.eval_with_env(&args[1], Arc::clone(&env))?;
+        builtins::mapcat(&[func, coll], self)
+    }
+
+    /// 全引数を評価してEvaluatorが必要な関数（transduce/into/alter等）を呼ぶ
+    fn eval_all_args(
+        &self,
+        args: &[Expr],
+        env: Arc<RwLock<Env>>,
+        f: fn(&[Value], &Evaluator) -> Result<Value, String>,
+    ) -> Result<Value, String> {
+        let vals: Vec<Value> = args
+            .iter()
+            .map(|e| self.eval_with_env(e, Arc::clone(&env)))
+            .collect::<Result<Vec<_>, _>>()?;
+        f(&vals, self)
+    }
+
     /// reduce関数の実装: (reduce f init coll) または (reduce f coll)
     fn eval_reduce(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
         let func = self.eval_with_env(&args[0], Arc::clone(&env))?;
@@ -650,6 +701,19 @@ impl Evaluator {
         }
     }
 
+    /// dosyncの実装: (dosync body...)
+    ///
+    /// 競合時はボディ全体を再評価する
+    fn eval_dosync(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
+        builtins::stm::dosync(self, || {
+            let mut result = Value::Nil;
+            for expr in args {
+                result = self.eval_with_env(expr, Arc::clone(&env))?;
+            }
+            Ok(result)
+        })
+    }
+
     /// swap!関数の実装: (swap! atom f args...)
     fn eval_swap(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
         let atom = self.eval_with_env(&args[0], Arc::clone(&env))?;
@@ -1056,7 +1120,9 @@ impl Evaluator {
     }
 
     fn eval_partition_by(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
-        check_args!(args, 2, "partition-by");
+        if args.len() != 1 && args.len() != 2 {
+            return Err(fmt_msg(MsgKey::Need1Or2Args, &["partition-by"]));
+        }
         let vals: Vec<Value> = args
             .iter()
             .map(|e| self.eval_with_env(e, Arc::clone(&env)))
@@ -1865,4 +1931,487 @@ mod tests {
         let result = eval_str(code).unwrap();
         assert_eq!(result, Value::Integer(1111));
     }
+
+    #[test]
+    fn test_macro_auto_gensym_hygiene() {
+        // foo# はマクロ展開ごとに一意なシンボルになり、ユーザー変数を捕捉しない
+        let code = r#"
+(mac my-or [a b] `(let [v# ,a] (if v# v# ,b)))
+(def v 5)
+(my-or nil v)
+"#;
+        assert_eq!(eval_str(code).unwrap(), Value::Integer(5));
+    }
+
+    #[test]
+    fn test_macro_destructuring_params() {
+        // macのパラメータはfnと同じ分解パターンを使える
+        let code = r#"
+(mac add-pair [[a b] & more] `(+ ,a ,b ,@more))
+(add-pair [1 2] 3 4)
+"#;
+        assert_eq!(eval_str(code).unwrap(), Value::Integer(10));
+        assert!(eval_str("(mac bad [[a b]] `(+ ,a ,b)) (bad 1)").is_err());
+    }
+
+    #[test]
+    fn test_macroexpand_all() {
+        // ネストしたマクロ呼び出しも再帰的に展開される
+        let code = r#"
+(mac twice [x] `(+ ,x ,x))
+(macroexpand-all '(twice (twice 1)))
+"#;
+        let result = eval_str(code).unwrap();
+        let inner = Value::List(
+            vec![
+                Value::Symbol(crate::intern::intern_symbol("+")),
+                Value::Integer(1),
+                Value::Integer(1),
+            ]
+            .into(),
+        );
+        let expected = Value::List(
+            vec![
+                Value::Symbol(crate::intern::intern_symbol("+")),
+                inner.clone(),
+                inner,
+            ]
+            .into(),
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_lazy_stream_pipeline() {
+        // 無限ストリームでもコアのシーケンス関数は遅延評価され、realizeで確定する
+        assert_eq!(
+            eval_str(
+                "((stream/iterate inc 0) |> (map (fn [x] (* x x))) |> (filter even?) |> (mapcat (fn [x] [x x])) |> (take 4) |> stream/realize)"
+            )
+            .unwrap(),
+            Value::List(
+                vec![0, 0, 4, 4]
+                    .into_iter()
+                    .map(Value::Integer)
+                    .collect()
+            )
+        );
+        assert_eq!(
+            eval_str("((stream/iterate inc 1) |> (take-while (fn [x] (<= x 4))) |> (reduce +))")
+                .unwrap(),
+            Value::Integer(10)
+        );
+    }
+
+    #[test]
+    fn test_lazy_stream_partition_and_errors() {
+        // partitionはストリームを真・偽の2つの遅延ストリームに分ける
+        assert_eq!(
+            eval_str(
+                "(let [[evens odds] (list/partition even? (stream/range 0 7))] [(stream/realize odds) (stream/realize evens)])"
+            )
+            .unwrap(),
+            eval_str("['(1 3 5) '(0 2 4 6)]").unwrap()
+        );
+        // 関数のエラーは黙って終端せず{:error}要素として流れる
+        assert_eq!(
+            eval_str(
+                "((stream/stream [1 0 2]) |> (map (fn [x] (/ 6 x))) |> stream/realize |> (map error?))"
+            )
+            .unwrap(),
+            eval_str("'(false true false)").unwrap()
+        );
+        assert_eq!(
+            eval_str(
+                r#"((stream/stream [1 "a" 2]) |> (filter even?) |> stream/realize |> (map error?))"#
+            )
+            .unwrap(),
+            eval_str("'(true false)").unwrap()
+        );
+        assert_eq!(
+            eval_str(
+                "((stream/stream [[1] 2]) |> (mapcat identity) |> stream/realize |> (map error?))"
+            )
+            .unwrap(),
+            eval_str("'(false true)").unwrap()
+        );
+        assert_eq!(
+            eval_str(
+                r#"((stream/stream [1 1 "a" 2]) |> (list/partition-by even?) |> stream/realize |> count)"#
+            )
+            .unwrap(),
+            Value::Integer(2)
+        );
+    }
+
+    #[test]
+    fn test_transducers() {
+        // comp で合成したトランスデューサは左から順に1パスで適用される
+        assert_eq!(
+            eval_str(
+                "(transduce (comp (filter odd?) (map (fn [x] (* x 10))) (take 2)) + 0 [1 2 3 4 5])"
+            )
+            .unwrap(),
+            Value::Integer(40)
+        );
+        assert_eq!(
+            eval_str("(into [] (comp (list/dedupe) (list/partition-by odd?)) (stream/stream [1 1 3 2 2 5]))")
+                .unwrap(),
+            Value::Vector(
+                vec![
+                    Value::List(vec![Value::Integer(1), Value::Integer(3)].into()),
+                    Value::List(vec![Value::Integer(2)].into()),
+                    Value::List(vec![Value::Integer(5)].into()),
+                ]
+                .into()
+            )
+        );
+        assert!(eval_str("((map inc) [1 2])").is_err());
+    }
+
+    #[test]
+    fn test_dynamic_binding() {
+        // ルート値とbindingによる再束縛（ボディを抜けると元に戻る）
+        assert_eq!(
+            eval_str(
+                "(defdynamic *req-id* \"none\")
+                 (defn current [] *req-id*)
+                 [(binding [*req-id* \"r1\"] (current)) (current)]"
+            )
+            .unwrap(),
+            Value::Vector(vec![Value::String("r1".into()), Value::String("none".into())].into())
+        );
+        // ローカル変数によるシャドウが優先
+        assert_eq!(
+            eval_str(
+                "(defdynamic *x* 1)
+                 (binding [*x* 2] (let [*x* 3] *x*))"
+            )
+            .unwrap(),
+            Value::Integer(3)
+        );
+        // go/run・pmapへの引き継ぎ
+        assert_eq!(
+            eval_str(
+                "(defdynamic *user* nil)
+                 (binding [*user* \"alice\"]
+                   (go/await (go/run (fn [] *user*))))"
+            )
+            .unwrap(),
+            Value::String("alice".into())
+        );
+        assert_eq!(
+            eval_str(
+                "(defdynamic *n* 0)
+                 (binding [*n* 10]
+                   (reduce + (pmap (fn [x] (+ x *n*)) (range 100))))"
+            )
+            .unwrap(),
+            Value::Integer(5950)
+        );
+        // 動的変数でないシンボルはエラー
+        assert!(eval_str("(def y 1) (binding [y 2] y)").is_err());
+    }
+
+    #[test]
+    fn test_namespaces() {
+        // ns内の定義はexportしない限り非公開
+        assert_eq!(
+            eval_str(
+                "(ns app.core (:export greet))
+                 (defn helper [x] (str \"hi \" x))
+                 (defn greet [x] (helper x))
+                 (defn shout [x] (str x \"!\"))
+                 (export shout)
+                 [(app.core/greet \"a\")
+                  (sort (map str (keys (ns-publics 'app.core))))
+                  (ns-resolve 'app.core 'helper)
+                  ((ns-resolve 'app.core 'shout) \"b\")]"
+            )
+            .unwrap(),
+            Value::Vector(
+                vec![
+                    Value::String("hi a".into()),
+                    Value::List(
+                        vec![Value::String("greet".into()), Value::String("shout".into())].into()
+                    ),
+                    Value::Nil,
+                    Value::String("b!".into()),
+                ]
+                .into()
+            )
+        );
+        // core/ で上書きされた組み込み関数を参照
+        assert_eq!(
+            eval_str(
+                "(def + (fn [a b] 0))
+                 [(+ 1 2) (core/+ 1 2) (core/map inc [1])]"
+            )
+            .unwrap(),
+            Value::Vector(
+                vec![
+                    Value::Integer(0),
+                    Value::Integer(3),
+                    Value::Vector(vec![Value::Integer(2)].into()),
+                ]
+                .into()
+            )
+        );
+        // 割り算の / は修飾シンボルとして扱わない
+        assert_eq!(eval_str("(/ 6 3)").unwrap(), Value::Integer(2));
+    }
+
+    #[test]
+    fn test_stm_refs() {
+        // 複数のgoタスクから2つのrefを協調的に更新（合計は常に保たれる）
+        assert_eq!(
+            eval_str(
+                "(def a (ref 100))
+                 (def b (ref 0))
+                 (def tasks (map (fn [_] (go/run (fn [] (dosync (alter a dec) (alter b inc)))))
+                                 (range 50)))
+                 (each go/await tasks)
+                 (dosync (ensure a) [@a @b (+ @a @b)])"
+            )
+            .unwrap(),
+            Value::Vector(vec![Value::Integer(50), Value::Integer(50), Value::Integer(100)].into())
+        );
+        // commuteとトランザクション外での更新エラー
+        assert_eq!(
+            eval_str("(def c (ref 0)) (dosync (commute c + 5) (commute c + 1)) @c").unwrap(),
+            Value::Integer(6)
+        );
+        // commuteの関数内でrefを参照してもコミットがデッドロックしない
+        assert_eq!(
+            eval_str("(def h (ref 1)) (dosync (commute h (fn [v] (+ v @h)))) @h").unwrap(),
+            Value::Integer(2)
+        );
+        assert!(eval_str("(def r (ref 0)) (alter r inc)").is_err());
+        // エラーで抜けたトランザクションは反映されない
+        assert_eq!(
+            eval_str("(def d (ref 1)) (try (dosync (alter d inc) (error \"boom\"))) @d").unwrap(),
+            Value::Integer(1)
+        );
+        // commuteで複製した後に他でコミットされた更新を、後続のalterが失わない
+        assert_eq!(
+            eval_str(
+                "(def e (ref 0))
+                 (def once (atom true))
+                 (dosync
+                   (commute e + 0)
+                   (when @once
+                     (reset! once false)
+                     (go/await (go/run (fn [] (dosync (alter e + 100))))))
+                   (alter e + 1))
+                 @e"
+            )
+            .unwrap(),
+            Value::Integer(101)
+        );
+    }
+
+    #[test]
+    fn test_server_with_timeout_returns_503() {
+        // ハンドラーの遅延はクライアント起因の408ではなく503
+        let code = r#"
+            (def slow (server/with-timeout (fn [req] (sleep 300) (server/ok "late")) 20))
+            (get (server/test-request slow {:path "/"}) :status)
+        "#;
+        assert_eq!(eval_str(code).unwrap(), Value::Integer(503));
+    }
+
+    #[test]
+    fn test_server_background_lifecycle() {
+        // :port 0で起動し、処理中のリクエストが終わってから:on-shutdownが呼ばれる
+        let code = r#"
+            (def events (atom []))
+            (def srv (server/serve
+                       (fn [req]
+                         (swap! events conj :started)
+                         (sleep 200)
+                         (swap! events conj :handled)
+                         (server/ok "done"))
+                       {:port 0 :block false :drain-timeout 5
+                        :on-shutdown (fn [] (swap! events conj :shutdown))}))
+            (def port (server/port srv))
+            (def resp (http/get-async (str "http://127.0.0.1:" port "/")))
+            (loop [i 0]
+              (if (or (not (empty? @events)) (> i 300))
+                nil
+                (do (sleep 10) (recur (inc i)))))
+            (server/stop srv)
+            [(> port 0) (get (go/await resp) :ok) @events srv]
+        "#;
+        let Value::Vector(result) = eval_str(code).unwrap() else {
+            panic!("expected vector");
+        };
+        assert_eq!(result[0], Value::Bool(true));
+        assert_eq!(result[1], Value::String("done".into()));
+        assert_eq!(
+            result[2],
+            eval_str("[:started :handled :shutdown]").unwrap()
+        );
+
+        // 停止後のハンドルは使えない
+        let Value::String(id) = &result[3] else {
+            panic!("expected server handle");
+        };
+        assert!(eval_str(&format!("(server/port \"{}\")", id)).is_err());
+    }
+
+    #[test]
+    fn test_atom_watch_and_validator() {
+        assert_eq!(
+            eval_str(
+                "(def log (atom []))
+                 (def x (atom 0))
+                 (add-watch x :log (fn [k r old new] (swap! log conj [k old new])))
+                 (swap! x inc)
+                 (reset! x 10)
+                 (remove-watch x :log)
+                 (swap! x inc)
+                 @log"
+            )
+            .unwrap(),
+            eval_str("[[:log 0 1] [:log 1 10]]").unwrap()
+        );
+        assert_eq!(
+            eval_str(
+                "(def n (atom 1))
+                 (set-validator! n positive?)
+                 [(error? (try (swap! n - 5))) @n]"
+            )
+            .unwrap(),
+            Value::Vector(vec![Value::Bool(true), Value::Integer(1)].into())
+        );
+    }
+
+    #[test]
+    fn test_chars_and_graphemes() {
+        assert_eq!(
+            eval_str(r#"[\a (char 12354) (to-int \A) (str \a "b" \c)]"#).unwrap(),
+            Value::Vector(
+                vec![
+                    Value::Char('a'),
+                    Value::Char('あ'),
+                    Value::Integer(65),
+                    Value::String("abc".into()),
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            eval_str(r#"(match (first (str/chars "xyz")) \x -> :x _ -> :other)"#).unwrap(),
+            Value::Keyword(crate::intern::intern_keyword("x"))
+        );
+        // str/charsの結果（Char）をそのままstr/*に渡せる
+        assert_eq!(
+            eval_str(
+                r#"[(apply str (map str/upper (filter str/alpha? (str/chars "a1b"))))
+                    (str/contains? "abc" (first (str/chars "cat")))]"#
+            )
+            .unwrap(),
+            Value::Vector(vec![Value::String("AB".into()), Value::Bool(true)].into())
+        );
+        // 結合文字・ZWJ絵文字は1つの書記素
+        assert_eq!(
+            eval_str("(count (str/graphemes \"e\u{301}👨\u{200d}👩\u{200d}👧\"))").unwrap(),
+            Value::Integer(2)
+        );
+        assert_eq!(
+            eval_str("(str/reverse \"ab👨\u{200d}👩\u{200d}👧\")").unwrap(),
+            Value::String("👨\u{200d}👩\u{200d}👧ba".into())
+        );
+        // 全角は2桁として扱う
+        assert_eq!(
+            eval_str(r#"[(str/width "日本語abc") (str/pad-left "日本" 6) (str/truncate "日本語のテキスト" 9)]"#)
+                .unwrap(),
+            Value::Vector(
+                vec![
+                    Value::Integer(9),
+                    Value::String("  日本".into()),
+                    Value::String("日本語...".into()),
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            eval_str(r#"(str/wrap "日本語の文章です" 6)"#).unwrap(),
+            Value::String("日本語\nの文章\nです".into())
+        );
+        assert_eq!(
+            eval_str("[(str/normalize \"e\u{301}\") (str/normalize \"ｱ①\" :nfkc)]").unwrap(),
+            Value::Vector(vec![Value::String("é".into()), Value::String("ア1".into())].into())
+        );
+    }
+
+    #[test]
+    fn test_tail_calls() {
+        // 末尾位置の自己再帰・相互再帰はスタックを消費しない
+        let code = r#"
+            (defn count-down [n] (if (= n 0) :done (count-down (dec n))))
+            (defn ev? [n] (if (= n 0) true (od? (dec n))))
+            (defn od? [n] (if (= n 0) false (ev? (dec n))))
+            (defn sum-to [n acc]
+              (let [m (dec n)]
+                (match n 0 -> acc _ -> (do (sum-to m (+ acc n))))))
+            [(count-down 50000) (ev? 50001) (sum-to 50000 0)]
+        "#;
+        assert_eq!(
+            eval_str(code).unwrap(),
+            Value::Vector(
+                vec![
+                    Value::Keyword(crate::intern::intern_keyword("done")),
+                    Value::Bool(false),
+                    Value::Integer(1250025000),
+                ]
+                .into()
+            )
+        );
+        // trampolineはサンクを返す相互再帰を実行する
+        let code = r#"
+            (defn ping [n] (if (= n 0) :ping (fn [] (pong (dec n)))))
+            (defn pong [n] (if (= n 0) :pong (fn [] (ping (dec n)))))
+            (trampoline ping 50001)
+        "#;
+        assert_eq!(
+            eval_str(code).unwrap(),
+            Value::Keyword(crate::intern::intern_keyword("pong"))
+        );
+    }
+
+    #[test]
+    fn test_set_literal() {
+        // #{...} は重複を除いたセットになり、順序に関係なく等しい
+        assert_eq!(
+            eval_str("(= #{1 2 2 3} #{3 2 1})").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(eval_str("(count #{1 2 2 3})").unwrap(), Value::Integer(3));
+        assert_eq!(
+            eval_str("(contains? (disj (conj #{1 2} 3) 1) 3)").unwrap(),
+            Value::Bool(true)
+        );
+        assert!(eval_str("#{1.5}").is_err());
+        // mapはリストを返す（重複を残し、ハッシュ化できない値も返せる）
+        assert_eq!(
+            eval_str("[(count (map even? #{1 2 3 4})) (list? (map (fn [x] 1.5) #{1 2}))]").unwrap(),
+            Value::Vector(vec![Value::Integer(4), Value::Bool(true)].into())
+        );
+        assert_eq!(
+            eval_str("(= (filter even? #{1 2 3 4}) #{2 4})").unwrap(),
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn test_match_set_pattern() {
+        // セットパターンは全要素を含むセットにマッチ
+        let code = r#"(match #{:a :b :c} #{:a :z} -> 1 #{:a :b} -> 2 _ -> 3)"#;
+        assert_eq!(eval_str(code).unwrap(), Value::Integer(2));
+        assert_eq!(
+            eval_str("(match [:a] #{:a} -> 1 _ -> 2)").unwrap(),
+            Value::Integer(2)
+        );
+    }
 }
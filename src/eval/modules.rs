@@ -3,13 +3,16 @@
 //! use、export、module、load_module等のモジュールシステム機能を提供します。
 
 use crate::i18n::{fmt_msg, MsgKey};
-use crate::value::{Env, Module, Value};
+use crate::value::{Env, Expr, MapKey, Module, Value};
 use parking_lot::RwLock;
 use std::path::PathBuf;
 use std::sync::{Arc, LazyLock};
 
 use super::helpers::qerr;
-use super::Evaluator;
+use super::{Evaluator, DOC_PREFIX};
+
+/// 組み込み関数の名前空間（core/map 等で参照）
+pub(super) const CORE_NS: &str = "core";
 
 /// 実行ファイルのディレクトリをキャッシュ（高速化）
 static EXE_DIR: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
@@ -308,8 +311,9 @@ impl Evaluator {
         // ロード中のモジュールリストに追加（exportキー用、スレッドローカル）
         self.loading_modules.write().push(name_arc.clone());
 
-        // 現在のモジュール名を保存（評価後に復元）
+        // 現在のモジュール名・名前空間を保存（評価後に復元）
         let prev_module = self.current_module.read().clone();
+        let prev_ns = self.current_ns.write().take();
 
         // 内部実装（エラーが発生する可能性がある処理）
         let result = (|| -> Result<Arc<Module>, String> {
@@ -370,7 +374,13 @@ impl Evaluator {
             // モジュールが登録されているか確認、なければデフォルトで全公開モジュールを作成
             let module = {
                 let modules_guard = self.modules.read();
-                let existing = modules_guard.get(name).cloned();
+                // nsを宣言したモジュールは名前空間名で登録されている
+                let existing = modules_guard.get(name).cloned().or_else(|| {
+                    self.current_ns
+                        .read()
+                        .as_ref()
+                        .and_then(|ns| modules_guard.get(ns).cloned())
+                });
                 std::mem::drop(modules_guard); // 明示的にロックを解放
 
                 if let Some(m) = existing {
@@ -419,6 +429,7 @@ impl Evaluator {
             );
         }
         *self.current_module.write() = prev_module;
+        *self.current_ns.write() = prev_ns;
 
         // module_statesの状態を更新
         match &result {
@@ -443,3 +454,145 @@ impl Evaluator {
         result
     }
 }
+
+impl Evaluator {
+    /// ns宣言の評価: (ns app.core) / (ns app.core (:export foo bar))
+    ///
+    /// 以降の定義はexportしない限り非公開になり、他の名前空間からは
+    /// `app.core/foo` の形式で公開シンボルのみ参照できます。
+    pub(super) fn eval_ns(&self, args: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
+        let name: Arc<str> = match args.first() {
+            Some(Expr::Symbol { name, .. }) => name.clone(),
+            Some(Expr::String { value, .. }) => Arc::from(value.as_str()),
+            _ => return Err(fmt_msg(MsgKey::NsNeedsName, &[])),
+        };
+
+        let mut exports = crate::new_hashset();
+        for clause in &args[1..] {
+            match clause {
+                Expr::Call { func, args, .. } if matches!(&**func, Expr::Keyword { name, .. } if &**name == "export") => {
+                    for arg in args {
+                        match arg {
+                            Expr::Symbol { name, .. } => {
+                                exports.insert(name.clone());
+                            }
+                            _ => return Err(fmt_msg(MsgKey::InvalidNsClause, &[&name])),
+                        }
+                    }
+                }
+                _ => return Err(fmt_msg(MsgKey::InvalidNsClause, &[&name])),
+            }
+        }
+
+        *self.current_module.write() = Some(name.clone());
+        *self.current_ns.write() = Some(name.clone());
+
+        let file_path = self.loading_modules.read().last().map(|s| s.to_string());
+        let module = Module {
+            name: name.clone(),
+            file_path,
+            env,
+            exports: Some(exports),
+        };
+        self.modules.write().insert(name, Arc::new(module));
+        Ok(Value::Nil)
+    }
+
+    /// 現在のns内で、exportされていない定義か（nsの定義はデフォルト非公開）
+    pub(super) fn is_private_in_ns(&self, name: &str) -> bool {
+        let Some(ns) = self.current_ns.read().clone() else {
+            return false;
+        };
+        if name.starts_with(DOC_PREFIX) {
+            return false;
+        }
+        self.modules
+            .read()
+            .get(&ns)
+            .and_then(|m| m.exports.as_ref().map(|set| !set.contains(name)))
+            .unwrap_or(false)
+    }
+
+    /// 名前空間修飾されたシンボル（app.core/foo）を解決
+    ///
+    /// 該当する名前空間がなければNone（go/run 等の通常のシンボルとして扱う）。
+    /// 非公開シンボルは同じ名前空間の中からのみ参照できます。
+    pub(super) fn resolve_qualified(&self, name: &str) -> Result<Option<Value>, String> {
+        let Some((ns, sym)) = name.split_once('/') else {
+            return Ok(None);
+        };
+        if ns.is_empty() || sym.is_empty() {
+            return Ok(None);
+        }
+        let Some(module) = self.modules.read().get(ns).cloned() else {
+            return Ok(None);
+        };
+        let in_same_ns = self.current_ns.read().as_deref() == Some(ns);
+        if !in_same_ns && !module.is_exported(sym) {
+            if module.env.read().get(sym).is_some() {
+                return Err(qerr(MsgKey::SymbolNotExported, &[sym, ns]));
+            }
+            return Ok(None);
+        }
+        let value = module.env.read().get(sym);
+        Ok(value)
+    }
+
+    /// ns-publics: 名前空間の公開シンボルを {シンボル 値} のマップで返す
+    pub(super) fn eval_ns_publics(&self, args: &[Value]) -> Result<Value, String> {
+        if args.len() != 1 {
+            return Err(fmt_msg(MsgKey::Need1Arg, &["ns-publics"]));
+        }
+        let module = self.find_ns(&args[0], "ns-publics")?;
+        let env = module.env.read();
+        let mut map = crate::new_hashmap();
+        for name in module.public_names() {
+            if let Some(value) = env.get(&name) {
+                map.insert(MapKey::Symbol(name), value);
+            }
+        }
+        Ok(Value::Map(map))
+    }
+
+    /// ns-resolve: 名前空間の公開シンボルを解決（見つからなければnil）
+    pub(super) fn eval_ns_resolve(&self, args: &[Value]) -> Result<Value, String> {
+        if args.len() != 2 {
+            return Err(fmt_msg(MsgKey::Need2Args, &["ns-resolve"]));
+        }
+        let module = self.find_ns(&args[0], "ns-resolve")?;
+        let sym = match &args[1] {
+            Value::Symbol(s) => s.to_string(),
+            Value::String(s) => s.clone(),
+            _ => {
+                return Err(fmt_msg(
+                    MsgKey::SecondArgMustBe,
+                    &["ns-resolve", "a symbol or string"],
+                ))
+            }
+        };
+        if !module.is_exported(&sym) {
+            return Ok(Value::Nil);
+        }
+        let value = module.env.read().get(&sym);
+        Ok(value.unwrap_or(Value::Nil))
+    }
+
+    /// 名前空間名（シンボルまたは文字列）からモジュールを取得
+    fn find_ns(&self, name: &Value, fn_name: &str) -> Result<Arc<Module>, String> {
+        let ns = match name {
+            Value::Symbol(s) => s.to_string(),
+            Value::String(s) => s.clone(),
+            _ => {
+                return Err(fmt_msg(
+                    MsgKey::FirstArgMustBe,
+                    &[fn_name, "a symbol or string"],
+                ))
+            }
+        };
+        self.modules
+            .read()
+            .get(ns.as_str())
+            .cloned()
+            .ok_or_else(|| qerr(MsgKey::ModuleNotFound, &[&ns]))
+    }
+}
@@ -9,6 +9,7 @@ use parking_lot::RwLock;
 use smallvec::SmallVec;
 use std::sync::Arc;
 
+use super::call::TailCall;
 use super::Evaluator;
 
 /// 通常のパターンマッチで使用される変数束縛の数
@@ -19,7 +20,7 @@ impl Evaluator {
     /// match式を評価
     ///
     /// 各アーム（パターン + ガード + ボディ）を順番に評価し、
-    /// 最初にマッチしたアームの本体を実行して結果を返す。
+    /// 最初にマッチしたアームの本体を末尾位置として評価する。
     ///
     /// # 引数
     /// - `value`: マッチ対象の値
@@ -31,12 +32,12 @@ impl Evaluator {
     /// - ガード条件評価中のエラー
     /// - マッチしたアームのボディ評価中のエラー
     /// - すべてのパターンがマッチしなかった場合
-    pub(super) fn eval_match(
+    pub(super) fn eval_match_tail(
         &self,
         value: &Value,
         arms: &[MatchArm],
         env: Arc<RwLock<Env>>,
-    ) -> Result<Value, String> {
+    ) -> Result<TailCall, String> {
         for arm in arms {
             let mut bindings: SmallVec<[(String, Value); TYPICAL_PATTERN_BINDINGS]> =
                 SmallVec::new();
@@ -75,7 +76,7 @@ impl Evaluator {
                     match_env_rc.write().set(var, result);
                 }
 
-                return self.eval_with_env(&arm.body, match_env_rc);
+                return self.eval_tail(&arm.body, match_env_rc);
             }
         }
         Err(msg(MsgKey::NoMatchingPattern).to_string())
@@ -310,11 +311,21 @@ impl Evaluator {
                 Ok(matches!(value, Value::Float(vf) if (vf - f).abs() < f64::EPSILON))
             }
             Pattern::String(s) => Ok(matches!(value, Value::String(vs) if vs == s)),
+            Pattern::Char(c) => Ok(matches!(value, Value::Char(vc) if vc == c)),
             Pattern::Keyword(k) => Ok(matches!(value, Value::Keyword(vk) if **vk == **k)),
             Pattern::Var(name) => {
                 bindings.push((name.to_string(), value.clone()));
                 Ok(true)
             }
+            Pattern::Set(patterns) => {
+                // セットパターンは全要素（リテラル）を含むセットにマッチ
+                let Value::Set(set) = value else {
+                    return Ok(false);
+                };
+                Ok(patterns
+                    .iter()
+                    .all(|p| set.contains(&self.fn_param_to_value(p))))
+            }
             Pattern::Vector(patterns, rest) => {
                 // VectorパターンはVectorとListの両方にマッチ（一貫性のため）
                 let values = match value {
@@ -11,7 +11,40 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use super::{Evaluator, DOC_PREFIX, RECUR_SENTINEL};
+use super::call::TailCall;
+use super::{dynamic, Evaluator, DOC_PREFIX, RECUR_SENTINEL};
+
+/// quasiquote内の `foo#` → 生成済みシンボルの対応表
+type AutoGensyms = HashMap<Arc<str>, Arc<str>>;
+
+/// テンプレート由来の値に含まれる `foo#` シンボルを一意なシンボルに置き換える
+///
+/// unquoteで埋め込まれた値は対象外（呼び出し側でテンプレート部分にのみ適用する）
+fn auto_gensym(value: Value, gensyms: &mut AutoGensyms) -> Value {
+    match value {
+        Value::Symbol(name) if name.len() > 1 && name.ends_with('#') => {
+            let sym = gensyms
+                .entry(name.clone())
+                .or_insert_with(|| {
+                    crate::builtins::core_state_meta::auto_gensym_symbol(&name[..name.len() - 1])
+                })
+                .clone();
+            Value::Symbol(sym)
+        }
+        Value::List(items) => {
+            Value::List(items.into_iter().map(|v| auto_gensym(v, gensyms)).collect())
+        }
+        Value::Vector(items) => {
+            Value::Vector(items.into_iter().map(|v| auto_gensym(v, gensyms)).collect())
+        }
+        Value::Map(map) => Value::Map(
+            map.into_iter()
+                .map(|(k, v)| (k, auto_gensym(v, gensyms)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
 
 // recurで評価済みの引数を一時保存するThreadLocalスタック
 // loop/recurの間でのみ使用し、二重評価を回避する
@@ -64,6 +97,16 @@ impl Evaluator {
 
     /// do式を評価
     pub(super) fn eval_do(&self, exprs: &[Expr], env: Arc<RwLock<Env>>) -> Result<Value, String> {
+        let tail = self.eval_do_tail(exprs, env)?;
+        self.finish_tail(tail)
+    }
+
+    /// doを末尾位置として評価（最後の式の呼び出しを末尾呼び出しとして返す）
+    pub(super) fn eval_do_tail(
+        &self,
+        exprs: &[Expr],
+        env: Arc<RwLock<Env>>,
+    ) -> Result<TailCall, String> {
         // deferスコープを作成し、RAIIガードで確実にクリーンアップ
         self.defer_stack.write().push(Vec::new());
 
@@ -73,11 +116,93 @@ impl Evaluator {
             env: Arc::clone(&env),
         };
 
+        let Some((last, init)) = exprs.split_last() else {
+            return Ok(TailCall::Value(Value::Nil));
+        };
+        for expr in init {
+            self.eval_with_env(expr, Arc::clone(&env))?;
+        }
+
+        match self.eval_tail(last, env)? {
+            // 未実行のdeferがある場合は、deferより先に呼び出しを完了させる
+            TailCall::Call(func, args)
+                if self
+                    .defer_stack
+                    .read()
+                    .last()
+                    .is_some_and(|defers| !defers.is_empty()) =>
+            {
+                self.apply_func(&func, *args).map(TailCall::Value)
+            }
+            tail => Ok(tail),
+        }
+    }
+
+    /// defdynamicを評価: (defdynamic name) / (defdynamic name init)
+    ///
+    /// 動的変数として宣言し、ルート値（省略時nil）を現在の環境に定義する
+    pub(super) fn eval_defdynamic(
+        &self,
+        args: &[Expr],
+        env: Arc<RwLock<Env>>,
+    ) -> Result<Value, String> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(fmt_msg(MsgKey::Need1Or2Args, &["defdynamic"]));
+        }
+        let name = match &args[0] {
+            Expr::Symbol { name, .. } => name.clone(),
+            other => {
+                return Err(fmt_msg(
+                    MsgKey::DynamicVarMustBeSymbol,
+                    &[&format!("{:?}", other)],
+                ))
+            }
+        };
+        let value = match args.get(1) {
+            Some(init) => self.eval_with_env(init, Arc::clone(&env))?,
+            None => Value::Nil,
+        };
+        dynamic::declare(name.clone());
+        env.write().set(name, value.clone());
+        Ok(value)
+    }
+
+    /// bindingを評価: (binding [name value ...] body...)
+    ///
+    /// 値は外側の束縛のもとで評価し、ボディ実行中だけ現在のスレッドの動的変数を再束縛する
+    pub(super) fn eval_binding(
+        &self,
+        args: &[Expr],
+        env: Arc<RwLock<Env>>,
+    ) -> Result<Value, String> {
+        let pairs = match args.first() {
+            Some(Expr::Vector { items, .. }) if items.len() % 2 == 0 => items,
+            _ => return Err(msg(MsgKey::BindingMustBeVector).to_string()),
+        };
+
+        let mut frame = dynamic::capture();
+        for pair in pairs.chunks(2) {
+            let name = match &pair[0] {
+                Expr::Symbol { name, .. } => name.clone(),
+                other => {
+                    return Err(fmt_msg(
+                        MsgKey::DynamicVarMustBeSymbol,
+                        &[&format!("{:?}", other)],
+                    ))
+                }
+            };
+            if !dynamic::is_dynamic(&name) {
+                return Err(fmt_msg(MsgKey::NotDynamicVar, &[&name]));
+            }
+            let value = self.eval_with_env(&pair[1], Arc::clone(&env))?;
+            frame.insert(name, value);
+        }
+
+        let _guard = dynamic::install(frame);
         let mut result = Value::Nil;
-        for expr in exprs {
+        for expr in &args[1..] {
             result = self.eval_with_env(expr, Arc::clone(&env))?;
         }
-
         Ok(result)
     }
 
@@ -157,7 +282,7 @@ impl Evaluator {
     pub(super) fn eval_mac(
         &self,
         name: &str,
-        params: &[std::sync::Arc<str>],
+        params: &[Pattern],
         is_variadic: bool,
         body: &Expr,
         env: Arc<RwLock<Env>>,
@@ -224,11 +349,25 @@ impl Evaluator {
     }
 
     /// quasiquoteを評価
+    ///
+    /// テンプレート中の `foo#` は展開ごとに一意なシンボル（auto-gensym）に置き換える。
+    /// 同じquasiquote内の同名の `foo#` は同じシンボルになる。
     pub(super) fn eval_quasiquote(
         &self,
         expr: &Expr,
         env: Arc<RwLock<Env>>,
         depth: usize,
+    ) -> Result<Value, String> {
+        let mut gensyms = HashMap::new();
+        self.eval_quasiquote_with(expr, env, depth, &mut gensyms)
+    }
+
+    fn eval_quasiquote_with(
+        &self,
+        expr: &Expr,
+        env: Arc<RwLock<Env>>,
+        depth: usize,
+        gensyms: &mut AutoGensyms,
     ) -> Result<Value, String> {
         match expr {
             Expr::Unquote { expr: e, .. } if depth == 0 => {
@@ -237,12 +376,12 @@ impl Evaluator {
             }
             Expr::Unquote { expr: e, .. } => {
                 // ネストしたquasiquote内のunquote
-                let inner = self.eval_quasiquote(e, env, depth - 1)?;
+                let inner = self.eval_quasiquote_with(e, env, depth - 1, gensyms)?;
                 Ok(inner)
             }
             Expr::Quasiquote { expr: e, .. } => {
                 // ネストしたquasiquote
-                self.eval_quasiquote(e, env, depth + 1)
+                self.eval_quasiquote_with(e, env, depth + 1, gensyms)
             }
             Expr::List { items, .. } => {
                 let mut result = Vec::with_capacity(items.len());
@@ -262,11 +401,13 @@ impl Evaluator {
                                 }
                             }
                         } else {
-                            let val = self.eval_quasiquote(e, Arc::clone(&env), depth - 1)?;
+                            let val =
+                                self.eval_quasiquote_with(e, Arc::clone(&env), depth - 1, gensyms)?;
                             result.push(val);
                         }
                     } else {
-                        let val = self.eval_quasiquote(item, Arc::clone(&env), depth)?;
+                        let val =
+                            self.eval_quasiquote_with(item, Arc::clone(&env), depth, gensyms)?;
                         result.push(val);
                     }
                 }
@@ -290,11 +431,13 @@ impl Evaluator {
                                 }
                             }
                         } else {
-                            let val = self.eval_quasiquote(e, Arc::clone(&env), depth - 1)?;
+                            let val =
+                                self.eval_quasiquote_with(e, Arc::clone(&env), depth - 1, gensyms)?;
                             result.push(val);
                         }
                     } else {
-                        let val = self.eval_quasiquote(item, Arc::clone(&env), depth)?;
+                        let val =
+                            self.eval_quasiquote_with(item, Arc::clone(&env), depth, gensyms)?;
                         result.push(val);
                     }
                 }
@@ -303,7 +446,7 @@ impl Evaluator {
             Expr::Call { func, args, .. } => {
                 // Callもリストとして扱う
                 let mut result = Vec::with_capacity(1 + args.len());
-                result.push(self.eval_quasiquote(func, Arc::clone(&env), depth)?);
+                result.push(self.eval_quasiquote_with(func, Arc::clone(&env), depth, gensyms)?);
                 for arg in args {
                     if let Expr::UnquoteSplice { expr: e, .. } = arg {
                         if depth == 0 {
@@ -320,11 +463,13 @@ impl Evaluator {
                                 }
                             }
                         } else {
-                            let val = self.eval_quasiquote(e, Arc::clone(&env), depth - 1)?;
+                            let val =
+                                self.eval_quasiquote_with(e, Arc::clone(&env), depth - 1, gensyms)?;
                             result.push(val);
                         }
                     } else {
-                        let val = self.eval_quasiquote(arg, Arc::clone(&env), depth)?;
+                        let val =
+                            self.eval_quasiquote_with(arg, Arc::clone(&env), depth, gensyms)?;
                         result.push(val);
                     }
                 }
@@ -339,10 +484,10 @@ impl Evaluator {
             } => {
                 let mut result = Vec::with_capacity(4);
                 result.push(Value::Symbol(crate::intern::intern_symbol("if")));
-                result.push(self.eval_quasiquote(test, Arc::clone(&env), depth)?);
-                result.push(self.eval_quasiquote(then, Arc::clone(&env), depth)?);
+                result.push(self.eval_quasiquote_with(test, Arc::clone(&env), depth, gensyms)?);
+                result.push(self.eval_quasiquote_with(then, Arc::clone(&env), depth, gensyms)?);
                 if let Some(o) = otherwise {
-                    result.push(self.eval_quasiquote(o, Arc::clone(&env), depth)?);
+                    result.push(self.eval_quasiquote_with(o, Arc::clone(&env), depth, gensyms)?);
                 }
                 Ok(Value::List(result.into()))
             }
@@ -365,10 +510,20 @@ impl Evaluator {
                                 }
                             }
                         } else {
-                            result.push(self.eval_quasiquote(us, Arc::clone(&env), depth - 1)?);
+                            result.push(self.eval_quasiquote_with(
+                                us,
+                                Arc::clone(&env),
+                                depth - 1,
+                                gensyms,
+                            )?);
                         }
                     } else {
-                        result.push(self.eval_quasiquote(e, Arc::clone(&env), depth)?);
+                        result.push(self.eval_quasiquote_with(
+                            e,
+                            Arc::clone(&env),
+                            depth,
+                            gensyms,
+                        )?);
                     }
                 }
                 Ok(Value::List(result.into()))
@@ -383,32 +538,43 @@ impl Evaluator {
                 let param_vals: Vec<Value> = if *is_variadic && params.len() == 1 {
                     vec![
                         Value::Symbol(crate::intern::intern_symbol("&")),
-                        self.fn_param_to_value(&params[0]),
+                        auto_gensym(self.fn_param_to_value(&params[0]), gensyms),
                     ]
                 } else if *is_variadic {
                     let mut v: Vec<Value> = params[..params.len() - 1]
                         .iter()
-                        .map(|p| self.fn_param_to_value(p))
+                        .map(|p| auto_gensym(self.fn_param_to_value(p), gensyms))
                         .collect();
                     v.push(Value::Symbol(crate::intern::intern_symbol("&")));
-                    v.push(self.fn_param_to_value(&params[params.len() - 1]));
+                    v.push(auto_gensym(
+                        self.fn_param_to_value(&params[params.len() - 1]),
+                        gensyms,
+                    ));
                     v
                 } else {
-                    params.iter().map(|p| self.fn_param_to_value(p)).collect()
+                    params
+                        .iter()
+                        .map(|p| auto_gensym(self.fn_param_to_value(p), gensyms))
+                        .collect()
                 };
                 items.push(Value::Vector(param_vals.into()));
-                items.push(self.eval_quasiquote(body, env, depth)?);
+                items.push(self.eval_quasiquote_with(body, env, depth, gensyms)?);
                 Ok(Value::List(items.into()))
             }
             Expr::Let { bindings, body, .. } => {
                 let mut items = vec![Value::Symbol(crate::intern::intern_symbol("let"))];
                 let mut binding_vec = Vec::new();
                 for (pattern, expr) in bindings {
-                    binding_vec.push(self.fn_param_to_value(pattern));
-                    binding_vec.push(self.eval_quasiquote(expr, Arc::clone(&env), depth)?);
+                    binding_vec.push(auto_gensym(self.fn_param_to_value(pattern), gensyms));
+                    binding_vec.push(self.eval_quasiquote_with(
+                        expr,
+                        Arc::clone(&env),
+                        depth,
+                        gensyms,
+                    )?);
                 }
                 items.push(Value::Vector(binding_vec.into()));
-                items.push(self.eval_quasiquote(body, env, depth)?);
+                items.push(self.eval_quasiquote_with(body, env, depth, gensyms)?);
                 Ok(Value::List(items.into()))
             }
             Expr::Def {
@@ -419,13 +585,13 @@ impl Evaluator {
             } => {
                 let mut items = vec![
                     Value::Symbol(crate::intern::intern_symbol("def")),
-                    Value::Symbol(crate::intern::intern_symbol(name)),
+                    auto_gensym(Value::Symbol(crate::intern::intern_symbol(name)), gensyms),
                 ];
-                items.push(self.eval_quasiquote(value, env, depth)?);
+                items.push(self.eval_quasiquote_with(value, env, depth, gensyms)?);
                 Ok(Value::List(items.into()))
             }
             // その他は変換してValueに
-            _ => self.expr_to_value(expr),
+            _ => Ok(auto_gensym(self.expr_to_value(expr)?, gensyms)),
         }
     }
 
@@ -436,6 +602,10 @@ impl Evaluator {
             Expr::Bool { value, .. } => Ok(Value::Bool(*value)),
             Expr::Integer { value, .. } => Ok(Value::Integer(*value)),
             Expr::Float { value, .. } => Ok(Value::Float(*value)),
+            Expr::BigInt { value, .. } => Ok(Value::BigInt(value.clone())),
+            Expr::Ratio { value, .. } => Ok(Value::Ratio(value.clone())),
+            Expr::Decimal { value, .. } => Ok(Value::Decimal(*value)),
+            Expr::Char { value, .. } => Ok(Value::Char(*value)),
             Expr::String { value, .. } => Ok(Value::String(value.clone())),
             Expr::Symbol { name, .. } => Ok(Value::Symbol(crate::intern::intern_symbol(name))),
             Expr::Keyword { name, .. } => Ok(Value::Keyword(crate::intern::intern_keyword(name))),
@@ -463,6 +633,13 @@ impl Evaluator {
                 }
                 Ok(Value::Map(map.into()))
             }
+            Expr::Set { items, .. } => {
+                let vals = items
+                    .iter()
+                    .map(|item| self.expr_to_value(item))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Value::set_from(vals)
+            }
             // 特殊形式やCallは評価せずにリストとして返す
             Expr::Call { func, args, .. } => {
                 let mut items = vec![self.expr_to_value(func)?];
@@ -696,23 +873,18 @@ impl Evaluator {
                 let param_vals: Vec<Value> = if *is_variadic && params.len() == 1 {
                     vec![
                         Value::Symbol(crate::intern::intern_symbol("&")),
-                        Value::Symbol(crate::intern::intern_symbol(&params[0])),
+                        self.fn_param_to_value(&params[0]),
                     ]
                 } else if *is_variadic {
                     let mut v: Vec<Value> = params[..params.len() - 1]
                         .iter()
-                        .map(|p| Value::Symbol(crate::intern::intern_symbol(p)))
+                        .map(|p| self.fn_param_to_value(p))
                         .collect();
                     v.push(Value::Symbol(crate::intern::intern_symbol("&")));
-                    v.push(Value::Symbol(crate::intern::intern_symbol(
-                        &params[params.len() - 1],
-                    )));
+                    v.push(self.fn_param_to_value(&params[params.len() - 1]));
                     v
                 } else {
-                    params
-                        .iter()
-                        .map(|p| Value::Symbol(crate::intern::intern_symbol(p)))
-                        .collect()
+                    params.iter().map(|p| self.fn_param_to_value(p)).collect()
                 };
                 items.push(Value::Vector(param_vals.into()));
                 items.push(self.expr_to_value(body)?);
@@ -791,10 +963,10 @@ impl Evaluator {
                 ));
             }
 
-            // 固定引数を設定
+            // 固定引数を設定（分解パターンも使用可能）
             for (param, arg) in mac.params.iter().zip(args.iter()).take(fixed_count) {
                 let arg_val = self.expr_to_value(arg)?;
-                new_env.set(param.clone(), arg_val);
+                self.bind_macro_param(mac, param, &arg_val, &mut new_env)?;
             }
 
             // 残りを可変引数として設定
@@ -802,7 +974,12 @@ impl Evaluator {
                 .iter()
                 .map(|e| self.expr_to_value(e))
                 .collect::<Result<Vec<_>, _>>()?;
-            new_env.set(mac.params[fixed_count].clone(), Value::List(rest.into()));
+            self.bind_macro_param(
+                mac,
+                &mac.params[fixed_count],
+                &Value::List(rest.into()),
+                &mut new_env,
+            )?;
         } else {
             // 通常の引数
             if mac.params.len() != args.len() {
@@ -818,7 +995,7 @@ impl Evaluator {
             for (param, arg) in mac.params.iter().zip(args.iter()) {
                 // 引数をそのまま環境に（評価しない）
                 let arg_val = self.expr_to_value(arg)?;
-                new_env.set(param.clone(), arg_val);
+                self.bind_macro_param(mac, param, &arg_val, &mut new_env)?;
             }
         }
 
@@ -830,6 +1007,81 @@ impl Evaluator {
         self.value_to_expr(&result)
     }
 
+    /// macroexpand-all - フォーム内のマクロ呼び出しをすべて再帰的に展開
+    ///
+    /// (macroexpand-all '(unless ok (log "ng")))
+    /// ;=> (if ok nil (do (log "ng")))
+    pub(super) fn eval_macroexpand_all(
+        &self,
+        args: &[Expr],
+        env: Arc<RwLock<Env>>,
+    ) -> Result<Value, String> {
+        if args.len() != 1 {
+            return Err(fmt_msg(MsgKey::Need1Arg, &["macroexpand-all"]));
+        }
+        let form = self.eval_with_env(&args[0], Arc::clone(&env))?;
+        self.macroexpand_all(form, &env)
+    }
+
+    fn macroexpand_all(&self, mut form: Value, env: &Arc<RwLock<Env>>) -> Result<Value, String> {
+        // 先頭がマクロである限り展開を繰り返す
+        while let Value::List(items) = &form {
+            let Some(Value::Symbol(head)) = items.front() else {
+                break;
+            };
+            match &**head {
+                // quoteされたフォームは展開しない
+                "quote" => return Ok(form),
+                "defn" | "defn-" => {
+                    form = crate::builtins::core_state_meta::native_macroexpand(&[form.clone()])?;
+                    continue;
+                }
+                _ => {}
+            }
+            let Some(Value::Macro(mac)) = env.read().get(head) else {
+                break;
+            };
+            let args = items
+                .iter()
+                .skip(1)
+                .map(|v| self.value_to_expr(v))
+                .collect::<Result<Vec<_>, _>>()?;
+            let expanded = self.expand_macro(&mac, &args, Arc::clone(env))?;
+            form = self.expr_to_value(&expanded)?;
+        }
+
+        // 部分フォームを展開
+        match form {
+            Value::List(items) => Ok(Value::List(
+                items
+                    .into_iter()
+                    .map(|v| self.macroexpand_all(v, env))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Vector(items) => Ok(Value::Vector(
+                items
+                    .into_iter()
+                    .map(|v| self.macroexpand_all(v, env))
+                    .collect::<Result<_, _>>()?,
+            )),
+            other => Ok(other),
+        }
+    }
+
+    /// マクロ引数（未評価のコード）をパラメータのパターンに束縛
+    ///
+    /// 分解に失敗した場合は、どのマクロの呼び出しかが分かるようにマクロ名を付けて返す
+    fn bind_macro_param(
+        &self,
+        mac: &Macro,
+        param: &Pattern,
+        value: &Value,
+        env: &mut Env,
+    ) -> Result<(), String> {
+        self.bind_fn_param(param, value, env)
+            .map_err(|e| fmt_msg(MsgKey::MacParamDestructureFailed, &[&mac.name, &e]))
+    }
+
     /// ValueをExprに変換（マクロ展開の結果をコードとして扱う、evalでも使用）
     pub fn value_to_expr(&self, val: &Value) -> Result<Expr, String> {
         match val {
@@ -848,6 +1100,22 @@ impl Evaluator {
                 value: *f,
                 span: Expr::dummy_span(),
             }),
+            Value::BigInt(n) => Ok(Expr::BigInt {
+                value: n.clone(),
+                span: Expr::dummy_span(),
+            }),
+            Value::Ratio(r) => Ok(Expr::Ratio {
+                value: r.clone(),
+                span: Expr::dummy_span(),
+            }),
+            Value::Decimal(d) => Ok(Expr::Decimal {
+                value: *d,
+                span: Expr::dummy_span(),
+            }),
+            Value::Char(c) => Ok(Expr::Char {
+                value: *c,
+                span: Expr::dummy_span(),
+            }),
             Value::String(s) => Ok(Expr::String {
                 value: s.clone(),
                 span: Expr::dummy_span(),
@@ -1235,21 +1503,37 @@ impl Evaluator {
                         "mac" if items.len() >= 4 => {
                             if let Value::Symbol(name) = &items[1] {
                                 if let Value::Vector(params_vec) = &items[2] {
-                                    // パラメータは単純なシンボルのリスト
-                                    let params: Result<Vec<_>, _> = params_vec
-                                        .iter()
-                                        .map(|v| match v {
-                                            Value::Symbol(s) => Ok(s.clone()),
-                                            _ => Err(fmt_msg(
-                                                MsgKey::MacroParamMustBeSymbol,
-                                                &[&format!("{:?}", v)],
-                                            )),
-                                        })
-                                        .collect();
-                                    // is_variadicのチェック
-                                    let is_variadic = params_vec
-                                        .iter()
-                                        .any(|v| matches!(v, Value::Symbol(s) if &**s == "&"));
+                                    // パラメータ（& 以降は可変長引数、各要素は分解パターン可）
+                                    let mut params = Vec::with_capacity(params_vec.len());
+                                    let mut is_variadic = false;
+                                    let mut iter = params_vec.iter();
+                                    while let Some(v) = iter.next() {
+                                        match v {
+                                            Value::Symbol(s) if &**s == "&" => {
+                                                is_variadic = true;
+                                                match iter.next() {
+                                                    Some(Value::Symbol(rest)) => {
+                                                        params.push(Pattern::Var(rest.clone()))
+                                                    }
+                                                    _ => {
+                                                        return Err(msg(
+                                                            MsgKey::MacVarargNeedsSymbol,
+                                                        )
+                                                        .to_string())
+                                                    }
+                                                }
+                                            }
+                                            Value::Symbol(_) | Value::Vector(_) | Value::Map(_) => {
+                                                params.push(self.value_to_pattern(v)?)
+                                            }
+                                            _ => {
+                                                return Err(fmt_msg(
+                                                    MsgKey::MacroParamMustBeSymbol,
+                                                    &[&format!("{:?}", v)],
+                                                ))
+                                            }
+                                        }
+                                    }
                                     // ボディ（複数式の場合はDoに包む）
                                     let body_exprs: Result<Vec<_>, _> = items
                                         .iter()
@@ -1267,7 +1551,7 @@ impl Evaluator {
                                     };
                                     return Ok(Expr::Mac {
                                         name: name.clone(),
-                                        params: params?,
+                                        params,
                                         is_variadic,
                                         body,
                                         span: Expr::dummy_span(),
@@ -1408,6 +1692,14 @@ impl Evaluator {
                     span: Expr::dummy_span(),
                 })
             }
+            Value::Set(items) => {
+                let exprs: Result<Vec<_>, _> =
+                    items.iter().map(|v| self.value_to_expr(v)).collect();
+                Ok(Expr::Set {
+                    items: exprs?,
+                    span: Expr::dummy_span(),
+                })
+            }
             _ => Err(msg(MsgKey::ValueCannotBeConverted).to_string()),
         }
     }
@@ -1430,6 +1722,7 @@ impl Evaluator {
             Value::Integer(n) => Ok(Pattern::Integer(*n)),
             Value::Float(f) => Ok(Pattern::Float(*f)),
             Value::String(s) => Ok(Pattern::String(s.clone())),
+            Value::Char(c) => Ok(Pattern::Char(*c)),
             Value::Keyword(k) => Ok(Pattern::Keyword(k.clone())),
             // ベクタパターン [x y] or [x y & rest]
             Value::Vector(items) => {
@@ -1488,6 +1781,21 @@ impl Evaluator {
                 }
                 Ok(Pattern::Map(pairs, as_var))
             }
+            // セットパターン #{:a :b}（要素はリテラルのみ）
+            Value::Set(items) => {
+                let patterns = items
+                    .iter()
+                    .map(|item| match item {
+                        Value::Nil
+                        | Value::Bool(_)
+                        | Value::Integer(_)
+                        | Value::String(_)
+                        | Value::Keyword(_) => self.value_to_pattern(item),
+                        _ => Err(fmt_msg(MsgKey::SetPatternMustBeLiteral, &[])),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Pattern::Set(patterns))
+            }
             _ => Err(fmt_msg(MsgKey::InvalidPattern, &[&format!("{:?}", value)])),
         }
     }
@@ -1516,6 +1824,7 @@ impl Evaluator {
             Pattern::Integer(n) => Value::Integer(*n),
             Pattern::Float(f) => Value::Float(*f),
             Pattern::String(s) => Value::String(s.clone()),
+            Pattern::Char(c) => Value::Char(*c),
             Pattern::Keyword(k) => Value::Keyword(crate::intern::intern_keyword(k)),
             Pattern::Vector(patterns, rest) => {
                 let mut items: Vec<Value> =
@@ -1551,6 +1860,9 @@ impl Evaluator {
                 }
                 Value::List(items.into())
             }
+            Pattern::Set(patterns) => {
+                Value::Set(patterns.iter().map(|p| self.pattern_to_value(p)).collect())
+            }
             Pattern::Or(patterns) => {
                 // Orパターンは特殊な構文 (p1 | p2 | p3)
                 // Valueとしては、中置演算子として表現
@@ -14,6 +14,7 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (NeedsSymbol, "{0} requires a symbol"),
         (VarargNeedsName, "'&' requires a variable name"),
         (UnexpectedPattern, "unexpected pattern: {0}"),
+        (SetPatternMustBeLiteral, "set pattern elements must be literals (nil, bool, integer, string, char or keyword)"),
         (RestNeedsVar, "'...' requires a variable name"),
         // レキサーエラー
         (UnexpectedChar, "unexpected character: {0}"),
@@ -23,6 +24,7 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             EmptyKeyword,
             "empty keyword: ':' must be followed by an identifier",
         ),
+        (InvalidCharLiteral, "invalid character literal: {0}"),
         // 評価器エラー
         (UndefinedVar, "undefined variable: {0}"),
         (
@@ -35,6 +37,7 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (DivisionByZero, "division by zero"),
         (IntegerOverflow, "integer overflow in {0} operation"),
         (IntegerUnderflow, "integer underflow in {0} operation"),
+        (DecimalOverflow, "decimal overflow in {0} operation"),
         (
             ExportOnlyInModule,
             "export can only be used inside a module definition",
@@ -55,6 +58,11 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             "symbol {0} is not exported from module {1}",
         ),
         (FileNotFound, "file not found: {0} ({1})"),
+        (NsNeedsName, "ns requires a namespace name"),
+        (
+            InvalidNsClause,
+            "ns {0}: invalid clause (expected (:export sym ...))",
+        ),
         // 引数エラー
         (NeedAtLeastNArgs, "{0} requires at least {1} argument(s)"),
         (NeedExactlyNArgs, "{0} requires exactly {1} argument(s)"),
@@ -90,6 +98,11 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         // 特殊な引数エラー
         (SplitTwoStrings, "split requires two strings"),
         (JoinStringAndList, "join requires a string and a list"),
+        (InvalidCodePoint, "{0}: invalid code point {1}"),
+        (
+            InvalidNormalizationForm,
+            "{0}: unknown normalization form {1} (expected :nfc, :nfd, :nfkc or :nfkd)",
+        ),
         (
             AssocMapAndKeyValues,
             "assoc requires a map and one or more key-value pairs",
@@ -118,6 +131,14 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             MacVariadicArgCountMismatch,
             "mac {0}: insufficient arguments (minimum {1}, got {2})",
         ),
+        (
+            MacParamDestructureFailed,
+            "mac {0}: cannot destructure argument: {1}",
+        ),
+        (
+            GensymPrefixMustBeString,
+            "gensym: prefix must be a string or symbol",
+        ),
         // quasiquote エラー
         (
             UnquoteOutsideQuasiquote,
@@ -175,6 +196,9 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         ),
         (AllElementsMustBe, "{0}: all elements must be {1}"),
         (SetOperationError, "{0}"),
+        (SetElementNotHashable, "cannot put {0} in a set (floats, functions and other unhashable values are not allowed)"),
+        (TransducerNotCallable, "a transducer cannot be called directly; use transduce, into or sequence"),
+        (MapEntryMustBePair, "{0}: map entries must be [key value] pairs"),
         // 並行処理エラー
         (ChannelClosed, "{0}: channel is closed"),
         (ExpectedKeyword, "{0}: expected {1} keyword"),
@@ -189,6 +213,16 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             TimeoutMustBeMs,
             "{0}: timeout must be an integer (milliseconds)",
         ),
+        (ValidatorRejected, "validator rejected the new value: {0}"),
+        (
+            AtomHooksNeedDirectCall,
+            "{0}: atoms with watches or a validator must be updated by calling ({0} ...) directly",
+        ),
+        (NoTransaction, "{0}: no transaction running (call inside dosync)"),
+        (
+            TransactionRetryLimit,
+            "dosync: transaction retried {0} times without committing",
+        ),
         // その他のエラー
         (UnsupportedNumberType, "unsupported number type"),
         (
@@ -208,6 +242,8 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (CannotConvertToInt, "{0}: cannot convert {1} to integer"),
         (CannotParseAsFloat, "{0}: cannot parse '{1}' as float"),
         (CannotConvertToFloat, "{0}: cannot convert {1} to float"),
+        (CannotParseAsDecimal, "{0}: cannot parse '{1}' as decimal"),
+        (CannotConvertToDecimal, "{0}: cannot convert {1} to decimal"),
         (FloatOutOfI64Range, "{0}: float value {1} is out of i64 range (-9223372036854775808 to 9223372036854775807)"),
         (FloatIsNanOrInfinity, "{0}: cannot convert NaN or Infinity to integer"),
         (CannotConvertToJson, "Cannot convert {0} to JSON"),
@@ -223,8 +259,35 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (InvalidAlgorithm, "{0}: invalid algorithm '{1}' (supported: {2})"),
         (InvalidFloat, "{0}: invalid float value"),
         (InvalidNumber, "{0}: invalid number value"),
+        (JwtInvalidKey, "{0}: invalid key: {1}"),
+        (JwtInvalidOption, "{0}: invalid {1} option: {2}"),
+        (JwtJwksLoadFailed, "{0}: failed to load JWKS from {1}: {2}"),
+        (JwtKeyNotFound, "no key in JWKS matches kid '{0}'"),
+        (JwtMissingClaim, "missing required claim: {0}"),
         // パスワードハッシュエラー
         (PasswordHashError, "{0}: password hash error: {1}"),
+        // 暗号エラー
+        (
+            CryptoUnsupportedAlgorithm,
+            "{0}: unsupported algorithm: {1} (supported: {2})",
+        ),
+        (CryptoInvalidKeyLength, "{0}: key must be {1} bytes (got {2})"),
+        (
+            CryptoOperationFailed,
+            "{0}: operation failed (wrong key, wrong options or tampered data)",
+        ),
+        (CryptoInvalidParameter, "{0}: invalid {1}: {2}"),
+        // データフォーマットエラー
+        (FormatParseError, "{0}: parse error: {1}"),
+        (FormatEncodeError, "{0}: cannot encode: {1}"),
+        (XmlInvalidElement, "{0}: invalid element: {1}"),
+        (JsonLineError, "{0}: line {1}: {2}"),
+        (JsonInvalidOption, "{0}: invalid {1} option: {2}"),
+        (
+            JsonPathInvalid,
+            "{0}: invalid JSONPath '{1}' at position {2}: {3}",
+        ),
+        (JsonSchemaInvalid, "{0}: invalid schema at {1}: {2}"),
         // 警告
         (
             RedefineBuiltin,
@@ -239,7 +302,7 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             CsvRecordMustBeList,
             "csv/stringify: each record must be a list",
         ),
-        (CsvParseNeed1Or3Args, "csv/parse requires 1 or 3 arguments"),
+        (CsvParseNeed1To3Args, "csv/parse requires 1 to 3 arguments"),
         (
             CsvDelimiterMustBeSingleChar,
             "csv/parse: delimiter must be a single character",
@@ -248,6 +311,12 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             CsvInvalidDelimiterArg,
             "csv/parse: invalid delimiter argument (use :delimiter \"char\")",
         ),
+        (CsvInvalidOption, "{0}: invalid {1} option: {2}"),
+        (CsvLineError, "{0}: line {1}: {2}"),
+        (CsvUnterminatedQuote, "unterminated quoted field"),
+        (CsvUnexpectedQuote, "unexpected quote character"),
+        (CsvFieldCount, "expected {0} fields, found {1}"),
+        (CsvTypeConversion, "column {0}: cannot convert '{1}' to {2}"),
         // コマンド実行エラー
         (CmdEmptyCommand, "Command cannot be empty"),
         (
@@ -288,6 +357,33 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             AssertExpectedException,
             "Assertion failed: expected exception but none was thrown",
         ),
+        // バリデーションエラー
+        (ValidateRequired, "required field"),
+        (ValidateTypeMismatch, "must be {0}"),
+        (ValidateMinLength, "must be at least {0} characters"),
+        (ValidateMaxLength, "must be at most {0} characters"),
+        (ValidatePattern, "does not match pattern: {0}"),
+        (ValidateMinValue, "must be at least {0}"),
+        (ValidateMaxValue, "must be at most {0}"),
+        (ValidateMinItems, "must have at least {0} items"),
+        (ValidateMaxItems, "must have at most {0} items"),
+        (ValidateEnum, "must be one of: {0}"),
+        (ValidateInvalidJson, "must be valid JSON: {0}"),
+        (ValidateExclusiveMin, "must be greater than {0}"),
+        (ValidateExclusiveMax, "must be less than {0}"),
+        (ValidateMultipleOf, "must be a multiple of {0}"),
+        (ValidateConst, "must be equal to {0}"),
+        (ValidateMinProperties, "must have at least {0} properties"),
+        (ValidateMaxProperties, "must have at most {0} properties"),
+        (ValidateAdditionalProperty, "additional property is not allowed"),
+        (ValidateUniqueItems, "duplicate of an earlier item"),
+        (ValidateDependentRequired, "required when '{0}' is present"),
+        (ValidateMinContains, "must contain at least {0} matching items"),
+        (ValidateMaxContains, "must contain at most {0} matching items"),
+        (ValidateAnyOf, "must match at least one schema in anyOf"),
+        (ValidateOneOf, "must match exactly one schema in oneOf ({0} matched)"),
+        (ValidateNot, "must not match the schema in not"),
+        (ValidateFalseSchema, "no value is allowed here"),
         // パスエラー
         (AllPathsMustBeStrings, "{0}: all paths must be strings"),
         // サーバーエラー
@@ -459,6 +555,39 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             ServerFailedToCreateRuntime,
             "Failed to create Tokio runtime: {0}",
         ),
+        (ServerInvalidRoute, "server/router: invalid route {0}: {1}"),
+        (
+            ServerInvalidRouteConstraint,
+            "server/router: invalid parameter constraint in {0}: {1}",
+        ),
+        (ServerDuplicateRouteName, "server/router: duplicate route name: {0}"),
+        (ServerRouterNotFound, "Router not found: {0}"),
+        (ServerRouteNotFound, "server/url-for: no route named {0}"),
+        (
+            ServerMissingRouteParam,
+            "server/url-for: missing parameter '{0}' for route {1}",
+        ),
+        (
+            ServerRouteParamMismatch,
+            "server/url-for: parameter '{0}' does not satisfy <{1}>: {2}",
+        ),
+        (ServerFailedToBind, "Failed to bind {0}: {1}"),
+        (
+            ServerNotRunning,
+            "{0}: server not found or already stopped: {1}",
+        ),
+        (
+            ServerInvalidOnShutdown,
+            "server/serve: :on-shutdown must be a function or a vector of functions",
+        ),
+        (
+            ServerInvalidTestRequest,
+            "server/test-request: invalid request: {0}",
+        ),
+        (
+            ServerOpenApiResponseMismatch,
+            "server/with-openapi: {0} {1} response does not match the schema: {2}",
+        ),
         // データベース汎用エラー（PostgreSQL/MySQL/SQLite共通）
         (DbFailedToConnect, "Failed to connect to database: {0}"),
         (DbFailedToExecuteQuery, "Failed to execute query: {0}"),
@@ -786,6 +915,7 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             "Failed to connect to WebSocket server: {0}",
         ),
         (WsConnectionNotFound, "WebSocket connection not found"),
+        (WsInvalidOption, "{0}: invalid {1} option: {2}"),
 
         // HTTP詳細エラー
         (HttpUnexpectedErrorFormat, "Unexpected error format"),
@@ -793,6 +923,21 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (HttpMissingBody, "Missing body in response"),
         (HttpErrorWithBody, "HTTP error {0}: {1}"),
         (HttpUnexpectedResponse, "Unexpected response format"),
+        (HttpInvalidRetry, "{0}: invalid :retry option: {1}"),
+        (
+            HttpInvalidCircuitBreaker,
+            "{0}: invalid :circuit-breaker option: {1}",
+        ),
+        (
+            HttpCircuitOpen,
+            "Circuit breaker is open for {0} (retry in {1}ms)",
+        ),
+        (HttpInvalidClientOption, "http/client: invalid {0} option: {1}"),
+        (
+            HttpClientNotFound,
+            "{0}: HTTP client not found or already closed: {1}",
+        ),
+        (HttpInvalidBodyOption, "{0}: invalid {1} option: {2}"),
 
         // 静的ファイルエラー
         (StaticFileInvalidPath, "Invalid path: {0}"),
@@ -865,6 +1010,18 @@ pub static EN_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             ":only clause must contain symbols, got: {0}",
         ),
         (InvalidUseMode, "Invalid use mode: {0}"),
+        (
+            DynamicVarMustBeSymbol,
+            "Dynamic variable name must be a symbol, got: {0}",
+        ),
+        (
+            BindingMustBeVector,
+            "binding requires a vector of name/value pairs: [name value ...]",
+        ),
+        (
+            NotDynamicVar,
+            "{0} is not a dynamic variable (declare it with defdynamic)",
+        ),
     ])
 });
 
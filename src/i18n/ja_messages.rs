@@ -14,6 +14,7 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (NeedsSymbol, "{0}にはシンボルが必要です"),
         (VarargNeedsName, "&の後には変数名が必要です"),
         (UnexpectedPattern, "予期しないパターン: {0}"),
+        (SetPatternMustBeLiteral, "セットパターンの要素はリテラル（nil、bool、整数、文字列、文字、キーワード）である必要があります"),
         (RestNeedsVar, "...の後には変数名が必要です"),
         // レキサーエラー
         (UnexpectedChar, "予期しない文字: {0}"),
@@ -23,6 +24,7 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             EmptyKeyword,
             "空のキーワード: ':' の後には識別子が必要です",
         ),
+        (InvalidCharLiteral, "不正な文字リテラル: {0}"),
         // 評価器エラー
         (UndefinedVar, "未定義の変数: {0}"),
         (
@@ -38,6 +40,7 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (DivisionByZero, "ゼロ除算エラー"),
         (IntegerOverflow, "{0}演算で整数オーバーフローが発生しました"),
         (IntegerUnderflow, "{0}演算で整数アンダーフローが発生しました"),
+        (DecimalOverflow, "{0}演算で10進数のオーバーフローが発生しました"),
         (
             ExportOnlyInModule,
             "exportはmodule定義の中でのみ使用できます",
@@ -67,6 +70,11 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             "シンボル{0}はモジュール{1}からエクスポートされていません",
         ),
         (FileNotFound, "ファイルが見つかりません: {0} ({1})"),
+        (NsNeedsName, "nsには名前空間名が必要です"),
+        (
+            InvalidNsClause,
+            "ns {0}: 無効な節です（(:export sym ...) が必要です）",
+        ),
         // 引数エラー
         (
             NeedAtLeastNArgs,
@@ -126,6 +134,11 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         // 特殊な引数エラー
         (SplitTwoStrings, "splitは2つの文字列が必要です"),
         (JoinStringAndList, "joinは文字列とリストが必要です"),
+        (InvalidCodePoint, "{0}: 不正なコードポイントです: {1}"),
+        (
+            InvalidNormalizationForm,
+            "{0}: 不明な正規化形式です: {1}（:nfc、:nfd、:nfkc、:nfkd のいずれか）",
+        ),
         (
             AssocMapAndKeyValues,
             "assocはマップと1つ以上のキー・値のペアが必要です",
@@ -157,6 +170,14 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             MacVariadicArgCountMismatch,
             "mac {0}: 引数の数が不足しています（最低: {1}, 実際: {2}）",
         ),
+        (
+            MacParamDestructureFailed,
+            "mac {0}: 引数を分解できません: {1}",
+        ),
+        (
+            GensymPrefixMustBeString,
+            "gensym: プレフィックスは文字列またはシンボルである必要があります",
+        ),
         // quasiquote エラー
         (
             UnquoteOutsideQuasiquote,
@@ -220,6 +241,9 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (SelectCaseMustBe, "{0}: ケースはリストである必要があります [channel handler] or [:timeout ms handler]"),
         (AllElementsMustBe, "{0}: 全ての要素は{1}である必要があります"),
         (SetOperationError, "{0}"),
+        (SetElementNotHashable, "{0}はセットに入れられません（浮動小数点数・関数などハッシュ化できない値は使用不可）"),
+        (TransducerNotCallable, "トランスデューサは直接呼び出せません（transduce・into・sequenceで使用してください）"),
+        (MapEntryMustBePair, "{0}: マップの要素は[キー 値]のペアである必要があります"),
         // 並行処理エラー
         (ChannelClosed, "{0}: チャネルは閉じられています"),
         (ExpectedKeyword, "{0}: {1}キーワードが必要です"),
@@ -228,6 +252,16 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (UnexpectedError, "{0}: 予期しないエラー"),
         (RecvArgs, "{0}: 1または3個の引数が必要です: ({0} ch) or ({0} ch :timeout ms)"),
         (TimeoutMustBeMs, "{0}: タイムアウトは整数（ミリ秒）である必要があります"),
+        (ValidatorRejected, "バリデータが新しい値を拒否しました: {0}"),
+        (
+            AtomHooksNeedDirectCall,
+            "{0}: ウォッチまたはバリデータを持つアトムは ({0} ...) として直接呼び出す必要があります",
+        ),
+        (NoTransaction, "{0}: トランザクション外です（dosync内で呼び出してください）"),
+        (
+            TransactionRetryLimit,
+            "dosync: トランザクションが{0}回リトライしてもコミットできませんでした",
+        ),
         // その他のエラー
         (UnsupportedNumberType, "サポートされていない数値型です"),
         (RailwayRequiresOkError, "|>? には {:ok/:error} マップが必要です"),
@@ -241,6 +275,8 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (CannotConvertToInt, "{0}: {1}を整数に変換できません"),
         (CannotParseAsFloat, "{0}: '{1}'を浮動小数点数としてパースできません"),
         (CannotConvertToFloat, "{0}: {1}を浮動小数点数に変換できません"),
+        (CannotParseAsDecimal, "{0}: '{1}'を10進数としてパースできません"),
+        (CannotConvertToDecimal, "{0}: {1}を10進数に変換できません"),
         (FloatOutOfI64Range, "{0}: 浮動小数点数値{1}はi64の範囲外です（-9223372036854775808から9223372036854775807）"),
         (FloatIsNanOrInfinity, "{0}: NaNまたは無限大を整数に変換できません"),
         (CannotConvertToJson, "{0}をJSONに変換できません"),
@@ -256,8 +292,26 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (InvalidAlgorithm, "{0}: 不正なアルゴリズム'{1}'（サポート: {2}）"),
         (InvalidFloat, "{0}: 不正な浮動小数点数値です"),
         (InvalidNumber, "{0}: 不正な数値です"),
+        (JwtInvalidKey, "{0}: 不正な鍵です: {1}"),
+        (JwtInvalidOption, "{0}: 不正な{1}オプションです: {2}"),
+        (JwtJwksLoadFailed, "{0}: {1} からJWKSを読み込めませんでした: {2}"),
+        (JwtKeyNotFound, "kid '{0}' に一致する鍵がJWKSにありません"),
+        (JwtMissingClaim, "必須クレームがありません: {0}"),
         // パスワードハッシュエラー
         (PasswordHashError, "{0}: パスワードハッシュエラー: {1}"),
+        // 暗号エラー
+        (CryptoUnsupportedAlgorithm, "{0}: 未対応のアルゴリズムです: {1}（対応: {2}）"),
+        (CryptoInvalidKeyLength, "{0}: 鍵は{1}バイトである必要があります（実際: {2}バイト）"),
+        (CryptoOperationFailed, "{0}: 処理に失敗しました（鍵・オプションの誤り、またはデータの改ざん）"),
+        (CryptoInvalidParameter, "{0}: {1}が不正です: {2}"),
+        // データフォーマットエラー
+        (FormatParseError, "{0}: パースエラー: {1}"),
+        (FormatEncodeError, "{0}: エンコードできません: {1}"),
+        (XmlInvalidElement, "{0}: 不正な要素です: {1}"),
+        (JsonLineError, "{0}: {1}行目: {2}"),
+        (JsonInvalidOption, "{0}: 不正な{1}オプションです: {2}"),
+        (JsonPathInvalid, "{0}: 不正なJSONPath '{1}'（{2}文字目）: {3}"),
+        (JsonSchemaInvalid, "{0}: {1} のスキーマが不正です: {2}"),
         // 警告
         (RedefineBuiltin, "警告: ビルトイン関数'{0}'を再定義しています ({1})"),
         (RedefineFunction, "警告: 関数'{0}'を再定義しています"),
@@ -266,9 +320,15 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (FileReadError, "{0}: ファイル読み込みエラー: {1}"),
         (CsvCannotSerialize, "csv/stringify: {0}をシリアライズできません"),
         (CsvRecordMustBeList, "csv/stringify: 各レコードはリストである必要があります"),
-        (CsvParseNeed1Or3Args, "csv/parseには1または3個の引数が必要です"),
+        (CsvParseNeed1To3Args, "csv/parseには1〜3個の引数が必要です"),
         (CsvDelimiterMustBeSingleChar, "csv/parse: デリミタは1文字である必要があります"),
         (CsvInvalidDelimiterArg, "csv/parse: 不正なデリミタ引数です (:delimiter \"char\" を使用してください)"),
+        (CsvInvalidOption, "{0}: 不正な{1}オプションです: {2}"),
+        (CsvLineError, "{0}: {1}行目: {2}"),
+        (CsvUnterminatedQuote, "クォートが閉じられていません"),
+        (CsvUnexpectedQuote, "予期しないクォート文字です"),
+        (CsvFieldCount, "{0}個のフィールドが必要ですが{1}個です"),
+        (CsvTypeConversion, "列{0}: '{1}'を{2}に変換できません"),
         // コマンド実行エラー
         (CmdEmptyCommand, "コマンドを空にすることはできません"),
         (CmdFirstArgMustBeString, "コマンドリストの最初の要素は文字列である必要があります"),
@@ -291,6 +351,33 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         // テストエラー
         (TestsFailed, "一部のテストが失敗しました"),
         (AssertExpectedException, "アサーション失敗: 例外が期待されましたがスローされませんでした"),
+        // バリデーションエラー
+        (ValidateRequired, "必須フィールドです"),
+        (ValidateTypeMismatch, "{0}である必要があります"),
+        (ValidateMinLength, "{0}文字以上である必要があります"),
+        (ValidateMaxLength, "{0}文字以下である必要があります"),
+        (ValidatePattern, "パターンに一致しません: {0}"),
+        (ValidateMinValue, "{0}以上である必要があります"),
+        (ValidateMaxValue, "{0}以下である必要があります"),
+        (ValidateMinItems, "{0}個以上である必要があります"),
+        (ValidateMaxItems, "{0}個以下である必要があります"),
+        (ValidateEnum, "{0}のいずれかである必要があります"),
+        (ValidateInvalidJson, "正しいJSONである必要があります: {0}"),
+        (ValidateExclusiveMin, "{0}より大きい必要があります"),
+        (ValidateExclusiveMax, "{0}より小さい必要があります"),
+        (ValidateMultipleOf, "{0}の倍数である必要があります"),
+        (ValidateConst, "{0}と等しい必要があります"),
+        (ValidateMinProperties, "プロパティが{0}個以上必要です"),
+        (ValidateMaxProperties, "プロパティは{0}個以下である必要があります"),
+        (ValidateAdditionalProperty, "追加のプロパティは許可されていません"),
+        (ValidateUniqueItems, "前の要素と重複しています"),
+        (ValidateDependentRequired, "'{0}'がある場合は必須です"),
+        (ValidateMinContains, "条件に合う要素が{0}個以上必要です"),
+        (ValidateMaxContains, "条件に合う要素は{0}個以下である必要があります"),
+        (ValidateAnyOf, "anyOfのいずれかのスキーマに一致する必要があります"),
+        (ValidateOneOf, "oneOfのスキーマにちょうど1つ一致する必要があります（{0}個一致）"),
+        (ValidateNot, "notのスキーマに一致してはいけません"),
+        (ValidateFalseSchema, "ここには値を置けません"),
         // パスエラー
         (AllPathsMustBeStrings, "{0}: すべてのパスは文字列である必要があります"),
         // サーバーエラー
@@ -360,6 +447,18 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (ServerStaticFileFailedToRead, "server/static-file: ファイル読み込み失敗: {0}"),
         (ServerStaticDirNotDirectory, "server/static-dir: {0}はディレクトリではありません"),
         (ServerFailedToCreateRuntime, "Tokioランタイム作成失敗: {0}"),
+        (ServerInvalidRoute, "server/router: 不正なルート定義 {0}: {1}"),
+        (ServerInvalidRouteConstraint, "server/router: {0}のパラメータ制約が不正です: {1}"),
+        (ServerDuplicateRouteName, "server/router: ルート名が重複しています: {0}"),
+        (ServerRouterNotFound, "ルーターが見つかりません: {0}"),
+        (ServerRouteNotFound, "server/url-for: ルート名{0}が見つかりません"),
+        (ServerMissingRouteParam, "server/url-for: ルート{1}のパラメータ'{0}'が指定されていません"),
+        (ServerRouteParamMismatch, "server/url-for: パラメータ'{0}'が制約<{1}>を満たしません: {2}"),
+        (ServerFailedToBind, "{0}へのバインドに失敗しました: {1}"),
+        (ServerNotRunning, "{0}: サーバーが見つからないか、既に停止しています: {1}"),
+        (ServerInvalidOnShutdown, "server/serve: :on-shutdownは関数または関数のベクタである必要があります"),
+        (ServerInvalidTestRequest, "server/test-request: 不正なリクエストです: {0}"),
+        (ServerOpenApiResponseMismatch, "server/with-openapi: {0} {1} のレスポンスがスキーマと一致しません: {2}"),
         // データベース汎用エラー（PostgreSQL/MySQL/SQLite共通）
         (DbFailedToConnect, "データベース接続失敗: {0}"),
         (DbFailedToExecuteQuery, "クエリ実行失敗: {0}"),
@@ -576,6 +675,7 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             "WebSocketサーバーへの接続に失敗しました: {0}",
         ),
         (WsConnectionNotFound, "WebSocket接続が見つかりません"),
+        (WsInvalidOption, "{0}: 不正な{1}オプションです: {2}"),
 
         // HTTP詳細エラー
         (HttpUnexpectedErrorFormat, "予期しないエラー形式"),
@@ -583,6 +683,12 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
         (HttpMissingBody, "レスポンスにボディがありません"),
         (HttpErrorWithBody, "HTTPエラー {0}: {1}"),
         (HttpUnexpectedResponse, "予期しないレスポンス形式"),
+        (HttpInvalidRetry, "{0}: :retryオプションが不正です: {1}"),
+        (HttpInvalidCircuitBreaker, "{0}: :circuit-breakerオプションが不正です: {1}"),
+        (HttpCircuitOpen, "{0}へのサーキットブレーカーが開いています（{1}ms後に再試行可能）"),
+        (HttpInvalidClientOption, "http/client: {0}オプションが不正です: {1}"),
+        (HttpClientNotFound, "{0}: HTTPクライアントが見つからないか、既に閉じられています: {1}"),
+        (HttpInvalidBodyOption, "{0}: {1}オプションが不正です: {2}"),
 
         // 静的ファイルエラー
         (StaticFileInvalidPath, "無効なパス: {0}"),
@@ -667,6 +773,18 @@ pub static JA_MSGS: LazyLock<HashMap<MsgKey, &'static str>> = LazyLock::new(|| {
             ":only節にはシンボルが必要です: {0}",
         ),
         (InvalidUseMode, "無効なuseモード: {0}"),
+        (
+            DynamicVarMustBeSymbol,
+            "動的変数名はシンボルである必要があります: {0}",
+        ),
+        (
+            BindingMustBeVector,
+            "bindingには名前と値のペアのベクタが必要です: [name value ...]",
+        ),
+        (
+            NotDynamicVar,
+            "{0}は動的変数ではありません（defdynamicで宣言してください）",
+        ),
     ])
 });
 
@@ -8,6 +8,7 @@ pub enum MsgKey {
     NeedsSymbol, // 共通化: Def/Let/Fn等で使用
     VarargNeedsName,
     UnexpectedPattern,
+    SetPatternMustBeLiteral,
     RestNeedsVar, // ...rest の後に変数名が必要
 
     // レキサーエラー
@@ -15,6 +16,7 @@ pub enum MsgKey {
     UnclosedString,
     NumberLiteralInvalid, // invalid number literal: {0}
     EmptyKeyword,         // empty keyword (: must be followed by identifier)
+    InvalidCharLiteral,   // invalid character literal: {0}
 
     // 評価器エラー
     UndefinedVar,
@@ -25,6 +27,7 @@ pub enum MsgKey {
     DivisionByZero,
     IntegerOverflow,  // integer overflow in {0} operation
     IntegerUnderflow, // integer underflow in {1} operation
+    DecimalOverflow,  // decimal overflow in {0} operation
     ExportOnlyInModule,
     CannotQuote, // 統合: CannotQuoteとCannotQuoteSpecialForm
     NoMatchingPattern,
@@ -39,7 +42,9 @@ pub enum MsgKey {
     SymbolNotFound,
     ModuleNotFound,
     SymbolNotExported,
-    FileNotFound, // file not found: {0} ({1})
+    FileNotFound,    // file not found: {0} ({1})
+    NsNeedsName,     // ns requires a namespace name
+    InvalidNsClause, // ns {0}: invalid clause (expected (:export sym ...))
 
     // 引数エラー（汎用）
     NeedAtLeastNArgs, // {0}には少なくとも{1}個の引数が必要
@@ -78,6 +83,8 @@ pub enum MsgKey {
     // 特殊な引数エラー
     SplitTwoStrings,
     JoinStringAndList,
+    InvalidCodePoint,         // {0}: invalid code point {1}
+    InvalidNormalizationForm, // {0}: unknown normalization form {1}
     AssocMapAndKeyValues,
     DissocMapAndKeys,
     VariadicFnNeedsOneParam,
@@ -93,6 +100,8 @@ pub enum MsgKey {
     VariadicMacroNeedsParams,
     MacArgCountMismatch, // mac {0}: 引数の数が一致しません（期待: {1}, 実際: {2}）
     MacVariadicArgCountMismatch, // mac {0}: 引数の数が不足しています（最低: {1}, 実際: {2}）
+    MacParamDestructureFailed, // mac {0}: 引数を分解できません: {1}
+    GensymPrefixMustBeString, // gensym: プレフィックスは文字列またはシンボルである必要があります
 
     // quasiquote エラー
     UnquoteOutsideQuasiquote,
@@ -125,15 +134,22 @@ pub enum MsgKey {
     SelectCaseMustBe, // {0}: ケースはリストである必要があります [channel handler] or [:timeout ms handler]
     AllElementsMustBe, // {0}: 全ての要素は{1}である必要があります
     SetOperationError, // {0}: set operation error
+    SetElementNotHashable, // {0}: type name
+    TransducerNotCallable, // トランスデューサを直接呼び出した
+    MapEntryMustBePair, // {0}: 関数名
 
     // 並行処理エラー
-    ChannelClosed,   // {0}: channel is closed
-    ExpectedKeyword, // {0}: expected {1} keyword
-    PromiseFailed,   // promise failed
-    NotAPromise,     // not a promise
-    UnexpectedError, // {0}: unexpected error
-    RecvArgs,        // {0}: requires 1 or 3 arguments: ({0} ch) or ({0} ch :timeout ms)
-    TimeoutMustBeMs, // {0}: timeout must be an integer (milliseconds)
+    ChannelClosed,           // {0}: channel is closed
+    ExpectedKeyword,         // {0}: expected {1} keyword
+    PromiseFailed,           // promise failed
+    NotAPromise,             // not a promise
+    UnexpectedError,         // {0}: unexpected error
+    RecvArgs,                // {0}: requires 1 or 3 arguments: ({0} ch) or ({0} ch :timeout ms)
+    TimeoutMustBeMs,         // {0}: timeout must be an integer (milliseconds)
+    ValidatorRejected,       // validator rejected the new value: {0}
+    AtomHooksNeedDirectCall, // {0}: atoms with watches or a validator must be updated by calling ({0} ...) directly
+    NoTransaction,           // {0}: no transaction running (call inside dosync)
+    TransactionRetryLimit,   // dosync: transaction retried {0} times without committing
 
     // その他のエラー
     UnsupportedNumberType,   // unsupported number type
@@ -148,6 +164,8 @@ pub enum MsgKey {
     CannotConvertToInt,      // {0}: cannot convert {1} to integer
     CannotParseAsFloat,      // {0}: cannot parse '{1}' as float
     CannotConvertToFloat,    // {0}: cannot convert {1} to float
+    CannotParseAsDecimal,    // {0}: cannot parse '{1}' as decimal
+    CannotConvertToDecimal,  // {0}: cannot convert {1} to decimal
     FloatOutOfI64Range,      // {0}: float value {1} is out of i64 range
     FloatIsNanOrInfinity,    // {0}: cannot convert NaN or Infinity to integer
     CannotConvertToJson,     // Cannot convert {0} to JSON
@@ -160,14 +178,34 @@ pub enum MsgKey {
     IntegerTooLargeForUsize, // {0}: integer {1} is too large for usize
 
     // JWT エラー
-    NeedNArgs,        // {0} requires {1} arguments
-    InvalidAlgorithm, // {0}: invalid algorithm: {1} (supported: {2})
-    InvalidFloat,     // {0}: invalid float value
-    InvalidNumber,    // {0}: invalid number value
+    NeedNArgs,         // {0} requires {1} arguments
+    InvalidAlgorithm,  // {0}: invalid algorithm: {1} (supported: {2})
+    InvalidFloat,      // {0}: invalid float value
+    InvalidNumber,     // {0}: invalid number value
+    JwtInvalidKey,     // {0}: invalid key: {1}
+    JwtInvalidOption,  // {0}: invalid {1} option: {2}
+    JwtJwksLoadFailed, // {0}: failed to load JWKS from {1}: {2}
+    JwtKeyNotFound,    // no key in JWKS matches kid '{0}'
+    JwtMissingClaim,   // missing required claim: {0}
 
     // パスワードハッシュエラー
     PasswordHashError, // {0}: password hash error: {1}
 
+    // 暗号エラー
+    CryptoUnsupportedAlgorithm, // {0}: unsupported algorithm: {1} (supported: {2})
+    CryptoInvalidKeyLength,     // {0}: key must be {1} bytes (got {2})
+    CryptoOperationFailed,      // {0}: operation failed (wrong key, wrong options or tampered data)
+    CryptoInvalidParameter,     // {0}: invalid {1}: {2}
+
+    // データフォーマットエラー（toml/xml/msgpack/cbor）
+    FormatParseError,  // {0}: parse error: {1}
+    FormatEncodeError, // {0}: cannot encode: {1}
+    XmlInvalidElement, // {0}: invalid element: {1}
+    JsonLineError,     // {0}: line {1}: {2}
+    JsonInvalidOption, // {0}: invalid {1} option: {2}
+    JsonPathInvalid,   // {0}: invalid JSONPath '{1}' at position {2}: {3}
+    JsonSchemaInvalid, // {0}: invalid schema at {1}: {2}
+
     // 警告
     RedefineBuiltin,  // warning: redefining builtin function: {0} ({1})
     RedefineFunction, // warning: redefining function: {0}
@@ -177,9 +215,15 @@ pub enum MsgKey {
     FileReadError,                // {0}: file read error: {1}
     CsvCannotSerialize,           // csv/stringify: cannot serialize {0}
     CsvRecordMustBeList,          // csv/stringify: each record must be a list
-    CsvParseNeed1Or3Args,         // csv/parse requires 1 or 3 arguments
+    CsvParseNeed1To3Args,         // csv/parse requires 1 to 3 arguments
     CsvDelimiterMustBeSingleChar, // csv/parse: delimiter must be a single character
     CsvInvalidDelimiterArg,       // csv/parse: invalid delimiter argument (use :delimiter "char")
+    CsvInvalidOption,             // {0}: invalid {1} option: {2}
+    CsvLineError,                 // {0}: line {1}: {2}
+    CsvUnterminatedQuote,         // unterminated quoted field
+    CsvUnexpectedQuote,           // unexpected quote character
+    CsvFieldCount,                // expected {0} fields, found {1}
+    CsvTypeConversion,            // column {0}: cannot convert '{1}' to {2}
 
     // Table エラー
     TableInvalidFormat,         // table: invalid format (expected {0})
@@ -219,6 +263,34 @@ pub enum MsgKey {
     TestsFailed,             // Some tests failed
     AssertExpectedException, // Assertion failed: expected exception but none was thrown
 
+    // バリデーションエラー
+    ValidateRequired,           // required field
+    ValidateTypeMismatch,       // must be {0}
+    ValidateMinLength,          // must be at least {0} characters
+    ValidateMaxLength,          // must be at most {0} characters
+    ValidatePattern,            // does not match pattern: {0}
+    ValidateMinValue,           // must be at least {0}
+    ValidateMaxValue,           // must be at most {0}
+    ValidateMinItems,           // must have at least {0} items
+    ValidateMaxItems,           // must have at most {0} items
+    ValidateEnum,               // must be one of: {0}
+    ValidateInvalidJson,        // must be valid JSON: {0}
+    ValidateExclusiveMin,       // must be greater than {0}
+    ValidateExclusiveMax,       // must be less than {0}
+    ValidateMultipleOf,         // must be a multiple of {0}
+    ValidateConst,              // must be equal to {0}
+    ValidateMinProperties,      // must have at least {0} properties
+    ValidateMaxProperties,      // must have at most {0} properties
+    ValidateAdditionalProperty, // additional property is not allowed
+    ValidateUniqueItems,        // duplicate of an earlier item
+    ValidateDependentRequired,  // required when '{0}' is present
+    ValidateMinContains,        // must contain at least {0} matching items
+    ValidateMaxContains,        // must contain at most {0} matching items
+    ValidateAnyOf,              // must match at least one schema in anyOf
+    ValidateOneOf,              // must match exactly one schema in oneOf ({0} matched)
+    ValidateNot,                // must not match the schema in not
+    ValidateFalseSchema,        // no value is allowed here
+
     // パスエラー
     AllPathsMustBeStrings, // {0}: all paths must be strings
 
@@ -294,6 +366,18 @@ pub enum MsgKey {
     ServerStaticFileFailedToRead, // server/static-file: failed to read file: {0}
     ServerStaticDirNotDirectory, // server/static-dir: {0} is not a directory
     ServerFailedToCreateRuntime, // Failed to create Tokio runtime: {0}
+    ServerInvalidRoute,       // server/router: invalid route {0}: {1}
+    ServerInvalidRouteConstraint, // server/router: invalid parameter constraint in {0}: {1}
+    ServerDuplicateRouteName, // server/router: duplicate route name: {0}
+    ServerRouterNotFound,     // Router not found: {0}
+    ServerRouteNotFound,      // server/url-for: no route named {0}
+    ServerMissingRouteParam,  // server/url-for: missing parameter '{0}' for route {1}
+    ServerRouteParamMismatch, // server/url-for: parameter '{0}' does not satisfy <{1}>: {2}
+    ServerFailedToBind,       // Failed to bind {0}: {1}
+    ServerNotRunning,         // {0}: server not found or already stopped: {1}
+    ServerInvalidOnShutdown, // server/serve: :on-shutdown must be a function or a vector of functions
+    ServerInvalidTestRequest, // server/test-request: invalid request: {0}
+    ServerOpenApiResponseMismatch, // server/with-openapi: {0} {1} response does not match the schema: {2}
 
     // データベース汎用エラー（PostgreSQL/MySQL/SQLite共通）
     DbFailedToConnect,             // Failed to connect to database: {0}
@@ -491,6 +575,7 @@ pub enum MsgKey {
     WsConnectionAlreadyClosed, // WebSocket connection is already closed
     WsFailedToConnect,         // Failed to connect to WebSocket server: {0}
     WsConnectionNotFound,      // WebSocket connection not found
+    WsInvalidOption,           // {0}: invalid {1} option: {2}
 
     // HTTP詳細エラー
     HttpUnexpectedErrorFormat, // Unexpected error format
@@ -498,6 +583,12 @@ pub enum MsgKey {
     HttpMissingBody,           // Missing body in response
     HttpErrorWithBody,         // HTTP error {0}: {1}
     HttpUnexpectedResponse,    // Unexpected response format
+    HttpInvalidRetry,          // {0}: invalid :retry option: {1}
+    HttpInvalidCircuitBreaker, // {0}: invalid :circuit-breaker option: {1}
+    HttpCircuitOpen,           // Circuit breaker is open for {0} (retry in {1}ms)
+    HttpInvalidClientOption,   // http/client: invalid {0} option: {1}
+    HttpClientNotFound,        // {0}: HTTP client not found or already closed: {1}
+    HttpInvalidBodyOption,     // {0}: invalid {1} option: {2}
 
     // 静的ファイルエラー
     StaticFileInvalidPath,          // Invalid path: {0}
@@ -542,4 +633,7 @@ pub enum MsgKey {
     ExportMustBeSymbol,         // export argument must be a symbol, got: {0}
     UseOnlyMustBeSymbols,       // :only clause must contain symbols, got: {0}
     InvalidUseMode,             // Invalid use mode: {0}
+    DynamicVarMustBeSymbol,     // Dynamic variable name must be a symbol, got: {0}
+    BindingMustBeVector,        // binding requires a vector of name/value pairs
+    NotDynamicVar,              // {0} is not a dynamic variable (declare it with defdynamic)
 }
@@ -69,6 +69,10 @@ pub enum Token {
     // リテラル
     Integer(i64),
     Float(f64),
+    BigInt(std::sync::Arc<num_bigint::BigInt>), // i64に収まらない整数リテラル
+    Ratio(std::sync::Arc<num_rational::BigRational>), // 1/3
+    Decimal(rust_decimal::Decimal),             // 12.34M
+    Char(char),                                 // \a, \space, \u3042
     String(String),
     FString(Vec<FStringPart>), // f"hello {name}"
     Symbol(std::sync::Arc<str>),
@@ -78,12 +82,13 @@ pub enum Token {
     Nil,
 
     // 括弧
-    LParen,   // (
-    RParen,   // )
-    LBracket, // [
-    RBracket, // ]
-    LBrace,   // {
-    RBrace,   // }
+    LParen,    // (
+    RParen,    // )
+    LBracket,  // [
+    RBracket,  // ]
+    LBrace,    // {
+    RBrace,    // }
+    HashBrace, // #{（セットリテラル）
 
     // その他
     Quote,         // '
@@ -110,6 +115,10 @@ impl Token {
         match self {
             Token::Integer(n) => n.to_string(),
             Token::Float(f) => f.to_string(),
+            Token::BigInt(n) => n.to_string(),
+            Token::Ratio(r) => r.to_string(),
+            Token::Decimal(d) => format!("{}M", d),
+            Token::Char(c) => char_literal(*c),
             Token::String(s) => format!("\"{}\"", s),
             Token::FString(_) => "f-string".to_string(),
             Token::Symbol(s) => s.to_string(),
@@ -123,6 +132,7 @@ impl Token {
             Token::RBracket => "]".to_string(),
             Token::LBrace => "{".to_string(),
             Token::RBrace => "}".to_string(),
+            Token::HashBrace => "#{".to_string(),
             Token::Quote => "'".to_string(),
             Token::Backquote => "`".to_string(),
             Token::Unquote => ",".to_string(),
@@ -146,7 +156,12 @@ impl Token {
     pub fn source_length(&self) -> usize {
         match self {
             // リテラル: 表示名の長さ
-            Token::Integer(_) | Token::Float(_) => self.display_name().len(),
+            Token::Integer(_)
+            | Token::Float(_)
+            | Token::BigInt(_)
+            | Token::Ratio(_)
+            | Token::Decimal(_) => self.display_name().len(),
+            Token::Char(_) => self.display_name().chars().count(),
             Token::String(s) => s.len() + 2, // クォート含む
             Token::FString(_) => 8,          // "f-string" の推定長（正確には難しい）
             Token::Symbol(s) => s.len(),
@@ -166,15 +181,45 @@ impl Token {
             | Token::Unquote
             | Token::At
             | Token::Bar => 1,
-            Token::UnquoteSplice => 2,                         // ",@"
+            Token::UnquoteSplice | Token::HashBrace => 2, // ",@", "#{"
             Token::Arrow | Token::FatArrow | Token::Pipe => 2, // "->", "=>", "|>"
             Token::PipeRailway | Token::Ellipsis | Token::ParallelPipe => 3, // "|>?", "...", "||>"
-            Token::AsyncPipe => 2,                             // "~>"
-            Token::Eof => 3,                                   // "EOF"
+            Token::AsyncPipe => 2,                        // "~>"
+            Token::Eof => 3,                              // "EOF"
         }
     }
 }
 
+/// 名前付き文字リテラル（\space 等）
+pub const CHAR_NAMES: &[(&str, char)] = &[
+    ("space", ' '),
+    ("newline", '\n'),
+    ("tab", '\t'),
+    ("return", '\r'),
+    ("backspace", '\u{8}'),
+    ("formfeed", '\u{c}'),
+    ("nul", '\0'),
+];
+
+/// 文字をリテラル表記に変換（\a、\space、制御文字は \u000B）
+pub fn char_literal(c: char) -> String {
+    if let Some((name, _)) = CHAR_NAMES.iter().find(|(_, ch)| *ch == c) {
+        format!("\\{}", name)
+    } else if c.is_control() {
+        format!("\\u{:04X}", c as u32)
+    } else {
+        format!("\\{}", c)
+    }
+}
+
+/// 整数リテラルのトークン（i64に収まればInteger、それ以外はBigInt）
+fn integer_token(n: num_bigint::BigInt) -> Token {
+    match num_traits::ToPrimitive::to_i64(&n) {
+        Some(i) => Token::Integer(i),
+        None => Token::BigInt(std::sync::Arc::new(n)),
+    }
+}
+
 /// Qiソースコードのレキサー
 ///
 /// ソースコードをトークン列に分割します。
@@ -639,15 +684,53 @@ impl<'a> Lexer<'a> {
             }
         }
 
+        // 10進数リテラル: 12.34M, 100M
+        if self.current() == Some('M') {
+            self.advance();
+            let token = num_str.parse().map(Token::Decimal).map_err(|_| {
+                self.error(MsgKey::NumberLiteralInvalid, &[&format!("{}M", num_str)])
+            })?;
+            return Ok(LocatedToken::new(token, start_span));
+        }
+
+        // 有理数リテラル: 1/3（約分し、割り切れれば整数になる）
+        if !is_float
+            && self.current() == Some('/')
+            && self.peek(1).is_some_and(|c| c.is_ascii_digit())
+        {
+            self.advance();
+            let mut denom_str = String::new();
+            while let Some(ch) = self.current().filter(|c| c.is_ascii_digit()) {
+                denom_str.push(ch);
+                self.advance();
+            }
+            let literal = format!("{}/{}", num_str, denom_str);
+            let (numer, denom) = match (
+                num_str.parse::<num_bigint::BigInt>(),
+                denom_str.parse::<num_bigint::BigInt>(),
+            ) {
+                (Ok(n), Ok(d)) if !num_traits::Zero::is_zero(&d) => (n, d),
+                _ => return Err(self.error(MsgKey::NumberLiteralInvalid, &[&literal])),
+            };
+            let ratio = num_rational::BigRational::new(numer, denom);
+            let token = if ratio.is_integer() {
+                integer_token(ratio.to_integer())
+            } else {
+                Token::Ratio(std::sync::Arc::new(ratio))
+            };
+            return Ok(LocatedToken::new(token, start_span));
+        }
+
         let token = if is_float {
             num_str
                 .parse()
                 .map(Token::Float)
                 .map_err(|_| self.error(MsgKey::NumberLiteralInvalid, &[&num_str]))?
         } else {
+            // i64に収まらない整数は多倍長整数として読む
             num_str
-                .parse()
-                .map(Token::Integer)
+                .parse::<num_bigint::BigInt>()
+                .map(integer_token)
                 .map_err(|_| self.error(MsgKey::NumberLiteralInvalid, &[&num_str]))?
         };
         Ok(LocatedToken::new(token, start_span))
@@ -685,6 +768,11 @@ impl<'a> Lexer<'a> {
             {
                 result.push(ch);
                 self.advance();
+            } else if ch == '#' && !is_keyword && !result.is_empty() {
+                // auto-gensym（quasiquote内の foo#）: # はシンボル末尾にのみ置ける
+                result.push(ch);
+                self.advance();
+                break;
             } else {
                 break;
             }
@@ -711,6 +799,45 @@ impl<'a> Lexer<'a> {
         Ok(LocatedToken::new(token, start_span))
     }
 
+    /// 文字リテラルを読み取る: \a、\あ、\space、\u3042
+    fn read_char(&mut self, start_span: Span) -> Result<LocatedToken, String> {
+        self.advance(); // \
+        let first = match self.current() {
+            Some(ch) if !ch.is_whitespace() => ch,
+            _ => return Err(self.error(MsgKey::InvalidCharLiteral, &["\\"])),
+        };
+        self.advance();
+
+        // 英数字が続く場合は名前付き文字（\space）またはコードポイント（\u3042）
+        let mut name = String::from(first);
+        if first.is_alphanumeric() {
+            while let Some(ch) = self.current() {
+                if !ch.is_alphanumeric() {
+                    break;
+                }
+                name.push(ch);
+                self.advance();
+            }
+        }
+        if name.chars().count() == 1 {
+            return Ok(LocatedToken::new(Token::Char(first), start_span));
+        }
+
+        let ch = CHAR_NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, c)| *c)
+            .or_else(|| {
+                let hex = name.strip_prefix('u')?;
+                if hex.len() != 4 {
+                    return None;
+                }
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            })
+            .ok_or_else(|| self.error(MsgKey::InvalidCharLiteral, &[&format!("\\{}", name)]))?;
+        Ok(LocatedToken::new(Token::Char(ch), start_span))
+    }
+
     pub fn next_token(&mut self) -> Result<LocatedToken, String> {
         loop {
             self.skip_whitespace();
@@ -749,6 +876,11 @@ impl<'a> Lexer<'a> {
                     self.advance();
                     return Ok(LocatedToken::new(Token::RBrace, start_span));
                 }
+                Some('#') if self.peek(1) == Some('{') => {
+                    self.advance(); // #
+                    self.advance(); // {
+                    return Ok(LocatedToken::new(Token::HashBrace, start_span));
+                }
                 Some('\'') => {
                     self.advance();
                     return Ok(LocatedToken::new(Token::Quote, start_span));
@@ -779,6 +911,9 @@ impl<'a> Lexer<'a> {
                     let s = self.read_string()?;
                     return Ok(LocatedToken::new(Token::String(s), start_span));
                 }
+                Some('\\') => {
+                    return self.read_char(start_span);
+                }
                 Some('|') if self.peek(1) == Some('|') && self.peek(2) == Some('>') => {
                     self.advance(); // |
                     self.advance(); // |
@@ -921,6 +1056,40 @@ mod tests {
         assert_eq!(lexer.next_token().unwrap().token, Token::Float(-2.5));
     }
 
+    #[test]
+    fn test_exact_numbers() {
+        use num_rational::BigRational;
+        use std::sync::Arc;
+
+        let mut lexer = Lexer::new("12.34M 1/3 4/2 99999999999999999999 1/0");
+        assert_eq!(
+            lexer.next_token().unwrap().token,
+            Token::Decimal("12.34".parse().unwrap())
+        );
+        assert_eq!(
+            lexer.next_token().unwrap().token,
+            Token::Ratio(Arc::new(BigRational::new(1.into(), 3.into())))
+        );
+        // 割り切れる有理数は整数になる
+        assert_eq!(lexer.next_token().unwrap().token, Token::Integer(2));
+        assert_eq!(
+            lexer.next_token().unwrap().token,
+            Token::BigInt(Arc::new("99999999999999999999".parse().unwrap()))
+        );
+        assert!(lexer.next_token().is_err());
+    }
+
+    #[test]
+    fn test_char_literals() {
+        let mut lexer = Lexer::new(r"\a \あ \space \newline \u3042 \( \abc");
+        for expected in ['a', 'あ', ' ', '\n', 'あ', '('] {
+            assert_eq!(lexer.next_token().unwrap().token, Token::Char(expected));
+        }
+        assert!(lexer.next_token().is_err());
+        assert_eq!(char_literal(' '), r"\space");
+        assert_eq!(char_literal('x'), r"\x");
+    }
+
     #[test]
     fn test_strings() {
         let mut lexer = Lexer::new(r#""hello" "world\n""#);
@@ -1019,4 +1188,26 @@ Welcome!""""#;
             _ => panic!("Expected multiline f-string"),
         }
     }
+
+    #[test]
+    fn test_auto_gensym_symbol() {
+        // # はシンボル末尾にのみ付き、そこでトークンが終わる
+        let mut lexer = Lexer::new("v# foo");
+        assert_eq!(
+            lexer.next_token().unwrap().token,
+            Token::Symbol("v#".into())
+        );
+        assert_eq!(
+            lexer.next_token().unwrap().token,
+            Token::Symbol("foo".into())
+        );
+    }
+
+    #[test]
+    fn test_set_literal_token() {
+        let mut lexer = Lexer::new("#{1}");
+        assert_eq!(lexer.next_token().unwrap().token, Token::HashBrace);
+        assert_eq!(lexer.next_token().unwrap().token, Token::Integer(1));
+        assert_eq!(lexer.next_token().unwrap().token, Token::RBrace);
+    }
 }
@@ -1489,7 +1489,7 @@ fn save_session(evaluator: &Evaluator) {
                     "filter" | "reduce" | "range" | "reverse" | "sort" |
                     "print" | "println" | "read-line" | "slurp" | "spit" |
                     "type" | "eval" | "apply" | "partial" | "comp" | "identity" |
-                    "macroexpand" | "gensym" | "source" | "stdin"
+                    "macroexpand" | "macroexpand-all" | "gensym" | "source" | "stdin"
                 )
             })
             .map(|(name, val)| (name.to_string(), val.clone()))
@@ -313,6 +313,42 @@ impl<'a> Parser<'a> {
                 self.advance();
                 Ok(Expr::Float { value, span })
             }
+            Some(Token::BigInt(n)) => {
+                let value = n.clone();
+                let span = self
+                    .current_span()
+                    .copied()
+                    .ok_or_else(|| self.error_with_line(MsgKey::UnexpectedEof, &[]))?;
+                self.advance();
+                Ok(Expr::BigInt { value, span })
+            }
+            Some(Token::Ratio(r)) => {
+                let value = r.clone();
+                let span = self
+                    .current_span()
+                    .copied()
+                    .ok_or_else(|| self.error_with_line(MsgKey::UnexpectedEof, &[]))?;
+                self.advance();
+                Ok(Expr::Ratio { value, span })
+            }
+            Some(Token::Decimal(d)) => {
+                let value = *d;
+                let span = self
+                    .current_span()
+                    .copied()
+                    .ok_or_else(|| self.error_with_line(MsgKey::UnexpectedEof, &[]))?;
+                self.advance();
+                Ok(Expr::Decimal { value, span })
+            }
+            Some(Token::Char(c)) => {
+                let value = *c;
+                let span = self
+                    .current_span()
+                    .copied()
+                    .ok_or_else(|| self.error_with_line(MsgKey::UnexpectedEof, &[]))?;
+                self.advance();
+                Ok(Expr::Char { value, span })
+            }
             Some(Token::String(_)) => {
                 // SAFETY: current()がSomeであることを確認済み
                 let span = self
@@ -353,6 +389,7 @@ impl<'a> Parser<'a> {
             Some(Token::LParen) => self.parse_list(),
             Some(Token::LBracket) => self.parse_vector(),
             Some(Token::LBrace) => self.parse_map(),
+            Some(Token::HashBrace) => self.parse_set(),
             Some(Token::Quote) => self.parse_quote(),
             Some(Token::Backquote) => self.parse_quasiquote(),
             Some(Token::Unquote) => self.parse_unquote(),
@@ -994,6 +1031,23 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_set(&mut self) -> Result<Expr, String> {
+        let start_span = self.current_span().copied().unwrap_or(Expr::dummy_span());
+        self.expect(Token::HashBrace)?;
+
+        let mut items = Vec::with_capacity(8);
+        while self.current() != Some(&Token::RBrace) {
+            items.push(self.parse_expr()?);
+        }
+
+        self.expect(Token::RBrace)?;
+
+        Ok(Expr::Set {
+            items,
+            span: start_span,
+        })
+    }
+
     fn parse_quote(&mut self) -> Result<Expr, String> {
         let start_span = self.current_span().copied().unwrap_or(Expr::dummy_span());
         self.advance(); // 'をスキップ
@@ -1274,33 +1328,8 @@ impl<'a> Parser<'a> {
             _ => return Err(self.error_with_line(MsgKey::NeedsSymbol, &["mac"])),
         };
 
-        // パラメータリスト
-        self.expect(Token::LBracket)?;
-        // 4: マクロパラメータは通常2-4個（例: [name body]）
-        let mut params = Vec::with_capacity(4);
-        let mut is_variadic = false;
-
-        while self.current() != Some(&Token::RBracket) {
-            match self.current() {
-                Some(Token::Symbol(s)) if &**s == "&" => {
-                    is_variadic = true;
-                    self.advance();
-                    // 次のシンボルが可変引数名
-                    match self.current() {
-                        Some(Token::Symbol(_)) => {
-                            params.push(self.take_symbol().expect("token should be a symbol"));
-                        }
-                        _ => return Err(self.error_with_line(MsgKey::MacVarargNeedsSymbol, &[])),
-                    }
-                }
-                Some(Token::Symbol(_)) => {
-                    params.push(self.take_symbol().expect("token should be a symbol"));
-                }
-                _ => return Err(self.error_with_line(MsgKey::NeedsSymbol, &["mac"])),
-            }
-        }
-
-        self.expect(Token::RBracket)?;
+        // パラメータリスト（fnと同じく [[name val] & body] のような分解パターンを使用可能）
+        let (params, is_variadic) = self.parse_fn_params()?;
 
         // 本体
         let body = Box::new(self.parse_expr()?);
@@ -1528,6 +1557,11 @@ impl<'a> Parser<'a> {
                 let s = self.take_string().expect("token should be a string");
                 Ok(Pattern::String(s))
             }
+            Some(Token::Char(c)) => {
+                let c = *c;
+                self.advance();
+                Ok(Pattern::Char(c))
+            }
             Some(Token::Keyword(_)) => {
                 let k = self.take_keyword().expect("token should be a keyword");
                 Ok(Pattern::Keyword(k))
@@ -1538,6 +1572,7 @@ impl<'a> Parser<'a> {
             }
             Some(Token::LBracket) => self.parse_vector_pattern(),
             Some(Token::LBrace) => self.parse_map_pattern(),
+            Some(Token::HashBrace) => self.parse_set_pattern(),
             Some(token) => {
                 Err(self.error_with_line(MsgKey::UnexpectedPattern, &[&token.display_name()]))
             }
@@ -1596,6 +1631,32 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_set_pattern(&mut self) -> Result<Pattern, String> {
+        self.expect(Token::HashBrace)?;
+
+        // セットパターンの要素はリテラルのみ（要素の有無で判定するため）
+        let mut patterns = Vec::with_capacity(4);
+        while self.current() != Some(&Token::RBrace) {
+            let pattern = self.parse_pattern()?;
+            if !matches!(
+                pattern,
+                Pattern::Nil
+                    | Pattern::Bool(_)
+                    | Pattern::Integer(_)
+                    | Pattern::String(_)
+                    | Pattern::Char(_)
+                    | Pattern::Keyword(_)
+            ) {
+                return Err(self.error_with_line(MsgKey::SetPatternMustBeLiteral, &[]));
+            }
+            patterns.push(pattern);
+        }
+
+        self.expect(Token::RBrace)?;
+
+        Ok(Pattern::Set(patterns))
+    }
+
     fn parse_map_pattern(&mut self) -> Result<Pattern, String> {
         self.expect(Token::LBrace)?;
 
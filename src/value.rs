@@ -26,6 +26,8 @@ pub enum MapKey {
     String(String),
     /// 整数
     Integer(i64),
+    /// 文字
+    Char(char),
 }
 
 impl MapKey {
@@ -36,6 +38,7 @@ impl MapKey {
             MapKey::Symbol(s) => s.to_string(),
             MapKey::String(s) => format!("\"{}\"", s),
             MapKey::Integer(i) => i.to_string(),
+            MapKey::Char(c) => crate::lexer::char_literal(*c),
         }
     }
 }
@@ -89,6 +92,7 @@ impl std::fmt::Display for MapKey {
             MapKey::Symbol(s) => write!(f, "{}", s),
             MapKey::String(s) => write!(f, "\"{}\"", s),
             MapKey::Integer(i) => write!(f, "{}", i),
+            MapKey::Char(c) => write!(f, "{}", crate::lexer::char_literal(*c)),
         }
     }
 }
@@ -124,6 +128,14 @@ pub enum Value {
     Integer(i64),
     /// 浮動小数点数
     Float(f64),
+    /// 多倍長整数（i64に収まらない整数、演算結果は常に正規化してi64に収まればIntegerに戻す）
+    BigInt(Arc<num_bigint::BigInt>),
+    /// 有理数（整数同士の割り切れない除算、分母が1になればIntegerに戻す）
+    Ratio(Arc<num_rational::BigRational>),
+    /// 10進数（12.34M、金額計算用の誤差のない小数）
+    Decimal(rust_decimal::Decimal),
+    /// 文字（Unicodeスカラー値、\a・\space・\u3042 リテラル）
+    Char(char),
     /// 文字列
     String(String),
     /// バイナリデータ（不変、共有可能）
@@ -138,6 +150,8 @@ pub enum Value {
     Vector(Vector<Value>),
     /// マップ（型安全なキー）
     Map(crate::HashMap<MapKey, Value>),
+    /// セット（永続ハッシュ集合、#{1 2 3}）
+    Set(crate::HashSet<Value>),
     /// 関数（クロージャ）
     Function(Arc<Function>),
     /// ネイティブ関数（Rustで実装された関数）
@@ -145,13 +159,17 @@ pub enum Value {
     /// マクロ
     Macro(Arc<Macro>),
     /// アトム（可変な参照）
-    Atom(Arc<RwLock<Value>>),
+    Atom(Arc<Atom>),
+    /// STM参照（dosync内でalter/commuteにより協調的に更新）
+    Ref(Arc<Ref>),
     /// チャネル（go/chan並行処理用）
     Channel(Arc<Channel>),
     /// スコープ（Structured Concurrency用）
     Scope(Arc<Scope>),
     /// ストリーム（遅延評価）
     Stream(Arc<RwLock<Stream>>),
+    /// ネイティブリソースへの不透明なハンドル（コンパイル済みルーターなど）
+    Handle(Arc<Handle>),
     /// ユニーク変数（マクロの衛生性）
     Uvar(u64),
 }
@@ -181,6 +199,45 @@ impl PartialEq for Channel {
     }
 }
 
+/// アトム（可変な参照）
+///
+/// 更新時はバリデータで新しい値を検証し、登録されたウォッチへ通知する
+#[derive(Debug)]
+pub struct Atom {
+    pub value: RwLock<Value>,
+    /// add-watchで登録された (キー, 関数)
+    pub watches: RwLock<Vec<(Value, Value)>>,
+    /// set-validator!で設定された検証関数
+    pub validator: RwLock<Option<Value>>,
+}
+
+impl Atom {
+    pub fn new(value: Value) -> Self {
+        Atom {
+            value: RwLock::new(value),
+            watches: RwLock::new(Vec::new()),
+            validator: RwLock::new(None),
+        }
+    }
+}
+
+/// STM参照（ref）
+///
+/// コミットのたびにversionを進め、トランザクションは読み取り時のversionで競合を検出する
+#[derive(Debug)]
+pub struct Ref {
+    /// 一意なID（コミット時のロック順序に使用）
+    pub id: u64,
+    pub state: RwLock<RefState>,
+}
+
+/// refのコミット済みの値とバージョン
+#[derive(Debug)]
+pub struct RefState {
+    pub value: Value,
+    pub version: u64,
+}
+
 /// スコープ（Structured Concurrency用）
 #[derive(Debug, Clone)]
 pub struct Scope {
@@ -195,6 +252,37 @@ impl PartialEq for Scope {
     }
 }
 
+/// ネイティブリソースへの不透明なハンドル
+///
+/// Qiからは中身を参照できず、等価性はポインタで比較する。
+/// グローバルなレジストリを介さないため、最後の参照が消えると中身も解放される。
+pub struct Handle {
+    /// 種類名（表示用、例: "router"）
+    pub kind: &'static str,
+    pub data: Arc<dyn std::any::Any + Send + Sync>,
+}
+
+impl Handle {
+    /// ハンドルを作成
+    pub fn new<T: std::any::Any + Send + Sync>(kind: &'static str, data: T) -> Self {
+        Handle {
+            kind,
+            data: Arc::new(data),
+        }
+    }
+
+    /// 中身を型Tとして取り出す（型が違えばNone）
+    pub fn downcast<T: std::any::Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.data.clone().downcast::<T>().ok()
+    }
+}
+
+impl std::fmt::Debug for Handle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Handle {{ kind: {:?} }}", self.kind)
+    }
+}
+
 /// ストリーム（遅延評価）
 pub struct Stream {
     pub next_fn: Box<dyn Fn() -> Option<Value> + Send + Sync>,
@@ -238,6 +326,7 @@ impl Value {
             Value::String(s) => Ok(MapKey::String(s.clone())),
             Value::Symbol(s) => Ok(MapKey::Symbol(s.clone())),
             Value::Integer(n) => Ok(MapKey::Integer(*n)),
+            Value::Char(c) => Ok(MapKey::Char(*c)),
             Value::Float(_) => Err(msg(MK::FloatKeyNotAllowed).to_string()),
             _ => Err(fmt_msg(MK::InvalidMapKey, &[self.type_name()])),
         }
@@ -250,6 +339,10 @@ impl Value {
             Value::Bool(_) => "boolean",
             Value::Integer(_) => "integer",
             Value::Float(_) => "float",
+            Value::BigInt(_) => "integer",
+            Value::Ratio(_) => "ratio",
+            Value::Decimal(_) => "decimal",
+            Value::Char(_) => "char",
             Value::String(_) => "string",
             Value::Bytes(_) => "bytes",
             Value::Symbol(_) => "symbol",
@@ -257,17 +350,65 @@ impl Value {
             Value::List(_) => "list",
             Value::Vector(_) => "vector",
             Value::Map(_) => "map",
+            Value::Set(_) => "set",
             Value::Function(_) => "function",
             Value::NativeFunc(_) => "function",
             Value::Macro(_) => "macro",
             Value::Atom(_) => "atom",
+            Value::Ref(_) => "ref",
             Value::Channel(_) => "channel",
             Value::Scope(_) => "scope",
             Value::Stream(_) => "stream",
+            Value::Handle(_) => "handle",
             Value::Uvar(_) => "uvar",
         }
     }
 
+    /// ハッシュ化できる値か（セットの要素として使えるか）
+    ///
+    /// Float・関数・アトムなどを（ネストも含めて）含む値はハッシュ化できない
+    pub fn is_hashable(&self) -> bool {
+        match self {
+            Value::List(items) | Value::Vector(items) => items.iter().all(Value::is_hashable),
+            Value::Map(m) => m.values().all(Value::is_hashable),
+            Value::Set(_) => true,
+            Value::Float(_)
+            | Value::Function(_)
+            | Value::NativeFunc(_)
+            | Value::Macro(_)
+            | Value::Atom(_)
+            | Value::Ref(_)
+            | Value::Channel(_)
+            | Value::Scope(_)
+            | Value::Stream(_)
+            | Value::Handle(_)
+            | Value::Uvar(_) => false,
+            _ => true,
+        }
+    }
+
+    /// 値の列からセットを構築（ハッシュ化できない要素はエラー）
+    pub fn set_from<I: IntoIterator<Item = Value>>(items: I) -> Result<Value, String> {
+        let mut set = crate::new_hashset();
+        for item in items {
+            if !item.is_hashable() {
+                return Err(crate::i18n::fmt_msg(
+                    crate::i18n::MsgKey::SetElementNotHashable,
+                    &[item.type_name()],
+                ));
+            }
+            set.insert(item);
+        }
+        Ok(Value::Set(set))
+    }
+
+    /// セットの要素を表示順に並べる（JSON等へ書き出すときに出力を安定させるため）
+    pub fn sorted_set_items(set: &crate::HashSet<Value>) -> im::Vector<Value> {
+        let mut items: Vec<Value> = set.iter().cloned().collect();
+        items.sort_by_cached_key(|v| v.to_string());
+        items.into()
+    }
+
     /// List/Vectorを統一的に扱うヘルパー
     ///
     /// ListまたはVectorの内部データ（im::Vector）への参照を返す
@@ -346,6 +487,10 @@ impl PartialEq for Value {
             (Value::Bool(a), Value::Bool(b)) => a == b,
             (Value::Integer(a), Value::Integer(b)) => a == b,
             (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            (Value::Ratio(a), Value::Ratio(b)) => a == b,
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
+            (Value::Char(a), Value::Char(b)) => a == b,
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Bytes(a), Value::Bytes(b)) => a.as_ref() == b.as_ref(),
             (Value::Symbol(a), Value::Symbol(b)) => a == b,
@@ -355,15 +500,18 @@ impl PartialEq for Value {
             // ListとVectorは内容が同じなら等しい（Lisp系言語の一般的な仕様）
             (Value::List(a), Value::Vector(b)) | (Value::Vector(a), Value::List(b)) => a == b,
             (Value::Map(a), Value::Map(b)) => a == b,
+            (Value::Set(a), Value::Set(b)) => a == b,
             (Value::NativeFunc(a), Value::NativeFunc(b)) => a == b,
             (Value::Uvar(a), Value::Uvar(b)) => a == b,
-            // 関数、マクロ、アトム、チャネル、スコープ、ストリームはポインタ比較
+            // 関数、マクロ、アトム、チャネル、スコープ、ストリーム、ハンドルはポインタ比較
             (Value::Function(a), Value::Function(b)) => Arc::ptr_eq(a, b),
             (Value::Macro(a), Value::Macro(b)) => Arc::ptr_eq(a, b),
             (Value::Atom(a), Value::Atom(b)) => Arc::ptr_eq(a, b),
+            (Value::Ref(a), Value::Ref(b)) => Arc::ptr_eq(a, b),
             (Value::Channel(a), Value::Channel(b)) => Arc::ptr_eq(a, b),
             (Value::Scope(a), Value::Scope(b)) => Arc::ptr_eq(a, b),
             (Value::Stream(a), Value::Stream(b)) => Arc::ptr_eq(a, b),
+            (Value::Handle(a), Value::Handle(b)) => Arc::ptr_eq(a, b),
             _ => false,
         }
     }
@@ -371,7 +519,7 @@ impl PartialEq for Value {
 
 /// Hashトレイト実装（集合演算の高速化）
 ///
-/// Float, Function, NativeFunc, Macro, Atom, Channel, Scope, Stream, Uvarは
+/// Float, Function, NativeFunc, Macro, Atom, Ref, Channel, Scope, Stream, Handle, Uvarは
 /// ハッシュ化できないため、これらの値を含むコレクションで集合演算を行うと
 /// エラーになります。
 impl Hash for Value {
@@ -383,6 +531,11 @@ impl Hash for Value {
             Value::Nil => {}
             Value::Bool(b) => b.hash(state),
             Value::Integer(i) => i.hash(state),
+            Value::BigInt(n) => n.hash(state),
+            Value::Ratio(r) => r.hash(state),
+            // Decimalのハッシュはスケールを正規化するため 1.5M と 1.50M は同じ値になる
+            Value::Decimal(d) => d.hash(state),
+            Value::Char(c) => c.hash(state),
             Value::String(s) => s.hash(state),
             Value::Bytes(b) => b.as_ref().hash(state),
             Value::Symbol(s) => s.hash(state),
@@ -400,15 +553,28 @@ impl Hash for Value {
                     v.hash(state);
                 }
             }
+            Value::Set(items) => {
+                // 要素の順序に依存しないよう、各要素のハッシュ値を加算して畳み込む
+                let mut combined: u64 = 0;
+                for item in items {
+                    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                    item.hash(&mut hasher);
+                    combined = combined.wrapping_add(hasher.finish());
+                }
+                items.len().hash(state);
+                combined.hash(state);
+            }
             // ハッシュ化できない型
             Value::Float(_)
             | Value::Function(_)
             | Value::NativeFunc(_)
             | Value::Macro(_)
             | Value::Atom(_)
+            | Value::Ref(_)
             | Value::Channel(_)
             | Value::Scope(_)
             | Value::Stream(_)
+            | Value::Handle(_)
             | Value::Uvar(_) => {
                 // ハッシュ化不可能な型：panicする
                 // 集合演算では事前に型チェックするため、通常はここには到達しない
@@ -459,7 +625,7 @@ impl PartialEq for Function {
 #[derive(Debug, Clone)]
 pub struct Macro {
     pub name: Arc<str>,
-    pub params: Vec<Arc<str>>,
+    pub params: Vec<Pattern>,
     pub body: Arc<Expr>,
     pub env: Arc<RwLock<Env>>,
     pub is_variadic: bool,
@@ -544,6 +710,26 @@ impl Module {
             }
         }
     }
+
+    /// 公開されているシンボル名の一覧（ns-publics用、ドキュメント用エントリは除外）
+    pub fn public_names(&self) -> Vec<Arc<str>> {
+        let env = self.env.read();
+        let mut names: Vec<Arc<str>> = match &self.exports {
+            None => env
+                .all_bindings()
+                .filter(|(_, b)| !b.is_private)
+                .map(|(name, _)| name.clone())
+                .collect(),
+            Some(set) => set
+                .iter()
+                .filter(|name| env.get(name).is_some())
+                .cloned()
+                .collect(),
+        };
+        names.retain(|name| !name.starts_with(crate::eval::DOC_PREFIX));
+        names.sort();
+        names
+    }
 }
 
 impl PartialEq for Module {
@@ -650,6 +836,16 @@ impl Env {
         self.parent.as_ref().and_then(|p| p.read().get(name))
     }
 
+    /// ルート環境より手前（let・関数引数等のローカルスコープ）で束縛されているか
+    ///
+    /// 動的変数がローカル変数にシャドウされているかの判定に使用
+    pub fn is_bound_locally(&self, name: &str) -> bool {
+        match &self.parent {
+            None => false,
+            Some(p) => self.bindings.contains_key(name) || p.read().is_bound_locally(name),
+        }
+    }
+
     pub fn get_binding(&self, name: &str) -> Option<Binding> {
         // ローカルバインディングをチェック（ロック不要、高速パス）
         if let Some(b) = self.bindings.get(name) {
@@ -673,6 +869,13 @@ impl Env {
         self.bindings.insert(name.into(), binding);
     }
 
+    /// ローカルバインディングを公開に切り替え（exportされた場合）
+    pub fn mark_public(&mut self, name: &str) {
+        if let Some(b) = self.bindings.get_mut(name) {
+            b.is_private = false;
+        }
+    }
+
     /// バインディングの反復子を取得（モジュールシステム用）
     pub fn bindings(&self) -> impl Iterator<Item = (&Arc<str>, &Value)> {
         self.bindings.iter().map(|(k, b)| (k, &b.value))
@@ -768,6 +971,22 @@ pub enum Expr {
         value: f64,
         span: Span,
     },
+    BigInt {
+        value: Arc<num_bigint::BigInt>,
+        span: Span,
+    },
+    Ratio {
+        value: Arc<num_rational::BigRational>,
+        span: Span,
+    },
+    Decimal {
+        value: rust_decimal::Decimal,
+        span: Span,
+    },
+    Char {
+        value: char,
+        span: Span,
+    },
     String {
         value: String,
         span: Span,
@@ -798,6 +1017,10 @@ pub enum Expr {
         pairs: Vec<(Expr, Expr)>,
         span: Span,
     },
+    Set {
+        items: Vec<Expr>,
+        span: Span,
+    },
 
     // 特殊形式
     Def {
@@ -880,7 +1103,8 @@ pub enum Expr {
     // マクロ
     Mac {
         name: std::sync::Arc<str>,
-        params: Vec<std::sync::Arc<str>>,
+        /// パラメータ（fnと同じ分解パターンを使用可能）
+        params: Vec<Pattern>,
         is_variadic: bool,
         body: Box<Expr>,
         span: Span,
@@ -929,6 +1153,10 @@ impl Expr {
             Expr::Bool { span, .. } => *span,
             Expr::Integer { span, .. } => *span,
             Expr::Float { span, .. } => *span,
+            Expr::BigInt { span, .. } => *span,
+            Expr::Ratio { span, .. } => *span,
+            Expr::Decimal { span, .. } => *span,
+            Expr::Char { span, .. } => *span,
             Expr::String { span, .. } => *span,
             Expr::FString { span, .. } => *span,
             Expr::Symbol { span, .. } => *span,
@@ -936,6 +1164,7 @@ impl Expr {
             Expr::List { span, .. } => *span,
             Expr::Vector { span, .. } => *span,
             Expr::Map { span, .. } => *span,
+            Expr::Set { span, .. } => *span,
             Expr::Def { span, .. } => *span,
             Expr::Fn { span, .. } => *span,
             Expr::Let { span, .. } => *span,
@@ -1020,6 +1249,8 @@ pub enum Pattern {
     Float(f64),
     /// 文字列リテラル - match専用
     String(String),
+    /// 文字リテラル - match専用
+    Char(char),
     /// キーワードリテラル - match専用
     Keyword(std::sync::Arc<str>),
 
@@ -1052,6 +1283,10 @@ pub enum Pattern {
         Option<std::sync::Arc<str>>,
     ), // (キー・パターン対, :as変数)
 
+    /// セットパターン #{:a :b} - match専用
+    /// 例: (match #{:a :b :c} #{:a :b} -> "has a and b") => 全要素（リテラル）を含むセットにマッチ
+    Set(Vec<Pattern>),
+
     /// As束縛 pattern :as var
     /// 例: (match [1 2] [x y :as all] -> all) => [1 2]
     As(Box<Pattern>, std::sync::Arc<str>),
@@ -1064,6 +1299,10 @@ impl fmt::Display for Value {
             Value::Bool(b) => write!(f, "{}", b),
             Value::Integer(n) => write!(f, "{}", n),
             Value::Float(n) => write!(f, "{}", n),
+            Value::BigInt(n) => write!(f, "{}", n),
+            Value::Ratio(r) => write!(f, "{}", r),
+            Value::Decimal(d) => write!(f, "{}M", d),
+            Value::Char(c) => write!(f, "{}", crate::lexer::char_literal(*c)),
             Value::String(s) => write!(f, "\"{}\"", s),
             Value::Bytes(b) => {
                 // バイナリデータを16進数表現で表示（最大64バイトまで）
@@ -1112,13 +1351,25 @@ impl fmt::Display for Value {
                 }
                 write!(f, "}}")
             }
+            Value::Set(items) => {
+                write!(f, "#{{")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "}}")
+            }
             Value::Function(_) => write!(f, "#<function>"),
             Value::NativeFunc(nf) => write!(f, "#<native-function:{}>", nf.name),
             Value::Macro(m) => write!(f, "#<macro:{}>", m.name),
-            Value::Atom(a) => write!(f, "#<atom:{}>", a.read()),
+            Value::Atom(a) => write!(f, "#<atom:{}>", a.value.read()),
+            Value::Ref(r) => write!(f, "#<ref:{}>", r.state.read().value),
             Value::Channel(_) => write!(f, "#<channel>"),
             Value::Scope(_) => write!(f, "#<scope>"),
             Value::Stream(_) => write!(f, "#<stream>"),
+            Value::Handle(h) => write!(f, "#<{}>", h.kind),
             Value::Uvar(id) => write!(f, "#<uvar:{}>", id),
         }
     }